@@ -1,4 +1,4 @@
-use super::types::Args;
+use super::types::{Args, StorageBackend};
 use sql_middleware::middleware::DatabaseType;
 
 impl Args {
@@ -12,6 +12,29 @@ impl Args {
     ///
     /// Will panic if the password file is not found
     pub fn validate(&mut self) -> Result<(), String> {
+        if self.storage == StorageBackend::File {
+            if self.data_dir.is_none() {
+                return Err("--data-dir is required with --storage=file".to_string());
+            }
+            if self.db_startup_script.is_some() || self.migrate {
+                return Err(
+                    "--db-startup-script and --migrate only apply to --storage=sql".to_string(),
+                );
+            }
+            return Ok(());
+        }
+        if self.storage == StorageBackend::R2 {
+            if self.db_startup_script.is_some() || self.migrate || self.db_populate_json.is_some() {
+                return Err(
+                    "--db-startup-script, --migrate and --db-populate-json don't apply to --storage=r2"
+                        .to_string(),
+                );
+            }
+            return Ok(());
+        }
+        if self.db_name.is_empty() {
+            return Err("Database name is required".to_string());
+        }
         if self.db_type == DatabaseType::Postgres {
             let secrets_locations = ["/secrets/db_password", "/run/secrets/db_password"];
 
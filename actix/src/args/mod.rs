@@ -5,7 +5,7 @@ pub mod database;
 pub mod types;
 pub mod validation;
 
-pub use types::{Args, CleanArgs};
+pub use types::{Args, CleanArgs, Command, StorageBackend};
 
 /// # Panics
 ///
@@ -56,6 +56,13 @@ impl CleanArgs {
             db_startup_script: args.db_startup_script,
             combined_sql_script,
             db_populate_json: args.db_populate_json,
+            migrate: args.migrate,
+            storage: args.storage,
+            data_dir: args.data_dir,
+            r2_endpoint: args.r2_endpoint,
+            r2_bucket: args.r2_bucket,
+            r2_region: args.r2_region,
+            command: args.command,
         }
     }
 }
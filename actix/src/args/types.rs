@@ -1,6 +1,49 @@
 use clap::Parser;
+use rusty_golf_core::storage::CompactionPolicy;
 use serde_json::Value;
 use sql_middleware::middleware::DatabaseType;
+use std::path::PathBuf;
+
+/// Where the server keeps events, picks and scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StorageBackend {
+    /// Sqlite or postgres, configured with the `--db-*` flags.
+    Sql,
+    /// JSON files under `--data-dir`, laid out like the serverless KV/R2 keys.
+    File,
+    /// An S3-compatible bucket (Cloudflare R2, `MinIO`, ...), configured with the `--r2-*`
+    /// flags or the `R2_*` environment variables.
+    R2,
+}
+
+/// One-off jobs run against the configured storage instead of starting the server.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum Command {
+    /// Write one event to a portable archive file.
+    ExportEvent {
+        #[arg(long)]
+        event_id: i32,
+        #[arg(long, value_name = "ARCHIVE_JSON")]
+        output: PathBuf,
+    },
+    /// Load an archive written by `export-event` on any backend, or by the setup tool.
+    ImportEvent {
+        #[arg(long, value_name = "ARCHIVE_JSON")]
+        input: PathBuf,
+        /// Delete and rebuild the event if it already exists.
+        #[arg(long, default_value_t = false)]
+        replace: bool,
+    },
+    /// Freeze a completed event into its archive, drop the live copies it replaces and thin
+    /// its score history. The event is served read-only from the archive afterwards.
+    ArchiveEvent {
+        #[arg(long)]
+        event_id: i32,
+        /// Keep at most one score-history snapshot per this many seconds; 0 keeps them all.
+        #[arg(long, default_value_t = CompactionPolicy::default().history_interval_seconds)]
+        history_interval_seconds: i64,
+    },
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -10,7 +53,7 @@ pub struct Args {
         short = 'd',
         long,
         value_name = "DATABASE_TYPE",
-        default_value = "Sqlite",
+        default_value = "sqlite",
         value_parser = clap::value_parser!(DatabaseType)
     )]
     pub db_type: DatabaseType,
@@ -34,8 +77,8 @@ pub struct Args {
     #[arg(short = 'w', long, value_name = "DATABASE_PASSWORD")]
     pub db_password: Option<String>,
 
-    /// For postgres, the name of the database. For sqlite, the filename.
-    #[arg(short = 'n', long, value_name = "DATABASE_NAME")]
+    /// For postgres, the name of the database. For sqlite, the filename. Required for `--storage=sql`.
+    #[arg(short = 'n', long, value_name = "DATABASE_NAME", default_value = "")]
     pub db_name: String,
     /// If specified, this sql is run on program startup. Be careful with the SQL you run here, don't mess up your own database.
     #[arg(long, value_name = "DATABASE_STARTUP_SCRIPT", value_parser = crate::args::validation::check_readable_file)]
@@ -46,6 +89,27 @@ pub struct Args {
         value_parser = crate::args::validation::check_readable_file_and_json
     )]
     pub db_populate_json: Option<Value>,
+    /// Apply pending schema migrations on startup.
+    #[arg(long, default_value_t = false)]
+    pub migrate: bool,
+    /// Storage backend: sql, file or r2.
+    #[arg(long, value_enum, default_value_t = StorageBackend::Sql)]
+    pub storage: StorageBackend,
+    /// Directory holding the JSON files for `--storage=file`.
+    #[arg(long, value_name = "DATA_DIR")]
+    pub data_dir: Option<PathBuf>,
+    /// S3 endpoint for `--storage=r2`, without the bucket. Defaults to `$R2_ENDPOINT`.
+    #[arg(long, value_name = "URL")]
+    pub r2_endpoint: Option<String>,
+    /// Bucket for `--storage=r2`. Defaults to `$R2_BUCKET`.
+    #[arg(long, value_name = "BUCKET")]
+    pub r2_bucket: Option<String>,
+    /// Signing region for `--storage=r2`. Defaults to `$R2_REGION`, then `auto`.
+    /// Credentials are only read from `R2_ACCESS_KEY_ID` and `R2_SECRET_ACCESS_KEY`.
+    #[arg(long, value_name = "REGION")]
+    pub r2_region: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
 #[derive(Debug, Clone)]
@@ -59,4 +123,11 @@ pub struct CleanArgs {
     pub db_startup_script: Option<String>,
     pub db_populate_json: Option<Value>,
     pub combined_sql_script: String,
+    pub migrate: bool,
+    pub storage: StorageBackend,
+    pub data_dir: Option<PathBuf>,
+    pub r2_endpoint: Option<String>,
+    pub r2_bucket: Option<String>,
+    pub r2_region: Option<String>,
+    pub command: Option<Command>,
 }
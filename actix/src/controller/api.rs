@@ -0,0 +1,32 @@
+use actix_web::http::StatusCode;
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use rusty_golf_core::api::{ApiError, handle_api_request, openapi_document, parse_api_request};
+use rusty_golf_core::storage::Storage;
+use std::collections::HashMap;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// Everything under `/api/v1`. Routing is done by core from the raw path, so both runtimes
+/// answer the same URLs with the same bodies.
+pub async fn api_v1(req: HttpRequest, storage: Data<dyn Storage>) -> HttpResponse {
+    let result = async {
+        let query = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        let request = parse_api_request(req.path(), &query)?;
+        handle_api_request(storage.get_ref(), &ActixEspnClient::new(), &request).await
+    }
+    .await;
+    match result {
+        Ok(body) => HttpResponse::Ok().json(body),
+        Err(err) => HttpResponse::build(
+            StatusCode::from_u16(err.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        )
+        .json(err.body()),
+    }
+}
+
+/// `/openapi.json`: the same document the serverless worker serves.
+pub async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(openapi_document())
+}
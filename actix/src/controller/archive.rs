@@ -0,0 +1,74 @@
+use rusty_golf_core::storage::{AdminStorage, EventArchive, Storage, export_event, import_event};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+/// What an archive held, for reporting after an export or import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub event_id: i32,
+    pub bettors: usize,
+    pub picks: usize,
+    pub has_scores: bool,
+    pub score_changes: usize,
+}
+
+impl From<&EventArchive> for ArchiveSummary {
+    fn from(archive: &EventArchive) -> Self {
+        Self {
+            event_id: archive.event_id,
+            bettors: archive.bettors.len(),
+            picks: archive.assignments.len(),
+            has_scores: archive.scores.is_some(),
+            score_changes: archive.score_changes.len(),
+        }
+    }
+}
+
+impl fmt::Display for ArchiveSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "event {}: {} bettors, {} picks, {}, {} score changes",
+            self.event_id,
+            self.bettors,
+            self.picks,
+            if self.has_scores {
+                "scores"
+            } else {
+                "no scores"
+            },
+            self.score_changes
+        )
+    }
+}
+
+/// Export `event_id` from `storage` to a pretty-printed archive at `path`.
+///
+/// # Errors
+/// Returns an error if the export fails or the file can't be written.
+pub async fn export_event_to_file(
+    storage: &dyn Storage,
+    event_id: i32,
+    path: &Path,
+) -> Result<ArchiveSummary, Box<dyn Error>> {
+    let archive = export_event(storage, event_id).await?;
+    let json = serde_json::to_string_pretty(&archive)?;
+    std::fs::write(path, json)?;
+    Ok(ArchiveSummary::from(&archive))
+}
+
+/// Import the archive at `path` into `storage`; see [`import_event`] for `replace`.
+///
+/// # Errors
+/// Returns an error if the file can't be read or parsed, or the import fails.
+pub async fn import_event_from_file(
+    storage: &dyn AdminStorage,
+    path: &Path,
+    replace: bool,
+) -> Result<ArchiveSummary, Box<dyn Error>> {
+    let json = std::fs::read_to_string(path)?;
+    let archive: EventArchive = serde_json::from_str(&json)?;
+    import_event(storage, &archive, replace).await?;
+    Ok(ArchiveSummary::from(&archive))
+}
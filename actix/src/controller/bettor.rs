@@ -0,0 +1,37 @@
+use actix_web::HttpResponse;
+use actix_web::web::{self, Data};
+use chrono::Utc;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::storage::Storage;
+use rusty_golf_core::view::bettor::{load_bettor_page, parse_bettor_request};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// `/bettor?name=&event=&yr=`: one bettor's dashboard, or their season without `event`.
+pub async fn bettor(
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+) -> HttpResponse {
+    let request = match parse_bettor_request(&query) {
+        Ok(request) => request,
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    match load_bettor_page(
+        storage.get_ref(),
+        &ActixEspnClient::new(),
+        &request,
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(markup) => HttpResponse::Ok()
+            .content_type("text/html")
+            .body(markup.into_string()),
+        Err(e @ CoreError::NotFound(_)) => {
+            HttpResponse::NotFound().json(json!({"error": e.to_string()}))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
@@ -0,0 +1,39 @@
+use actix_web::HttpResponse;
+use actix_web::http::header;
+use actix_web::web::{self, Data};
+use chrono::Utc;
+use rusty_golf_core::calendar::{load_tee_time_calendar, parse_tee_time_request};
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::storage::Storage;
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// `/tee-times.ics?event=&yr=&bettor=`: the picked golfers' tee times as a calendar feed.
+pub async fn tee_times(
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+) -> HttpResponse {
+    let request = match parse_tee_time_request(&query) {
+        Ok(request) => request,
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    match load_tee_time_calendar(
+        storage.get_ref(),
+        &ActixEspnClient::new(),
+        &request,
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(file) => HttpResponse::Ok()
+            .content_type(file.content_type)
+            .insert_header((header::CONTENT_DISPOSITION, file.content_disposition()))
+            .body(file.body),
+        Err(e @ CoreError::NotFound(_)) => {
+            HttpResponse::NotFound().json(json!({"error": e.to_string()}))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
@@ -1,173 +1,171 @@
+use crate::model::database_write::Transaction;
+use rusty_golf_core::model::{PrefillEvent, parse_prefill_events};
 use serde_json::Value;
 use sql_middleware::{
     SqlMiddlewareDbError,
     middleware::{ConfigAndPool, DatabaseType, MiddlewarePoolConnection, RowValues},
 };
-
-/// # Errors
-///
-/// Will return `Err` if the database query fails
-pub async fn db_prefill(
-    json: &Value,
-    config_and_pool: &ConfigAndPool,
-    db_type: DatabaseType,
-) -> Result<(), SqlMiddlewareDbError> {
-    let conn = config_and_pool.get_connection().await?;
-
-    match db_type {
-        DatabaseType::Sqlite => prefill_sqlite(conn, json).await?,
-        DatabaseType::Postgres => unimplemented!(),
-    }
-    Ok(())
+use std::fmt;
+
+/// Rows written by [`db_prefill`]; events that already exist are skipped, not rewritten.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrefillCounts {
+    pub events_inserted: usize,
+    pub events_skipped: usize,
+    pub bettors_inserted: usize,
+    pub golfers_inserted: usize,
+    pub event_user_players_inserted: usize,
 }
 
-async fn prefill_sqlite(
-    mut conn: MiddlewarePoolConnection,
-    json: &Value,
-) -> Result<(), SqlMiddlewareDbError> {
-    if cfg!(debug_assertions) {
-        let _pretty_json = serde_json::to_string_pretty(json).unwrap();
+impl fmt::Display for PrefillCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} events inserted ({} skipped), {} bettors, {} golfers, {} event_user_player rows",
+            self.events_inserted,
+            self.events_skipped,
+            self.bettors_inserted,
+            self.golfers_inserted,
+            self.event_user_players_inserted
+        )
     }
-
-    let data = json.as_array().unwrap();
-
-    prefill_sqlite_inner(&mut conn, data).await
 }
 
-async fn prefill_sqlite_inner(
-    conn: &mut MiddlewarePoolConnection,
-    data: &[Value],
-) -> Result<(), SqlMiddlewareDbError> {
-    for datum in data {
-        process_event_datum(conn, datum).await?;
-    }
-    Ok(())
+/// Dialect-specific statements; both variants take the same parameters in the same order.
+struct PrefillSql {
+    event_exists: &'static str,
+    insert_event: &'static str,
+    insert_bettor: &'static str,
+    insert_golfer: &'static str,
+    insert_eup_with_factor: &'static str,
+    insert_eup_without_factor: &'static str,
 }
 
-async fn process_event_datum(
-    conn: &mut MiddlewarePoolConnection,
-    datum: &Value,
-) -> Result<(), SqlMiddlewareDbError> {
-    let espn_id = datum["event"].as_i64().unwrap();
-    let year = datum["year"].as_i64().unwrap();
+const SQLITE_SQL: PrefillSql = PrefillSql {
+    event_exists: "SELECT 1 FROM event WHERE espn_id = ?1 AND year = ?2;",
+    insert_event: "INSERT INTO event (name, espn_id, year, score_view_step_factor, start_date, end_date, completed) \
+         VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7);",
+    insert_bettor: "INSERT INTO bettor (name) SELECT ?1 WHERE NOT EXISTS (SELECT 1 from bettor where name = ?1);",
+    insert_golfer: "INSERT INTO golfer (name, espn_id) SELECT ?1, ?2 WHERE NOT EXISTS (SELECT 1 from golfer where espn_id = ?2);",
+    insert_eup_with_factor: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         select (select event_id from event where espn_id = ?1),(select user_id from bettor where name = ?2),(select golfer_id from golfer where espn_id = ?3), ?4;",
+    insert_eup_without_factor: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         select (select event_id from event where espn_id = ?1),(select user_id from bettor where name = ?2),(select golfer_id from golfer where espn_id = ?3), NULL;",
+};
 
-    if event_exists(conn, espn_id, year).await? {
-        println!("Event {espn_id} and year {year} already exist in the db. Skipping db prefill.");
-    } else {
-        insert_event(conn, datum).await?;
-        let data_to_fill = datum["data_to_fill_if_event_and_year_missing"]
-            .as_array()
-            .unwrap();
-        for data in data_to_fill {
-            insert_bettors(conn, data["bettors"].as_array().unwrap()).await?;
-            insert_golfers(conn, data["golfers"].as_array().unwrap()).await?;
-            insert_event_user_players(conn, data["event_user_player"].as_array().unwrap(), datum)
-                .await?;
+const POSTGRES_SQL: PrefillSql = PrefillSql {
+    event_exists: "SELECT 1 FROM event WHERE espn_id = $1 AND year = $2;",
+    insert_event: "INSERT INTO event (name, espn_id, year, score_view_step_factor, start_date, end_date, completed) \
+         VALUES ($1, $2, $3, $4::float8, $5::text::timestamptz AT TIME ZONE 'utc', $6::text::timestamptz AT TIME ZONE 'utc', $7);",
+    insert_bettor: "INSERT INTO bettor (name) SELECT $1::text WHERE NOT EXISTS (SELECT 1 FROM bettor WHERE name = $1::text);",
+    insert_golfer: "INSERT INTO golfer (name, espn_id) SELECT $1::text, $2::int4 WHERE NOT EXISTS (SELECT 1 FROM golfer WHERE espn_id = $2::int4);",
+    insert_eup_with_factor: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         SELECT (SELECT event_id FROM event WHERE espn_id = $1),(SELECT user_id FROM bettor WHERE name = $2),(SELECT golfer_id FROM golfer WHERE espn_id = $3), $4::float8;",
+    insert_eup_without_factor: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         SELECT (SELECT event_id FROM event WHERE espn_id = $1),(SELECT user_id FROM bettor WHERE name = $2),(SELECT golfer_id FROM golfer WHERE espn_id = $3), NULL;",
+};
+
+/// Insert events, bettors, golfers and picks from the `--db-populate-json` payload.
+///
+/// Events that already exist are skipped, so running the same file twice is a no-op.
+/// Each event is written in its own transaction along with its golfers, bettors and picks.
+///
+/// # Errors
+///
+/// Will return `Err` if the JSON is malformed or a database query fails
+pub async fn db_prefill(
+    json: &Value,
+    config_and_pool: &ConfigAndPool,
+    db_type: DatabaseType,
+) -> Result<PrefillCounts, SqlMiddlewareDbError> {
+    let events = parse_prefill_events(json).map_err(SqlMiddlewareDbError::Other)?;
+    let sql = match db_type {
+        DatabaseType::Sqlite => &SQLITE_SQL,
+        DatabaseType::Postgres => &POSTGRES_SQL,
+    };
+
+    let mut conn = config_and_pool.get_connection().await?;
+    let mut counts = PrefillCounts::default();
+    for event in &events {
+        if event_exists(&mut conn, sql, event).await? {
+            println!(
+                "Event {} and year {} already exist in the db. Skipping db prefill.",
+                event.event, event.year
+            );
+            counts.events_skipped += 1;
+            continue;
         }
+
+        // One transaction per event: a failure halfway leaves no event row behind, so the
+        // next run doesn't mistake the event for already filled.
+        let mut tx = Transaction::begin(&mut conn).await?;
+        insert_event_rows(&mut tx, sql, event, &mut counts).await?;
+        tx.commit().await?;
     }
-    Ok(())
+    Ok(counts)
 }
 
 async fn event_exists(
     conn: &mut MiddlewarePoolConnection,
-    espn_id: i64,
-    year: i64,
+    sql: &PrefillSql,
+    event: &PrefillEvent,
 ) -> Result<bool, SqlMiddlewareDbError> {
-    let params = [RowValues::Int(espn_id), RowValues::Int(year)];
+    let params = [RowValues::Int(event.event), RowValues::Int(event.year)];
     let result_set = conn
-        .query("SELECT 1 FROM event WHERE espn_id = ?1 AND year = ?2;")
+        .query(sql.event_exists)
         .params(&params)
         .select()
         .await?;
     Ok(!result_set.results.is_empty())
 }
 
-async fn insert_event(
-    conn: &mut MiddlewarePoolConnection,
-    datum: &Value,
+async fn insert_event_rows(
+    tx: &mut Transaction<'_>,
+    sql: &PrefillSql,
+    event: &PrefillEvent,
+    counts: &mut PrefillCounts,
 ) -> Result<(), SqlMiddlewareDbError> {
     let params = [
-        RowValues::Text(datum["name"].as_str().unwrap().to_string()),
-        RowValues::Int(datum["event"].as_i64().unwrap()),
-        RowValues::Int(datum["year"].as_i64().unwrap()),
-        RowValues::Float(datum["score_view_step_factor"].as_f64().unwrap()),
+        RowValues::Text(event.name.clone()),
+        RowValues::Int(event.event),
+        RowValues::Int(event.year),
+        RowValues::Float(event.score_view_step_factor),
+        optional_text(event.start_date.as_deref()),
+        optional_text(event.end_date.as_deref()),
+        RowValues::Int(i64::from(event.completed)),
     ];
-    conn.query(
-        "INSERT INTO event (name, espn_id, year, score_view_step_factor) VALUES(?1, ?2, ?3, ?4);",
-    )
-    .params(&params)
-    .dml()
-    .await?;
-    Ok(())
-}
-
-async fn insert_bettors(
-    conn: &mut MiddlewarePoolConnection,
-    bettors: &[Value],
-) -> Result<(), SqlMiddlewareDbError> {
-    for bettor in bettors {
-        let params = [RowValues::Text(bettor.as_str().unwrap().to_string())];
-        conn.query("INSERT INTO bettor (name) SELECT ?1 WHERE NOT EXISTS (SELECT 1 from bettor where name = ?1);")
-            .params(&params)
-            .dml()
-            .await?;
-    }
-    Ok(())
-}
+    counts.events_inserted += tx.dml(sql.insert_event, &params).await?;
 
-async fn insert_golfers(
-    conn: &mut MiddlewarePoolConnection,
-    golfers: &[Value],
-) -> Result<(), SqlMiddlewareDbError> {
-    for golfer in golfers {
-        let params = [
-            RowValues::Text(golfer["name"].as_str().unwrap().to_string()),
-            RowValues::Int(golfer["espn_id"].as_i64().unwrap()),
-        ];
-        conn.query("INSERT INTO golfer (name, espn_id) SELECT ?1, ?2 WHERE NOT EXISTS (SELECT 1 from golfer where espn_id = ?2);")
-            .params(&params)
-            .dml()
-            .await?;
+    for data in &event.data_to_fill_if_event_and_year_missing {
+        for bettor in &data.bettors {
+            let params = [RowValues::Text(bettor.clone())];
+            counts.bettors_inserted += tx.dml(sql.insert_bettor, &params).await?;
+        }
+        for golfer in &data.golfers {
+            let params = [
+                RowValues::Text(golfer.name.clone()),
+                RowValues::Int(golfer.espn_id),
+            ];
+            counts.golfers_inserted += tx.dml(sql.insert_golfer, &params).await?;
+        }
+        for eup in &data.event_user_player {
+            let mut params = vec![
+                RowValues::Int(event.event),
+                RowValues::Text(eup.bettor.clone()),
+                RowValues::Int(eup.golfer_espn_id),
+            ];
+            let query = if let Some(factor) = eup.score_view_step_factor {
+                params.push(RowValues::Float(factor));
+                sql.insert_eup_with_factor
+            } else {
+                sql.insert_eup_without_factor
+            };
+            counts.event_user_players_inserted += tx.dml(query, &params).await?;
+        }
     }
     Ok(())
 }
 
-async fn insert_event_user_players(
-    conn: &mut MiddlewarePoolConnection,
-    event_user_players: &[Value],
-    datum: &Value,
-) -> Result<(), SqlMiddlewareDbError> {
-    for event_user_player in event_user_players {
-        let mut params = vec![
-            RowValues::Int(datum["event"].as_i64().unwrap()),
-            RowValues::Text(event_user_player["bettor"].as_str().unwrap().to_string()),
-            RowValues::Int(event_user_player["golfer_espn_id"].as_i64().unwrap()),
-        ];
-
-        let mut query_columns = "(event_id, user_id, golfer_id".to_string();
-        let mut query_values =
-            " select (select event_id from event where espn_id = ?1),".to_string();
-        query_values.push_str("(select user_id from bettor where name = ?2),");
-        query_values.push_str("(select golfer_id from golfer where espn_id = ?3)");
-
-        if event_user_player.get("score_view_step_factor").is_some() {
-            query_columns.push_str(", score_view_step_factor");
-            query_values.push_str(", ?4");
-            params.push(RowValues::Float(
-                event_user_player["score_view_step_factor"]
-                    .as_f64()
-                    .unwrap(),
-            ));
-        } else {
-            query_columns.push_str(", score_view_step_factor");
-            query_values.push_str(", NULL");
-        }
-
-        query_columns.push(')');
-        query_values.push(';');
-
-        let query = format!("INSERT INTO event_user_player {query_columns}{query_values}");
-        conn.query(&query).params(&params).dml().await?;
-    }
-    Ok(())
+fn optional_text(value: Option<&str>) -> RowValues {
+    value.map_or(RowValues::Null, |v| RowValues::Text(v.to_string()))
 }
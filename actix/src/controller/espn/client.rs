@@ -1,7 +1,9 @@
 use crate::model::{PlayerJsonResponse, Scores};
 use reqwest::Client;
 use rusty_golf_core::error::CoreError;
-use rusty_golf_core::espn::EspnApiClient;
+use rusty_golf_core::espn::{
+    ESPN_SCOREBOARD_HEADER_URL, EspnApiClient, EventCompletion, parse_event_completion,
+};
 use std::collections::HashMap;
 
 pub struct ActixEspnClient;
@@ -86,6 +88,18 @@ impl EspnApiClient for ActixEspnClient {
         eprintln!("ESPN fetch failed: falling back to offline fixtures for event {event_id}.");
         Ok(Some(scores_vec))
     }
+
+    async fn event_completion(&self, event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        let payload = Client::new()
+            .get(ESPN_SCOREBOARD_HEADER_URL)
+            .send()
+            .await
+            .map_err(|e| CoreError::Network(e.to_string()))?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| CoreError::Network(e.to_string()))?;
+        parse_event_completion(&payload, event_id)
+    }
 }
 
 async fn get_json_from_espn(
@@ -0,0 +1,36 @@
+use actix_web::HttpResponse;
+use actix_web::http::header;
+use actix_web::web::{self, Data};
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::export::{load_export, parse_export_request};
+use rusty_golf_core::storage::Storage;
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// `/export/{table}?event=&yr=&format=csv|xlsx`: standings, rounds or golfers as a
+/// download.
+pub async fn export(
+    table: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+) -> HttpResponse {
+    let request = match parse_export_request(&table, &query) {
+        Ok(request) => request,
+        Err(e @ CoreError::NotFound(_)) => {
+            return HttpResponse::NotFound().json(json!({"error": e.to_string()}));
+        }
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    match load_export(storage.get_ref(), &ActixEspnClient::new(), &request).await {
+        Ok(file) => HttpResponse::Ok()
+            .content_type(file.content_type)
+            .insert_header((header::CONTENT_DISPOSITION, file.content_disposition()))
+            .body(file.body),
+        Err(e @ CoreError::NotFound(_)) => {
+            HttpResponse::NotFound().json(json!({"error": e.to_string()}))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
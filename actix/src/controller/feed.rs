@@ -0,0 +1,28 @@
+use actix_web::HttpResponse;
+use actix_web::web::{self, Data};
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::feed::{FEED_CONTENT_TYPE, load_feed};
+use rusty_golf_core::score::parse_score_request;
+use rusty_golf_core::storage::Storage;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// `/feed.atom?event=&yr=`: the event's lead changes, completed rounds and eagles as Atom.
+pub async fn feed(
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+) -> HttpResponse {
+    let request = match parse_score_request(&query) {
+        Ok(request) => request,
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    match load_feed(storage.get_ref(), request.event_id, request.year).await {
+        Ok(body) => HttpResponse::Ok()
+            .content_type(FEED_CONTENT_TYPE)
+            .body(body),
+        Err(e @ CoreError::NotFound(_)) => {
+            HttpResponse::NotFound().json(json!({"error": e.to_string()}))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
@@ -0,0 +1,37 @@
+use actix_web::HttpResponse;
+use actix_web::web::{self, Data};
+use chrono::Utc;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::storage::Storage;
+use rusty_golf_core::view::golfer::{load_golfer_page, parse_golfer_request};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// `/golfer?espn_id=&event=&yr=`: one golfer's scorecard and their other events.
+pub async fn golfer(
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+) -> HttpResponse {
+    let request = match parse_golfer_request(&query) {
+        Ok(request) => request,
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    match load_golfer_page(
+        storage.get_ref(),
+        &ActixEspnClient::new(),
+        &request,
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(markup) => HttpResponse::Ok()
+            .content_type("text/html")
+            .body(markup.into_string()),
+        Err(e @ CoreError::NotFound(_)) => {
+            HttpResponse::NotFound().json(json!({"error": e.to_string()}))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
@@ -1,24 +1,28 @@
 use actix_web::web::{self, Data};
 use actix_web::{HttpResponse, Responder};
+use rusty_golf_core::storage::Storage;
 use serde_json::json;
 use std::collections::HashMap;
 
 use crate::mvu::runtime::run_score;
 use crate::mvu::score as mvu_score;
-use crate::storage::SqlStorage;
 use crate::view::score::chart::render_drop_down_bar_pure;
 use crate::view::score::types::RefreshData;
 use crate::view::score::{
-    render_line_score_tables, render_summary_scores, scores_and_last_refresh_to_line_score_tables,
+    fragment_url, render_line_score_tables, render_next_update, render_polled_fragment,
+    render_summary_scores, scores_and_last_refresh_to_line_score_tables,
 };
 
+// Handlers read the storage as `Data<dyn Storage>`, so whichever backend the server was
+// started with must be registered with `Data::from(Arc<dyn Storage>)`.
+//
 // The `implicit_hasher` lint is allowed here because the `HashMap` is created by `actix-web`
 // as part of the query string parsing. We cannot control the hasher used in this case,
 // and the performance impact is negligible for a small number of query parameters.
 #[allow(clippy::implicit_hasher)]
 pub async fn scores(
     query: web::Query<HashMap<String, String>>,
-    storage: Data<SqlStorage>,
+    storage: Data<dyn Storage>,
 ) -> impl Responder {
     let storage_ref = storage.get_ref();
 
@@ -59,7 +63,7 @@ pub async fn scores(
 #[allow(clippy::implicit_hasher)]
 pub async fn scores_summary(
     query: web::Query<HashMap<String, String>>,
-    storage: Data<SqlStorage>,
+    storage: Data<dyn Storage>,
 ) -> impl Responder {
     let storage_ref = storage.get_ref();
 
@@ -91,7 +95,13 @@ pub async fn scores_summary(
     };
 
     let summary = crate::controller::score::group_by_bettor_name_and_round(&data.score_struct);
-    let markup = render_summary_scores(&summary);
+    let markup = render_polled_fragment(
+        "score-summary",
+        &fragment_url("summary", model.event_id, model.year, true),
+        model.refresh_interval(),
+        false,
+        render_summary_scores(&summary),
+    );
     HttpResponse::Ok()
         .content_type("text/html")
         .body(markup.into_string())
@@ -100,7 +110,7 @@ pub async fn scores_summary(
 #[allow(clippy::implicit_hasher)]
 pub async fn scores_chart(
     query: web::Query<HashMap<String, String>>,
-    storage: Data<SqlStorage>,
+    storage: Data<dyn Storage>,
 ) -> impl Responder {
     let storage_ref = storage.get_ref();
     let mut model = match mvu_score::decode_request_to_model(&query, storage_ref).await {
@@ -134,7 +144,13 @@ pub async fn scores_chart(
         crate::controller::score::group_by_bettor_name_and_round(&data.score_struct);
     let detailed_scores =
         crate::controller::score::group_by_bettor_golfer_round(&data.score_struct);
-    let markup = render_drop_down_bar_pure(&summary_scores_x, &detailed_scores, global, factors);
+    let markup = render_polled_fragment(
+        "score-chart",
+        &fragment_url("chart", model.event_id, model.year, model.expanded),
+        model.refresh_interval(),
+        false,
+        render_drop_down_bar_pure(&summary_scores_x, &detailed_scores, global, factors),
+    );
     HttpResponse::Ok()
         .content_type("text/html")
         .body(markup.into_string())
@@ -143,7 +159,7 @@ pub async fn scores_chart(
 #[allow(clippy::implicit_hasher)]
 pub async fn scores_linescore(
     query: web::Query<HashMap<String, String>>,
-    storage: Data<SqlStorage>,
+    storage: Data<dyn Storage>,
 ) -> impl Responder {
     let storage_ref = storage.get_ref();
     let mut model = match mvu_score::decode_request_to_model(&query, storage_ref).await {
@@ -173,7 +189,17 @@ pub async fn scores_linescore(
         last_refresh: data.last_refresh.clone(),
         last_refresh_source: data.last_refresh_source.clone(),
     };
-    let markup = render_line_score_tables(&bettor_struct, &refresh_data);
+    let refresh_interval = model.refresh_interval();
+    let markup = maud::html! {
+        (render_polled_fragment(
+            "linescore",
+            &fragment_url("linescore", model.event_id, model.year, model.expanded),
+            refresh_interval,
+            false,
+            render_line_score_tables(&bettor_struct, &refresh_data),
+        ))
+        (render_next_update(refresh_interval, true))
+    };
     HttpResponse::Ok()
         .content_type("text/html")
         .body(markup.into_string())
@@ -1,7 +1,9 @@
 pub mod data_service;
 pub mod http_handlers;
+pub mod stream;
 
 pub use data_service::*;
 pub use http_handlers::*;
 pub use rusty_golf_core::score::score_aggregators::*;
 pub use rusty_golf_core::score::sort_utils::*;
+pub use stream::{ScoreStreams, scores_stream};
@@ -0,0 +1,238 @@
+//! `/scores/stream`: Server-Sent Events for a live scoreboard.
+//!
+//! One poller per event refreshes scores through the usual cache rules and, whenever a
+//! refresh stored new scores, broadcasts a `standings` event (the `/api/v1` standings) and a
+//! `changes` event (the score changes that refresh detected) to every open stream. Pollers
+//! stop once their last stream closes.
+
+use actix_web::web::{self, Bytes, Data};
+use actix_web::{HttpResponse, Responder};
+use chrono::NaiveDateTime;
+use futures::stream::{self, StreamExt};
+use rusty_golf_core::api::standings_from_scores;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::EspnApiClient;
+use rusty_golf_core::model::{ScoreChange, ScoresAndLastRefresh};
+use rusty_golf_core::score::{cache_max_age_for_event, load_score_context, parse_score_request};
+use rusty_golf_core::storage::Storage;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::controller::espn::ActixEspnClient;
+
+/// How often a poller looks for new scores when none is configured.
+pub const DEFAULT_STREAM_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Sent on idle polls so proxies keep the connection open and closed clients are noticed.
+const KEEP_ALIVE_FRAME: &str = ": keep-alive\n\n";
+
+const CHANNEL_CAPACITY: usize = 16;
+
+/// The open score streams, shared by every worker through `app_data`.
+pub struct ScoreStreams {
+    espn_api: Arc<dyn EspnApiClient>,
+    poll_interval: Duration,
+    channels: Mutex<HashMap<(i32, i32), broadcast::Sender<String>>>,
+}
+
+impl Default for ScoreStreams {
+    fn default() -> Self {
+        Self::new(
+            Arc::new(ActixEspnClient::new()),
+            DEFAULT_STREAM_POLL_INTERVAL,
+        )
+    }
+}
+
+impl ScoreStreams {
+    #[must_use]
+    pub fn new(espn_api: Arc<dyn EspnApiClient>, poll_interval: Duration) -> Self {
+        Self {
+            espn_api,
+            poll_interval,
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Join the event's broadcast, starting its poller if nobody is listening yet.
+    fn subscribe(
+        self: &Arc<Self>,
+        storage: &Data<dyn Storage>,
+        event_id: i32,
+        year: i32,
+        seen: Option<NaiveDateTime>,
+    ) -> broadcast::Receiver<String> {
+        let mut channels = self.channels.lock().expect("score stream lock poisoned");
+        if let Some(sender) = channels.get(&(event_id, year))
+            && sender.receiver_count() > 0
+        {
+            return sender.subscribe();
+        }
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        channels.insert((event_id, year), sender.clone());
+        let streams = Arc::clone(self);
+        let storage = storage.clone();
+        actix_web::rt::spawn(async move {
+            streams
+                .poll(storage.get_ref(), event_id, year, seen, &sender)
+                .await;
+        });
+        receiver
+    }
+
+    async fn poll(
+        &self,
+        storage: &dyn Storage,
+        event_id: i32,
+        year: i32,
+        mut seen: Option<NaiveDateTime>,
+        sender: &broadcast::Sender<String>,
+    ) {
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+            {
+                let mut channels = self.channels.lock().expect("score stream lock poisoned");
+                if sender.receiver_count() == 0 {
+                    if channels
+                        .get(&(event_id, year))
+                        .is_some_and(|current| current.same_channel(sender))
+                    {
+                        channels.remove(&(event_id, year));
+                    }
+                    return;
+                }
+            }
+            let frames =
+                match next_frames(storage, self.espn_api.as_ref(), event_id, year, seen).await {
+                    Ok((refreshed, frames)) => {
+                        seen = Some(refreshed);
+                        frames
+                    }
+                    Err(err) => {
+                        eprintln!("score stream for event {event_id}: {err}");
+                        Vec::new()
+                    }
+                };
+            if frames.is_empty() {
+                let _ = sender.send(KEEP_ALIVE_FRAME.to_string());
+            }
+            for frame in frames {
+                let _ = sender.send(frame);
+            }
+        }
+    }
+}
+
+/// A change as the stream sends it: the stored change plus the sentence the page shows.
+#[derive(Serialize)]
+struct StreamChange<'a> {
+    #[serde(flatten)]
+    change: &'a ScoreChange,
+    message: String,
+}
+
+/// Refresh the event's scores and, if they were stored after `seen`, the frames announcing
+/// them. Also returns when the scores were last refreshed.
+///
+/// # Errors
+/// Returns an error if the scores can't be loaded.
+pub async fn next_frames(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    event_id: i32,
+    year: i32,
+    seen: Option<NaiveDateTime>,
+) -> Result<(NaiveDateTime, Vec<String>), CoreError> {
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context =
+        load_score_context(storage, espn_api, event_id, year, true, cache_max_age).await?;
+    let scores = context.from_db_scores;
+    if seen.is_some_and(|seen| seen >= scores.last_refresh) {
+        return Ok((scores.last_refresh, Vec::new()));
+    }
+    let mut frames = vec![standings_frame(event_id, &scores)];
+    if seen.is_some() {
+        // Changes are best-effort, as they are when a refresh stores them.
+        let changes = storage
+            .get_recent_score_changes(event_id)
+            .await
+            .unwrap_or_default();
+        let fresh: Vec<StreamChange> = changes
+            .iter()
+            .filter(|change| change.detected_at == scores.last_refresh)
+            .rev()
+            .map(|change| StreamChange {
+                change,
+                message: change.to_string(),
+            })
+            .collect();
+        if !fresh.is_empty() {
+            frames.push(sse_frame("changes", &json!(fresh)));
+        }
+    }
+    Ok((scores.last_refresh, frames))
+}
+
+fn standings_frame(event_id: i32, scores: &ScoresAndLastRefresh) -> String {
+    sse_frame("standings", &json!(standings_from_scores(event_id, scores)))
+}
+
+fn sse_frame(event: &str, data: &serde_json::Value) -> String {
+    // Serialized JSON has no raw newlines, so it is always a single `data:` line.
+    format!("event: {event}\ndata: {data}\n\n")
+}
+
+/// `GET /scores/stream?event=&yr=`: the current standings, then an event for every refresh
+/// that stores new scores.
+#[allow(clippy::implicit_hasher)]
+pub async fn scores_stream(
+    query: web::Query<HashMap<String, String>>,
+    storage: Data<dyn Storage>,
+    streams: Data<ScoreStreams>,
+) -> impl Responder {
+    let request = match parse_score_request(&query) {
+        Ok(request) => request,
+        Err(e) => return HttpResponse::BadRequest().json(json!({"error": e.to_string()})),
+    };
+    let (event_id, year) = (request.event_id, request.year);
+    let (refreshed, initial) = match next_frames(
+        storage.get_ref(),
+        streams.espn_api.as_ref(),
+        event_id,
+        year,
+        None,
+    )
+    .await
+    {
+        Ok(first) => first,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({"error": e.to_string()}));
+        }
+    };
+    let receiver = streams
+        .into_inner()
+        .subscribe(&storage, event_id, year, Some(refreshed));
+
+    let updates = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(frame) => return Some((frame, receiver)),
+                // A slow client skips what it missed; the next standings frame catches it up.
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let body = stream::iter(initial)
+        .chain(updates)
+        .map(|frame| Ok::<_, actix_web::Error>(Bytes::from(frame)));
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(body)
+}
@@ -1,7 +1,14 @@
 pub mod model;
 pub mod controller {
+    pub mod api;
+    pub mod archive;
+    pub mod bettor;
+    pub mod calendar;
     pub mod db_prefill;
     pub mod espn;
+    pub mod export;
+    pub mod feed;
+    pub mod golfer;
     pub mod score;
 }
 pub mod storage;
@@ -1,8 +1,20 @@
-use rusty_golf_actix::args;
-use rusty_golf_actix::controller::{db_prefill, score::scores};
+use rusty_golf_actix::args::{self, Command, StorageBackend};
+use rusty_golf_actix::controller::api::{api_v1, openapi_json};
+use rusty_golf_actix::controller::archive::{export_event_to_file, import_event_from_file};
+use rusty_golf_actix::controller::bettor::bettor;
+use rusty_golf_actix::controller::calendar::tee_times;
+use rusty_golf_actix::controller::db_prefill;
+use rusty_golf_actix::controller::export::export;
+use rusty_golf_actix::controller::feed::feed;
+use rusty_golf_actix::controller::golfer::golfer;
+use rusty_golf_actix::controller::score::{
+    ScoreStreams, scores, scores_chart, scores_linescore, scores_stream, scores_summary,
+};
+use rusty_golf_actix::model::migrations::{self, SchemaStatus};
 use rusty_golf_actix::mvu::runtime::run_score;
 use rusty_golf_actix::mvu::score::{Deps, Msg, decode_request_to_model};
-use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_actix::storage::{FileStorage, R2Storage, R2StorageConfig, SqlStorage};
+use rusty_golf_actix::view::events::{load_event_index, render_events_page};
 use rusty_golf_actix::view::index::{
     DEFAULT_INDEX_TITLE, render_index_template_with_scores, try_resolve_index_title,
 };
@@ -10,40 +22,108 @@ use sql_middleware::middleware::{
     ConfigAndPool, DatabaseType, PgConfig, PostgresOptions, SqliteOptions,
 };
 
+use rusty_golf_core::storage::{
+    AdminStorage, CachedStorage, CompactionPolicy, Storage, archive_completed_event,
+};
+
 use actix_files::Files;
 use actix_web::web::Data;
 use actix_web::{App, HttpResponse, HttpServer, Responder, web};
+use chrono::Utc;
+use serde_json::json;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[actix_web::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = args::args_checks();
-    let args_for_web = args.clone();
 
-    let (config_and_pool, db_type) = init_config_and_pool(&args).await?;
-    run_startup_tasks(&args, &config_and_pool, db_type).await?;
+    let storage = init_storage(&args).await?;
+    match args.command.clone() {
+        Some(command) => run_command(command, storage.as_ref()).await,
+        None => serve(storage, args).await,
+    }
+}
 
-    let storage = SqlStorage::new(config_and_pool.clone());
+async fn init_storage(
+    args: &args::CleanArgs,
+) -> Result<Arc<dyn AdminStorage>, Box<dyn std::error::Error>> {
+    // Every page load reads the event's details and picks several times; keep them in memory.
+    Ok(match args.storage {
+        StorageBackend::Sql => {
+            let (config_and_pool, db_type) = init_config_and_pool(args).await?;
+            run_startup_tasks(args, &config_and_pool, db_type).await?;
+            Arc::new(CachedStorage::new(SqlStorage::new(config_and_pool)))
+        }
+        StorageBackend::File => Arc::new(CachedStorage::new(init_file_storage(args).await?)),
+        StorageBackend::R2 => {
+            let config = R2StorageConfig::from_env_with(
+                args.r2_endpoint.clone(),
+                args.r2_bucket.clone(),
+                args.r2_region.clone(),
+            )?;
+            let signer = Arc::new(config.signer());
+            Arc::new(CachedStorage::new(R2Storage::new(config, signer)))
+        }
+    })
+}
 
+async fn run_command(
+    command: Command,
+    storage: &dyn AdminStorage,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Command::ExportEvent { event_id, output } => {
+            let summary = export_event_to_file(storage, event_id, &output).await?;
+            println!("exported {summary} to {}", output.display());
+        }
+        Command::ImportEvent { input, replace } => {
+            let summary = import_event_from_file(storage, &input, replace).await?;
+            println!("imported {summary} from {}", input.display());
+        }
+        Command::ArchiveEvent {
+            event_id,
+            history_interval_seconds,
+        } => {
+            let policy = CompactionPolicy {
+                history_interval_seconds,
+            };
+            let report = archive_completed_event(storage, event_id, &policy).await?;
+            println!(
+                "archived event {event_id}: {} live records removed, {} history snapshots kept, {} pruned",
+                report.records_removed, report.history_kept, report.history_pruned
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn serve(
+    storage: Arc<dyn AdminStorage>,
+    args: args::CleanArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let storage: Data<dyn Storage> = Data::from(storage as Arc<dyn Storage>);
+    // One set of pollers for the whole server, not one per worker.
+    let streams = Data::new(ScoreStreams::default());
     HttpServer::new(move || {
         App::new()
-            .app_data(Data::new(storage.clone()))
-            .app_data(Data::new(args_for_web.clone()))
+            .app_data(storage.clone())
+            .app_data(streams.clone())
+            .app_data(Data::new(args.clone()))
             .route("/", web::get().to(index))
             .route("/scores", web::get().to(scores))
-            .route(
-                "/scores/summary",
-                web::get().to(rusty_golf_actix::controller::score::http_handlers::scores_summary),
-            )
-            .route(
-                "/scores/chart",
-                web::get().to(rusty_golf_actix::controller::score::http_handlers::scores_chart),
-            )
-            .route(
-                "/scores/linescore",
-                web::get().to(rusty_golf_actix::controller::score::http_handlers::scores_linescore),
-            )
+            .route("/scores/summary", web::get().to(scores_summary))
+            .route("/scores/chart", web::get().to(scores_chart))
+            .route("/scores/linescore", web::get().to(scores_linescore))
+            .route("/scores/stream", web::get().to(scores_stream))
+            .route("/bettor", web::get().to(bettor))
+            .route("/golfer", web::get().to(golfer))
+            .route("/export/{table}", web::get().to(export))
+            .route("/tee-times.ics", web::get().to(tee_times))
+            .route("/feed.atom", web::get().to(feed))
             .route("/health", web::get().to(HttpResponse::Ok))
+            .route("/api/v1/{tail:.*}", web::get().to(api_v1))
+            .route("/openapi.json", web::get().to(openapi_json))
             .service(Files::new("/static", "./static").show_files_listing()) // Serve the static files
     })
     .bind("0.0.0.0:5201")?
@@ -54,8 +134,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 async fn index(
     query: web::Query<HashMap<String, String>>,
-    storage: Data<SqlStorage>,
+    storage: Data<dyn Storage>,
 ) -> impl Responder {
+    if !query.contains_key("event") {
+        return events_index(storage.get_ref()).await;
+    }
     let event_str = query.get("event").cloned().unwrap_or_default();
 
     let title = match try_resolve_index_title(storage.get_ref(), &event_str).await {
@@ -103,6 +186,16 @@ async fn index(
         .body(markup.into_string())
 }
 
+/// The landing page without an `event`: every event, grouped by year.
+async fn events_index(storage: &dyn Storage) -> HttpResponse {
+    match load_event_index(storage, Utc::now()).await {
+        Ok(entries) => HttpResponse::Ok()
+            .content_type("text/html")
+            .body(render_events_page(&entries).into_string()),
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error": e.to_string()})),
+    }
+}
+
 async fn init_config_and_pool(
     args: &args::CleanArgs,
 ) -> Result<(ConfigAndPool, DatabaseType), Box<dyn std::error::Error>> {
@@ -133,6 +226,23 @@ async fn init_config_and_pool(
     }
 }
 
+async fn init_file_storage(
+    args: &args::CleanArgs,
+) -> Result<FileStorage, Box<dyn std::error::Error>> {
+    let data_dir = args
+        .data_dir
+        .clone()
+        .ok_or("--data-dir is required with --storage=file")?;
+    std::fs::create_dir_all(&data_dir)?;
+    let storage = FileStorage::new(data_dir);
+
+    if let Some(json_data) = &args.db_populate_json {
+        let added = storage.seed_prefill_json(json_data).await?;
+        println!("file prefill: {added} events added");
+    }
+    Ok(storage)
+}
+
 async fn run_startup_tasks(
     args: &args::CleanArgs,
     config_and_pool: &ConfigAndPool,
@@ -143,8 +253,27 @@ async fn run_startup_tasks(
             .await?;
     }
 
+    if args.migrate {
+        let applied = migrations::run_migrations(config_and_pool).await?;
+        if applied.is_empty() {
+            println!("Schema is up to date.");
+        } else {
+            println!("Applied schema migrations: {applied:?}");
+        }
+    }
+    match migrations::check_schema_version(config_and_pool).await? {
+        SchemaStatus::Behind { current, latest } => eprintln!(
+            "Warning: database schema is at version {current}, latest is {latest}. Restart with --migrate to upgrade."
+        ),
+        SchemaStatus::Unversioned => eprintln!(
+            "Warning: database schema is not versioned. Restart with --migrate to adopt migrations."
+        ),
+        SchemaStatus::Current => {}
+    }
+
     if let Some(json_data) = &args.db_populate_json {
-        db_prefill::db_prefill(json_data, config_and_pool, db_type).await?;
+        let counts = db_prefill::db_prefill(json_data, config_and_pool, db_type).await?;
+        println!("db prefill: {counts}");
     }
 
     Ok(())
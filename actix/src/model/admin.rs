@@ -0,0 +1,354 @@
+use crate::model::database_write::execute_in_transaction;
+use rusty_golf_core::storage::{Assignment, EventConfig, GolferRecord, PlayerStepFactor};
+use sql_middleware::SqlMiddlewareDbError;
+use sql_middleware::middleware::RowValues as RowValues2;
+use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection};
+use std::collections::HashSet;
+
+/// Dialect-specific statements; both variants take the same parameters in the same order.
+/// Every statement keyed by event takes the event's ESPN id as its first parameter.
+struct AdminSql {
+    event_exists: &'static str,
+    golfer_exists: &'static str,
+    picks: &'static str,
+    insert_event: &'static str,
+    update_event: &'static str,
+    delete_statistics: &'static str,
+    delete_score_changes: &'static str,
+    delete_picks: &'static str,
+    delete_archive: &'static str,
+    delete_event: &'static str,
+    clear_last_refresh: &'static str,
+    upsert_golfer: &'static str,
+    insert_bettor: &'static str,
+    insert_pick: &'static str,
+    clear_step_factors: &'static str,
+    set_step_factor: &'static str,
+}
+
+const SQLITE_SQL: AdminSql = AdminSql {
+    event_exists: "SELECT 1 FROM event WHERE espn_id = ?1;",
+    golfer_exists: "SELECT 1 FROM golfer WHERE espn_id = ?1;",
+    picks: "SELECT g.espn_id AS golfer_espn_id, b.name AS bettorname FROM event_user_player AS eup \
+         JOIN event AS e ON e.event_id = eup.event_id JOIN golfer AS g ON g.golfer_id = eup.golfer_id \
+         JOIN bettor AS b ON b.user_id = eup.user_id WHERE e.espn_id = ?1;",
+    insert_event: "INSERT INTO event (espn_id, name, year, score_view_step_factor, refresh_from_espn, start_date, end_date, completed) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);",
+    update_event: "UPDATE event SET name = ?2, year = ?3, score_view_step_factor = ?4, refresh_from_espn = ?5, \
+         start_date = ?6, end_date = ?7, completed = ?8 WHERE espn_id = ?1;",
+    delete_statistics: "DELETE FROM eup_statistic WHERE event_espn_id = ?1;",
+    delete_score_changes: "DELETE FROM score_change WHERE event_espn_id = ?1;",
+    delete_picks: "DELETE FROM event_user_player WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = ?1);",
+    delete_archive: "DELETE FROM event_archive WHERE event_espn_id = ?1;",
+    delete_event: "DELETE FROM event WHERE espn_id = ?1;",
+    clear_last_refresh: "UPDATE event SET last_refresh_ts = NULL WHERE espn_id = ?1;",
+    upsert_golfer: "INSERT INTO golfer (espn_id, name) VALUES (?1, ?2) \
+         ON CONFLICT (espn_id) DO UPDATE SET name = excluded.name;",
+    insert_bettor: "INSERT INTO bettor (name) SELECT ?1 WHERE NOT EXISTS (SELECT 1 FROM bettor WHERE name = ?1);",
+    insert_pick: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         SELECT (SELECT event_id FROM event WHERE espn_id = ?1), (SELECT MIN(user_id) FROM bettor WHERE name = ?2), \
+         (SELECT golfer_id FROM golfer WHERE espn_id = ?3), NULL;",
+    clear_step_factors: "UPDATE event_user_player SET score_view_step_factor = NULL \
+         WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = ?1);",
+    set_step_factor: "UPDATE event_user_player SET score_view_step_factor = ?4 \
+         WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = ?1) \
+         AND golfer_id IN (SELECT golfer_id FROM golfer WHERE espn_id = ?2) \
+         AND user_id IN (SELECT user_id FROM bettor WHERE name = ?3);",
+};
+
+const POSTGRES_SQL: AdminSql = AdminSql {
+    event_exists: "SELECT 1 FROM event WHERE espn_id = $1;",
+    golfer_exists: "SELECT 1 FROM golfer WHERE espn_id = $1;",
+    picks: "SELECT g.espn_id AS golfer_espn_id, b.name AS bettorname FROM event_user_player AS eup \
+         JOIN event AS e ON e.event_id = eup.event_id JOIN golfer AS g ON g.golfer_id = eup.golfer_id \
+         JOIN bettor AS b ON b.user_id = eup.user_id WHERE e.espn_id = $1;",
+    insert_event: "INSERT INTO event (espn_id, name, year, score_view_step_factor, refresh_from_espn, start_date, end_date, completed) \
+         VALUES ($1, $2, $3, $4::float8, $5, $6::text::timestamptz AT TIME ZONE 'utc', $7::text::timestamptz AT TIME ZONE 'utc', $8);",
+    update_event: "UPDATE event SET name = $2, year = $3, score_view_step_factor = $4::float8, refresh_from_espn = $5, \
+         start_date = $6::text::timestamptz AT TIME ZONE 'utc', end_date = $7::text::timestamptz AT TIME ZONE 'utc', \
+         completed = $8 WHERE espn_id = $1;",
+    delete_statistics: "DELETE FROM eup_statistic WHERE event_espn_id = $1;",
+    delete_score_changes: "DELETE FROM score_change WHERE event_espn_id = $1;",
+    delete_picks: "DELETE FROM event_user_player WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = $1);",
+    delete_archive: "DELETE FROM event_archive WHERE event_espn_id = $1;",
+    delete_event: "DELETE FROM event WHERE espn_id = $1;",
+    clear_last_refresh: "UPDATE event SET last_refresh_ts = NULL WHERE espn_id = $1;",
+    upsert_golfer: "INSERT INTO golfer (espn_id, name) VALUES ($1, $2::text) \
+         ON CONFLICT (espn_id) DO UPDATE SET name = excluded.name;",
+    insert_bettor: "INSERT INTO bettor (name) SELECT $1::text WHERE NOT EXISTS (SELECT 1 FROM bettor WHERE name = $1::text);",
+    insert_pick: "INSERT INTO event_user_player (event_id, user_id, golfer_id, score_view_step_factor) \
+         SELECT (SELECT event_id FROM event WHERE espn_id = $1), (SELECT MIN(user_id) FROM bettor WHERE name = $2::text), \
+         (SELECT golfer_id FROM golfer WHERE espn_id = $3), NULL;",
+    clear_step_factors: "UPDATE event_user_player SET score_view_step_factor = NULL \
+         WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = $1);",
+    set_step_factor: "UPDATE event_user_player SET score_view_step_factor = $4::float8 \
+         WHERE event_id IN (SELECT event_id FROM event WHERE espn_id = $1) \
+         AND golfer_id IN (SELECT golfer_id FROM golfer WHERE espn_id = $2) \
+         AND user_id IN (SELECT user_id FROM bettor WHERE name = $3::text);",
+};
+
+type Statement = (&'static str, Vec<RowValues2>);
+
+fn admin_sql(conn: &MiddlewarePoolConnection) -> &'static AdminSql {
+    match conn {
+        MiddlewarePoolConnection::Postgres { .. } => &POSTGRES_SQL,
+        MiddlewarePoolConnection::Sqlite { .. } => &SQLITE_SQL,
+    }
+}
+
+/// Run `statements` in order in one transaction, returning the rows each one changed.
+async fn run_statements(
+    conn: &mut MiddlewarePoolConnection,
+    statements: &[Statement],
+) -> Result<Vec<usize>, SqlMiddlewareDbError> {
+    let statements: Vec<(&str, &[RowValues2])> = statements
+        .iter()
+        .map(|(query, params)| (*query, params.as_slice()))
+        .collect();
+    execute_in_transaction(conn, &statements).await
+}
+
+async fn exists(
+    conn: &mut MiddlewarePoolConnection,
+    query: &str,
+    id: i64,
+) -> Result<bool, SqlMiddlewareDbError> {
+    let params = [RowValues2::Int(id)];
+    let result_set = conn.query(query).params(&params).select().await?;
+    Ok(!result_set.results.is_empty())
+}
+
+async fn require_event(
+    conn: &mut MiddlewarePoolConnection,
+    sql: &AdminSql,
+    event_id: i32,
+) -> Result<(), SqlMiddlewareDbError> {
+    if exists(conn, sql.event_exists, i64::from(event_id)).await? {
+        Ok(())
+    } else {
+        Err(SqlMiddlewareDbError::Other(format!(
+            "event {event_id} not found"
+        )))
+    }
+}
+
+fn event_params(event_id: i32, config: &EventConfig) -> Vec<RowValues2> {
+    vec![
+        RowValues2::Int(i64::from(event_id)),
+        RowValues2::Text(config.event_name.clone()),
+        RowValues2::Int(i64::from(config.year)),
+        RowValues2::Float(f64::from(config.score_view_step_factor)),
+        RowValues2::Int(config.refresh_from_espn),
+        optional_text(config.start_date.as_deref()),
+        optional_text(config.end_date.as_deref()),
+        RowValues2::Int(i64::from(config.completed)),
+    ]
+}
+
+fn optional_text(value: Option<&str>) -> RowValues2 {
+    value.map_or(RowValues2::Null, |v| RowValues2::Text(v.to_string()))
+}
+
+/// # Errors
+///
+/// Will return `Err` if the event already exists or the database query fails
+pub async fn create_event_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    config: &EventConfig,
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    if exists(&mut conn, sql.event_exists, i64::from(event_id)).await? {
+        return Err(SqlMiddlewareDbError::Other(format!(
+            "event {event_id} already exists"
+        )));
+    }
+    let params = event_params(event_id, config);
+    conn.query(sql.insert_event).params(&params).dml().await?;
+    Ok(())
+}
+
+/// # Errors
+///
+/// Will return `Err` if the event doesn't exist or the database query fails
+pub async fn update_event_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    config: &EventConfig,
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    let params = event_params(event_id, config);
+    let updated = conn.query(sql.update_event).params(&params).dml().await?;
+    if updated == 0 {
+        return Err(SqlMiddlewareDbError::Other(format!(
+            "event {event_id} not found"
+        )));
+    }
+    Ok(())
+}
+
+/// Delete the event with its picks, statistics, score changes and archive. Returns whether
+/// the event existed. `eup_statistic_hx` is an audit trail and is left alone.
+///
+/// # Errors
+///
+/// Will return `Err` if the database query fails
+pub async fn delete_event_from_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+) -> Result<bool, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    let id = || vec![RowValues2::Int(i64::from(event_id))];
+    let counts = run_statements(
+        &mut conn,
+        &[
+            (sql.delete_statistics, id()),
+            (sql.delete_score_changes, id()),
+            (sql.delete_picks, id()),
+            (sql.delete_archive, id()),
+            (sql.delete_event, id()),
+        ],
+    )
+    .await?;
+    Ok(counts.last().is_some_and(|deleted| *deleted > 0))
+}
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails, e.g. a new name collides with another
+/// golfer's
+pub async fn upsert_golfers_in_db(
+    config_and_pool: &ConfigAndPool,
+    golfers: &[GolferRecord],
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    let statements: Vec<Statement> = golfers
+        .iter()
+        .map(|golfer| {
+            (
+                sql.upsert_golfer,
+                vec![
+                    RowValues2::Int(golfer.espn_id),
+                    RowValues2::Text(golfer.name.clone()),
+                ],
+            )
+        })
+        .collect();
+    run_statements(&mut conn, &statements).await?;
+    Ok(())
+}
+
+/// Replace the event's picks, creating bettors as needed. Stored statistics and the last
+/// refresh are cleared because they belong to the old picks.
+///
+/// # Errors
+///
+/// Will return `Err` if the event or a golfer doesn't exist or the database query fails
+pub async fn set_assignments_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    assignments: &[Assignment],
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    require_event(&mut conn, sql, event_id).await?;
+    let mut checked = HashSet::new();
+    for assignment in assignments {
+        let golfer = assignment.golfer_espn_id;
+        if checked.insert(golfer) && !exists(&mut conn, sql.golfer_exists, golfer).await? {
+            return Err(SqlMiddlewareDbError::Other(format!(
+                "event {event_id}: unknown golfer espn_id {golfer}"
+            )));
+        }
+    }
+
+    let id = || vec![RowValues2::Int(i64::from(event_id))];
+    let mut statements: Vec<Statement> = vec![
+        (sql.delete_statistics, id()),
+        (sql.delete_picks, id()),
+        (sql.clear_last_refresh, id()),
+    ];
+    let mut bettors = HashSet::new();
+    for assignment in assignments {
+        if bettors.insert(assignment.bettor_name.as_str()) {
+            statements.push((
+                sql.insert_bettor,
+                vec![RowValues2::Text(assignment.bettor_name.clone())],
+            ));
+        }
+        statements.push((
+            sql.insert_pick,
+            vec![
+                RowValues2::Int(i64::from(event_id)),
+                RowValues2::Text(assignment.bettor_name.clone()),
+                RowValues2::Int(assignment.golfer_espn_id),
+            ],
+        ));
+    }
+    run_statements(&mut conn, &statements).await?;
+    Ok(())
+}
+
+/// Replace every per-pick step factor on the event.
+///
+/// # Errors
+///
+/// Will return `Err` if the event doesn't exist, a factor names a pick the event doesn't
+/// have, or the database query fails
+pub async fn set_player_step_factors_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    factors: &[PlayerStepFactor],
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let sql = admin_sql(&conn);
+    require_event(&mut conn, sql, event_id).await?;
+    let params = [RowValues2::Int(i64::from(event_id))];
+    let picks: HashSet<(i64, String)> = conn
+        .query(sql.picks)
+        .params(&params)
+        .select()
+        .await?
+        .results
+        .iter()
+        .filter_map(|row| {
+            let golfer_espn_id = row
+                .get("golfer_espn_id")
+                .and_then(|v| v.as_int())
+                .copied()?;
+            let bettor_name = row.get("bettorname").and_then(|v| v.as_text())?.to_string();
+            Some((golfer_espn_id, bettor_name))
+        })
+        .collect();
+    if let Some(missing) = factors
+        .iter()
+        .find(|factor| !picks.contains(&(factor.golfer_espn_id, factor.bettor_name.clone())))
+    {
+        return Err(SqlMiddlewareDbError::Other(format!(
+            "event {event_id}: no pick of golfer {} by {}",
+            missing.golfer_espn_id, missing.bettor_name
+        )));
+    }
+
+    let mut statements: Vec<Statement> = vec![(
+        sql.clear_step_factors,
+        vec![RowValues2::Int(i64::from(event_id))],
+    )];
+    statements.extend(factors.iter().map(|factor| {
+        (
+            sql.set_step_factor,
+            vec![
+                RowValues2::Int(i64::from(event_id)),
+                RowValues2::Int(factor.golfer_espn_id),
+                RowValues2::Text(factor.bettor_name.clone()),
+                RowValues2::Float(f64::from(factor.step_factor)),
+            ],
+        )
+    }));
+    run_statements(&mut conn, &statements).await?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use chrono::NaiveDateTime;
+use rusty_golf_core::storage::{CompactionPolicy, CompactionReport, EventArchive};
+use sql_middleware::SqlMiddlewareDbError;
+use sql_middleware::middleware::RowValues as RowValues2;
+use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection};
+use std::collections::HashSet;
+
+use crate::model::database_read::{execute_query, parse_json_field};
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails or the stored archive cannot be parsed
+pub async fn get_event_archive_from_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+) -> Result<Option<EventArchive>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let query = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            "SELECT archive::text AS archive FROM event_archive WHERE event_espn_id = $1;"
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            "SELECT archive FROM event_archive WHERE event_espn_id = ?1;"
+        }
+    };
+    let res = execute_query(&mut conn, query, vec![RowValues2::Int(i64::from(event_id))]).await?;
+    res.results
+        .first()
+        .map(|row| parse_json_field(row, "archive"))
+        .transpose()
+}
+
+/// Store the archive and thin the event's `eup_statistic_hx` to one refresh per
+/// `policy.history_interval_seconds`. The relational rows stay: every query already reads
+/// them, and `delete_event_from_db` still owns their cleanup.
+///
+/// # Errors
+///
+/// Will return `Err` if the event is already archived, the archive cannot be serialized, or
+/// the database query fails
+pub async fn archive_event_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    archive: &EventArchive,
+    policy: &CompactionPolicy,
+) -> Result<CompactionReport, SqlMiddlewareDbError> {
+    let archive_json = serde_json::to_string(archive)
+        .map_err(|e| SqlMiddlewareDbError::Other(format!("Failed to serialize archive: {e}")))?;
+    let mut conn = config_and_pool.get_connection().await?;
+    let (insert, refreshes, prune) = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => (
+            "INSERT INTO event_archive (event_espn_id, archive) VALUES ($1, $2::jsonb);",
+            "SELECT DISTINCT hx_ts FROM eup_statistic_hx WHERE event_espn_id = $1 ORDER BY hx_ts;",
+            "DELETE FROM eup_statistic_hx WHERE event_espn_id = $1 AND hx_ts = $2;",
+        ),
+        MiddlewarePoolConnection::Sqlite { .. } => (
+            "INSERT INTO event_archive (event_espn_id, archive) VALUES (?1, ?2);",
+            "SELECT DISTINCT hx_ts FROM eup_statistic_hx WHERE event_espn_id = ?1 ORDER BY hx_ts;",
+            "DELETE FROM eup_statistic_hx WHERE event_espn_id = ?1 AND hx_ts = ?2;",
+        ),
+    };
+
+    let id = RowValues2::Int(i64::from(event_id));
+    // Every row a refresh writes shares its hx_ts, so each distinct one is a snapshot.
+    let snapshots: Vec<(NaiveDateTime, RowValues2)> =
+        execute_query(&mut conn, refreshes, vec![id.clone()])
+            .await?
+            .results
+            .iter()
+            .filter_map(|row| {
+                let value = row.get("hx_ts")?.clone();
+                Some((hx_timestamp(&value)?, value))
+            })
+            .collect();
+    let taken: Vec<NaiveDateTime> = snapshots.iter().map(|(taken_at, _)| *taken_at).collect();
+    let pruned: HashSet<NaiveDateTime> = policy.history_to_prune(&taken).into_iter().collect();
+
+    conn.query(insert)
+        .params(&[id.clone(), RowValues2::Text(archive_json)])
+        .dml()
+        .await?;
+    for (_, value) in snapshots
+        .iter()
+        .filter(|(taken_at, _)| pruned.contains(taken_at))
+    {
+        conn.query(prune)
+            .params(&[id.clone(), value.clone()])
+            .dml()
+            .await?;
+    }
+
+    Ok(CompactionReport {
+        event_id,
+        records_removed: 0,
+        history_kept: snapshots.len() - pruned.len(),
+        history_pruned: pruned.len(),
+    })
+}
+
+/// SQLite keeps `hx_ts` as `CURRENT_TIMESTAMP` text; Postgres returns a timestamp.
+pub(crate) fn hx_timestamp(value: &RowValues2) -> Option<NaiveDateTime> {
+    value
+        .as_timestamp()
+        .or_else(|| NaiveDateTime::parse_from_str(value.as_text()?, "%Y-%m-%d %H:%M:%S").ok())
+}
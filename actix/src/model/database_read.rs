@@ -48,6 +48,8 @@ pub async fn execute_query(
     conn.query(query).params(params.as_slice()).select().await
 }
 
+/// Every stored score for the event; empty if none were stored yet.
+///
 /// # Errors
 ///
 /// Will return `Err` if the database query fails
@@ -88,7 +90,7 @@ fn get_scores_query(conn: &MiddlewarePoolConnection) -> &'static str {
     }
 }
 
-fn build_score_from_row(row: &CustomDbRow) -> Result<Scores, SqlMiddlewareDbError> {
+pub(crate) fn build_score_from_row(row: &CustomDbRow) -> Result<Scores, SqlMiddlewareDbError> {
     Ok(Scores {
         group: row
             .get("grp")
@@ -1,6 +1,6 @@
-use sql_middleware::SqlMiddlewareDbError;
 use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection};
 use sql_middleware::middleware::{QueryAndParams as QueryAndParams2, RowValues as RowValues2};
+use sql_middleware::{SqlMiddlewareDbError, postgres, sqlite};
 
 use crate::model::types::Scores;
 
@@ -16,6 +16,9 @@ pub async fn execute_batch_sql(
     conn.execute_batch(query).await
 }
 
+/// Upsert the event's scores and stamp its last refresh in one transaction, so a failed
+/// refresh leaves the previous scores in place rather than a mix of old and new.
+///
 /// # Errors
 ///
 /// Will return `Err` if the database query fails
@@ -25,33 +28,49 @@ pub async fn store_scores_in_db(
     scores: &[Scores],
 ) -> Result<(), SqlMiddlewareDbError> {
     let mut conn = config_and_pool.get_connection().await?;
-    let queries = build_insert_queries(scores, event_id)?;
+    let insert_stmt = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            include_str!("../sql/functions/postgres/04_sp_set_eup_statistic.sql")
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            include_str!("../sql/functions/sqlite/04_sp_set_eup_statistic.sql")
+        }
+    };
+    let mut queries = build_insert_queries(scores, event_id, insert_stmt)?;
 
     if queries.is_empty() {
         return Ok(());
     }
 
-    match &mut conn {
-        sqlite_conn @ MiddlewarePoolConnection::Sqlite { .. } => {
-            execute_sqlite_queries(sqlite_conn, queries).await?;
-        }
-        MiddlewarePoolConnection::Postgres { .. } => {
-            return Err(SqlMiddlewareDbError::Other(
-                "Database type not supported for this operation".to_string(),
-            ));
+    let last_refresh = QueryAndParams2 {
+        query: match &conn {
+            MiddlewarePoolConnection::Postgres { .. } => {
+                include_str!("../sql/functions/postgres/06_sp_set_event_last_refresh.sql")
+            }
+            MiddlewarePoolConnection::Sqlite { .. } => {
+                include_str!("../sql/functions/sqlite/09_sp_set_event_last_refresh.sql")
+            }
         }
-    }
+        .to_string(),
+        params: vec![RowValues2::Int(i64::from(event_id))],
+    };
+
+    queries.push(last_refresh);
+    let queries: Vec<(&str, &[RowValues2])> = queries
+        .iter()
+        .map(|query| (query.query.as_str(), query.params.as_slice()))
+        .collect();
+    execute_in_transaction(&mut conn, &queries).await?;
     Ok(())
 }
 
 fn build_insert_queries(
     scores: &[Scores],
     event_id: i32,
+    insert_stmt: &str,
 ) -> Result<Vec<QueryAndParams2>, SqlMiddlewareDbError> {
     let mut queries = vec![];
     for score in scores {
-        let insert_stmt = include_str!("../sql/functions/sqlite/04_sp_set_eup_statistic.sql");
-
         let rounds_json = serde_json::to_string(&score.detailed_statistics.rounds)
             .map_err(|e| SqlMiddlewareDbError::Other(format!("Failed to serialize rounds: {e}")))?;
 
@@ -97,44 +116,106 @@ fn build_insert_queries(
     Ok(queries)
 }
 
-async fn execute_sqlite_queries(
-    sqlite_conn: &mut MiddlewarePoolConnection,
-    queries: Vec<QueryAndParams2>,
-) -> Result<(), SqlMiddlewareDbError> {
-    let Some(first) = queries.first() else {
-        return Ok(());
-    };
+/// An open transaction on either dialect.
+///
+/// Dropping it without [`Transaction::commit`] rolls back, so an early `?` return never
+/// leaves partial writes behind.
+pub(crate) enum Transaction<'conn> {
+    Postgres(postgres::Tx<'conn>),
+    Sqlite(sqlite::Tx<'conn>),
+}
 
-    let insert_sql = first.query.clone();
-    let mut prepared = sqlite_conn.prepare_sqlite_statement(&insert_sql).await?;
+impl<'conn> Transaction<'conn> {
+    /// # Errors
+    ///
+    /// Will return `Err` if the transaction cannot be started
+    pub(crate) async fn begin(
+        conn: &'conn mut MiddlewarePoolConnection,
+    ) -> Result<Self, SqlMiddlewareDbError> {
+        match conn {
+            MiddlewarePoolConnection::Postgres { client, .. } => {
+                Ok(Self::Postgres(postgres::begin_transaction(client).await?))
+            }
+            sqlite_conn @ MiddlewarePoolConnection::Sqlite { .. } => {
+                Ok(Self::Sqlite(sqlite::begin_transaction(sqlite_conn).await?))
+            }
+        }
+    }
 
-    for query in queries {
-        prepared.execute(&query.params).await?;
+    /// Run one statement, returning the number of rows it touched.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the database query fails
+    pub(crate) async fn dml(
+        &mut self,
+        query: &str,
+        params: &[RowValues2],
+    ) -> Result<usize, SqlMiddlewareDbError> {
+        match self {
+            Self::Postgres(tx) => tx.execute_dml(query, params).await,
+            Self::Sqlite(tx) => {
+                let prepared = tx.prepare(query)?;
+                tx.execute(&prepared).params(params).run().await
+            }
+        }
     }
 
-    Ok(())
+    /// # Errors
+    ///
+    /// Will return `Err` if the commit fails
+    pub(crate) async fn commit(self) -> Result<(), SqlMiddlewareDbError> {
+        match self {
+            Self::Postgres(tx) => tx.commit().await,
+            Self::Sqlite(tx) => tx.commit().await,
+        }
+        .map(|_| ())
+    }
 }
 
+/// Run `queries` in one transaction, returning the rows each one touched. Either every
+/// query is applied or none are.
+///
+/// # Errors
+///
+/// Will return `Err` if any query fails; nothing is written in that case
+pub(crate) async fn execute_in_transaction(
+    conn: &mut MiddlewarePoolConnection,
+    queries: &[(&str, &[RowValues2])],
+) -> Result<Vec<usize>, SqlMiddlewareDbError> {
+    let mut tx = Transaction::begin(conn).await?;
+    let mut counts = Vec::with_capacity(queries.len());
+    for (query, params) in queries {
+        counts.push(tx.dml(query, params).await?);
+    }
+    tx.commit().await?;
+    Ok(counts)
+}
+
+/// Whether the event's scores were refreshed within the last `max_age_seconds`.
+///
+/// Uses the event's `last_refresh_ts`, falling back to the newest `eup_statistic` row for
+/// events last written before that column existed. A non-positive age is never fresh; the
+/// "serve from storage forever" case (-1) is handled by the caller.
+///
 /// # Errors
 ///
 /// Will return `Err` if the database query fails
 pub async fn event_and_scores_already_in_db(
     config_and_pool: &ConfigAndPool,
     event_id: i32,
-    cache_max_age: i64,
+    max_age_seconds: i64,
 ) -> Result<bool, SqlMiddlewareDbError> {
     use crate::model::database_read::execute_query;
-    use crate::model::event::get_event_details;
-    use sql_middleware::middleware::RowValues as RowValues2;
 
-    if get_event_details(config_and_pool, event_id).await.is_err() {
+    if max_age_seconds <= 0 {
         return Ok(false);
     }
 
     let mut conn = config_and_pool.get_connection().await?;
     let query = match &conn {
         MiddlewarePoolConnection::Postgres { .. } => {
-            "SELECT min(ins_ts) as ins_ts FROM eup_statistic WHERE event_espn_id = $1;"
+            include_str!("../sql/functions/postgres/05_sp_get_event_and_scores_already_in_db.sql")
         }
         MiddlewarePoolConnection::Sqlite { .. } => {
             include_str!("../sql/functions/sqlite/05_sp_get_event_and_scores_already_in_db.sql")
@@ -144,30 +225,30 @@ pub async fn event_and_scores_already_in_db(
     let query_result =
         execute_query(&mut conn, query, vec![RowValues2::Int(i64::from(event_id))]).await?;
 
-    if let Some(results) = query_result.results.first() {
-        let now = chrono::Utc::now().naive_utc();
-        let val = results
-            .get("ins_ts")
-            .and_then(sql_middleware::RowValues::as_timestamp);
-        if let Some(final_val) = val {
-            if cfg!(debug_assertions) {
-                let now_human_readable_fmt = now.format("%Y-%m-%d %H:%M:%S").to_string();
-                let z_clone = final_val;
-                let z_human_readable_fmt = z_clone.format("%Y-%m-%d %H:%M:%S").to_string();
-                let diff = now.signed_duration_since(z_clone);
-                let diff_days = diff.num_days();
-                let pass = diff_days >= cache_max_age;
-                println!(
-                    "Now: {now_human_readable_fmt}, Last Refresh: {z_human_readable_fmt}, Diff: {diff_days} days, Pass: {pass}"
-                );
-            }
-
-            let diff = now.signed_duration_since(final_val);
-            Ok(diff.num_days() >= cache_max_age)
-        } else {
-            Ok(false)
-        }
-    } else {
-        Ok(false)
+    let Some(row) = query_result.results.first() else {
+        return Ok(false);
+    };
+    // No stored scores means nothing to serve, whatever the refresh timestamp says.
+    let Some(newest_score) = row
+        .get("ins_ts")
+        .and_then(sql_middleware::RowValues::as_timestamp)
+    else {
+        return Ok(false);
+    };
+    let last_refresh = row
+        .get("last_refresh_ts")
+        .and_then(sql_middleware::RowValues::as_timestamp)
+        .unwrap_or(newest_score);
+
+    let now = chrono::Utc::now().naive_utc();
+    let age_seconds = now.signed_duration_since(last_refresh).num_seconds();
+    let fresh = age_seconds <= max_age_seconds;
+    if cfg!(debug_assertions) {
+        println!(
+            "Now: {}, Last Refresh: {}, Age: {age_seconds}s, Max Age: {max_age_seconds}s, Fresh: {fresh}",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            last_refresh.format("%Y-%m-%d %H:%M:%S")
+        );
     }
+    Ok(fresh)
 }
@@ -9,6 +9,31 @@ pub struct EventTitleAndScoreViewConf {
     pub refresh_from_espn: i64,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub completed: bool,
+}
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails
+pub async fn list_event_ids(
+    config_and_pool: &ConfigAndPool,
+) -> Result<Vec<i32>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let res = execute_query(
+        &mut conn,
+        "SELECT espn_id FROM event ORDER BY espn_id;",
+        vec![],
+    )
+    .await?;
+    res.results
+        .iter()
+        .map(|row| {
+            row.get("espn_id")
+                .and_then(|v| v.as_int())
+                .and_then(|v| i32::try_from(*v).ok())
+                .ok_or(SqlMiddlewareDbError::Other("espn_id not found".to_string()))
+        })
+        .collect()
 }
 
 /// # Errors
@@ -22,7 +47,9 @@ pub async fn get_event_details(
 
     let query = match &conn {
         MiddlewarePoolConnection::Postgres { .. } => {
-            "SELECT name AS eventname, ins_ts, score_view_step_factor, refresh_from_espn, end_date \
+            "SELECT name AS eventname, ins_ts, score_view_step_factor::float8 AS score_view_step_factor, \
+             refresh_from_espn, to_char(start_date, 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') AS start_date, \
+             to_char(end_date, 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"') AS end_date, completed \
              FROM event WHERE espn_id = $1"
         }
         MiddlewarePoolConnection::Sqlite { .. } => {
@@ -54,11 +81,18 @@ pub async fn get_event_details(
                     .ok_or(SqlMiddlewareDbError::Other(
                         "Refresh from ESPN flag not found".to_string(),
                     ))?,
-                start_date: None,
+                start_date: row
+                    .get("start_date")
+                    .and_then(|v| v.as_text())
+                    .map(ToString::to_string),
                 end_date: row
                     .get("end_date")
                     .and_then(|v| v.as_text())
                     .map(ToString::to_string),
+                completed: row
+                    .get("completed")
+                    .and_then(|v| v.as_int())
+                    .is_some_and(|v| *v != 0),
             })
         })
         .next_back()
@@ -66,3 +100,35 @@ pub async fn get_event_details(
             "No results found".to_string(),
         )))
 }
+
+/// Mark an event completed, filling in `end_date` only if it was never set.
+///
+/// # Errors
+///
+/// Will return `Err` if the database query fails or the event doesn't exist
+pub async fn mark_event_completed_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    end_date: Option<&str>,
+) -> Result<(), SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let query = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            include_str!("../sql/functions/postgres/07_sp_set_event_completed.sql")
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            include_str!("../sql/functions/sqlite/10_sp_set_event_completed.sql")
+        }
+    };
+    let params = [
+        RowValues2::Int(i64::from(event_id)),
+        end_date.map_or(RowValues2::Null, |date| RowValues2::Text(date.to_string())),
+    ];
+    let updated = conn.query(query).params(&params).dml().await?;
+    if updated == 0 {
+        return Err(SqlMiddlewareDbError::Other(format!(
+            "event {event_id} not found"
+        )));
+    }
+    Ok(())
+}
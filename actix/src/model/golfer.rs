@@ -63,6 +63,9 @@ pub async fn get_golfers_from_db(
     Ok(scores)
 }
 
+/// Per-pick step factors, read from the picks themselves so they're there before any
+/// scores are stored.
+///
 /// # Errors
 ///
 /// Will return `Err` if the database query fails
@@ -72,7 +75,25 @@ pub async fn get_player_step_factors(
 ) -> Result<HashMap<(i64, String), f32>, SqlMiddlewareDbError> {
     let mut conn = config_and_pool.get_connection().await?;
 
-    let query = include_str!("../sql/functions/sqlite/03_sp_get_scores.sql");
+    let query = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            "SELECT g.espn_id AS golfer_espn_id, b.name AS bettorname, \
+             eup.score_view_step_factor::float8 AS score_view_step_factor \
+             FROM event_user_player AS eup \
+             JOIN event AS e ON e.event_id = eup.event_id \
+             JOIN golfer AS g ON g.golfer_id = eup.golfer_id \
+             JOIN bettor AS b ON b.user_id = eup.user_id \
+             WHERE e.espn_id = $1 AND eup.score_view_step_factor IS NOT NULL"
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            "SELECT g.espn_id AS golfer_espn_id, b.name AS bettorname, eup.score_view_step_factor \
+             FROM event_user_player AS eup \
+             JOIN event AS e ON e.event_id = eup.event_id \
+             JOIN golfer AS g ON g.golfer_id = eup.golfer_id \
+             JOIN bettor AS b ON b.user_id = eup.user_id \
+             WHERE e.espn_id = ?1 AND eup.score_view_step_factor IS NOT NULL"
+        }
+    };
 
     let query_result =
         execute_query(&mut conn, query, vec![RowValues2::Int(i64::from(event_id))]).await?;
@@ -0,0 +1,261 @@
+use sql_middleware::SqlMiddlewareDbError;
+use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection, RowValues};
+
+/// One forward-only schema step, written once per dialect.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    sqlite: &'static str,
+    postgres: &'static str,
+    /// SQLite has no `ADD COLUMN IF NOT EXISTS`, so column additions are listed here and only
+    /// applied when missing; databases built from the current schema files already have them.
+    sqlite_columns: &'static [AddColumn],
+}
+
+struct AddColumn {
+    table: &'static str,
+    column: &'static str,
+    definition: &'static str,
+}
+
+/// Every migration in order. Append new entries; never edit one that has shipped.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        sqlite: include_str!("../sql/migrations/sqlite/0001_baseline.sql"),
+        postgres: include_str!("../sql/migrations/postgres/0001_baseline.sql"),
+        sqlite_columns: &[],
+    },
+    Migration {
+        version: 2,
+        name: "event_start_date",
+        sqlite: include_str!("../sql/migrations/sqlite/0002_event_start_date.sql"),
+        postgres: include_str!("../sql/migrations/postgres/0002_event_start_date.sql"),
+        sqlite_columns: &[],
+    },
+    Migration {
+        version: 3,
+        name: "event_last_refresh",
+        sqlite: "",
+        postgres: include_str!("../sql/migrations/postgres/0003_event_last_refresh.sql"),
+        sqlite_columns: &[AddColumn {
+            table: "event",
+            column: "last_refresh_ts",
+            definition: "DATETIME",
+        }],
+    },
+    Migration {
+        version: 4,
+        name: "event_completed",
+        sqlite: "",
+        postgres: include_str!("../sql/migrations/postgres/0004_event_completed.sql"),
+        sqlite_columns: &[AddColumn {
+            table: "event",
+            column: "completed",
+            definition: "INTEGER NOT NULL DEFAULT 0",
+        }],
+    },
+    Migration {
+        version: 5,
+        name: "event_archive",
+        sqlite: include_str!("../sql/migrations/sqlite/0005_event_archive.sql"),
+        postgres: include_str!("../sql/migrations/postgres/0005_event_archive.sql"),
+        sqlite_columns: &[],
+    },
+];
+
+/// Where the database stands relative to the migrations compiled into this binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaStatus {
+    /// No `schema_version` table; the schema was built by hand or by a startup script.
+    Unversioned,
+    Behind {
+        current: i64,
+        latest: i64,
+    },
+    Current,
+}
+
+#[must_use]
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails
+pub async fn current_schema_version(
+    config_and_pool: &ConfigAndPool,
+) -> Result<Option<i64>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    read_schema_version(&mut conn).await
+}
+
+/// Compare the database schema with this binary.
+///
+/// # Errors
+///
+/// Will return `Err` if the database query fails, or if the database was migrated by a newer
+/// build than this one; serving from a schema we don't understand risks corrupting it.
+pub async fn check_schema_version(
+    config_and_pool: &ConfigAndPool,
+) -> Result<SchemaStatus, SqlMiddlewareDbError> {
+    let latest = latest_schema_version();
+    match current_schema_version(config_and_pool).await? {
+        None => Ok(SchemaStatus::Unversioned),
+        Some(current) if current > latest => Err(newer_schema_error(current, latest)),
+        Some(current) if current < latest => Ok(SchemaStatus::Behind { current, latest }),
+        Some(_) => Ok(SchemaStatus::Current),
+    }
+}
+
+/// Apply every migration newer than the recorded schema version, returning the versions applied.
+///
+/// Each migration and its `schema_version` row are sent as one batch, which both backends run
+/// in a single transaction, so a failed step leaves the database at the previous version.
+///
+/// # Errors
+///
+/// Will return `Err` if a migration fails or the database schema is newer than this binary
+pub async fn run_migrations(
+    config_and_pool: &ConfigAndPool,
+) -> Result<Vec<i64>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let is_postgres = matches!(conn, MiddlewarePoolConnection::Postgres { .. });
+    let create_table = if is_postgres {
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version BIGINT NOT NULL PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_ts TIMESTAMP NOT NULL DEFAULT now()
+        );"
+    } else {
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER NOT NULL PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );"
+    };
+    conn.execute_batch(create_table).await?;
+
+    let current = read_schema_version(&mut conn).await?.unwrap_or(0);
+    let latest = latest_schema_version();
+    if current > latest {
+        return Err(newer_schema_error(current, latest));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let body = if is_postgres {
+            migration.postgres.to_string()
+        } else {
+            let mut body = String::new();
+            for add in migration.sqlite_columns {
+                if !sqlite_column_exists(&mut conn, add.table, add.column).await? {
+                    body.push_str(&format!(
+                        "ALTER TABLE {} ADD COLUMN {} {};\n",
+                        add.table, add.column, add.definition
+                    ));
+                }
+            }
+            body.push_str(&without_existing_sqlite_columns(&mut conn, migration.sqlite).await?);
+            body
+        };
+        let batch = format!(
+            "{body}\nINSERT INTO schema_version (version, name) VALUES ({}, '{}');",
+            migration.version, migration.name
+        );
+        conn.execute_batch(&batch).await.map_err(|e| {
+            SqlMiddlewareDbError::Other(format!(
+                "Migration {} ({}) failed: {e}",
+                migration.version, migration.name
+            ))
+        })?;
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+async fn read_schema_version(
+    conn: &mut MiddlewarePoolConnection,
+) -> Result<Option<i64>, SqlMiddlewareDbError> {
+    let exists_query = match conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            "SELECT count(*) AS ex FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_name = 'schema_version';"
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            "SELECT count(*) AS ex FROM sqlite_master WHERE type = 'table' AND name = 'schema_version';"
+        }
+    };
+    let exists = conn.query(exists_query).select().await?;
+    let table_exists = exists
+        .results
+        .first()
+        .and_then(|row| row.get("ex"))
+        .and_then(|v| v.as_int())
+        .is_some_and(|count| *count > 0);
+    if !table_exists {
+        return Ok(None);
+    }
+
+    let res = conn
+        .query("SELECT max(version) AS version FROM schema_version;")
+        .select()
+        .await?;
+    Ok(res
+        .results
+        .first()
+        .and_then(|row| row.get("version"))
+        .and_then(|v| v.as_int())
+        .copied()
+        .or(Some(0)))
+}
+
+/// `sql` without the `ALTER TABLE .. ADD COLUMN` statements whose column is already there.
+/// Migration 2 shipped its column as plain SQL before `sqlite_columns` existed, and databases
+/// built from the current schema files already have it.
+async fn without_existing_sqlite_columns(
+    conn: &mut MiddlewarePoolConnection,
+    sql: &str,
+) -> Result<String, SqlMiddlewareDbError> {
+    let mut kept = String::new();
+    for statement in sql.split_inclusive(';') {
+        let words: Vec<&str> = statement.trim_end_matches(';').split_whitespace().collect();
+        if let ["ALTER", "TABLE", table, "ADD", "COLUMN", column, ..] = words[..]
+            && sqlite_column_exists(conn, table, column).await?
+        {
+            continue;
+        }
+        kept.push_str(statement);
+    }
+    Ok(kept)
+}
+
+async fn sqlite_column_exists(
+    conn: &mut MiddlewarePoolConnection,
+    table: &str,
+    column: &str,
+) -> Result<bool, SqlMiddlewareDbError> {
+    let res = conn
+        .query("SELECT count(*) AS ex FROM pragma_table_info(?1) WHERE name = ?2;")
+        .params(&[
+            RowValues::Text(table.to_string()),
+            RowValues::Text(column.to_string()),
+        ])
+        .select()
+        .await?;
+    Ok(res
+        .results
+        .first()
+        .and_then(|row| row.get("ex"))
+        .and_then(|v| v.as_int())
+        .is_some_and(|count| *count > 0))
+}
+
+fn newer_schema_error(current: i64, latest: i64) -> SqlMiddlewareDbError {
+    SqlMiddlewareDbError::Other(format!(
+        "Database schema version {current} is newer than this build supports ({latest}); \
+         refusing to start. Upgrade rusty-golf before using this database."
+    ))
+}
@@ -1,7 +1,12 @@
+pub mod admin;
+pub mod archive;
 pub mod database_read;
 pub mod database_write;
 pub mod event;
 pub mod golfer;
+pub mod migrations;
+pub mod score_change;
+pub mod score_history;
 
 pub mod score {
     pub use rusty_golf_core::model::score::*;
@@ -15,8 +20,13 @@ pub mod utils {
     pub use rusty_golf_core::model::utils::*;
 }
 
+pub use admin::*;
+pub use archive::*;
 pub use database_read::*;
 pub use database_write::*;
 pub use event::*;
 pub use golfer::*;
+pub use migrations::*;
 pub use rusty_golf_core::model::*;
+pub use score_change::*;
+pub use score_history::*;
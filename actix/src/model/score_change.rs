@@ -0,0 +1,88 @@
+use rusty_golf_core::score::MAX_RECENT_SCORE_CHANGES;
+use sql_middleware::SqlMiddlewareDbError;
+use sql_middleware::middleware::RowValues as RowValues2;
+use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection};
+
+use crate::model::database_read::{execute_query, parse_json_field};
+use crate::model::database_write::execute_in_transaction;
+use crate::model::types::ScoreChange;
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails or a change cannot be serialized
+pub async fn store_score_changes_in_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    changes: &[ScoreChange],
+) -> Result<(), SqlMiddlewareDbError> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = config_and_pool.get_connection().await?;
+    let (insert, prune) = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => (
+            include_str!("../sql/functions/postgres/08_sp_set_score_change.sql"),
+            include_str!("../sql/functions/postgres/10_sp_prune_score_changes.sql"),
+        ),
+        MiddlewarePoolConnection::Sqlite { .. } => (
+            include_str!("../sql/functions/sqlite/06_sp_set_score_change.sql"),
+            include_str!("../sql/functions/sqlite/08_sp_prune_score_changes.sql"),
+        ),
+    };
+
+    let mut insert_params = Vec::with_capacity(changes.len());
+    for change in changes {
+        let change_json = serde_json::to_string(change).map_err(|e| {
+            SqlMiddlewareDbError::Other(format!("Failed to serialize score change: {e}"))
+        })?;
+        insert_params.push(vec![
+            RowValues2::Int(i64::from(event_id)),
+            RowValues2::Text(change_json),
+            RowValues2::Timestamp(change.detected_at),
+        ]);
+    }
+    let prune_params = vec![
+        RowValues2::Int(i64::from(event_id)),
+        RowValues2::Int(max_recent_changes()),
+    ];
+
+    let mut queries: Vec<(&str, &[RowValues2])> = insert_params
+        .iter()
+        .map(|params| (insert, params.as_slice()))
+        .collect();
+    queries.push((prune, prune_params.as_slice()));
+    execute_in_transaction(&mut conn, &queries).await?;
+    Ok(())
+}
+
+/// # Errors
+///
+/// Will return `Err` if the database query fails or a stored change cannot be parsed
+pub async fn get_recent_score_changes_from_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+) -> Result<Vec<ScoreChange>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let query = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            include_str!("../sql/functions/postgres/09_sp_get_score_changes.sql")
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            include_str!("../sql/functions/sqlite/07_sp_get_score_changes.sql")
+        }
+    };
+    let params = vec![
+        RowValues2::Int(i64::from(event_id)),
+        RowValues2::Int(max_recent_changes()),
+    ];
+    let res = execute_query(&mut conn, query, params).await?;
+    res.results
+        .iter()
+        .map(|row| parse_json_field(row, "change"))
+        .collect()
+}
+
+fn max_recent_changes() -> i64 {
+    i64::try_from(MAX_RECENT_SCORE_CHANGES).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDateTime;
+use sql_middleware::SqlMiddlewareDbError;
+use sql_middleware::middleware::RowValues as RowValues2;
+use sql_middleware::middleware::{ConfigAndPool, MiddlewarePoolConnection};
+
+use crate::model::archive::hx_timestamp;
+use crate::model::database_read::{build_score_from_row, execute_query};
+use crate::model::types::{RefreshSource, ScoresAndLastRefresh};
+
+const HX_TS_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// When each of the event's `eup_statistic_hx` snapshots was taken, oldest first. Every row
+/// a refresh replaces shares its `hx_ts`, so each distinct one is a snapshot.
+///
+/// # Errors
+///
+/// Will return `Err` if the database query fails
+pub async fn list_score_snapshots_from_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+) -> Result<Vec<NaiveDateTime>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let query = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => {
+            "SELECT DISTINCT hx_ts FROM eup_statistic_hx WHERE event_espn_id = $1 ORDER BY hx_ts;"
+        }
+        MiddlewarePoolConnection::Sqlite { .. } => {
+            "SELECT DISTINCT hx_ts FROM eup_statistic_hx WHERE event_espn_id = ?1 ORDER BY hx_ts;"
+        }
+    };
+    let res = execute_query(&mut conn, query, vec![RowValues2::Int(i64::from(event_id))]).await?;
+    Ok(res
+        .results
+        .iter()
+        .filter_map(|row| hx_timestamp(row.get("hx_ts")?))
+        .collect())
+}
+
+/// The scores a refresh at `taken_at` replaced, stamped with when they were stored.
+///
+/// # Errors
+///
+/// Will return `Err` if the database query fails or a stored statistic cannot be parsed
+pub async fn get_score_snapshot_from_db(
+    config_and_pool: &ConfigAndPool,
+    event_id: i32,
+    taken_at: NaiveDateTime,
+) -> Result<Option<ScoresAndLastRefresh>, SqlMiddlewareDbError> {
+    let mut conn = config_and_pool.get_connection().await?;
+    let (query, hx_ts) = match &conn {
+        MiddlewarePoolConnection::Postgres { .. } => (
+            "SELECT hx.golfer_espn_id, hx.eup_id, hx.grp, hx.rounds::text AS rounds, \
+             hx.round_scores::text AS round_scores, hx.tee_times::text AS tee_times, \
+             hx.holes_completed_by_round::text AS holes_completed_by_round, \
+             hx.line_scores::text AS line_scores, g.name AS golfername, b.name AS bettorname, \
+             hx.total_score, hx.ins_ts, eup.score_view_step_factor \
+             FROM eup_statistic_hx AS hx \
+             JOIN golfer AS g ON hx.golfer_espn_id = g.espn_id \
+             JOIN event_user_player AS eup ON hx.eup_id = eup.eup_id \
+             JOIN bettor AS b ON b.user_id = eup.user_id \
+             WHERE hx.event_espn_id = $1 AND hx.hx_ts = $2 \
+             ORDER BY hx.grp, hx.eup_id;",
+            RowValues2::Timestamp(taken_at),
+        ),
+        MiddlewarePoolConnection::Sqlite { .. } => (
+            "SELECT hx.golfer_espn_id, hx.eup_id, hx.grp, hx.rounds, hx.round_scores, \
+             hx.tee_times, hx.holes_completed_by_round, hx.line_scores, \
+             g.name AS golfername, b.name AS bettorname, hx.total_score, hx.ins_ts, \
+             eup.score_view_step_factor \
+             FROM eup_statistic_hx AS hx \
+             JOIN golfer AS g ON hx.golfer_espn_id = g.espn_id \
+             JOIN event_user_player AS eup ON hx.eup_id = eup.eup_id \
+             JOIN bettor AS b ON b.user_id = eup.user_id \
+             WHERE hx.event_espn_id = ?1 AND hx.hx_ts = ?2 \
+             ORDER BY hx.grp, hx.eup_id;",
+            RowValues2::Text(taken_at.format(HX_TS_FORMAT).to_string()),
+        ),
+    };
+    let res = execute_query(
+        &mut conn,
+        query,
+        vec![RowValues2::Int(i64::from(event_id)), hx_ts],
+    )
+    .await?;
+    if res.results.is_empty() {
+        return Ok(None);
+    }
+    let last_refresh = res
+        .results
+        .iter()
+        .filter_map(|row| row.get("ins_ts"))
+        .filter_map(sql_middleware::RowValues::as_timestamp)
+        .max()
+        .unwrap_or(taken_at);
+    Ok(Some(ScoresAndLastRefresh {
+        last_refresh,
+        score_struct: res
+            .results
+            .iter()
+            .map(build_score_from_row)
+            .collect::<Result<Vec<_>, _>>()?,
+        last_refresh_source: RefreshSource::Db,
+    }))
+}
@@ -1,6 +1,9 @@
 use crate::model::{ScoreData, ScoresAndLastRefresh};
+use chrono::Utc;
 use maud::Markup;
 use rusty_golf_core::error::CoreError;
+use rusty_golf_core::score::refresh_interval;
+use rusty_golf_core::storage::EventDetails;
 use std::collections::HashMap;
 
 mod score_decode;
@@ -22,6 +25,7 @@ pub struct ScoreModel {
     pub error: Option<CoreError>,
     pub from_db_scores: Option<ScoresAndLastRefresh>,
     pub global_step_factor: Option<f32>,
+    pub event_details: Option<EventDetails>,
     pub player_step_factors: Option<HashMap<(i64, String), f32>>,
 }
 
@@ -47,16 +51,26 @@ impl ScoreModel {
             error: None,
             from_db_scores: None,
             global_step_factor: None,
+            event_details: None,
             player_step_factors: None,
         }
     }
+
+    /// Seconds until the page's fragments poll again; `None` once scores are final or
+    /// before the scores and event details are loaded.
+    #[must_use]
+    pub fn refresh_interval(&self) -> Option<u32> {
+        let details = self.event_details.as_ref()?;
+        let data = self.data.as_ref()?;
+        refresh_interval(details, &data.score_struct, Utc::now())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     PageLoad,
     ScoresLoaded(ScoreData),
-    EventConfigLoaded(f32),
+    EventConfigLoaded(EventDetails),
     PlayerFactorsLoaded(HashMap<(i64, String), f32>),
     DbScoresLoaded(ScoresAndLastRefresh),
     Rendered(Markup),
@@ -74,11 +88,13 @@ pub enum Effect {
 
 pub fn update(model: &mut ScoreModel, msg: Msg) -> Vec<Effect> {
     match msg {
+        // Effects run last-first: `LoadScores` stores what it fetched before `LoadDbScores`
+        // reads it back, so an event with no stored scores yet still renders.
         Msg::PageLoad => vec![
-            Effect::LoadScores,
-            Effect::LoadEventConfig,
-            Effect::LoadPlayerFactors,
             Effect::LoadDbScores,
+            Effect::LoadPlayerFactors,
+            Effect::LoadEventConfig,
+            Effect::LoadScores,
         ],
         Msg::ScoresLoaded(data) => {
             model.data = Some(data);
@@ -93,8 +109,9 @@ pub fn update(model: &mut ScoreModel, msg: Msg) -> Vec<Effect> {
                 vec![]
             }
         }
-        Msg::EventConfigLoaded(step) => {
-            model.global_step_factor = Some(step);
+        Msg::EventConfigLoaded(event_details) => {
+            model.global_step_factor = Some(event_details.score_view_step_factor);
+            model.event_details = Some(event_details);
             if !model.want_json
                 && model.data.is_some()
                 && model.from_db_scores.is_some()
@@ -30,7 +30,7 @@ pub async fn run_effect(effect: Effect, model: &ScoreModel, deps: Deps<'_>) -> M
             }
         }
         Effect::LoadEventConfig => match deps.storage.get_event_details(model.event_id).await {
-            Ok(event_details) => Msg::EventConfigLoaded(event_details.score_view_step_factor),
+            Ok(event_details) => Msg::EventConfigLoaded(event_details),
             Err(e) => Msg::Failed(CoreError::from(e)),
         },
         Effect::LoadPlayerFactors => {
@@ -69,6 +69,7 @@ fn render_template(model: &ScoreModel) -> Msg {
             player_factors,
             model.event_id,
             model.year,
+            model.refresh_interval(),
             model.use_cache,
         );
         Msg::Rendered(markup)
@@ -0,0 +1,4 @@
+SELECT e.last_refresh_ts AS last_refresh_ts,
+    (SELECT max(s.ins_ts) FROM eup_statistic AS s WHERE s.event_espn_id = $1) AS ins_ts
+FROM event AS e
+WHERE e.espn_id = $1;
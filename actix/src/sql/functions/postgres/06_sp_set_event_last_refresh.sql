@@ -0,0 +1,3 @@
+UPDATE event
+SET last_refresh_ts = timezone('utc', now())
+WHERE espn_id = $1;
@@ -0,0 +1,3 @@
+UPDATE event
+SET completed = 1, end_date = COALESCE(end_date, $2::text::timestamptz AT TIME ZONE 'utc')
+WHERE espn_id = $1;
@@ -0,0 +1,5 @@
+SELECT change::text AS change
+FROM score_change
+WHERE event_espn_id = $1
+ORDER BY score_change_id DESC
+LIMIT $2;
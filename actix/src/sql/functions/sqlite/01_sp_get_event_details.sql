@@ -1,3 +1,3 @@
-SELECT e.name AS eventname, ins_ts, score_view_step_factor, refresh_from_espn, end_date
+SELECT e.name AS eventname, ins_ts, score_view_step_factor, refresh_from_espn, start_date, end_date, completed
 FROM event AS e
 WHERE e.espn_id = ?1;
@@ -1,6 +1,7 @@
-SELECT min(ins_ts) as ins_ts
-FROM eup_statistic
-WHERE event_espn_id = ?1;
+SELECT e.last_refresh_ts AS last_refresh_ts,
+    (SELECT max(s.ins_ts) FROM eup_statistic AS s WHERE s.event_espn_id = ?1) AS ins_ts
+FROM event AS e
+WHERE e.espn_id = ?1;
 
         -- old query below, i guess i was thinking i would just ck if the event exists? Odd reasoning, over-complicated.
         -- SELECT CASE 
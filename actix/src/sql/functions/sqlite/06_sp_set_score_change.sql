@@ -0,0 +1,2 @@
+INSERT INTO score_change (event_espn_id, change, detected_at)
+VALUES (?1, ?2, ?3);
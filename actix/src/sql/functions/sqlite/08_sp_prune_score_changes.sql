@@ -0,0 +1,9 @@
+DELETE FROM score_change
+WHERE event_espn_id = ?1
+    AND score_change_id NOT IN (
+        SELECT score_change_id
+        FROM score_change
+        WHERE event_espn_id = ?1
+        ORDER BY score_change_id DESC
+        LIMIT ?2
+        );
@@ -0,0 +1,3 @@
+UPDATE event
+SET last_refresh_ts = CURRENT_TIMESTAMP
+WHERE espn_id = ?1;
@@ -0,0 +1,3 @@
+UPDATE event
+SET completed = 1, end_date = COALESCE(end_date, ?2)
+WHERE espn_id = ?1;
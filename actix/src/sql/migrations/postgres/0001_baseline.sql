@@ -0,0 +1,128 @@
+-- Baseline: the schema from actix/src/sql/schema/postgres. Every statement is guarded so an
+-- existing database can adopt version 1 as-is.
+CREATE TABLE IF NOT EXISTS event (
+    event_id SERIAL PRIMARY KEY,
+    espn_id INTEGER NOT NULL,
+    year INT NOT NULL,
+    name TEXT NOT NULL,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now(),
+    score_view_step_factor real not null default 3.0,
+    refresh_from_espn INTEGER not null DEFAULT 1,
+    end_date TIMESTAMP,
+
+    UNIQUE (espn_id)
+);
+
+CREATE TABLE IF NOT EXISTS golfer (
+    golfer_id SERIAL PRIMARY KEY,
+    espn_id INTEGER NOT NULL UNIQUE,
+    name TEXT NOT NULL UNIQUE,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS bettor (
+    user_id SERIAL NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now()
+    );
+
+CREATE TABLE IF NOT EXISTS event_user_player (
+    eup_id SERIAL PRIMARY KEY,
+    event_id INTEGER NOT NULL REFERENCES event(event_id),
+    user_id INTEGER NOT NULL REFERENCES bettor(user_id),
+    golfer_id INTEGER NOT NULL REFERENCES golfer(golfer_id),
+    last_refresh_ts TIMESTAMP,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now(),
+    score_view_step_factor REAL DEFAULT 3.0,
+
+    UNIQUE (event_id, user_id, golfer_id)
+);
+
+ALTER TABLE event_user_player ADD COLUMN IF NOT EXISTS score_view_step_factor REAL DEFAULT 3.0;
+
+CREATE TABLE IF NOT EXISTS eup_statistic (
+    eup_stat_id SERIAL PRIMARY KEY,
+    event_espn_id INT NOT NULL REFERENCES event(espn_id),
+    golfer_espn_id INT NOT NULL REFERENCES golfer(espn_id),
+    eup_id INT NOT NULL REFERENCES event_user_player(eup_id),
+    grp INT NOT NULL,
+    rounds JSONB NOT NULL,
+    round_scores JSONB NOT NULL,
+    tee_times JSONB NOT NULL,
+    holes_completed_by_round JSONB NOT NULL,
+    line_scores JSONB NOT NULL,
+    total_score INT NOT NULL,
+    upd_ts TIMESTAMP NOT NULL DEFAULT now(),
+    ins_ts TIMESTAMP NOT NULL DEFAULT now(),
+
+    UNIQUE (golfer_espn_id, eup_id)
+);
+
+CREATE TABLE IF NOT EXISTS eup_statistic_hx (
+    hx_id SERIAL PRIMARY KEY,
+    event_espn_id INT NOT NULL,
+    golfer_espn_id INT NOT NULL,
+    eup_id INT NOT NULL,
+    grp INT NOT NULL,
+    rounds JSONB NOT NULL,
+    round_scores JSONB NOT NULL,
+    tee_times JSONB NOT NULL,
+    holes_completed_by_round JSONB NOT NULL,
+    line_scores JSONB NOT NULL,
+    total_score INT NOT NULL,
+    ins_ts TIMESTAMP NOT NULL,
+    hx_ts TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE
+    OR REPLACE FUNCTION eup_statistic_capture_hx ()
+RETURNS TRIGGER AS $$
+
+BEGIN
+    INSERT INTO eup_statistic_hx (
+        event_espn_id,
+        golfer_espn_id,
+        eup_id,
+        grp,
+        rounds,
+        round_scores,
+        tee_times,
+        holes_completed_by_round,
+        line_scores,
+        total_score,
+        ins_ts
+    )
+    VALUES (
+        OLD.event_espn_id,
+        OLD.golfer_espn_id,
+        OLD.eup_id,
+        OLD.grp,
+        OLD.rounds,
+        OLD.round_scores,
+        OLD.tee_times,
+        OLD.holes_completed_by_round,
+        OLD.line_scores,
+        OLD.total_score,
+        OLD.ins_ts
+    );
+    RETURN NEW;
+END;$$
+
+LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS eup_statistic_before_update ON eup_statistic;
+
+CREATE TRIGGER eup_statistic_before_update
+BEFORE UPDATE ON eup_statistic
+FOR EACH ROW
+EXECUTE FUNCTION eup_statistic_capture_hx();
+
+CREATE TABLE IF NOT EXISTS score_change (
+    score_change_id SERIAL PRIMARY KEY,
+    event_espn_id INT NOT NULL,
+    change JSONB NOT NULL,
+    detected_at TIMESTAMP NOT NULL,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS score_change_event_idx ON score_change (event_espn_id, score_change_id);
@@ -0,0 +1 @@
+ALTER TABLE event ADD COLUMN IF NOT EXISTS last_refresh_ts TIMESTAMP;
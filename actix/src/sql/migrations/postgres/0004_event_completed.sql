@@ -0,0 +1 @@
+ALTER TABLE event ADD COLUMN IF NOT EXISTS completed INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- One frozen document per archived event; see `rusty_golf_core::storage::EventArchive`.
+CREATE TABLE IF NOT EXISTS event_archive (
+    event_espn_id INT PRIMARY KEY,
+    archive JSON NOT NULL,
+    ins_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -7,6 +7,9 @@ CREATE TABLE IF NOT EXISTS event (
     score_view_step_factor real not null default 3.0,
     refresh_from_espn INTEGER not null DEFAULT 1,
     end_date TIMESTAMP,
+    start_date TIMESTAMP,
+    last_refresh_ts TIMESTAMP,
+    completed INTEGER NOT NULL DEFAULT 0,
 
     UNIQUE (espn_id)
 );
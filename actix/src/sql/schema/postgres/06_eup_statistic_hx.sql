@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS eup_statistic_hx (
+    hx_id SERIAL PRIMARY KEY,
+    event_espn_id INT NOT NULL,
+    golfer_espn_id INT NOT NULL,
+    eup_id INT NOT NULL,
+    grp INT NOT NULL,
+    rounds JSONB NOT NULL,
+    round_scores JSONB NOT NULL,
+    tee_times JSONB NOT NULL,
+    holes_completed_by_round JSONB NOT NULL,
+    line_scores JSONB NOT NULL,
+    total_score INT NOT NULL,
+    ins_ts TIMESTAMP NOT NULL,
+    hx_ts TIMESTAMP NOT NULL DEFAULT now()
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS event_archive (
+    event_espn_id INT PRIMARY KEY,
+    archive JSONB NOT NULL,
+    ins_ts TIMESTAMP NOT NULL DEFAULT now()
+);
@@ -7,5 +7,8 @@ CREATE TABLE IF NOT EXISTS event (
     score_view_step_factor real not null default 3.0, --deprecated
     refresh_from_espn INTEGER not null DEFAULT 1,
     end_date TEXT,
+    start_date TEXT,
+    last_refresh_ts DATETIME,
+    completed INTEGER NOT NULL DEFAULT 0,
     UNIQUE (espn_id)
 );
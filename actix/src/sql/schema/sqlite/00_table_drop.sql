@@ -3,6 +3,9 @@
 --     delete from player;
 --     delete from event;
 
+DROP TABLE IF EXISTS event_archive;
+DROP TABLE IF EXISTS eup_statistic_hx;
+DROP TABLE IF EXISTS score_change;
 DROP TABLE IF EXISTS eup_statistic;
 DROP TABLE IF EXISTS event_user_player;
 DROP TABLE IF EXISTS bettor;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS score_change (
+    score_change_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_espn_id INT NOT NULL,
+    change JSON NOT NULL,
+    detected_at DATETIME NOT NULL,
+    ins_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX IF NOT EXISTS score_change_event_idx ON score_change (event_espn_id, score_change_id);
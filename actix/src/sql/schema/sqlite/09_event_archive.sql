@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS event_archive (
+    event_espn_id INT PRIMARY KEY,
+    archive JSON NOT NULL,
+    ins_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,449 @@
+use chrono::{DateTime, Utc};
+use rusty_golf_core::model::{PrefillEvent, parse_prefill_events};
+use rusty_golf_core::score::merge_recent_score_changes;
+use rusty_golf_core::storage::{EventArchive, EventDetails, Storage, StorageError};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::file_types::{EventDetailsDoc, GolferAssignment, LastRefreshDoc, PlayerFactorEntry};
+use crate::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh, Statistic};
+
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A [`Storage`] backed by JSON files under a data directory.
+///
+/// Files use the serverless key layout: `events/<id>/scores.json` for what lives in R2 and
+/// `kv/event:<id>:details` (and friends) for what lives in KV. Every write goes to a temp
+/// file in the same directory and is renamed into place, so readers never see partial JSON.
+#[derive(Clone)]
+pub struct FileStorage {
+    data_dir: PathBuf,
+    // Serializes read-modify-write updates within this process.
+    pub(super) write_lock: Arc<Mutex<()>>,
+}
+
+impl FileStorage {
+    #[must_use]
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    #[must_use]
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Write event details, golfers and player factors for each event in a
+    /// `--db-populate-json` payload, returning how many events were added.
+    ///
+    /// Events that already have details on disk are skipped. Eup ids are numbered from 1 per
+    /// event, and each bettor's picks are grouped 1.. in payload order, as the serverless
+    /// admin seed does.
+    ///
+    /// # Errors
+    /// Returns an error if the payload is malformed, a pick names an unknown golfer, or a
+    /// file can't be written.
+    pub async fn seed_prefill_json(&self, json: &serde_json::Value) -> Result<usize, StorageError> {
+        let events = parse_prefill_events(json)?;
+        // Golfers are global in SQL, so a pick may name a golfer listed under another event.
+        let golfer_names: HashMap<i64, &str> = events
+            .iter()
+            .flat_map(|event| &event.data_to_fill_if_event_and_year_missing)
+            .flat_map(|data| &data.golfers)
+            .map(|golfer| (golfer.espn_id, golfer.name.as_str()))
+            .collect();
+        let _guard = self.write_lock.lock().await;
+        let mut added = 0;
+        for event in &events {
+            let event_id = i32::try_from(event.event)
+                .map_err(|_| StorageError::new(format!("event id {} out of range", event.event)))?;
+            let details_path = self.kv_path(&Self::event_details_key(event_id));
+            if tokio::fs::try_exists(&details_path)
+                .await
+                .map_err(|e| io_error(&details_path, &e))?
+            {
+                continue;
+            }
+            self.seed_event(event_id, event, &golfer_names).await?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    async fn seed_event(
+        &self,
+        event_id: i32,
+        event: &PrefillEvent,
+        golfer_names: &HashMap<i64, &str>,
+    ) -> Result<(), StorageError> {
+        let mut bettor_counts: HashMap<&str, i64> = HashMap::new();
+        let mut golfers = Vec::new();
+        let mut factors = Vec::new();
+        let mut eup_id = 1_i64;
+        for data in &event.data_to_fill_if_event_and_year_missing {
+            for eup in &data.event_user_player {
+                let golfer_name = golfer_names.get(&eup.golfer_espn_id).ok_or_else(|| {
+                    StorageError::new(format!(
+                        "event {event_id}: unknown golfer espn_id {}",
+                        eup.golfer_espn_id
+                    ))
+                })?;
+                let group = bettor_counts.entry(eup.bettor.as_str()).or_insert(0);
+                *group += 1;
+                #[allow(clippy::cast_possible_truncation)]
+                let step_factor = eup.score_view_step_factor.map(|v| v as f32);
+                golfers.push(GolferAssignment {
+                    eup_id,
+                    espn_id: eup.golfer_espn_id,
+                    golfer_name: (*golfer_name).to_string(),
+                    bettor_name: eup.bettor.clone(),
+                    group: *group,
+                    score_view_step_factor: step_factor,
+                });
+                if let Some(step_factor) = step_factor {
+                    factors.push(PlayerFactorEntry {
+                        golfer_espn_id: eup.golfer_espn_id,
+                        bettor_name: eup.bettor.clone(),
+                        step_factor,
+                    });
+                }
+                eup_id += 1;
+            }
+        }
+
+        self.write_json(&self.kv_path(&Self::golfers_key(event_id)), &golfers)
+            .await?;
+        self.write_json(&self.kv_path(&Self::player_factors_key(event_id)), &factors)
+            .await?;
+        // Details go last: their presence is what marks the event as seeded.
+        #[allow(clippy::cast_possible_truncation)]
+        let details = EventDetailsDoc {
+            event_name: event.name.clone(),
+            score_view_step_factor: event.score_view_step_factor as f32,
+            refresh_from_espn: 1,
+            start_date: event.start_date.clone(),
+            end_date: event.end_date.clone(),
+            completed: event.completed,
+        };
+        self.write_json(&self.kv_path(&Self::event_details_key(event_id)), &details)
+            .await
+    }
+
+    /// The event's archive, read whenever a live file it replaced is missing.
+    async fn archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        self.read_json(&self.object_path(&Self::archive_key(event_id)))
+            .await
+    }
+
+    pub(super) fn object_path(&self, key: &str) -> PathBuf {
+        self.data_dir.join(key)
+    }
+
+    pub(super) fn kv_path(&self, key: &str) -> PathBuf {
+        self.data_dir.join("kv").join(key)
+    }
+
+    pub(super) async fn read_json<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+    ) -> Result<Option<T>, StorageError> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(path, &e)),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| StorageError::new(format!("{}: {e}", path.display())))
+    }
+
+    pub(super) async fn write_json<T: Serialize>(
+        &self,
+        path: &Path,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let body = serde_json::to_vec(value).map_err(|e| StorageError::new(e.to_string()))?;
+        let parent = path
+            .parent()
+            .ok_or_else(|| StorageError::new(format!("{} has no parent", path.display())))?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| io_error(parent, &e))?;
+
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let tmp_path = parent.join(format!(
+            ".{file_name}.{}.{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let written = async {
+            let mut file = tokio::fs::File::create(&tmp_path).await?;
+            file.write_all(&body).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&tmp_path, path).await
+        }
+        .await;
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(io_error(path, &e));
+        }
+        Ok(())
+    }
+
+    /// Remove a file; one that's already gone counts as removed.
+    pub(super) async fn remove_file(&self, path: &Path) -> Result<bool, StorageError> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(io_error(path, &e)),
+        }
+    }
+}
+
+pub(super) fn io_error(path: &Path, e: &std::io::Error) -> StorageError {
+    StorageError::new(format!("{}: {e}", path.display()))
+}
+
+#[async_trait::async_trait]
+impl Storage for FileStorage {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        let kv_dir = self.data_dir.join("kv");
+        let mut entries = match tokio::fs::read_dir(&kv_dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error(&kv_dir, &e)),
+        };
+        let mut ids = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| io_error(&kv_dir, &e))?
+        {
+            let name = entry.file_name();
+            // Temp files start with a dot, so they never match.
+            if let Some(id) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix("event:"))
+                .and_then(|rest| rest.strip_suffix(":details"))
+                .and_then(|id| id.parse().ok())
+            {
+                ids.push(id);
+            }
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
+        let details = self
+            .read_json::<EventDetailsDoc>(&self.kv_path(&Self::event_details_key(event_id)))
+            .await?
+            .ok_or_else(|| StorageError::new("event details not found"))?;
+
+        Ok(EventDetails {
+            event_name: details.event_name,
+            score_view_step_factor: details.score_view_step_factor,
+            refresh_from_espn: details.refresh_from_espn,
+            start_date: details.start_date,
+            end_date: details.end_date,
+            completed: details.completed,
+        })
+    }
+
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError> {
+        let Some(assignments) = self
+            .read_json::<Vec<GolferAssignment>>(&self.kv_path(&Self::golfers_key(event_id)))
+            .await?
+        else {
+            return match self.archive(event_id).await? {
+                Some(archive) => archive.picks(),
+                None => Ok(Vec::new()),
+            };
+        };
+        Ok(assignments
+            .into_iter()
+            .map(|assignment| Scores {
+                eup_id: assignment.eup_id,
+                espn_id: assignment.espn_id,
+                golfer_name: assignment.golfer_name,
+                bettor_name: assignment.bettor_name,
+                detailed_statistics: Statistic {
+                    eup_id: assignment.eup_id,
+                    rounds: Vec::new(),
+                    round_scores: Vec::new(),
+                    tee_times: Vec::new(),
+                    holes_completed_by_round: Vec::new(),
+                    line_scores: Vec::new(),
+                    total_score: 0,
+                },
+                group: assignment.group,
+                score_view_step_factor: assignment.score_view_step_factor,
+            })
+            .collect())
+    }
+
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError> {
+        let Some(entries) = self
+            .read_json::<Vec<PlayerFactorEntry>>(&self.kv_path(&Self::player_factors_key(event_id)))
+            .await?
+        else {
+            return Ok(self
+                .archive(event_id)
+                .await?
+                .map(|archive| archive.player_step_factors())
+                .unwrap_or_default());
+        };
+        Ok(entries
+            .into_iter()
+            .map(|entry| ((entry.golfer_espn_id, entry.bettor_name), entry.step_factor))
+            .collect())
+    }
+
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError> {
+        let Some(mut scores) = self
+            .read_json::<ScoresAndLastRefresh>(&self.object_path(&Self::scores_key(event_id)))
+            .await?
+        else {
+            return self
+                .archive(event_id)
+                .await?
+                .and_then(|archive| archive.archived_scores())
+                .ok_or_else(|| StorageError::new("scores not found"));
+        };
+        scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
+            RefreshSource::Espn
+        } else {
+            RefreshSource::File
+        };
+        Ok(scores)
+    }
+
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
+        let now = Utc::now().naive_utc();
+        let payload = ScoresAndLastRefresh {
+            score_struct: scores.to_vec(),
+            last_refresh: now,
+            last_refresh_source: RefreshSource::Espn,
+        };
+        self.write_json(&self.object_path(&Self::scores_key(event_id)), &payload)
+            .await?;
+
+        let last_refresh = LastRefreshDoc {
+            ts: DateTime::<Utc>::from_naive_utc_and_offset(now, Utc).to_rfc3339(),
+            source: RefreshSource::Espn,
+        };
+        self.write_json(
+            &self.kv_path(&Self::last_refresh_key(event_id)),
+            &last_refresh,
+        )
+        .await
+    }
+
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError> {
+        if max_age_seconds <= 0 {
+            return Ok(false);
+        }
+        let details_path = self.kv_path(&Self::event_details_key(event_id));
+        if !tokio::fs::try_exists(&details_path)
+            .await
+            .map_err(|e| io_error(&details_path, &e))?
+        {
+            return Ok(false);
+        }
+        let Some(last_refresh) = self
+            .read_json::<LastRefreshDoc>(&self.kv_path(&Self::last_refresh_key(event_id)))
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let last_refresh_ts = DateTime::parse_from_rfc3339(&last_refresh.ts)
+            .map_err(|e| StorageError::new(e.to_string()))?
+            .naive_utc();
+        let diff = Utc::now()
+            .naive_utc()
+            .signed_duration_since(last_refresh_ts);
+        Ok(diff.num_seconds() <= max_age_seconds)
+    }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        let path = self.kv_path(&Self::event_details_key(event_id));
+        let mut details = self
+            .read_json::<EventDetailsDoc>(&path)
+            .await?
+            .ok_or_else(|| StorageError::new("event details not found"))?;
+        details.completed = true;
+        if details.end_date.is_none() {
+            details.end_date = end_date.map(ToString::to_string);
+        }
+        self.write_json(&path, &details).await
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let _guard = self.write_lock.lock().await;
+        let path = self.kv_path(&Self::score_changes_key(event_id));
+        let existing = self
+            .read_json::<Vec<ScoreChange>>(&path)
+            .await?
+            .unwrap_or_default();
+        let merged = merge_recent_score_changes(existing, changes);
+        self.write_json(&path, &merged).await
+    }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        match self
+            .read_json::<Vec<ScoreChange>>(&self.kv_path(&Self::score_changes_key(event_id)))
+            .await?
+        {
+            Some(changes) => Ok(changes),
+            None => Ok(self
+                .archive(event_id)
+                .await?
+                .map(|archive| archive.score_changes)
+                .unwrap_or_default()),
+        }
+    }
+
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        self.archive(event_id).await
+    }
+}
@@ -0,0 +1,194 @@
+use rusty_golf_core::storage::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    GolferRecord, PlayerStepFactor, Storage, StorageError, apply_player_step_factors,
+    picks_from_assignments,
+};
+use std::collections::HashMap;
+
+use super::FileStorage;
+use super::file_types::{EventDetailsDoc, GolferAssignment, PlayerFactorEntry};
+use crate::model::Scores;
+
+impl FileStorage {
+    async fn require_event(&self, event_id: i32) -> Result<(), StorageError> {
+        self.read_json::<EventDetailsDoc>(&self.kv_path(&Self::event_details_key(event_id)))
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| StorageError::new("event details not found"))
+    }
+
+    async fn golfer_directory(&self) -> Result<Vec<GolferRecord>, StorageError> {
+        Ok(self
+            .read_json::<Vec<GolferRecord>>(&self.kv_path(Self::golfer_directory_key()))
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Write the event's picks and the factor list derived from them.
+    async fn write_picks(&self, event_id: i32, picks: Vec<Scores>) -> Result<(), StorageError> {
+        let factors: Vec<PlayerFactorEntry> = picks
+            .iter()
+            .filter_map(|pick| {
+                Some(PlayerFactorEntry {
+                    golfer_espn_id: pick.espn_id,
+                    bettor_name: pick.bettor_name.clone(),
+                    step_factor: pick.score_view_step_factor?,
+                })
+            })
+            .collect();
+        let golfers: Vec<GolferAssignment> = picks
+            .into_iter()
+            .map(|pick| GolferAssignment {
+                eup_id: pick.eup_id,
+                espn_id: pick.espn_id,
+                golfer_name: pick.golfer_name,
+                bettor_name: pick.bettor_name,
+                group: pick.group,
+                score_view_step_factor: pick.score_view_step_factor,
+            })
+            .collect();
+        self.write_json(&self.kv_path(&Self::golfers_key(event_id)), &golfers)
+            .await?;
+        self.write_json(&self.kv_path(&Self::player_factors_key(event_id)), &factors)
+            .await
+    }
+}
+
+fn event_doc(config: &EventConfig) -> EventDetailsDoc {
+    EventDetailsDoc {
+        event_name: config.event_name.clone(),
+        score_view_step_factor: config.score_view_step_factor,
+        refresh_from_espn: config.refresh_from_espn,
+        start_date: config.start_date.clone(),
+        end_date: config.end_date.clone(),
+        completed: config.completed,
+    }
+}
+
+#[async_trait::async_trait]
+impl AdminStorage for FileStorage {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        let path = self.kv_path(&Self::event_details_key(event_id));
+        if self.read_json::<EventDetailsDoc>(&path).await?.is_some() {
+            return Err(StorageError::new(format!(
+                "event {event_id} already exists"
+            )));
+        }
+        self.write_json(&path, &event_doc(config)).await
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        self.require_event(event_id).await?;
+        self.write_json(
+            &self.kv_path(&Self::event_details_key(event_id)),
+            &event_doc(config),
+        )
+        .await
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        let _guard = self.write_lock.lock().await;
+        for key in [
+            Self::golfers_key(event_id),
+            Self::player_factors_key(event_id),
+            Self::last_refresh_key(event_id),
+            Self::score_changes_key(event_id),
+        ] {
+            self.remove_file(&self.kv_path(&key)).await?;
+        }
+        for key in [Self::scores_key(event_id), Self::archive_key(event_id)] {
+            self.remove_file(&self.object_path(&key)).await?;
+        }
+        // Details go last, mirroring seeding: while they exist the event still does.
+        self.remove_file(&self.kv_path(&Self::event_details_key(event_id)))
+            .await
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        let mut directory: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        for golfer in golfers {
+            directory.insert(golfer.espn_id, golfer.name.clone());
+        }
+        let mut directory: Vec<GolferRecord> = directory
+            .into_iter()
+            .map(|(espn_id, name)| GolferRecord { espn_id, name })
+            .collect();
+        directory.sort_by_key(|golfer| golfer.espn_id);
+        self.write_json(&self.kv_path(Self::golfer_directory_key()), &directory)
+            .await
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        self.require_event(event_id).await?;
+        let names: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        let picks = picks_from_assignments(event_id, assignments, &names)?;
+        self.write_picks(event_id, picks).await?;
+        self.remove_file(&self.kv_path(&Self::last_refresh_key(event_id)))
+            .await?;
+        self.remove_file(&self.object_path(&Self::scores_key(event_id)))
+            .await?;
+        Ok(())
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        let _guard = self.write_lock.lock().await;
+        self.require_event(event_id).await?;
+        let mut picks = self.get_golfers_for_event(event_id).await?;
+        apply_player_step_factors(event_id, &mut picks, factors)?;
+        self.write_picks(event_id, picks).await
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        _policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        let _guard = self.write_lock.lock().await;
+        self.require_event(event_id).await?;
+        // The archive goes first so every read has somewhere to land; details stay.
+        self.write_json(&self.object_path(&Self::archive_key(event_id)), archive)
+            .await?;
+        let mut records_removed = 0;
+        for key in [
+            Self::golfers_key(event_id),
+            Self::player_factors_key(event_id),
+            Self::last_refresh_key(event_id),
+            Self::score_changes_key(event_id),
+        ] {
+            records_removed += usize::from(self.remove_file(&self.kv_path(&key)).await?);
+        }
+        records_removed += usize::from(
+            self.remove_file(&self.object_path(&Self::scores_key(event_id)))
+                .await?,
+        );
+        // File storage keeps no score history.
+        Ok(CompactionReport {
+            event_id,
+            records_removed,
+            ..CompactionReport::default()
+        })
+    }
+}
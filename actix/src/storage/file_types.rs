@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::FileStorage;
+use crate::model::RefreshSource;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventDetailsDoc {
+    pub event_name: String,
+    pub score_view_step_factor: f32,
+    pub refresh_from_espn: i64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GolferAssignment {
+    pub eup_id: i64,
+    pub espn_id: i64,
+    pub golfer_name: String,
+    pub bettor_name: String,
+    pub group: i64,
+    pub score_view_step_factor: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlayerFactorEntry {
+    pub golfer_espn_id: i64,
+    pub bettor_name: String,
+    pub step_factor: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LastRefreshDoc {
+    pub ts: String,
+    pub source: RefreshSource,
+}
+
+// Same keys the serverless worker uses: R2 object keys live under the data dir, KV keys
+// under `kv/`.
+impl FileStorage {
+    pub(crate) fn scores_key(event_id: i32) -> String {
+        format!("events/{event_id}/scores.json")
+    }
+
+    pub(crate) fn archive_key(event_id: i32) -> String {
+        format!("events/{event_id}/archive.json")
+    }
+
+    pub(crate) fn event_details_key(event_id: i32) -> String {
+        format!("event:{event_id}:details")
+    }
+
+    pub(crate) fn golfers_key(event_id: i32) -> String {
+        format!("event:{event_id}:golfers")
+    }
+
+    pub(crate) fn player_factors_key(event_id: i32) -> String {
+        format!("event:{event_id}:player_factors")
+    }
+
+    pub(crate) fn last_refresh_key(event_id: i32) -> String {
+        format!("event:{event_id}:last_refresh")
+    }
+
+    /// Golfers shared by every event, as a list of `GolferRecord`s.
+    pub(crate) fn golfer_directory_key() -> &'static str {
+        "golfers:directory"
+    }
+
+    pub(crate) fn score_changes_key(event_id: i32) -> String {
+        format!("event:{event_id}:score_changes")
+    }
+}
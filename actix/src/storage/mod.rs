@@ -1,18 +1,32 @@
 use async_trait::async_trait;
-use rusty_golf_core::storage::{EventDetails, Storage, StorageError};
+use chrono::NaiveDateTime;
+use rusty_golf_core::storage::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    EventDetails, GolferRecord, PlayerStepFactor, Storage, StorageError,
+};
 use sql_middleware::middleware::ConfigAndPool;
 use std::collections::HashMap;
 
 use crate::model::{
-    RefreshSource, Scores, ScoresAndLastRefresh, event_and_scores_already_in_db, get_event_details,
-    get_golfers_from_db, get_player_step_factors, get_scores_from_db, store_scores_in_db,
+    RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh, archive_event_in_db,
+    create_event_in_db, delete_event_from_db, event_and_scores_already_in_db,
+    get_event_archive_from_db, get_event_details, get_golfers_from_db, get_player_step_factors,
+    get_recent_score_changes_from_db, get_score_snapshot_from_db, get_scores_from_db,
+    list_event_ids, list_score_snapshots_from_db, mark_event_completed_in_db,
+    set_assignments_in_db, set_player_step_factors_in_db, store_score_changes_in_db,
+    store_scores_in_db, update_event_in_db, upsert_golfers_in_db,
 };
 
+pub mod file;
+mod file_admin;
+mod file_types;
 pub mod r2;
+mod r2_admin;
 pub mod r2_config;
 pub mod r2_signing;
 mod r2_types;
 
+pub use file::FileStorage;
 pub use r2::{MissingSigner, R2Storage, R2StorageConfig, S3Signer, SigV4Signer};
 
 #[derive(Clone)]
@@ -34,6 +48,12 @@ impl SqlStorage {
 
 #[async_trait]
 impl Storage for SqlStorage {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        list_event_ids(&self.config_and_pool)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
     async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
         let details = get_event_details(&self.config_and_pool, event_id)
             .await
@@ -44,7 +64,7 @@ impl Storage for SqlStorage {
             refresh_from_espn: details.refresh_from_espn,
             start_date: details.start_date,
             end_date: details.end_date,
-            completed: false,
+            completed: details.completed,
         })
     }
 
@@ -68,9 +88,13 @@ impl Storage for SqlStorage {
         event_id: i32,
         source: RefreshSource,
     ) -> Result<ScoresAndLastRefresh, StorageError> {
-        get_scores_from_db(&self.config_and_pool, event_id, source)
+        let scores = get_scores_from_db(&self.config_and_pool, event_id, source)
             .await
-            .map_err(|e| StorageError::new(e.to_string()))
+            .map_err(|e| StorageError::new(e.to_string()))?;
+        if scores.score_struct.is_empty() {
+            return Err(StorageError::new("scores not found"));
+        }
+        Ok(scores)
     }
 
     async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
@@ -88,4 +112,116 @@ impl Storage for SqlStorage {
             .await
             .map_err(|e| StorageError::new(e.to_string()))
     }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        mark_event_completed_in_db(&self.config_and_pool, event_id, end_date)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        store_score_changes_in_db(&self.config_and_pool, event_id, changes)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        get_recent_score_changes_from_db(&self.config_and_pool, event_id)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        get_event_archive_from_db(&self.config_and_pool, event_id)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn list_score_snapshots(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        list_score_snapshots_from_db(&self.config_and_pool, event_id)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn get_score_snapshot(
+        &self,
+        event_id: i32,
+        taken_at: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        get_score_snapshot_from_db(&self.config_and_pool, event_id, taken_at)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl AdminStorage for SqlStorage {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        create_event_in_db(&self.config_and_pool, event_id, config)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        update_event_in_db(&self.config_and_pool, event_id, config)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        delete_event_from_db(&self.config_and_pool, event_id)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        upsert_golfers_in_db(&self.config_and_pool, golfers)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        set_assignments_in_db(&self.config_and_pool, event_id, assignments)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        set_player_step_factors_in_db(&self.config_and_pool, event_id, factors)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        archive_event_in_db(&self.config_and_pool, event_id, archive, policy)
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))
+    }
 }
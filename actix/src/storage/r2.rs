@@ -1,12 +1,13 @@
 use chrono::NaiveDateTime;
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
-use rusty_golf_core::storage::{EventDetails, Storage, StorageError};
+use rusty_golf_core::storage::{EventArchive, EventDetails, Storage, StorageError};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::r2_types::R2EventDetails;
-use crate::model::{RefreshSource, Scores, ScoresAndLastRefresh, Statistic};
+use crate::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh, Statistic};
+use rusty_golf_core::score::merge_recent_score_changes;
 
 pub use super::r2_config::R2StorageConfig;
 pub use super::r2_signing::{MissingSigner, S3Signer, SigV4Signer};
@@ -34,7 +35,7 @@ impl R2Storage {
         format!("{base}/{bucket}/{key}")
     }
 
-    async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+    pub(super) async fn get_object(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
         let url = self.object_url(key);
         let headers = self.signer.sign("GET", &url, HeaderMap::new(), None)?;
         let resp = self
@@ -88,7 +89,77 @@ impl R2Storage {
         Ok(())
     }
 
-    async fn get_json<T: for<'de> Deserialize<'de>>(
+    pub(super) async fn delete_object(&self, key: &str) -> Result<(), StorageError> {
+        let url = self.object_url(key);
+        let headers = self.signer.sign("DELETE", &url, HeaderMap::new(), None)?;
+        let resp = self
+            .client
+            .delete(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| StorageError::new(e.to_string()))?;
+
+        // S3 answers 204 whether or not the object existed; some gateways answer 404.
+        if !resp.status().is_success() && resp.status().as_u16() != 404 {
+            return Err(StorageError::new(format!(
+                "R2 DELETE failed with status {}",
+                resp.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Every key under `prefix`, following `ListObjectsV2` continuation tokens.
+    pub(super) async fn list_keys(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let base = self.object_url("");
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut url = reqwest::Url::parse(base.trim_end_matches('/'))
+                .map_err(|e| StorageError::new(format!("invalid url: {e}")))?;
+            {
+                let mut query = url.query_pairs_mut();
+                query.append_pair("list-type", "2");
+                query.append_pair("prefix", prefix);
+                if let Some(token) = &continuation {
+                    query.append_pair("continuation-token", token);
+                }
+            }
+            let headers = self
+                .signer
+                .sign("GET", url.as_str(), HeaderMap::new(), None)?;
+            let resp = self
+                .client
+                .get(url)
+                .headers(headers)
+                .send()
+                .await
+                .map_err(|e| StorageError::new(e.to_string()))?;
+            if !resp.status().is_success() {
+                return Err(StorageError::new(format!(
+                    "R2 LIST failed with status {}",
+                    resp.status()
+                )));
+            }
+            let body = resp
+                .text()
+                .await
+                .map_err(|e| StorageError::new(e.to_string()))?;
+
+            keys.extend(xml_values(&body, "Key").map(ToString::to_string));
+            let truncated = xml_values(&body, "IsTruncated").next() == Some("true");
+            continuation = xml_values(&body, "NextContinuationToken")
+                .next()
+                .map(ToString::to_string);
+            if !truncated || continuation.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    pub(super) async fn get_json<T: for<'de> Deserialize<'de>>(
         &self,
         key: &str,
     ) -> Result<Option<T>, StorageError> {
@@ -100,7 +171,11 @@ impl R2Storage {
         Ok(Some(parsed))
     }
 
-    async fn put_json<T: Serialize>(&self, key: &str, value: &T) -> Result<(), StorageError> {
+    pub(super) async fn put_json<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
         let body = serde_json::to_vec(value).map_err(|e| StorageError::new(e.to_string()))?;
         self.put_object(key, body).await
     }
@@ -108,6 +183,22 @@ impl R2Storage {
 
 #[async_trait::async_trait]
 impl Storage for R2Storage {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        let mut ids: Vec<i32> = self
+            .list_keys("events/")
+            .await?
+            .iter()
+            .filter_map(|key| {
+                key.strip_prefix("events/")?
+                    .strip_suffix("/event.json")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
     async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
         let key = Self::event_key(event_id);
         let details = self
@@ -152,21 +243,20 @@ impl Storage for R2Storage {
             return Ok(golfers);
         }
 
-        Err(StorageError::new("golfers not found"))
+        match self.get_event_archive(event_id).await? {
+            Some(archive) => archive.picks(),
+            None => Ok(Vec::new()),
+        }
     }
 
     async fn get_player_step_factors(
         &self,
         event_id: i32,
     ) -> Result<HashMap<(i64, String), f32>, StorageError> {
-        let scores_key = Self::scores_key(event_id);
-        let scores = self
-            .get_json::<ScoresAndLastRefresh>(&scores_key)
+        // Picks carry their factor whether they come from golfers.json or stored scores.
+        let step_factors = self
+            .get_golfers_for_event(event_id)
             .await?
-            .ok_or_else(|| StorageError::new("scores not found"))?;
-
-        let step_factors = scores
-            .score_struct
             .iter()
             .filter_map(|score| {
                 let step = score.score_view_step_factor?;
@@ -183,10 +273,13 @@ impl Storage for R2Storage {
         source: RefreshSource,
     ) -> Result<ScoresAndLastRefresh, StorageError> {
         let key = Self::scores_key(event_id);
-        let mut scores = self
-            .get_json::<ScoresAndLastRefresh>(&key)
-            .await?
-            .ok_or_else(|| StorageError::new("scores not found"))?;
+        let Some(mut scores) = self.get_json::<ScoresAndLastRefresh>(&key).await? else {
+            return self
+                .get_event_archive(event_id)
+                .await?
+                .and_then(|archive| archive.archived_scores())
+                .ok_or_else(|| StorageError::new("scores not found"));
+        };
         scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
             RefreshSource::Espn
         } else {
@@ -209,6 +302,9 @@ impl Storage for R2Storage {
         event_id: i32,
         max_age_seconds: i64,
     ) -> Result<bool, StorageError> {
+        if max_age_seconds <= 0 {
+            return Ok(false);
+        }
         let key = Self::scores_key(event_id);
         let Some(scores) = self.get_json::<ScoresAndLastRefresh>(&key).await? else {
             return Ok(false);
@@ -216,8 +312,77 @@ impl Storage for R2Storage {
 
         let now = chrono::Utc::now().naive_utc();
         let last_refresh: NaiveDateTime = scores.last_refresh;
-        // Match SQL behavior: compare cache age in days.
         let diff = now.signed_duration_since(last_refresh);
-        Ok(diff.num_days() >= max_age_seconds)
+        Ok(diff.num_seconds() <= max_age_seconds)
+    }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let key = Self::event_key(event_id);
+        let mut details = self
+            .get_json::<R2EventDetails>(&key)
+            .await?
+            .ok_or_else(|| StorageError::new("event details not found"))?;
+        details.completed = true;
+        if details.end_date.is_none() {
+            details.end_date = end_date.map(ToString::to_string);
+        }
+        self.put_json(&key, &details).await
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let key = Self::score_changes_key(event_id);
+        let existing = self
+            .get_json::<Vec<ScoreChange>>(&key)
+            .await?
+            .unwrap_or_default();
+        let merged = merge_recent_score_changes(existing, changes);
+        self.put_json(&key, &merged).await
     }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        match self
+            .get_json::<Vec<ScoreChange>>(&Self::score_changes_key(event_id))
+            .await?
+        {
+            Some(changes) => Ok(changes),
+            None => Ok(self
+                .get_event_archive(event_id)
+                .await?
+                .map(|archive| archive.score_changes)
+                .unwrap_or_default()),
+        }
+    }
+
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        self.get_json(&Self::archive_key(event_id)).await
+    }
+}
+
+/// The text of every `<tag>` element in a `ListObjectsV2` response. Our keys need no
+/// entity decoding.
+fn xml_values<'a>(body: &'a str, tag: &str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut rest = body;
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let len = rest[start..].find(&close)?;
+        let value = &rest[start..start + len];
+        rest = &rest[start + len + close.len()..];
+        Some(value)
+    })
 }
@@ -0,0 +1,146 @@
+use rusty_golf_core::storage::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    GolferRecord, PlayerStepFactor, Storage, StorageError, apply_player_step_factors,
+    picks_from_assignments,
+};
+use std::collections::HashMap;
+
+use super::R2Storage;
+use super::r2_types::R2EventDetails;
+
+impl R2Storage {
+    async fn require_event(&self, event_id: i32) -> Result<(), StorageError> {
+        self.get_json::<R2EventDetails>(&Self::event_key(event_id))
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| StorageError::new("event details not found"))
+    }
+
+    async fn golfer_directory(&self) -> Result<Vec<GolferRecord>, StorageError> {
+        Ok(self
+            .get_json::<Vec<GolferRecord>>(Self::golfer_directory_key())
+            .await?
+            .unwrap_or_default())
+    }
+}
+
+fn event_doc(config: &EventConfig) -> R2EventDetails {
+    R2EventDetails {
+        event_name: config.event_name.clone(),
+        score_view_step_factor: config.score_view_step_factor,
+        refresh_from_espn: config.refresh_from_espn,
+        start_date: config.start_date.clone(),
+        end_date: config.end_date.clone(),
+        completed: config.completed,
+    }
+}
+
+#[async_trait::async_trait]
+impl AdminStorage for R2Storage {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        let key = Self::event_key(event_id);
+        if self.get_object(&key).await?.is_some() {
+            return Err(StorageError::new(format!(
+                "event {event_id} already exists"
+            )));
+        }
+        self.put_json(&key, &event_doc(config)).await
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        self.put_json(&Self::event_key(event_id), &event_doc(config))
+            .await
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        let key = Self::event_key(event_id);
+        let existed = self.get_object(&key).await?.is_some();
+        for object in [
+            Self::golfers_key(event_id),
+            Self::scores_key(event_id),
+            Self::score_changes_key(event_id),
+            Self::archive_key(event_id),
+            key,
+        ] {
+            self.delete_object(&object).await?;
+        }
+        Ok(existed)
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        let mut directory: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        for golfer in golfers {
+            directory.insert(golfer.espn_id, golfer.name.clone());
+        }
+        let mut directory: Vec<GolferRecord> = directory
+            .into_iter()
+            .map(|(espn_id, name)| GolferRecord { espn_id, name })
+            .collect();
+        directory.sort_by_key(|golfer| golfer.espn_id);
+        self.put_json(Self::golfer_directory_key(), &directory)
+            .await
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        let names: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        let picks = picks_from_assignments(event_id, assignments, &names)?;
+        self.put_json(&Self::golfers_key(event_id), &picks).await?;
+        self.delete_object(&Self::scores_key(event_id)).await
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        let mut picks = self.get_golfers_for_event(event_id).await?;
+        apply_player_step_factors(event_id, &mut picks, factors)?;
+        self.put_json(&Self::golfers_key(event_id), &picks).await
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        _policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        self.require_event(event_id).await?;
+        // The archive goes first so every read has somewhere to land; event.json stays.
+        self.put_json(&Self::archive_key(event_id), archive).await?;
+        let live = [
+            Self::golfers_key(event_id),
+            Self::scores_key(event_id),
+            Self::score_changes_key(event_id),
+        ];
+        let mut records_removed = 0;
+        for object in &live {
+            if self.get_object(object).await?.is_some() {
+                self.delete_object(object).await?;
+                records_removed += 1;
+            }
+        }
+        // The actix R2 backend keeps no score history.
+        Ok(CompactionReport {
+            event_id,
+            records_removed,
+            ..CompactionReport::default()
+        })
+    }
+}
@@ -26,19 +26,36 @@ impl R2StorageConfig {
     /// # Errors
     /// Returns an error if required environment variables are missing.
     pub fn from_env() -> Result<Self, StorageError> {
+        Self::from_env_with(None, None, None)
+    }
+
+    /// Like [`Self::from_env`], with `endpoint`, `bucket` and `region` taking precedence over
+    /// their environment variables when given (the actix `--r2-*` flags).
+    ///
+    /// # Errors
+    /// Returns an error if a required value is neither given nor in the environment.
+    pub fn from_env_with(
+        endpoint: Option<String>,
+        bucket: Option<String>,
+        region: Option<String>,
+    ) -> Result<Self, StorageError> {
         dotenvy::dotenv().ok();
 
-        let endpoint =
-            std::env::var("R2_ENDPOINT").map_err(|_| StorageError::new("missing R2_ENDPOINT"))?;
-        let bucket =
-            std::env::var("R2_BUCKET").map_err(|_| StorageError::new("missing R2_BUCKET"))?;
+        let endpoint = endpoint
+            .or_else(|| std::env::var("R2_ENDPOINT").ok())
+            .ok_or_else(|| StorageError::new("missing R2_ENDPOINT"))?;
+        let bucket = bucket
+            .or_else(|| std::env::var("R2_BUCKET").ok())
+            .ok_or_else(|| StorageError::new("missing R2_BUCKET"))?;
         let access_key_id = std::env::var("R2_ACCESS_KEY_ID")
             .or_else(|_| std::env::var("AWS_ACCESS_KEY_ID"))
             .map_err(|_| StorageError::new("missing R2_ACCESS_KEY_ID"))?;
         let secret_access_key = std::env::var("R2_SECRET_ACCESS_KEY")
             .or_else(|_| std::env::var("AWS_SECRET_ACCESS_KEY"))
             .map_err(|_| StorageError::new("missing R2_SECRET_ACCESS_KEY"))?;
-        let region = std::env::var("R2_REGION").unwrap_or_else(|_| "auto".to_string());
+        let region = region
+            .or_else(|| std::env::var("R2_REGION").ok())
+            .unwrap_or_else(|| "auto".to_string());
         let service = std::env::var("R2_SERVICE").unwrap_or_else(|_| "s3".to_string());
 
         Ok(Self {
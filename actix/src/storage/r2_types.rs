@@ -18,6 +18,10 @@ impl R2Storage {
         format!("events/{event_id}/scores.json")
     }
 
+    pub(crate) fn archive_key(event_id: i32) -> String {
+        format!("events/{event_id}/archive.json")
+    }
+
     pub(crate) fn golfers_key(event_id: i32) -> String {
         format!("events/{event_id}/golfers.json")
     }
@@ -25,4 +29,13 @@ impl R2Storage {
     pub(crate) fn event_key(event_id: i32) -> String {
         format!("events/{event_id}/event.json")
     }
+
+    /// Golfers shared by every event, as a list of `GolferRecord`s.
+    pub(crate) fn golfer_directory_key() -> &'static str {
+        "golfers/directory.json"
+    }
+
+    pub(crate) fn score_changes_key(event_id: i32) -> String {
+        format!("events/{event_id}/score_changes.json")
+    }
 }
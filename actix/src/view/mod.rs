@@ -1,3 +1,3 @@
-pub use rusty_golf_core::view::index;
+pub use rusty_golf_core::view::{events, index};
 
 pub mod score;
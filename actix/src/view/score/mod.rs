@@ -4,6 +4,7 @@ use maud::Markup;
 use rusty_golf_core::storage::Storage;
 use sql_middleware::middleware::ConfigAndPool;
 
+use crate::model::database_read::get_scores_from_db;
 use crate::model::{RefreshSource, ScoreData};
 use crate::storage::SqlStorage;
 
@@ -18,8 +19,9 @@ pub async fn render_scores_template(
     config_and_pool: &ConfigAndPool,
     event_id: i32,
 ) -> Result<Markup, Box<dyn std::error::Error>> {
+    // Read the rows directly: an event with no stored scores yet renders empty tables.
+    let from_db_scores = get_scores_from_db(config_and_pool, event_id, RefreshSource::Db).await?;
     let storage = SqlStorage::new(config_and_pool.clone());
-    let from_db_scores = storage.get_scores(event_id, RefreshSource::Db).await?;
     let bettor_struct = scores_and_last_refresh_to_line_score_tables(&from_db_scores);
     let event_details = storage.get_event_details(event_id).await?;
     let player_step_factors = storage.get_player_step_factors(event_id).await?;
@@ -32,6 +34,7 @@ pub async fn render_scores_template(
         &player_step_factors,
         event_id,
         0,
+        None,
         true,
     ))
 }
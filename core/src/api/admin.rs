@@ -0,0 +1,216 @@
+//! Request and response bodies of the serverless `/listing` admin mode and `/admin/*`
+//! endpoints (see `serverless/docs/admin_endpoints.md`). They live here, not in the
+//! worker crate, so [`openapi_document`](super::openapi_document) can describe them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{RefreshSource, ScoreChange, Scores};
+use crate::score::ScoreHistoryRetention;
+use crate::storage::EventArchive;
+
+/// One event in the `/listing` admin JSON.
+#[derive(Clone, Debug, Serialize)]
+pub struct EventListing {
+    pub event_id: i32,
+    pub event_name: String,
+    pub year: Option<i32>,
+    pub score_view_step_factor: f32,
+    pub refresh_from_espn: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminListingResponse {
+    pub events: Vec<EventListing>,
+    pub kv_keys: Vec<String>,
+    /// Empty when `event_id` is given.
+    pub r2_keys: Vec<String>,
+    pub event_id: Option<i32>,
+    pub scores_exists: Option<bool>,
+    pub espn_cache_exists: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminSeedRequest {
+    pub event_id: i32,
+    pub refresh_from_espn: i64,
+    pub event: AdminEupEvent,
+    pub score_struct: Vec<Scores>,
+    pub espn_cache: serde_json::Value,
+    pub auth_tokens: Option<Vec<String>>,
+    pub last_refresh: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminEupEvent {
+    pub event: i64,
+    pub name: String,
+    pub score_view_step_factor: serde_json::Value,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+    pub data_to_fill_if_event_and_year_missing: Vec<AdminEupDataFill>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminEupDataFill {
+    pub golfers: Vec<AdminEupGolfer>,
+    pub event_user_player: Vec<AdminEupEventUserPlayer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminEupGolfer {
+    pub espn_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminEupEventUserPlayer {
+    pub bettor: String,
+    pub golfer_espn_id: i64,
+    pub score_view_step_factor: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCleanupRequest {
+    pub event_id: i32,
+    #[serde(default)]
+    pub include_auth_tokens: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCleanupScoresRequest {
+    pub event_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminExportRequest {
+    pub event_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminImportRequest {
+    pub archive: EventArchive,
+    #[serde(default)]
+    pub replace: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminArchiveRequest {
+    pub event_id: i32,
+    /// Defaults to `CompactionPolicy::default()`.
+    #[serde(default)]
+    pub history_interval_seconds: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminHistorySnapshot {
+    pub taken_at: String,
+    pub key: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminHistoryResponse {
+    pub event_id: i32,
+    pub retention: ScoreHistoryRetention,
+    pub snapshots: Vec<AdminHistorySnapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminCacheFlushRequest {
+    pub event_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminUpdateDatesRequest {
+    pub event_id: i32,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub completed: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminEspnFailRequest {
+    pub event_id: i32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminTestLockRequest {
+    pub event_id: i32,
+    pub token: String,
+    pub ttl_secs: Option<i64>,
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTestLockResponse {
+    pub acquired: bool,
+    pub is_first: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminTestUnlockRequest {
+    pub event_id: AdminEventSelector,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTestUnlockResponse {
+    pub is_last: bool,
+}
+
+/// One `Storage` call for `/admin/test_storage`, which lets the native test suite hold the
+/// worker's storage to the same contract as the other backends. `method` names the trait
+/// method; only the arguments it takes are read. `set_last_refresh` isn't a trait method:
+/// it moves the event's last refresh to `last_refresh` (RFC 3339).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AdminTestStorageRequest {
+    pub method: String,
+    #[serde(default)]
+    pub event_id: i32,
+    #[serde(default)]
+    pub source: Option<RefreshSource>,
+    #[serde(default)]
+    pub scores: Vec<Scores>,
+    #[serde(default)]
+    pub changes: Vec<ScoreChange>,
+    #[serde(default)]
+    pub max_age_seconds: Option<i64>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub last_refresh: Option<String>,
+}
+
+/// An event id, or `"all"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AdminEventSelector {
+    All(String),
+    Id(i32),
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct CacheStatus {
+    pub exists: bool,
+    pub remaining_ttl_seconds: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStatusKeys {
+    pub kv: String,
+    pub r2_scores: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStatusResponse {
+    pub event_id: i32,
+    pub year: i32,
+    pub in_memory: CacheStatus,
+    pub kv: CacheStatus,
+    pub r2: CacheStatus,
+    pub keys: CacheStatusKeys,
+}
@@ -0,0 +1,166 @@
+//! Wire types for `/api/v1`. They are built from storage and score structs but never share
+//! them, so internal fields can change without breaking API clients.
+
+use serde::{Deserialize, Serialize};
+
+/// `GET /api/v1/events`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EventList {
+    pub events: Vec<EventSummary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EventSummary {
+    pub event_id: i32,
+    pub name: String,
+    /// The ESPN season; taken from the start date, then the end date.
+    pub year: i32,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub completed: bool,
+}
+
+/// `GET /api/v1/events/{event_id}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EventDetail {
+    #[serde(flatten)]
+    pub event: EventSummary,
+    /// Bettors in the order their first pick was made.
+    pub bettors: Vec<EventBettor>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EventBettor {
+    pub bettor_name: String,
+    pub picks: Vec<Pick>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Pick {
+    pub golfer_espn_id: i64,
+    pub golfer_name: String,
+    pub group: i64,
+}
+
+/// Where and when the scores behind a response were last refreshed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Freshness {
+    /// UTC, RFC 3339.
+    pub refreshed_at: String,
+    /// `espn`, `database`, `kv`, `r2`, `file`, `memory` or `archive`.
+    pub source: String,
+}
+
+/// `GET /api/v1/events/{event_id}/standings`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Standings {
+    pub event_id: i32,
+    #[serde(flatten)]
+    pub freshness: Freshness,
+    /// Best (lowest) total first.
+    pub standings: Vec<Standing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Standing {
+    /// 1-based; tied bettors share a rank and the next rank is skipped.
+    pub rank: usize,
+    pub bettor_name: String,
+    /// Strokes relative to par, summed over the bettor's golfers.
+    pub total_score: i32,
+}
+
+/// `GET /api/v1/events/{event_id}/bettors/{bettor_name}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BettorRoster {
+    pub event_id: i32,
+    #[serde(flatten)]
+    pub freshness: Freshness,
+    pub bettor_name: String,
+    pub rank: usize,
+    pub total_score: i32,
+    pub golfers: Vec<RosterGolfer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RosterGolfer {
+    pub golfer_espn_id: i64,
+    pub golfer_name: String,
+    pub group: i64,
+    pub total_score: i32,
+    /// Relative to par, one entry per round started.
+    pub round_scores: Vec<i32>,
+}
+
+/// `GET /api/v1/events/{event_id}/golfers/{golfer_espn_id}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GolferScorecard {
+    pub event_id: i32,
+    #[serde(flatten)]
+    pub freshness: Freshness,
+    pub golfer_espn_id: i64,
+    pub golfer_name: String,
+    /// Every bettor who picked this golfer.
+    pub picked_by: Vec<String>,
+    pub total_score: i32,
+    pub rounds: Vec<ScorecardRound>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScorecardRound {
+    /// 1-based.
+    pub round: i32,
+    /// Relative to par.
+    pub score: i32,
+    /// As ESPN's scoreboard shows it, in US Central time.
+    pub tee_time: Option<String>,
+    pub holes: Vec<ScorecardHole>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScorecardHole {
+    pub hole: i32,
+    pub par: i32,
+    pub strokes: i32,
+}
+
+/// `GET /api/v1/events/{event_id}/rounds`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RoundSummaries {
+    pub event_id: i32,
+    #[serde(flatten)]
+    pub freshness: Freshness,
+    pub rounds: Vec<RoundSummary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RoundSummary {
+    /// 1-based.
+    pub round: i32,
+    /// In standings order.
+    pub bettors: Vec<BettorRound>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BettorRound {
+    pub bettor_name: String,
+    /// The bettor's golfers' scores for this round, relative to par.
+    pub score: i32,
+    /// This round and every one before it.
+    pub cumulative: i32,
+}
+
+/// The body of every `/api/v1` error response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ErrorBody {
+    pub error: ErrorDetail,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ErrorDetail {
+    /// Same as the HTTP status.
+    pub status: u16,
+    /// `bad_request`, `not_found`, `upstream_unavailable` or `internal`.
+    pub code: String,
+    pub message: String,
+}
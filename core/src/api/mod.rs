@@ -0,0 +1,20 @@
+//! The versioned public JSON API, mounted at [`API_V1_PREFIX`] by both runtimes.
+//!
+//! A runtime hands the raw path and query to [`parse_api_request`], answers with
+//! [`handle_api_request`], and sends [`ApiError::body`] with its status on failure. Responses
+//! are [`dto`] types only, never storage or view structs. [`openapi_document`] describes
+//! these endpoints and the other JSON ones, and is served at [`OPENAPI_PATH`].
+
+pub mod admin;
+pub mod dto;
+mod error;
+mod openapi;
+mod request;
+pub mod schema;
+mod service;
+
+pub use error::ApiError;
+pub use openapi::{OPENAPI_PATH, openapi_document};
+pub use request::{API_V1_PREFIX, ApiRequest, ApiRoute, parse_api_request};
+pub use service::{ApiResponse, handle_api_request, standings_from_scores};
+pub(crate) use service::{golfer_scorecard, round_summaries, summarize_event};
@@ -0,0 +1,459 @@
+use serde_json::{Map, Value, json};
+
+use super::API_V1_PREFIX;
+use super::admin::{
+    AdminArchiveRequest, AdminCacheFlushRequest, AdminCleanupRequest, AdminCleanupScoresRequest,
+    AdminEspnFailRequest, AdminExportRequest, AdminHistoryResponse, AdminImportRequest,
+    AdminListingResponse, AdminSeedRequest, AdminTestLockRequest, AdminTestLockResponse,
+    AdminTestStorageRequest, AdminTestUnlockRequest, AdminTestUnlockResponse,
+    AdminUpdateDatesRequest, CacheStatusResponse,
+};
+use super::dto::{
+    BettorRoster, ErrorBody, EventDetail, EventList, GolferScorecard, RoundSummaries, Standings,
+};
+use super::schema::{ApiSchema, Components};
+use crate::model::ScoreData;
+use crate::storage::{CompactionReport, EventArchive};
+
+/// Where both runtimes serve [`openapi_document`].
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// One operation: its method, path and parameters plus the types it reads and writes.
+struct Operation {
+    method: &'static str,
+    path: String,
+    summary: &'static str,
+    tag: &'static str,
+    parameters: Vec<Value>,
+    request: Option<Value>,
+    /// `(status, description, body)`; a `None` body is plain text.
+    responses: Vec<(u16, &'static str, Option<Value>)>,
+    admin: bool,
+}
+
+impl Operation {
+    fn new(method: &'static str, path: String, summary: &'static str, tag: &'static str) -> Self {
+        Self {
+            method,
+            path,
+            summary,
+            tag,
+            parameters: Vec::new(),
+            request: None,
+            responses: Vec::new(),
+            admin: false,
+        }
+    }
+
+    fn param(mut self, location: &str, name: &str, required: bool, schema: Value) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": location,
+            "required": required,
+            "schema": schema,
+        }));
+        self
+    }
+
+    fn body(mut self, schema: Value) -> Self {
+        self.request = Some(schema);
+        self
+    }
+
+    fn respond(mut self, status: u16, description: &'static str, body: Option<Value>) -> Self {
+        self.responses.push((status, description, body));
+        self
+    }
+
+    fn admin(mut self) -> Self {
+        self.admin = true;
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut responses = Map::new();
+        for (status, description, body) in &self.responses {
+            let content = match body {
+                Some(schema) => json!({ "application/json": { "schema": schema } }),
+                None => json!({ "text/plain": { "schema": { "type": "string" } } }),
+            };
+            responses.insert(
+                status.to_string(),
+                json!({ "description": description, "content": content }),
+            );
+        }
+        let mut operation = Map::new();
+        operation.insert("summary".to_string(), json!(self.summary));
+        operation.insert("tags".to_string(), json!([self.tag]));
+        if !self.parameters.is_empty() {
+            operation.insert("parameters".to_string(), json!(self.parameters));
+        }
+        if let Some(schema) = &self.request {
+            operation.insert(
+                "requestBody".to_string(),
+                json!({
+                    "required": true,
+                    "content": { "application/json": { "schema": schema } },
+                }),
+            );
+        }
+        operation.insert("responses".to_string(), Value::Object(responses));
+        if self.admin {
+            operation.insert(
+                "security".to_string(),
+                json!([{ "adminHeader": [] }, { "adminQuery": [] }]),
+            );
+        }
+        Value::Object(operation)
+    }
+}
+
+/// The OpenAPI 3.0 description of every JSON endpoint either runtime serves: `/api/v1`,
+/// `/scores?json=1`, and the serverless `/listing` admin mode and `/admin/*` endpoints.
+/// Schemas come from the Rust types the handlers serialize and parse.
+#[must_use]
+pub fn openapi_document() -> Value {
+    let mut components = Components::new();
+    let operations = operations(&mut components);
+
+    let mut paths = Map::new();
+    for operation in &operations {
+        let item = paths
+            .entry(operation.path.clone())
+            .or_insert_with(|| json!({}));
+        item[operation.method] = operation.to_json();
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "rusty-golf",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Golf pool scores. `/api/v1` is the stable API; `/scores?json=1` \
+                            is the page's own data and may change with it. `/listing` admin \
+                            mode and `/admin/*` exist only on the serverless worker.",
+        },
+        "paths": paths,
+        "components": {
+            "schemas": components,
+            "securitySchemes": {
+                "adminHeader": { "type": "apiKey", "in": "header", "name": "x-admin-token" },
+                "adminQuery": { "type": "apiKey", "in": "query", "name": "admin_token" },
+            },
+        },
+    })
+}
+
+#[allow(clippy::too_many_lines)]
+fn operations(c: &mut Components) -> Vec<Operation> {
+    let int = json!({ "type": "integer" });
+    let string = json!({ "type": "string" });
+    let error = ErrorBody::schema(c);
+    let event_id = |operation: Operation| operation.param("path", "event_id", true, int.clone());
+    let api_query = |operation: Operation| {
+        operation.param("query", "yr", false, int.clone()).param(
+            "query",
+            "cache",
+            false,
+            json!({ "type": "string", "enum": ["0", "1"] }),
+        )
+    };
+    let api = |path: &str| format!("{API_V1_PREFIX}{path}");
+    let api_errors = |operation: Operation| {
+        operation
+            .respond(400, "Malformed id or parameter", Some(error.clone()))
+            .respond(404, "Unknown event, bettor or golfer", Some(error.clone()))
+            .respond(
+                502,
+                "Scores had to come from ESPN and couldn't",
+                Some(error.clone()),
+            )
+            .respond(500, "Storage failure", Some(error.clone()))
+    };
+
+    let mut operations = vec![
+        api_errors(
+            Operation::new("get", api("/events"), "List stored events", "api").respond(
+                200,
+                "Every event, by id",
+                Some(EventList::schema(c)),
+            ),
+        ),
+        api_errors(event_id(
+            Operation::new(
+                "get",
+                api("/events/{event_id}"),
+                "An event and its picks",
+                "api",
+            )
+            .respond(200, "The event", Some(EventDetail::schema(c))),
+        )),
+        api_errors(api_query(event_id(
+            Operation::new(
+                "get",
+                api("/events/{event_id}/standings"),
+                "Bettors ranked by total score",
+                "api",
+            )
+            .respond(200, "Standings", Some(Standings::schema(c))),
+        ))),
+        api_errors(api_query(
+            event_id(
+                Operation::new(
+                    "get",
+                    api("/events/{event_id}/bettors/{bettor_name}"),
+                    "One bettor's golfers",
+                    "api",
+                )
+                .respond(200, "The bettor's roster", Some(BettorRoster::schema(c))),
+            )
+            .param("path", "bettor_name", true, string.clone()),
+        )),
+        api_errors(api_query(
+            event_id(
+                Operation::new(
+                    "get",
+                    api("/events/{event_id}/golfers/{golfer_espn_id}"),
+                    "One golfer's scorecard",
+                    "api",
+                )
+                .respond(200, "The scorecard", Some(GolferScorecard::schema(c))),
+            )
+            .param("path", "golfer_espn_id", true, int.clone()),
+        )),
+        api_errors(api_query(event_id(
+            Operation::new(
+                "get",
+                api("/events/{event_id}/rounds"),
+                "Each bettor's score by round",
+                "api",
+            )
+            .respond(200, "Round summaries", Some(RoundSummaries::schema(c))),
+        ))),
+        Operation::new(
+            "get",
+            "/scores".to_string(),
+            "The scoreboard page, or its data with json=1",
+            "scores",
+        )
+        .param("query", "event", true, int.clone())
+        .param("query", "yr", true, int.clone())
+        .param(
+            "query",
+            "json",
+            false,
+            json!({ "type": "string", "enum": ["0", "1"] }),
+        )
+        .param(
+            "query",
+            "cache",
+            false,
+            json!({ "type": "string", "enum": ["0", "1"] }),
+        )
+        .param(
+            "query",
+            "expanded",
+            false,
+            json!({ "type": "string", "enum": ["0", "1"] }),
+        )
+        .param("query", "as_of", false, string.clone())
+        .respond(
+            200,
+            "With json=1, the scoreboard data",
+            Some(ScoreData::schema(c)),
+        )
+        .respond(400, "Missing or malformed parameter", None),
+        Operation::new(
+            "get",
+            "/listing".to_string(),
+            "Admin JSON mode of the event listing",
+            "admin",
+        )
+        .param("header", "x-admin-token", true, string.clone())
+        .param("query", "event_id", false, int.clone())
+        .respond(
+            200,
+            "Events and storage keys",
+            Some(AdminListingResponse::schema(c)),
+        ),
+    ];
+
+    let admin = |method: &'static str, path: &str, summary: &'static str| {
+        Operation::new(method, format!("/admin/{path}"), summary, "admin")
+            .admin()
+            .respond(401, "Missing or wrong admin token", None)
+            .respond(404, "Admin endpoints are disabled", None)
+    };
+    let done = |operation: Operation, text: &'static str| operation.respond(200, text, None);
+    operations.extend([
+        done(
+            admin("post", "seed", "Seed an event").body(AdminSeedRequest::schema(c)),
+            "`seeded`",
+        ),
+        done(
+            admin("post", "cleanup", "Delete an event's data").body(AdminCleanupRequest::schema(c)),
+            "`cleaned`",
+        ),
+        admin("post", "export", "Export an event as an archive")
+            .body(AdminExportRequest::schema(c))
+            .respond(200, "The archive", Some(EventArchive::schema(c))),
+        done(
+            admin("post", "import", "Rebuild an event from an archive")
+                .body(AdminImportRequest::schema(c)),
+            "`imported`",
+        ),
+        admin(
+            "post",
+            "archive",
+            "Archive a completed event and compact its history",
+        )
+        .body(AdminArchiveRequest::schema(c))
+        .respond(200, "What was compacted", Some(CompactionReport::schema(c))),
+        admin("get", "history", "List an event's score snapshots")
+            .param("query", "event_id", true, int.clone())
+            .respond(
+                200,
+                "Snapshots, oldest first",
+                Some(AdminHistoryResponse::schema(c)),
+            ),
+        done(
+            admin("post", "cleanup_scores", "Delete an event's scores")
+                .body(AdminCleanupScoresRequest::schema(c)),
+            "`cleaned scores`",
+        ),
+        done(
+            admin("post", "cache_flush", "Flush an event's score caches")
+                .body(AdminCacheFlushRequest::schema(c)),
+            "`cache flushed`",
+        ),
+        done(
+            admin("post", "event_update_dates", "Update an event's dates")
+                .body(AdminUpdateDatesRequest::schema(c)),
+            "`updated`",
+        ),
+        done(
+            admin("post", "espn_fail", "Force ESPN fetches to fail")
+                .body(AdminEspnFailRequest::schema(c)),
+            "`updated`",
+        ),
+        admin("post", "test_lock", "Take a test lock")
+            .body(AdminTestLockRequest::schema(c))
+            .respond(200, "Lock state", Some(AdminTestLockResponse::schema(c))),
+        admin("post", "test_unlock", "Release a test lock")
+            .body(AdminTestUnlockRequest::schema(c))
+            .respond(200, "Lock state", Some(AdminTestUnlockResponse::schema(c))),
+        admin("post", "test_storage", "Make one storage call")
+            .body(AdminTestStorageRequest::schema(c))
+            .respond(200, "What the call returned", None)
+            .respond(500, "The call failed, as text", None),
+    ]);
+    // Cache status takes an instrument or auth token instead of the admin token.
+    for method in ["get", "post"] {
+        operations.push(
+            Operation::new(
+                method,
+                "/admin/cache_status".to_string(),
+                "Cache status for an event and year",
+                "admin",
+            )
+            .param("query", "event", true, int.clone())
+            .param("query", "yr", true, int.clone())
+            .param("query", "auth_token", false, string.clone())
+            .param("header", "x-instrument-token", false, string.clone())
+            .respond(200, "Cache status", Some(CacheStatusResponse::schema(c)))
+            .respond(400, "Missing or malformed parameter", None)
+            .respond(401, "No valid token", None),
+        );
+    }
+    operations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    found.push(target);
+                }
+                map.values().for_each(|child| refs(child, found));
+            }
+            Value::Array(items) => items.iter().for_each(|child| refs(child, found)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn every_ref_resolves() {
+        let document = openapi_document();
+        let schemas = &document["components"]["schemas"];
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        assert!(found.len() > 20);
+        for target in found {
+            let name = target
+                .strip_prefix("#/components/schemas/")
+                .expect("local ref");
+            assert!(schemas[name].is_object(), "{name} is registered");
+        }
+    }
+
+    #[test]
+    fn documents_api_and_admin_paths() {
+        let document = openapi_document();
+        let paths = document["paths"].as_object().unwrap();
+        for (path, method) in [
+            ("/api/v1/events", "get"),
+            ("/api/v1/events/{event_id}/golfers/{golfer_espn_id}", "get"),
+            ("/scores", "get"),
+            ("/listing", "get"),
+            ("/admin/seed", "post"),
+            ("/admin/archive", "post"),
+            ("/admin/history", "get"),
+            ("/admin/cache_status", "post"),
+        ] {
+            assert!(paths[path][method].is_object(), "{method} {path}");
+        }
+        assert_eq!(
+            paths["/scores"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ScoreData"
+        );
+    }
+
+    #[test]
+    fn schemas_follow_serde() {
+        let document = openapi_document();
+        let schemas = &document["components"]["schemas"];
+
+        let summary = &schemas["EventSummary"];
+        let required = summary["required"].as_array().unwrap();
+        assert!(required.contains(&json!("event_id")));
+        assert!(
+            !required.contains(&json!("start_date")),
+            "Option is optional"
+        );
+        assert_eq!(summary["properties"]["start_date"]["nullable"], true);
+
+        let cleanup = schemas["AdminCleanupRequest"]["required"]
+            .as_array()
+            .unwrap();
+        assert_eq!(
+            cleanup,
+            &[json!("event_id")],
+            "#[serde(default)] is optional"
+        );
+
+        // Flattened freshness fields sit beside the standings.
+        assert_eq!(
+            schemas["Standings"]["allOf"][0]["$ref"],
+            "#/components/schemas/Freshness"
+        );
+        assert_eq!(
+            schemas["RefreshSource"]["enum"],
+            json!(["Db", "R2", "Kv", "Memory", "File", "Archive", "Espn"])
+        );
+        assert_eq!(schemas["HoleResult"]["enum"][5], "double_bogey_or_worse");
+    }
+}
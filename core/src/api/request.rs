@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use super::ApiError;
+
+/// Where both runtimes mount the API.
+pub const API_V1_PREFIX: &str = "/api/v1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiRoute {
+    Events,
+    Event { event_id: i32 },
+    Standings { event_id: i32 },
+    Bettor { event_id: i32, bettor_name: String },
+    Golfer { event_id: i32, golfer_espn_id: i64 },
+    Rounds { event_id: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiRequest {
+    pub route: ApiRoute,
+    /// Overrides the year derived from the event's dates when refreshing from ESPN.
+    pub year: Option<i32>,
+    pub use_cache: bool,
+}
+
+/// Parse a request path (still percent-encoded, with or without [`API_V1_PREFIX`]) and its
+/// query parameters. Only `yr` and `cache=0` are read from the query, as on `/scores`.
+///
+/// # Errors
+/// Returns `not_found` for paths that name no resource and `bad_request` for malformed ids
+/// or parameters.
+pub fn parse_api_request<S: BuildHasher>(
+    path: &str,
+    query: &HashMap<String, String, S>,
+) -> Result<ApiRequest, ApiError> {
+    let route = parse_api_route(path)?;
+    let year = query
+        .get("yr")
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| ApiError::bad_request(format!("yr must be a year, got {value:?}")))
+        })
+        .transpose()?;
+    let use_cache = !matches!(query.get("cache").map(String::as_str), Some("0"));
+    Ok(ApiRequest {
+        route,
+        year,
+        use_cache,
+    })
+}
+
+fn parse_api_route(path: &str) -> Result<ApiRoute, ApiError> {
+    let relative = path.strip_prefix(API_V1_PREFIX).unwrap_or(path);
+    let segments = relative
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect::<Option<Vec<String>>>()
+        .ok_or_else(|| ApiError::bad_request("path is not valid percent-encoded UTF-8"))?;
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let route = match segments.as_slice() {
+        ["events"] => ApiRoute::Events,
+        ["events", id] => ApiRoute::Event {
+            event_id: parse_id(id, "event id")?,
+        },
+        ["events", id, "standings"] => ApiRoute::Standings {
+            event_id: parse_id(id, "event id")?,
+        },
+        ["events", id, "rounds"] => ApiRoute::Rounds {
+            event_id: parse_id(id, "event id")?,
+        },
+        ["events", id, "bettors", bettor_name] => ApiRoute::Bettor {
+            event_id: parse_id(id, "event id")?,
+            bettor_name: (*bettor_name).to_string(),
+        },
+        ["events", id, "golfers", golfer_id] => ApiRoute::Golfer {
+            event_id: parse_id(id, "event id")?,
+            golfer_espn_id: parse_id(golfer_id, "golfer id")?,
+        },
+        _ => {
+            return Err(ApiError::not_found(format!(
+                "no API resource at {API_V1_PREFIX}{relative}"
+            )));
+        }
+    };
+    Ok(route)
+}
+
+fn parse_id<T: std::str::FromStr>(value: &str, what: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::bad_request(format!("{what} must be a number, got {value:?}")))
+}
+
+/// Decode `%XX` escapes; `None` if an escape is malformed or the result isn't UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(path: &str) -> Result<ApiRoute, ApiError> {
+        parse_api_request(path, &HashMap::new()).map(|request| request.route)
+    }
+
+    #[test]
+    fn paths_map_to_routes() {
+        assert_eq!(route("/api/v1/events/"), Ok(ApiRoute::Events));
+        assert_eq!(
+            route("/api/v1/events/401580351/bettors/Mr%20Pink"),
+            Ok(ApiRoute::Bettor {
+                event_id: 401_580_351,
+                bettor_name: "Mr Pink".to_string(),
+            })
+        );
+        assert_eq!(
+            route("/events/1/golfers/3470"),
+            Ok(ApiRoute::Golfer {
+                event_id: 1,
+                golfer_espn_id: 3470,
+            })
+        );
+        assert_eq!(route("/api/v1/events/abc").map_err(|e| e.status), Err(400));
+        assert_eq!(
+            route("/api/v1/events/1/nope").map_err(|e| e.status),
+            Err(404)
+        );
+        assert_eq!(
+            route("/api/v1/events/1/bettors/%zz").map_err(|e| e.status),
+            Err(400)
+        );
+    }
+
+    #[test]
+    fn query_sets_year_and_cache() {
+        let query = HashMap::from([
+            ("yr".to_string(), "2024".to_string()),
+            ("cache".to_string(), "0".to_string()),
+        ]);
+        let request = parse_api_request("/api/v1/events/1/standings", &query).unwrap();
+        assert_eq!(request.year, Some(2024));
+        assert!(!request.use_cache);
+
+        let bad_year = HashMap::from([("yr".to_string(), "last".to_string())]);
+        assert!(parse_api_request("/api/v1/events", &bad_year).is_err());
+    }
+}
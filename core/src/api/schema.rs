@@ -0,0 +1,688 @@
+//! JSON Schema (the OpenAPI 3.0 dialect) for the types the runtimes send and accept.
+//!
+//! Structs register themselves under `#/components/schemas` through [`api_schema!`], which
+//! also destructures the struct so a renamed, added or retyped field stops the build until
+//! its schema is updated. Unit enums list what serde actually writes for each variant.
+
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+use super::admin::{
+    AdminArchiveRequest, AdminCacheFlushRequest, AdminCleanupRequest, AdminCleanupScoresRequest,
+    AdminEspnFailRequest, AdminEupDataFill, AdminEupEvent, AdminEupEventUserPlayer, AdminEupGolfer,
+    AdminEventSelector, AdminExportRequest, AdminHistoryResponse, AdminHistorySnapshot,
+    AdminImportRequest, AdminListingResponse, AdminSeedRequest, AdminTestLockRequest,
+    AdminTestLockResponse, AdminTestStorageRequest, AdminTestUnlockRequest,
+    AdminTestUnlockResponse, AdminUpdateDatesRequest, CacheStatus, CacheStatusKeys,
+    CacheStatusResponse, EventListing,
+};
+use super::dto::{
+    BettorRoster, BettorRound, ErrorBody, ErrorDetail, EventBettor, EventDetail, EventList,
+    EventSummary, Freshness, GolferScorecard, Pick, RosterGolfer, RoundSummaries, RoundSummary,
+    ScorecardHole, ScorecardRound, Standing, Standings,
+};
+use crate::model::{
+    Bettors, HoleResult, IntStat, LineScore, RefreshSource, ScoreChange, ScoreChangeKind,
+    ScoreData, ScoreDisplay, Scores, ScoresAndLastRefresh, Statistic, StringStat,
+};
+use crate::score::ScoreHistoryRetention;
+use crate::storage::{
+    Assignment, CompactionReport, EventArchive, EventConfig, GolferRecord, PlayerStepFactor,
+};
+
+/// Named schemas, keyed by Rust type name.
+pub type Components = BTreeMap<String, Value>;
+
+pub trait ApiSchema {
+    /// Whether serde accepts the field being absent.
+    const OPTIONAL: bool = false;
+
+    /// The schema for a value of this type: inline for primitives, otherwise a `$ref` to
+    /// the entry this adds to `components`.
+    fn schema(components: &mut Components) -> Value;
+}
+
+/// A `$ref` to `name`, registering the schema `build` makes the first time it's seen.
+pub(crate) fn component(
+    components: &mut Components,
+    name: &str,
+    build: impl FnOnce(&mut Components) -> Value,
+) -> Value {
+    if !components.contains_key(name) {
+        // Recursive types find the placeholder instead of recursing forever.
+        components.insert(name.to_string(), Value::Null);
+        let schema = build(components);
+        components.insert(name.to_string(), schema);
+    }
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// An object schema from `(name, schema, required)` fields, with `flattened` schemas merged
+/// in through `allOf` as serde's `#[serde(flatten)]` does.
+pub(crate) fn object_schema(
+    description: Option<&str>,
+    fields: Vec<(&str, Value, bool)>,
+    flattened: Vec<Value>,
+) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, schema, is_required) in fields {
+        if is_required {
+            required.push(Value::from(name));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+    let mut object = Map::new();
+    object.insert("type".to_string(), json!("object"));
+    object.insert("properties".to_string(), Value::Object(properties));
+    if !required.is_empty() {
+        object.insert("required".to_string(), Value::Array(required));
+    }
+    let mut schema = if flattened.is_empty() {
+        object
+    } else {
+        let mut all_of = flattened;
+        all_of.push(Value::Object(object));
+        let mut wrapper = Map::new();
+        wrapper.insert("allOf".to_string(), Value::Array(all_of));
+        wrapper
+    };
+    if let Some(description) = description {
+        schema.insert("description".to_string(), json!(description));
+    }
+    Value::Object(schema)
+}
+
+/// A string schema listing what serde writes for each of `variants`.
+pub(crate) fn unit_enum_schema<T: Serialize>(variants: &[T]) -> Value {
+    let names: Vec<Value> = variants
+        .iter()
+        .map(|variant| serde_json::to_value(variant).unwrap_or(Value::Null))
+        .collect();
+    json!({ "type": "string", "enum": names })
+}
+
+/// Implement [`ApiSchema`] for a struct from its field list.
+///
+/// `#[flatten]` and `#[default]` mirror the field's serde attributes of the same name;
+/// the optional string after the type name becomes the schema's description.
+macro_rules! api_schema {
+    ($ty:ident $($description:literal)? {
+        $( $(#[$marker:ident])* $field:ident : $field_ty:ty ),* $(,)?
+    }) => {
+        impl $crate::api::schema::ApiSchema for $ty {
+            fn schema(components: &mut $crate::api::schema::Components) -> serde_json::Value {
+                #[allow(dead_code, clippy::used_underscore_binding)]
+                fn fields_match(value: &$ty) {
+                    let $ty { $($field),* } = value;
+                    $( let _: &$field_ty = $field; )*
+                }
+                $crate::api::schema::component(components, stringify!($ty), |components| {
+                    let description: Option<&str> = None $(.or(Some($description)))?;
+                    let mut fields = Vec::new();
+                    let mut flattened = Vec::new();
+                    $(
+                        let markers: &[&str] = &[$(stringify!($marker)),*];
+                        let schema =
+                            <$field_ty as $crate::api::schema::ApiSchema>::schema(components);
+                        if markers.contains(&"flatten") {
+                            flattened.push(schema);
+                        } else {
+                            let required = !markers.contains(&"default")
+                                && !<$field_ty as $crate::api::schema::ApiSchema>::OPTIONAL;
+                            fields.push((stringify!($field), schema, required));
+                        }
+                    )*
+                    $crate::api::schema::object_schema(description, fields, flattened)
+                })
+            }
+        }
+    };
+}
+
+/// Implement [`ApiSchema`] for a unit-only enum; the match keeps the list exhaustive.
+macro_rules! api_unit_enum_schema {
+    ($ty:ident { $($variant:ident),* $(,)? }) => {
+        impl $crate::api::schema::ApiSchema for $ty {
+            fn schema(components: &mut $crate::api::schema::Components) -> serde_json::Value {
+                #[allow(dead_code)]
+                fn variants_match(value: &$ty) {
+                    match value {
+                        $( $ty::$variant => {} )*
+                    }
+                }
+                $crate::api::schema::component(components, stringify!($ty), |_| {
+                    $crate::api::schema::unit_enum_schema(&[$($ty::$variant),*])
+                })
+            }
+        }
+    };
+}
+
+macro_rules! primitive_schema {
+    ($($ty:ty => $schema:tt),* $(,)?) => {
+        $(
+            impl ApiSchema for $ty {
+                fn schema(_: &mut Components) -> Value {
+                    json!($schema)
+                }
+            }
+        )*
+    };
+}
+
+primitive_schema! {
+    bool => { "type": "boolean" },
+    String => { "type": "string" },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    u16 => { "type": "integer", "minimum": 0 },
+    u32 => { "type": "integer", "minimum": 0 },
+    usize => { "type": "integer", "minimum": 0 },
+    f32 => { "type": "number", "format": "float" },
+    serde_json::Value => {},
+    chrono::NaiveDateTime => {
+        "type": "string",
+        "description": "UTC without an offset, e.g. 2024-05-16T18:04:05",
+    },
+}
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn schema(components: &mut Components) -> Value {
+        let inner = T::schema(components);
+        // OpenAPI 3.0 ignores siblings of `$ref`, so wrap it to say it may be null.
+        if inner.get("$ref").is_some() {
+            json!({ "allOf": [inner], "nullable": true })
+        } else {
+            let mut inner = inner;
+            if let Value::Object(map) = &mut inner {
+                map.insert("nullable".to_string(), json!(true));
+            }
+            inner
+        }
+    }
+}
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+    fn schema(components: &mut Components) -> Value {
+        json!({ "type": "array", "items": T::schema(components) })
+    }
+}
+
+// `/api/v1`
+api_schema!(EventList { events: Vec<EventSummary> });
+api_schema!(EventSummary {
+    event_id: i32,
+    name: String,
+    year: i32,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    completed: bool,
+});
+api_schema!(EventDetail {
+    #[flatten]
+    event: EventSummary,
+    bettors: Vec<EventBettor>,
+});
+api_schema!(EventBettor {
+    bettor_name: String,
+    picks: Vec<Pick>,
+});
+api_schema!(Pick {
+    golfer_espn_id: i64,
+    golfer_name: String,
+    group: i64,
+});
+api_schema!(Freshness "Where and when the scores behind a response were last refreshed." {
+    refreshed_at: String,
+    source: String,
+});
+api_schema!(Standings {
+    event_id: i32,
+    #[flatten]
+    freshness: Freshness,
+    standings: Vec<Standing>,
+});
+api_schema!(Standing {
+    rank: usize,
+    bettor_name: String,
+    total_score: i32,
+});
+api_schema!(BettorRoster {
+    event_id: i32,
+    #[flatten]
+    freshness: Freshness,
+    bettor_name: String,
+    rank: usize,
+    total_score: i32,
+    golfers: Vec<RosterGolfer>,
+});
+api_schema!(RosterGolfer {
+    golfer_espn_id: i64,
+    golfer_name: String,
+    group: i64,
+    total_score: i32,
+    round_scores: Vec<i32>,
+});
+api_schema!(GolferScorecard {
+    event_id: i32,
+    #[flatten]
+    freshness: Freshness,
+    golfer_espn_id: i64,
+    golfer_name: String,
+    picked_by: Vec<String>,
+    total_score: i32,
+    rounds: Vec<ScorecardRound>,
+});
+api_schema!(ScorecardRound {
+    round: i32,
+    score: i32,
+    tee_time: Option<String>,
+    holes: Vec<ScorecardHole>,
+});
+api_schema!(ScorecardHole {
+    hole: i32,
+    par: i32,
+    strokes: i32,
+});
+api_schema!(RoundSummaries {
+    event_id: i32,
+    #[flatten]
+    freshness: Freshness,
+    rounds: Vec<RoundSummary>,
+});
+api_schema!(RoundSummary {
+    round: i32,
+    bettors: Vec<BettorRound>,
+});
+api_schema!(BettorRound {
+    bettor_name: String,
+    score: i32,
+    cumulative: i32,
+});
+api_schema!(ErrorBody "The body of every `/api/v1` error response." {
+    error: ErrorDetail,
+});
+api_schema!(ErrorDetail {
+    status: u16,
+    code: String,
+    message: String,
+});
+
+// `/scores?json=1`
+api_schema!(ScoreData "The scoreboard behind the `/scores` page." {
+    bettor_struct: Vec<Bettors>,
+    score_struct: Vec<Scores>,
+    last_refresh: String,
+    last_refresh_source: RefreshSource,
+    cache_hit: bool,
+});
+api_schema!(Bettors {
+    bettor_name: String,
+    total_score: i32,
+    scoreboard_position_name: String,
+    scoreboard_position: usize,
+});
+api_schema!(Scores "One pick: a bettor's golfer and that golfer's statistics." {
+    eup_id: i64,
+    espn_id: i64,
+    golfer_name: String,
+    bettor_name: String,
+    detailed_statistics: Statistic,
+    group: i64,
+    score_view_step_factor: Option<f32>,
+});
+api_schema!(Statistic {
+    eup_id: i64,
+    rounds: Vec<IntStat>,
+    round_scores: Vec<IntStat>,
+    tee_times: Vec<StringStat>,
+    holes_completed_by_round: Vec<IntStat>,
+    line_scores: Vec<LineScore>,
+    total_score: i32,
+});
+api_schema!(IntStat { val: i32 });
+api_schema!(StringStat { val: String });
+api_schema!(LineScore {
+    round: i32,
+    hole: i32,
+    score: i32,
+    par: i32,
+    score_display: ScoreDisplay,
+});
+api_unit_enum_schema!(ScoreDisplay {
+    DoubleCondor,
+    Condor,
+    Albatross,
+    Eagle,
+    Birdie,
+    Par,
+    Bogey,
+    DoubleBogey,
+    TripleBogey,
+    QuadrupleBogey,
+    QuintupleBogey,
+    SextupleBogey,
+    SeptupleBogey,
+    OctupleBogey,
+    NonupleBogey,
+    DodecupleBogey,
+});
+api_unit_enum_schema!(RefreshSource {
+    Db,
+    R2,
+    Kv,
+    Memory,
+    File,
+    Archive,
+    Espn,
+});
+
+// Event archives (`/admin/export`, `/admin/import`)
+api_schema!(EventArchive "A portable copy of one event; see `core/src/storage/archive.rs`." {
+    version: u32,
+    event_id: i32,
+    event: EventConfig,
+    golfers: Vec<GolferRecord>,
+    bettors: Vec<String>,
+    assignments: Vec<Assignment>,
+    step_factors: Vec<PlayerStepFactor>,
+    #[default]
+    scores: Option<ScoresAndLastRefresh>,
+    #[default]
+    score_changes: Vec<ScoreChange>,
+});
+api_schema!(EventConfig {
+    event_name: String,
+    year: i32,
+    score_view_step_factor: f32,
+    refresh_from_espn: i64,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    #[default]
+    completed: bool,
+});
+api_schema!(GolferRecord {
+    espn_id: i64,
+    name: String,
+});
+api_schema!(Assignment {
+    bettor_name: String,
+    golfer_espn_id: i64,
+});
+api_schema!(PlayerStepFactor {
+    golfer_espn_id: i64,
+    bettor_name: String,
+    step_factor: f32,
+});
+api_schema!(ScoresAndLastRefresh {
+    score_struct: Vec<Scores>,
+    last_refresh: chrono::NaiveDateTime,
+    last_refresh_source: RefreshSource,
+});
+api_schema!(ScoreChange {
+    detected_at: chrono::NaiveDateTime,
+    #[flatten]
+    change: ScoreChangeKind,
+});
+api_unit_enum_schema!(HoleResult {
+    Ace,
+    Albatross,
+    Eagle,
+    Birdie,
+    Bogey,
+    DoubleBogeyOrWorse,
+});
+
+impl ApiSchema for ScoreChangeKind {
+    fn schema(components: &mut Components) -> Value {
+        #[allow(dead_code)]
+        fn variants_match(value: &ScoreChangeKind) {
+            match value {
+                ScoreChangeKind::Hole {
+                    bettor_name: _,
+                    golfer_name: _,
+                    golfer_espn_id: _,
+                    round: _,
+                    hole: _,
+                    par: _,
+                    strokes: _,
+                    result: _,
+                }
+                | ScoreChangeKind::RoundFinished {
+                    bettor_name: _,
+                    golfer_name: _,
+                    golfer_espn_id: _,
+                    round: _,
+                    round_score: _,
+                }
+                | ScoreChangeKind::PositionChange {
+                    bettor_name: _,
+                    from_position: _,
+                    to_position: _,
+                }
+                | ScoreChangeKind::LeadChange {
+                    leaders: _,
+                    previous_leaders: _,
+                } => {}
+            }
+        }
+        component(components, "ScoreChangeKind", |components| {
+            let string = String::schema(components);
+            let i32_schema = i32::schema(components);
+            let i64_schema = i64::schema(components);
+            let usize_schema = usize::schema(components);
+            let names = Vec::<String>::schema(components);
+            let result = HoleResult::schema(components);
+            let variant = |kind: &str, fields: Vec<(&str, Value)>| {
+                let mut fields: Vec<(&str, Value, bool)> = fields
+                    .into_iter()
+                    .map(|(name, schema)| (name, schema, true))
+                    .collect();
+                fields.insert(
+                    0,
+                    ("kind", json!({ "type": "string", "enum": [kind] }), true),
+                );
+                object_schema(None, fields, Vec::new())
+            };
+            json!({
+                "description": "What changed; `kind` says which fields are present.",
+                "oneOf": [
+                    variant("hole", vec![
+                        ("bettor_name", string.clone()),
+                        ("golfer_name", string.clone()),
+                        ("golfer_espn_id", i64_schema.clone()),
+                        ("round", i32_schema.clone()),
+                        ("hole", i32_schema.clone()),
+                        ("par", i32_schema.clone()),
+                        ("strokes", i32_schema.clone()),
+                        ("result", result),
+                    ]),
+                    variant("round_finished", vec![
+                        ("bettor_name", string.clone()),
+                        ("golfer_name", string.clone()),
+                        ("golfer_espn_id", i64_schema),
+                        ("round", i32_schema.clone()),
+                        ("round_score", i32_schema),
+                    ]),
+                    variant("position_change", vec![
+                        ("bettor_name", string),
+                        ("from_position", usize_schema.clone()),
+                        ("to_position", usize_schema),
+                    ]),
+                    variant("lead_change", vec![
+                        ("leaders", names.clone()),
+                        ("previous_leaders", names),
+                    ]),
+                ],
+                "discriminator": { "propertyName": "kind" },
+            })
+        })
+    }
+}
+
+// `/listing` and `/admin/*`
+api_schema!(AdminListingResponse "`/listing` admin JSON mode." {
+    events: Vec<EventListing>,
+    kv_keys: Vec<String>,
+    r2_keys: Vec<String>,
+    event_id: Option<i32>,
+    scores_exists: Option<bool>,
+    espn_cache_exists: Option<bool>,
+});
+api_schema!(EventListing {
+    event_id: i32,
+    event_name: String,
+    year: Option<i32>,
+    score_view_step_factor: f32,
+    refresh_from_espn: i64,
+});
+api_schema!(AdminSeedRequest {
+    event_id: i32,
+    refresh_from_espn: i64,
+    event: AdminEupEvent,
+    score_struct: Vec<Scores>,
+    espn_cache: serde_json::Value,
+    auth_tokens: Option<Vec<String>>,
+    last_refresh: Option<String>,
+});
+api_schema!(AdminEupEvent {
+    event: i64,
+    name: String,
+    score_view_step_factor: serde_json::Value,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    #[default]
+    completed: bool,
+    data_to_fill_if_event_and_year_missing: Vec<AdminEupDataFill>,
+});
+api_schema!(AdminEupDataFill {
+    golfers: Vec<AdminEupGolfer>,
+    event_user_player: Vec<AdminEupEventUserPlayer>,
+});
+api_schema!(AdminEupGolfer {
+    espn_id: i64,
+    name: String,
+});
+api_schema!(AdminEupEventUserPlayer {
+    bettor: String,
+    golfer_espn_id: i64,
+    score_view_step_factor: Option<serde_json::Value>,
+});
+api_schema!(AdminCleanupRequest {
+    event_id: i32,
+    #[default]
+    include_auth_tokens: bool,
+});
+api_schema!(AdminCleanupScoresRequest { event_id: i32 });
+api_schema!(AdminExportRequest { event_id: i32 });
+api_schema!(AdminImportRequest {
+    archive: EventArchive,
+    #[default]
+    replace: bool,
+});
+api_schema!(AdminArchiveRequest {
+    event_id: i32,
+    #[default]
+    history_interval_seconds: Option<i64>,
+});
+api_schema!(CompactionReport {
+    event_id: i32,
+    records_removed: usize,
+    history_kept: usize,
+    history_pruned: usize,
+});
+api_schema!(AdminHistoryResponse {
+    event_id: i32,
+    retention: ScoreHistoryRetention,
+    snapshots: Vec<AdminHistorySnapshot>,
+});
+api_schema!(ScoreHistoryRetention {
+    max_snapshots: usize,
+    min_interval_seconds: i64,
+});
+api_schema!(AdminHistorySnapshot {
+    taken_at: String,
+    key: String,
+});
+api_schema!(AdminCacheFlushRequest { event_id: i32 });
+api_schema!(AdminUpdateDatesRequest {
+    event_id: i32,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    completed: Option<bool>,
+});
+api_schema!(AdminEspnFailRequest {
+    event_id: i32,
+    enabled: bool,
+});
+api_schema!(AdminTestLockRequest {
+    event_id: i32,
+    token: String,
+    ttl_secs: Option<i64>,
+    mode: Option<String>,
+    #[default]
+    force: bool,
+});
+api_schema!(AdminTestLockResponse {
+    acquired: bool,
+    is_first: bool,
+});
+api_schema!(AdminTestUnlockRequest {
+    event_id: AdminEventSelector,
+    token: String,
+});
+api_schema!(AdminTestUnlockResponse { is_last: bool });
+api_schema!(AdminTestStorageRequest {
+    method: String,
+    #[default]
+    event_id: i32,
+    #[default]
+    source: Option<RefreshSource>,
+    #[default]
+    scores: Vec<Scores>,
+    #[default]
+    changes: Vec<ScoreChange>,
+    #[default]
+    max_age_seconds: Option<i64>,
+    #[default]
+    end_date: Option<String>,
+    #[default]
+    last_refresh: Option<String>,
+});
+api_schema!(CacheStatusResponse {
+    event_id: i32,
+    year: i32,
+    in_memory: CacheStatus,
+    kv: CacheStatus,
+    r2: CacheStatus,
+    keys: CacheStatusKeys,
+});
+api_schema!(CacheStatus {
+    exists: bool,
+    remaining_ttl_seconds: Option<i64>,
+});
+api_schema!(CacheStatusKeys {
+    kv: String,
+    r2_scores: String,
+});
+
+impl ApiSchema for AdminEventSelector {
+    fn schema(components: &mut Components) -> Value {
+        #[allow(dead_code)]
+        fn variants_match(value: &AdminEventSelector) {
+            match value {
+                AdminEventSelector::All(_) | AdminEventSelector::Id(_) => {}
+            }
+        }
+        component(components, "AdminEventSelector", |components| {
+            json!({
+                "description": "An event id, or `\"all\"`.",
+                "oneOf": [{ "type": "string", "enum": ["all"] }, i32::schema(components)],
+            })
+        })
+    }
+}
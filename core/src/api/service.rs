@@ -0,0 +1,321 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::dto::{
+    BettorRoster, BettorRound, EventBettor, EventDetail, EventList, EventSummary, Freshness,
+    GolferScorecard, Pick, RosterGolfer, RoundSummaries, RoundSummary, ScorecardHole,
+    ScorecardRound, Standing, Standings,
+};
+use super::{ApiError, ApiRequest, ApiRoute};
+use crate::espn::EspnApiClient;
+use crate::model::{RefreshSource, Scores, ScoresAndLastRefresh};
+use crate::score::{cache_max_age_for_event, load_score_context, rank_bettors};
+use crate::storage::{EventDetails, Storage, year_from_dates};
+
+/// Any `/api/v1` response body; serializes as the DTO it holds.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ApiResponse {
+    Events(EventList),
+    Event(EventDetail),
+    Standings(Standings),
+    Bettor(BettorRoster),
+    Golfer(GolferScorecard),
+    Rounds(RoundSummaries),
+}
+
+/// Answer an API request. Score resources refresh through the same cache rules as
+/// `/scores`; events and event details only read storage.
+///
+/// # Errors
+/// Returns `not_found` for unknown events, bettors and golfers, `upstream_unavailable` if
+/// scores had to come from ESPN and couldn't, and `internal` for storage failures.
+pub async fn handle_api_request(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    request: &ApiRequest,
+) -> Result<ApiResponse, ApiError> {
+    let response = match &request.route {
+        ApiRoute::Events => ApiResponse::Events(list_events(storage).await?),
+        ApiRoute::Event { event_id } => ApiResponse::Event(event_detail(storage, *event_id).await?),
+        ApiRoute::Standings { event_id } => {
+            let scores = load_scores(storage, espn_api, *event_id, request).await?;
+            ApiResponse::Standings(standings_from_scores(*event_id, &scores))
+        }
+        ApiRoute::Bettor {
+            event_id,
+            bettor_name,
+        } => {
+            let scores = load_scores(storage, espn_api, *event_id, request).await?;
+            ApiResponse::Bettor(bettor_roster(*event_id, bettor_name, &scores)?)
+        }
+        ApiRoute::Golfer {
+            event_id,
+            golfer_espn_id,
+        } => {
+            let scores = load_scores(storage, espn_api, *event_id, request).await?;
+            ApiResponse::Golfer(golfer_scorecard(*event_id, *golfer_espn_id, &scores)?)
+        }
+        ApiRoute::Rounds { event_id } => {
+            let scores = load_scores(storage, espn_api, *event_id, request).await?;
+            ApiResponse::Rounds(round_summaries(*event_id, &scores))
+        }
+    };
+    Ok(response)
+}
+
+/// The `/api/v1` standings for scores already loaded, for pushing them without a request.
+#[must_use]
+pub fn standings_from_scores(event_id: i32, scores: &ScoresAndLastRefresh) -> Standings {
+    Standings {
+        event_id,
+        freshness: freshness(scores),
+        standings: ranked(&scores.score_struct),
+    }
+}
+
+async fn list_events(storage: &dyn Storage) -> Result<EventList, ApiError> {
+    let ids = storage
+        .list_events()
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let mut events = Vec::with_capacity(ids.len());
+    for event_id in ids {
+        events.push(event_summary(storage, event_id).await?);
+    }
+    Ok(EventList { events })
+}
+
+async fn event_summary(storage: &dyn Storage, event_id: i32) -> Result<EventSummary, ApiError> {
+    // `get_event_details` fails for a missing event without saying why.
+    let details = storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| ApiError::not_found(format!("event {event_id} not found")))?;
+    Ok(summarize_event(event_id, details))
+}
+
+/// An event's details as the API lists them.
+pub(crate) fn summarize_event(event_id: i32, details: EventDetails) -> EventSummary {
+    EventSummary {
+        event_id,
+        year: year_from_dates(details.start_date.as_deref(), details.end_date.as_deref()),
+        name: details.event_name,
+        start_date: details.start_date,
+        end_date: details.end_date,
+        completed: details.completed,
+    }
+}
+
+async fn event_detail(storage: &dyn Storage, event_id: i32) -> Result<EventDetail, ApiError> {
+    let event = event_summary(storage, event_id).await?;
+    let picks = storage
+        .get_golfers_for_event(event_id)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+    let mut bettors: Vec<EventBettor> = Vec::new();
+    for pick in picks {
+        let entry = match bettors
+            .iter()
+            .position(|bettor| bettor.bettor_name == pick.bettor_name)
+        {
+            Some(index) => &mut bettors[index],
+            None => {
+                bettors.push(EventBettor {
+                    bettor_name: pick.bettor_name.clone(),
+                    picks: Vec::new(),
+                });
+                bettors.last_mut().expect("just pushed")
+            }
+        };
+        entry.picks.push(Pick {
+            golfer_espn_id: pick.espn_id,
+            golfer_name: pick.golfer_name,
+            group: pick.group,
+        });
+    }
+    Ok(EventDetail { event, bettors })
+}
+
+async fn load_scores(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    event_id: i32,
+    request: &ApiRequest,
+) -> Result<ScoresAndLastRefresh, ApiError> {
+    let event = event_summary(storage, event_id).await?;
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context = load_score_context(
+        storage,
+        espn_api,
+        event_id,
+        request.year.unwrap_or(event.year),
+        request.use_cache,
+        cache_max_age,
+    )
+    .await?;
+    Ok(context.from_db_scores)
+}
+
+fn freshness(scores: &ScoresAndLastRefresh) -> Freshness {
+    let source = match scores.last_refresh_source {
+        RefreshSource::Db => "database",
+        RefreshSource::R2 => "r2",
+        RefreshSource::Kv => "kv",
+        RefreshSource::Memory => "memory",
+        RefreshSource::File => "file",
+        RefreshSource::Archive => "archive",
+        RefreshSource::Espn => "espn",
+    };
+    Freshness {
+        refreshed_at: scores.last_refresh.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        source: source.to_string(),
+    }
+}
+
+fn ranked(scores: &[Scores]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = Vec::new();
+    for (index, bettor) in rank_bettors(scores).into_iter().enumerate() {
+        let rank = match standings.last() {
+            Some(previous) if previous.total_score == bettor.total_score => previous.rank,
+            _ => index + 1,
+        };
+        standings.push(Standing {
+            rank,
+            bettor_name: bettor.bettor_name,
+            total_score: bettor.total_score,
+        });
+    }
+    standings
+}
+
+fn bettor_roster(
+    event_id: i32,
+    bettor_name: &str,
+    scores: &ScoresAndLastRefresh,
+) -> Result<BettorRoster, ApiError> {
+    let standing = ranked(&scores.score_struct)
+        .into_iter()
+        .find(|standing| standing.bettor_name == bettor_name)
+        .ok_or_else(|| {
+            ApiError::not_found(format!(
+                "bettor {bettor_name} has no picks in event {event_id}"
+            ))
+        })?;
+    let golfers = scores
+        .score_struct
+        .iter()
+        .filter(|golfer| golfer.bettor_name == bettor_name)
+        .map(|golfer| RosterGolfer {
+            golfer_espn_id: golfer.espn_id,
+            golfer_name: golfer.golfer_name.clone(),
+            group: golfer.group,
+            total_score: golfer.detailed_statistics.total_score,
+            round_scores: golfer
+                .detailed_statistics
+                .round_scores
+                .iter()
+                .map(|score| score.val)
+                .collect(),
+        })
+        .collect();
+    Ok(BettorRoster {
+        event_id,
+        freshness: freshness(scores),
+        bettor_name: standing.bettor_name,
+        rank: standing.rank,
+        total_score: standing.total_score,
+        golfers,
+    })
+}
+
+pub(crate) fn golfer_scorecard(
+    event_id: i32,
+    golfer_espn_id: i64,
+    scores: &ScoresAndLastRefresh,
+) -> Result<GolferScorecard, ApiError> {
+    let picks: Vec<&Scores> = scores
+        .score_struct
+        .iter()
+        .filter(|golfer| golfer.espn_id == golfer_espn_id)
+        .collect();
+    // Every pick of a golfer carries the same statistics.
+    let golfer = picks.first().ok_or_else(|| {
+        ApiError::not_found(format!(
+            "golfer {golfer_espn_id} isn't picked in event {event_id}"
+        ))
+    })?;
+    let stats = &golfer.detailed_statistics;
+    let rounds = stats
+        .rounds
+        .iter()
+        .enumerate()
+        .map(|(index, round)| ScorecardRound {
+            round: round.val + 1,
+            score: stats.round_scores.get(index).map_or(0, |score| score.val),
+            tee_time: stats
+                .tee_times
+                .get(index)
+                .map(|tee_time| tee_time.val.clone()),
+            holes: stats
+                .line_scores
+                .iter()
+                .filter(|line| line.round == round.val)
+                .map(|line| ScorecardHole {
+                    hole: line.hole,
+                    par: line.par,
+                    strokes: line.score,
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(GolferScorecard {
+        event_id,
+        freshness: freshness(scores),
+        golfer_espn_id,
+        golfer_name: golfer.golfer_name.clone(),
+        picked_by: picks.iter().map(|pick| pick.bettor_name.clone()).collect(),
+        total_score: stats.total_score,
+        rounds,
+    })
+}
+
+pub(crate) fn round_summaries(event_id: i32, scores: &ScoresAndLastRefresh) -> RoundSummaries {
+    let standings = ranked(&scores.score_struct);
+    let round_count = scores
+        .score_struct
+        .iter()
+        .map(|golfer| golfer.detailed_statistics.round_scores.len())
+        .max()
+        .unwrap_or(0);
+    let mut cumulative: HashMap<&str, i32> = HashMap::new();
+    let rounds = (0..round_count)
+        .map(|index| RoundSummary {
+            round: i32::try_from(index).unwrap_or(i32::MAX).saturating_add(1),
+            bettors: standings
+                .iter()
+                .map(|standing| {
+                    let score = scores
+                        .score_struct
+                        .iter()
+                        .filter(|golfer| golfer.bettor_name == standing.bettor_name)
+                        .filter_map(|golfer| golfer.detailed_statistics.round_scores.get(index))
+                        .map(|score| score.val)
+                        .sum();
+                    let total = cumulative.entry(&standing.bettor_name).or_insert(0);
+                    *total += score;
+                    BettorRound {
+                        bettor_name: standing.bettor_name.clone(),
+                        score,
+                        cumulative: *total,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+    RoundSummaries {
+        event_id,
+        freshness: freshness(scores),
+        rounds,
+    }
+}
@@ -0,0 +1,277 @@
+//! `/tee-times.ics`: an iCalendar feed of the picked golfers' tee times for one event,
+//! optionally narrowed to one bettor's picks.
+
+use chrono::{DateTime, Datelike, Utc};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::error::CoreError;
+use crate::espn::EspnApiClient;
+use crate::espn::processing::tee_time_to_utc;
+use crate::export::ExportFile;
+use crate::model::Scores;
+use crate::score::{cache_max_age_for_event, load_score_context, parse_score_request};
+use crate::storage::Storage;
+
+pub const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+/// How long each tee time blocks out: about a round of golf.
+const ROUND_DURATION: &str = "PT4H30M";
+
+/// A parsed `/tee-times.ics` query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeeTimeRequest {
+    pub event_id: i32,
+    pub year: i32,
+    /// Only this bettor's picks; everyone's when `None`.
+    pub bettor_name: Option<String>,
+}
+
+/// Parse `event`, `yr` and the optional `bettor` of a `/tee-times.ics` query.
+///
+/// # Errors
+/// Returns an error if `event` or `yr` is missing or invalid.
+pub fn parse_tee_time_request<S: BuildHasher>(
+    query: &HashMap<String, String, S>,
+) -> Result<TeeTimeRequest, CoreError> {
+    let score_request = parse_score_request(query)?;
+    let bettor_name = query
+        .get("bettor")
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    Ok(TeeTimeRequest {
+        event_id: score_request.event_id,
+        year: score_request.year,
+        bettor_name,
+    })
+}
+
+/// One golfer's tee time for one round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeeTime {
+    pub golfer_name: String,
+    pub golfer_espn_id: i64,
+    /// 1-based.
+    pub round: usize,
+    pub starts_at: DateTime<Utc>,
+    pub picked_by: Vec<String>,
+}
+
+/// Every picked golfer's tee times in order, one per golfer and round however many bettors
+/// picked them. Tee times that can't be read back are skipped.
+#[must_use]
+pub fn tee_times(scores: &[Scores], bettor_name: Option<&str>, year: i32) -> Vec<TeeTime> {
+    let mut tee_times: Vec<TeeTime> = Vec::new();
+    let picks = scores
+        .iter()
+        .filter(|golfer| bettor_name.is_none_or(|bettor| golfer.bettor_name == bettor));
+    for golfer in picks {
+        for (index, tee_time) in golfer.detailed_statistics.tee_times.iter().enumerate() {
+            let Some(starts_at) = tee_time_to_utc(&tee_time.val, year) else {
+                continue;
+            };
+            let round = index + 1;
+            match tee_times
+                .iter_mut()
+                .find(|seen| seen.golfer_espn_id == golfer.espn_id && seen.round == round)
+            {
+                Some(seen) => seen.picked_by.push(golfer.bettor_name.clone()),
+                None => tee_times.push(TeeTime {
+                    golfer_name: golfer.golfer_name.clone(),
+                    golfer_espn_id: golfer.espn_id,
+                    round,
+                    starts_at,
+                    picked_by: vec![golfer.bettor_name.clone()],
+                }),
+            }
+        }
+    }
+    tee_times.sort_by(|a, b| {
+        a.starts_at
+            .cmp(&b.starts_at)
+            .then_with(|| a.golfer_name.cmp(&b.golfer_name))
+    });
+    tee_times
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Append a content line, folded so no line is longer than 75 octets (RFC 5545 §3.1).
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn ical_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// The calendar for `tee_times`. Times are in UTC so every calendar app shows them in its
+/// own time zone, and each tee time keeps its UID across refreshes so subscribers see it
+/// move rather than duplicate.
+#[must_use]
+pub fn write_ical(
+    event_id: i32,
+    event_name: &str,
+    bettor_name: Option<&str>,
+    tee_times: &[TeeTime],
+    now: DateTime<Utc>,
+) -> String {
+    let calendar_name = match bettor_name {
+        Some(bettor) => format!("{event_name} tee times ({bettor})"),
+        None => format!("{event_name} tee times"),
+    };
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//rusty-golf//tee times//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(
+        &mut out,
+        &format!("X-WR-CALNAME:{}", escape_text(&calendar_name)),
+    );
+    push_line(&mut out, "REFRESH-INTERVAL;VALUE=DURATION:PT1H");
+    push_line(&mut out, "X-PUBLISHED-TTL:PT1H");
+    for tee_time in tee_times {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(
+            &mut out,
+            &format!(
+                "UID:{event_id}-{}-r{}@rusty-golf",
+                tee_time.golfer_espn_id, tee_time.round
+            ),
+        );
+        push_line(&mut out, &format!("DTSTAMP:{}", ical_time(now)));
+        push_line(
+            &mut out,
+            &format!("DTSTART:{}", ical_time(tee_time.starts_at)),
+        );
+        push_line(&mut out, &format!("DURATION:{ROUND_DURATION}"));
+        push_line(
+            &mut out,
+            &format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{} R{}", tee_time.golfer_name, tee_time.round))
+            ),
+        );
+        push_line(
+            &mut out,
+            &format!(
+                "DESCRIPTION:{}",
+                escape_text(&format!(
+                    "{event_name}, round {}. Picked by {}.",
+                    tee_time.round,
+                    tee_time.picked_by.join(" & ")
+                ))
+            ),
+        );
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Load the event's scores by the same cache rules as `/scores` and build its tee-time
+/// calendar. Stored tee times carry no year, so it is taken from the event's start date,
+/// or `yr` if it has none.
+///
+/// # Errors
+/// Returns `NotFound` if the event doesn't exist or the bettor has no picks in it, and other
+/// errors if scores can't be loaded.
+pub async fn load_tee_time_calendar(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    request: &TeeTimeRequest,
+    now: DateTime<Utc>,
+) -> Result<ExportFile, CoreError> {
+    let event_id = request.event_id;
+    let details = storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| CoreError::NotFound(format!("event {event_id} not found")))?;
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context = load_score_context(
+        storage,
+        espn_api,
+        event_id,
+        request.year,
+        true,
+        cache_max_age,
+    )
+    .await?;
+    let scores = &context.from_db_scores.score_struct;
+    let bettor_name = request.bettor_name.as_deref();
+    if let Some(bettor) = bettor_name
+        && !scores.iter().any(|golfer| golfer.bettor_name == bettor)
+    {
+        return Err(CoreError::NotFound(format!(
+            "bettor {bettor} has no picks in event {event_id}"
+        )));
+    }
+    let year = details
+        .start_date
+        .as_deref()
+        .and_then(|start| DateTime::parse_from_rfc3339(start).ok())
+        .map_or(request.year, |start| start.year());
+    let body = write_ical(
+        event_id,
+        &details.event_name,
+        bettor_name,
+        &tee_times(scores, bettor_name, year),
+        now,
+    );
+    Ok(ExportFile {
+        filename: format!("{event_id}-{}-tee-times.ics", request.year),
+        content_type: CALENDAR_CONTENT_TYPE,
+        body: body.into_bytes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_lines_fold_on_character_boundaries() {
+        let mut out = String::new();
+        let line = format!("DESCRIPTION:{}", "Åberg ".repeat(20));
+        push_line(&mut out, &line);
+        let lines: Vec<&str> = out.split_terminator("\r\n").collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines[1..].iter().all(|line| line.starts_with(' ')));
+        let unfolded: String = out.replace("\r\n ", "");
+        assert_eq!(unfolded, format!("{line}\r\n"));
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(
+            escape_text("Smith, Jo; \\ok\nthen"),
+            "Smith\\, Jo\\; \\\\ok\\nthen"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::error::CoreError;
 use crate::model::{RefreshSource, Scores, ScoresAndLastRefresh};
+use crate::score::{diff_score_snapshots, promote_completed_if_ready};
 use crate::storage::Storage;
 use crate::timed;
 use crate::timing::TimingSink;
@@ -165,35 +166,66 @@ pub async fn fetch_scores_from_espn_with_timing(
         "storage.get_scores_db_ms",
         storage.get_scores(event_id, RefreshSource::Db).await
     );
-    let existing_scores = match existing_scores {
-        Ok(existing) => Some(existing.score_struct),
-        Err(_) => None,
-    };
+    let previous = existing_scores.ok();
     let merged_scores = timed!(
         timing,
         "scores.merge_event_ms",
-        merge_scores_for_event(&scores, fetched_scores, existing_scores)
+        merge_scores_for_event(
+            &scores,
+            fetched_scores,
+            previous.as_ref().map(|p| p.score_struct.as_slice()),
+        )
     );
     let stored = timed!(
         timing,
         "storage.store_scores_ms",
         crate::score::context::store_scores_and_reload(storage, event_id, &merged_scores).await
     )?;
+
+    if let Some(previous) = previous {
+        let changes = diff_score_snapshots(&previous, &stored);
+        if !changes.is_empty() {
+            // Change history is best-effort; a failed write must not fail the refresh.
+            if let Err(e) = timed!(
+                timing,
+                "storage.store_score_changes_ms",
+                storage.store_score_changes(event_id, &changes).await
+            ) {
+                eprintln!("Warning: failed to store score changes for event {event_id}: {e}");
+            }
+        }
+    }
+
+    // Same for promotion: the scoreboard header being down shouldn't fail a refresh.
+    if let Err(e) = timed!(
+        timing,
+        "espn.promote_completed_ms",
+        promote_completed_if_ready(
+            storage,
+            api,
+            event_id,
+            &stored.score_struct,
+            chrono::Utc::now().naive_utc()
+        )
+        .await
+    ) {
+        eprintln!("Warning: failed to check completion for event {event_id}: {e}");
+    }
     Ok((stored, false))
 }
 
 fn merge_scores_for_event(
     expected_scores: &[Scores],
     fetched_scores: Vec<Scores>,
-    existing_scores: Option<Vec<Scores>>,
+    existing_scores: Option<&[Scores]>,
 ) -> Vec<Scores> {
     let fetched_by_id: HashMap<i64, Scores> = fetched_scores
         .into_iter()
         .map(|score| (score.eup_id, score))
         .collect();
-    let existing_by_id: HashMap<i64, Scores> = existing_scores
+    let existing_by_id: HashMap<i64, &Scores> = existing_scores
         .unwrap_or_default()
-        .into_iter()
+        .iter()
         .map(|score| (score.eup_id, score))
         .collect();
 
@@ -203,7 +235,7 @@ fn merge_scores_for_event(
             fetched_by_id
                 .get(&expected.eup_id)
                 .cloned()
-                .or_else(|| existing_by_id.get(&expected.eup_id).cloned())
+                .or_else(|| existing_by_id.get(&expected.eup_id).map(|s| (*s).clone()))
                 .unwrap_or_else(|| expected.clone())
         })
         .collect()
@@ -1,5 +1,6 @@
 mod fetch;
 pub mod processing;
+mod scoreboard;
 
 use crate::error::CoreError;
 use crate::model::{PlayerJsonResponse, Scores};
@@ -9,6 +10,7 @@ pub use fetch::{
     FetchScoresRequest, fetch_scores_from_espn, fetch_scores_from_espn_with_timing,
     go_get_espn_data, go_get_espn_data_with_timing,
 };
+pub use scoreboard::{ESPN_SCOREBOARD_HEADER_URL, EventCompletion, parse_event_completion};
 
 #[cfg(not(target_arch = "wasm32"))]
 #[async_trait]
@@ -23,6 +25,11 @@ pub trait EspnApiClient: Send + Sync {
     async fn fallback_scores(&self, _event_id: i32) -> Result<Option<Vec<Scores>>, CoreError> {
         Ok(None)
     }
+
+    /// Completion state from the ESPN scoreboard header; `None` if the header doesn't list it.
+    async fn event_completion(&self, _event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        Ok(None)
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -38,4 +45,9 @@ pub trait EspnApiClient {
     async fn fallback_scores(&self, _event_id: i32) -> Result<Option<Vec<Scores>>, CoreError> {
         Ok(None)
     }
+
+    /// Completion state from the ESPN scoreboard header; `None` if the header doesn't list it.
+    async fn event_completion(&self, _event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        Ok(None)
+    }
 }
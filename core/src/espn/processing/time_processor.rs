@@ -1,5 +1,11 @@
 use crate::model::{StringStat, take_a_char_off};
-use chrono::DateTime;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+/// Tee times are shown in US Central time, taken as a fixed UTC-5.
+fn central_timezone() -> FixedOffset {
+    FixedOffset::east_opt(-5 * 3600)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC timezone offset is always valid"))
+}
 
 /// Convert a tee time string into a display-ready value.
 ///
@@ -23,11 +29,7 @@ pub fn process_tee_time(tee_time: &str) -> Option<StringStat> {
         }
     };
 
-    let central_timezone = chrono::offset::FixedOffset::east_opt(-5 * 3600).unwrap_or_else(|| {
-        chrono::offset::FixedOffset::east_opt(0).expect("UTC timezone offset is always valid")
-    });
-
-    let parsed_time_in_central = parsed_time.with_timezone(&central_timezone);
+    let parsed_time_in_central = parsed_time.with_timezone(&central_timezone());
 
     let special_format_time =
         take_a_char_off(&parsed_time_in_central.format("%-m/%d %-I:%M%P").to_string());
@@ -40,3 +42,54 @@ pub fn process_tee_time(tee_time: &str) -> Option<StringStat> {
         })
     }
 }
+
+/// The instant a tee time from [`process_tee_time`] stands for, such as `5/16 7:15a`. That
+/// form drops the year, so the caller supplies it.
+#[must_use]
+pub fn tee_time_to_utc(stored: &str, year: i32) -> Option<DateTime<Utc>> {
+    let (date, time) = stored.trim().split_once(' ')?;
+    let (month, day) = date.split_once('/')?;
+    let time = time.trim_end_matches(['m', 'M']);
+    let pm = match time.chars().last()? {
+        'a' | 'A' => false,
+        'p' | 'P' => true,
+        _ => return None,
+    };
+    let (hour, minute) = time[..time.len() - 1].split_once(':')?;
+    let hour: u32 = hour.parse().ok()?;
+    if !(1..=12).contains(&hour) {
+        return None;
+    }
+    let hour = hour % 12 + if pm { 12 } else { 0 };
+    let local = NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?)?
+        .and_hms_opt(hour, minute.parse().ok()?, 0)?;
+    central_timezone()
+        .from_local_datetime(&local)
+        .single()
+        .map(|tee_time| tee_time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tee_times_round_trip_through_their_display_form() {
+        let stored = process_tee_time("2024-05-16T12:15Z").expect("parses");
+        assert_eq!(stored.val, "5/16 7:15a");
+        assert_eq!(
+            tee_time_to_utc(&stored.val, 2024),
+            Some(Utc.with_ymd_and_hms(2024, 5, 16, 12, 15, 0).unwrap())
+        );
+        assert_eq!(
+            tee_time_to_utc("5/17 12:40p", 2024),
+            Some(Utc.with_ymd_and_hms(2024, 5, 17, 17, 40, 0).unwrap())
+        );
+        assert_eq!(
+            tee_time_to_utc("5/17 12:05a", 2024),
+            Some(Utc.with_ymd_and_hms(2024, 5, 17, 5, 5, 0).unwrap())
+        );
+        assert_eq!(tee_time_to_utc("8:10 AM", 2024), None);
+        assert_eq!(tee_time_to_utc("13/40 7:15a", 2024), None);
+    }
+}
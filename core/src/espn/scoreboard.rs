@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::CoreError;
+
+pub const ESPN_SCOREBOARD_HEADER_URL: &str = "https://site.web.api.espn.com/apis/v2/scoreboard/header?sport=golf&league=pga&region=us&lang=en&contentorigin=espn";
+
+/// What the scoreboard header says about one event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCompletion {
+    pub completed: bool,
+    pub end_date: Option<String>,
+}
+
+/// Find `event_id` in a scoreboard header payload.
+///
+/// Either `fullStatus.completed` or `fullStatus.type.completed` counts as completed.
+///
+/// # Errors
+/// Returns an error if the payload is not shaped like a scoreboard header.
+pub fn parse_event_completion(
+    payload: &Value,
+    event_id: i32,
+) -> Result<Option<EventCompletion>, CoreError> {
+    let header = ScoreboardHeader::deserialize(payload)?;
+    let event_id = event_id.to_string();
+    Ok(header
+        .sports
+        .into_iter()
+        .flat_map(|sport| sport.leagues)
+        .flat_map(|league| league.events)
+        .find(|event| event.id == event_id)
+        .map(|event| EventCompletion {
+            completed: event
+                .full_status
+                .as_ref()
+                .is_some_and(ScoreboardFullStatus::completed),
+            end_date: event.end_date,
+        }))
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardHeader {
+    sports: Vec<ScoreboardSport>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardSport {
+    leagues: Vec<ScoreboardLeague>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardLeague {
+    events: Vec<ScoreboardEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardEvent {
+    id: String,
+    #[serde(rename = "endDate")]
+    end_date: Option<String>,
+    #[serde(rename = "fullStatus")]
+    full_status: Option<ScoreboardFullStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardFullStatus {
+    #[serde(default)]
+    completed: bool,
+    #[serde(rename = "type")]
+    status_type: Option<ScoreboardStatusType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreboardStatusType {
+    #[serde(default)]
+    completed: bool,
+}
+
+impl ScoreboardFullStatus {
+    fn completed(&self) -> bool {
+        self.completed
+            || self
+                .status_type
+                .as_ref()
+                .is_some_and(|status| status.completed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_nested_completion_flag() {
+        let payload = json!({
+            "sports": [{ "leagues": [{ "events": [
+                { "id": "1", "endDate": "2026-04-14T12:00:00Z" },
+                {
+                    "id": "401580351",
+                    "endDate": "2026-04-14T12:00:00Z",
+                    "fullStatus": { "type": { "completed": true } }
+                }
+            ]}]}]
+        });
+        assert_eq!(
+            parse_event_completion(&payload, 401_580_351).unwrap(),
+            Some(EventCompletion {
+                completed: true,
+                end_date: Some("2026-04-14T12:00:00Z".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_event_completion(&payload, 1)
+                .unwrap()
+                .map(|e| e.completed),
+            Some(false)
+        );
+        assert_eq!(parse_event_completion(&payload, 2).unwrap(), None);
+    }
+}
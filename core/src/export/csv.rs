@@ -0,0 +1,68 @@
+use super::{Cell, Sheet};
+
+/// Lets Excel detect UTF-8, so names like Åberg survive the round trip.
+const UTF8_BOM: &str = "\u{feff}";
+
+/// RFC 4180 CSV: CRLF line endings, fields quoted only when they need it.
+///
+/// Text that a spreadsheet would read as a formula is prefixed with `'`.
+#[must_use]
+pub fn write_csv(sheet: &Sheet) -> Vec<u8> {
+    let mut out = String::from(UTF8_BOM);
+    write_row(&mut out, sheet.header.iter().map(|title| text_field(title)));
+    for row in &sheet.rows {
+        write_row(
+            &mut out,
+            row.iter().map(|cell| match cell {
+                Cell::Text(text) => text_field(text),
+                Cell::Number(number) => number.to_string(),
+            }),
+        );
+    }
+    out.into_bytes()
+}
+
+fn write_row(out: &mut String, fields: impl Iterator<Item = String>) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push_str(&field);
+    }
+    out.push_str("\r\n");
+}
+
+fn text_field(text: &str) -> String {
+    let text = if text.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{text}")
+    } else {
+        text.to_string()
+    };
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_only_what_needs_it() {
+        let sheet = Sheet {
+            name: "Standings",
+            header: vec!["Bettor".to_string(), "Total".to_string()],
+            rows: vec![
+                vec![Cell::from("Smith, \"Jo\""), Cell::Number(-3)],
+                vec![Cell::from("=1+1"), Cell::from("")],
+            ],
+        };
+        let csv = String::from_utf8(write_csv(&sheet)).unwrap();
+        assert_eq!(
+            csv,
+            "\u{feff}Bettor,Total\r\n\"Smith, \"\"Jo\"\"\",-3\r\n'=1+1,\r\n"
+        );
+    }
+}
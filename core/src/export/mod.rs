@@ -0,0 +1,387 @@
+//! Spreadsheet exports of an event's standings, round summary and per-golfer rounds.
+//!
+//! Both runtimes serve `/export/{table}?event=&yr=&format=csv|xlsx`; everything but writing
+//! the response happens here so the files are byte-for-byte the same.
+
+mod csv;
+mod xlsx;
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::api::standings_from_scores;
+use crate::error::CoreError;
+use crate::espn::EspnApiClient;
+use crate::model::ScoresAndLastRefresh;
+use crate::score::{
+    cache_max_age_for_event, group_by_bettor_golfer_round, group_by_bettor_name_and_round,
+    load_score_context, parse_score_request,
+};
+use crate::storage::Storage;
+
+pub use csv::write_csv;
+pub(crate) use xlsx::escape as escape_xml;
+pub use xlsx::write_xlsx;
+
+/// Which table to export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    /// One row per bettor: rank and total.
+    Standings,
+    /// One row per bettor, one column per round (`AllBettorScoresByRound`).
+    Rounds,
+    /// One row per pick, one column per round (`SummaryDetailedScores`).
+    Golfers,
+}
+
+impl ExportTable {
+    /// The `{table}` path segment.
+    #[must_use]
+    pub fn slug(self) -> &'static str {
+        match self {
+            Self::Standings => "standings",
+            Self::Rounds => "rounds",
+            Self::Golfers => "golfers",
+        }
+    }
+
+    /// The worksheet name in XLSX exports.
+    #[must_use]
+    pub fn sheet_name(self) -> &'static str {
+        match self {
+            Self::Standings => "Standings",
+            Self::Rounds => "Rounds",
+            Self::Golfers => "Golfers",
+        }
+    }
+
+    #[must_use]
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        [Self::Standings, Self::Rounds, Self::Golfers]
+            .into_iter()
+            .find(|table| table.slug() == slug)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    #[must_use]
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+}
+
+/// A parsed `/export/{table}` request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportRequest {
+    pub table: ExportTable,
+    pub format: ExportFormat,
+    pub event_id: i32,
+    pub year: i32,
+    pub use_cache: bool,
+}
+
+/// Parse the `{table}` path segment and the `event`, `yr`, `cache` and `format` query
+/// parameters. `format` defaults to CSV.
+///
+/// # Errors
+/// Returns `NotFound` for an unknown table, and other errors for missing or invalid
+/// parameters.
+pub fn parse_export_request<S: BuildHasher>(
+    table: &str,
+    query: &HashMap<String, String, S>,
+) -> Result<ExportRequest, CoreError> {
+    let table = ExportTable::from_slug(table)
+        .ok_or_else(|| CoreError::NotFound(format!("no export named {table:?}")))?;
+    let score_request = parse_score_request(query)?;
+    let format = match query.get("format").map(|format| format.trim()) {
+        None | Some("" | "csv") => ExportFormat::Csv,
+        Some("xlsx") => ExportFormat::Xlsx,
+        Some(other) => {
+            return Err(CoreError::Other(format!(
+                "format must be csv or xlsx, got {other:?}"
+            )));
+        }
+    };
+    Ok(ExportRequest {
+        table,
+        format,
+        event_id: score_request.event_id,
+        year: score_request.year,
+        use_cache: score_request.use_cache,
+    })
+}
+
+/// A spreadsheet cell. Numbers stay numbers in XLSX so they can be summed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    Text(String),
+    Number(i64),
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<i32> for Cell {
+    fn from(number: i32) -> Self {
+        Self::Number(number.into())
+    }
+}
+
+impl From<i64> for Cell {
+    fn from(number: i64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<usize> for Cell {
+    fn from(number: usize) -> Self {
+        Self::Number(i64::try_from(number).unwrap_or(i64::MAX))
+    }
+}
+
+/// A header row and the rows under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    pub name: &'static str,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+/// A finished export, ready to send.
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub filename: String,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl ExportFile {
+    /// The `Content-Disposition` header value that makes browsers save the file.
+    #[must_use]
+    pub fn content_disposition(&self) -> String {
+        format!("attachment; filename=\"{}\"", self.filename)
+    }
+}
+
+fn round_headers(rounds: usize) -> impl Iterator<Item = String> {
+    (1..=rounds).map(|round| format!("R{round}"))
+}
+
+/// Bettors by rank, as on the scoreboard. Ties keep the order the scores are stored in.
+fn rank_of(event_id: i32, scores: &ScoresAndLastRefresh) -> HashMap<String, usize> {
+    standings_from_scores(event_id, scores)
+        .standings
+        .into_iter()
+        .enumerate()
+        .map(|(position, standing)| (standing.bettor_name, position))
+        .collect()
+}
+
+/// Build `table` from an event's scores.
+#[must_use]
+pub fn build_sheet(table: ExportTable, event_id: i32, scores: &ScoresAndLastRefresh) -> Sheet {
+    let (header, rows) = match table {
+        ExportTable::Standings => standings_sheet(event_id, scores),
+        ExportTable::Rounds => rounds_sheet(event_id, scores),
+        ExportTable::Golfers => golfers_sheet(event_id, scores),
+    };
+    Sheet {
+        name: table.sheet_name(),
+        header,
+        rows,
+    }
+}
+
+fn standings_sheet(event_id: i32, scores: &ScoresAndLastRefresh) -> (Vec<String>, Vec<Vec<Cell>>) {
+    let header = ["Rank", "Bettor", "Total"].map(String::from).to_vec();
+    let rows = standings_from_scores(event_id, scores)
+        .standings
+        .into_iter()
+        .map(|standing| {
+            vec![
+                standing.rank.into(),
+                standing.bettor_name.into(),
+                standing.total_score.into(),
+            ]
+        })
+        .collect();
+    (header, rows)
+}
+
+fn rounds_sheet(event_id: i32, scores: &ScoresAndLastRefresh) -> (Vec<String>, Vec<Vec<Cell>>) {
+    let mut summary = group_by_bettor_name_and_round(&scores.score_struct).summary_scores;
+    let ranks = rank_of(event_id, scores);
+    summary.sort_by_key(|bettor| ranks.get(&bettor.bettor_name).copied());
+    let rounds = summary
+        .iter()
+        .flat_map(|bettor| bettor.computed_rounds.iter())
+        .map(|&round| usize::try_from(round + 1).unwrap_or(0))
+        .max()
+        .unwrap_or(0);
+
+    let header = std::iter::once("Bettor".to_string())
+        .chain(round_headers(rounds))
+        .chain(std::iter::once("Total".to_string()))
+        .collect();
+    let rows = summary
+        .into_iter()
+        .map(|bettor| {
+            let mut by_round = vec![None; rounds];
+            for (&round, &score) in bettor
+                .computed_rounds
+                .iter()
+                .zip(&bettor.scores_aggregated_by_golf_grp_by_rd)
+            {
+                if let Some(slot) = usize::try_from(round)
+                    .ok()
+                    .and_then(|round| by_round.get_mut(round))
+                {
+                    *slot = Some(score);
+                }
+            }
+            let total: isize = by_round.iter().flatten().sum();
+            std::iter::once(bettor.bettor_name.into())
+                .chain(
+                    by_round
+                        .into_iter()
+                        .map(|score| round_cell(score.map(isize_cell))),
+                )
+                .chain(std::iter::once(isize_cell(total)))
+                .collect()
+        })
+        .collect();
+    (header, rows)
+}
+
+fn golfers_sheet(event_id: i32, scores: &ScoresAndLastRefresh) -> (Vec<String>, Vec<Vec<Cell>>) {
+    let mut detailed = group_by_bettor_golfer_round(&scores.score_struct).detailed_scores;
+    let ranks = rank_of(event_id, scores);
+    detailed.sort_by_key(|detail| ranks.get(&detail.bettor_name).copied());
+    let rounds = detailed
+        .iter()
+        .flat_map(|detail| detail.rounds.iter())
+        .map(|&round| usize::try_from(round).unwrap_or(0))
+        .max()
+        .unwrap_or(0);
+
+    let header = ["Bettor", "Golfer", "ESPN ID"]
+        .map(String::from)
+        .into_iter()
+        .chain(round_headers(rounds))
+        .chain(std::iter::once("Total".to_string()))
+        .collect();
+    let rows = detailed
+        .into_iter()
+        .map(|detail| {
+            let mut by_round = vec![None; rounds];
+            for (&round, &score) in detail.rounds.iter().zip(&detail.scores) {
+                if let Some(slot) = usize::try_from(round - 1)
+                    .ok()
+                    .and_then(|round| by_round.get_mut(round))
+                {
+                    *slot = Some(score);
+                }
+            }
+            let total: i32 = by_round.iter().flatten().sum();
+            [
+                detail.bettor_name.into(),
+                detail.golfer_name.into(),
+                detail.golfer_espn_id.into(),
+            ]
+            .into_iter()
+            .chain(
+                by_round
+                    .into_iter()
+                    .map(|score| round_cell(score.map(Cell::from))),
+            )
+            .chain(std::iter::once(total.into()))
+            .collect()
+        })
+        .collect();
+    (header, rows)
+}
+
+fn isize_cell(number: isize) -> Cell {
+    Cell::Number(i64::try_from(number).unwrap_or(i64::MAX))
+}
+
+/// A round that hasn't been played yet is left blank rather than shown as level par.
+fn round_cell(score: Option<Cell>) -> Cell {
+    score.unwrap_or_else(|| Cell::Text(String::new()))
+}
+
+/// Write `sheet` in `format`, named for the event, year and table.
+#[must_use]
+pub fn export_file(request: &ExportRequest, sheet: &Sheet) -> ExportFile {
+    let body = match request.format {
+        ExportFormat::Csv => write_csv(sheet),
+        ExportFormat::Xlsx => write_xlsx(sheet),
+    };
+    ExportFile {
+        filename: format!(
+            "{}-{}-{}.{}",
+            request.event_id,
+            request.year,
+            request.table.slug(),
+            request.format.extension()
+        ),
+        content_type: request.format.content_type(),
+        body,
+    }
+}
+
+/// Load the event's scores by the same cache rules as `/scores` and export the requested
+/// table.
+///
+/// # Errors
+/// Returns `NotFound` if the event doesn't exist, and other errors if scores can't be
+/// loaded.
+pub async fn load_export(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    request: &ExportRequest,
+) -> Result<ExportFile, CoreError> {
+    let event_id = request.event_id;
+    storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| CoreError::NotFound(format!("event {event_id} not found")))?;
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context = load_score_context(
+        storage,
+        espn_api,
+        event_id,
+        request.year,
+        request.use_cache,
+        cache_max_age,
+    )
+    .await?;
+    let sheet = build_sheet(request.table, event_id, &context.from_db_scores);
+    Ok(export_file(request, &sheet))
+}
@@ -0,0 +1,283 @@
+use std::fmt::Write;
+
+use super::{Cell, Sheet};
+
+const CONTENT_TYPES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+    r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+    r#"<Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>"#,
+    r#"</Types>"#,
+);
+
+const ROOT_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+const WORKBOOK_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+    r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+/// Style 0 is the default; style 1 is bold, for the header row.
+const STYLES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    r#"<fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts>"#,
+    r#"<fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills>"#,
+    r#"<borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>"#,
+    r#"<cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>"#,
+    r#"<cellXfs count="2"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>"#,
+    r#"<xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs>"#,
+    r#"<cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles>"#,
+    r#"</styleSheet>"#,
+);
+
+/// A one-sheet workbook with a bold, frozen header row. Text is written as inline strings,
+/// so there is no shared string table to keep in step.
+#[must_use]
+pub fn write_xlsx(sheet: &Sheet) -> Vec<u8> {
+    let workbook = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+        ),
+        escape(sheet.name)
+    );
+    write_zip(&[
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", ROOT_RELS.as_bytes()),
+        ("xl/workbook.xml", workbook.as_bytes()),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes()),
+        ("xl/styles.xml", STYLES.as_bytes()),
+        ("xl/worksheets/sheet1.xml", worksheet(sheet).as_bytes()),
+    ])
+}
+
+fn worksheet(sheet: &Sheet) -> String {
+    let mut xml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        r#"<sheetViews><sheetView workbookViewId="0">"#,
+        r#"<pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>"#,
+        r#"</sheetView></sheetViews><sheetData>"#,
+    ));
+    let header: Vec<Cell> = sheet
+        .header
+        .iter()
+        .map(|title| Cell::Text(title.clone()))
+        .collect();
+    for (index, row) in std::iter::once(&header).chain(&sheet.rows).enumerate() {
+        let row_number = index + 1;
+        let style = if index == 0 { r#" s="1""# } else { "" };
+        let _ = write!(xml, r#"<row r="{row_number}">"#);
+        for (column, cell) in row.iter().enumerate() {
+            let reference = format!("{}{row_number}", column_name(column));
+            match cell {
+                Cell::Text(text) if text.is_empty() => {}
+                Cell::Text(text) => {
+                    let _ = write!(
+                        xml,
+                        r#"<c r="{reference}" t="inlineStr"{style}><is><t>{}</t></is></c>"#,
+                        escape(text)
+                    );
+                }
+                Cell::Number(number) => {
+                    let _ = write!(xml, r#"<c r="{reference}"{style}><v>{number}</v></c>"#);
+                }
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// `0` is `A`, `25` is `Z`, `26` is `AA`.
+fn column_name(index: usize) -> String {
+    let mut name = Vec::new();
+    let mut remaining = index + 1;
+    while remaining > 0 {
+        let letter = u8::try_from((remaining - 1) % 26).unwrap_or(0);
+        name.push(char::from(b'A' + letter));
+        remaining = (remaining - 1) / 26;
+    }
+    name.iter().rev().collect()
+}
+
+/// Escape XML text, dropping control characters XML 1.0 can't carry.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch < ' ' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// 1980-01-01 00:00, the earliest DOS date, so the same sheet always zips to the same bytes.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// A zip archive with every entry stored uncompressed. Spreadsheet apps accept stored
+/// entries, and the sheets exported here are small.
+fn write_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    fn put_u16(out: &mut Vec<u8>, value: u16) {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    fn put_u32(out: &mut Vec<u8>, value: u32) {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    let len32 = |len: usize| u32::try_from(len).unwrap_or(u32::MAX);
+    let len16 = |len: usize| u16::try_from(len).unwrap_or(u16::MAX);
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, data) in files {
+        let offset = len32(out.len());
+        let crc = crc32fast::hash(data);
+
+        put_u32(&mut out, 0x0403_4b50);
+        put_u16(&mut out, 20); // version needed: 2.0
+        put_u16(&mut out, 0); // flags
+        put_u16(&mut out, 0); // method: stored
+        put_u16(&mut out, 0); // time
+        put_u16(&mut out, DOS_DATE);
+        put_u32(&mut out, crc);
+        put_u32(&mut out, len32(data.len()));
+        put_u32(&mut out, len32(data.len()));
+        put_u16(&mut out, len16(name.len()));
+        put_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        put_u32(&mut central, 0x0201_4b50);
+        put_u16(&mut central, 20); // version made by
+        put_u16(&mut central, 20); // version needed
+        put_u16(&mut central, 0); // flags
+        put_u16(&mut central, 0); // method: stored
+        put_u16(&mut central, 0); // time
+        put_u16(&mut central, DOS_DATE);
+        put_u32(&mut central, crc);
+        put_u32(&mut central, len32(data.len()));
+        put_u32(&mut central, len32(data.len()));
+        put_u16(&mut central, len16(name.len()));
+        put_u16(&mut central, 0); // extra field length
+        put_u16(&mut central, 0); // comment length
+        put_u16(&mut central, 0); // disk number
+        put_u16(&mut central, 0); // internal attributes
+        put_u32(&mut central, 0); // external attributes
+        put_u32(&mut central, offset);
+        central.extend_from_slice(name.as_bytes());
+    }
+    let central_offset = len32(out.len());
+    let central_size = len32(central.len());
+    out.extend_from_slice(&central);
+
+    put_u32(&mut out, 0x0605_4b50);
+    put_u16(&mut out, 0); // this disk
+    put_u16(&mut out, 0); // disk with the central directory
+    put_u16(&mut out, len16(files.len()));
+    put_u16(&mut out, len16(files.len()));
+    put_u32(&mut out, central_size);
+    put_u32(&mut out, central_offset);
+    put_u16(&mut out, 0); // comment length
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walk the local headers of a stored zip, checking each entry's CRC.
+    fn entries(zip: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let u16_at = |at: usize| usize::from(u16::from_le_bytes([zip[at], zip[at + 1]]));
+        let u32_at = |at: usize| u32::from_le_bytes(zip[at..at + 4].try_into().unwrap());
+        let mut entries = Vec::new();
+        let mut at = 0;
+        while u32_at(at) == 0x0403_4b50 {
+            let crc = u32_at(at + 14);
+            let size = usize::try_from(u32_at(at + 18)).unwrap();
+            let name_len = u16_at(at + 26);
+            let name_start = at + 30;
+            let data_start = name_start + name_len;
+            let data = zip[data_start..data_start + size].to_vec();
+            assert_eq!(crc32fast::hash(&data), crc);
+            let name = String::from_utf8(zip[name_start..data_start].to_vec()).unwrap();
+            entries.push((name, data));
+            at = data_start + size;
+        }
+        entries
+    }
+
+    #[test]
+    fn column_names_roll_over() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn writes_a_readable_workbook() {
+        let sheet = Sheet {
+            name: "Standings",
+            header: vec!["Bettor".to_string(), "Total".to_string()],
+            rows: vec![
+                vec![Cell::from("Ludvig <Åberg>"), Cell::Number(-3)],
+                vec![Cell::from("Player2"), Cell::from("")],
+            ],
+        };
+        let zip = write_xlsx(&sheet);
+        let entries = entries(&zip);
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "[Content_Types].xml",
+                "_rels/.rels",
+                "xl/workbook.xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/styles.xml",
+                "xl/worksheets/sheet1.xml",
+            ]
+        );
+        let end_of_central_directory = &zip[zip.len() - 22..];
+        assert_eq!(end_of_central_directory[..4], 0x0605_4b50_u32.to_le_bytes());
+        assert_eq!(end_of_central_directory[8..12], [6, 0, 6, 0]);
+
+        let worksheet = String::from_utf8(entries[5].1.clone()).unwrap();
+        assert!(
+            worksheet
+                .contains(r#"<row r="1"><c r="A1" t="inlineStr" s="1"><is><t>Bettor</t></is></c>"#)
+        );
+        assert!(worksheet.contains(
+            r#"<c r="A2" t="inlineStr"><is><t>Ludvig &lt;Åberg&gt;</t></is></c><c r="B2"><v>-3</v></c>"#
+        ));
+        assert!(
+            worksheet.contains(
+                r#"<row r="3"><c r="A3" t="inlineStr"><is><t>Player2</t></is></c></row>"#
+            )
+        );
+        assert_eq!(write_xlsx(&sheet), zip, "output is deterministic");
+    }
+}
@@ -0,0 +1,503 @@
+//! `/feed.atom`: an Atom feed of an event's notable moments, for following the pool in a
+//! feed reader.
+//!
+//! Entries come from the event's stored score snapshots, never from a live refresh, and a
+//! snapshot once read is always read, so refreshing the scores never re-dates, reorders or
+//! duplicates what a reader has already seen.
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::api::standings_from_scores;
+use crate::error::CoreError;
+use crate::export::escape_xml;
+use crate::model::{HoleResult, RefreshSource, ScoreChangeKind, ScoresAndLastRefresh};
+use crate::score::diff_score_snapshots;
+use crate::storage::Storage;
+use crate::view::bettor::to_par;
+
+pub const FEED_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+
+/// Snapshots taken sooner than this after the last one read are skipped. A lead that changes
+/// hands and back within it isn't news, and it keeps a four-day event to a few hundred reads.
+const SNAPSHOT_INTERVAL_SECONDS: i64 = 900;
+
+/// Feed readers only look at the recent past.
+const MAX_ENTRIES: usize = 100;
+
+const HOLES_PER_ROUND: usize = 18;
+
+/// One notable moment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// Unique within the event; the tail of the entry's `tag:` id.
+    pub key: String,
+    pub updated: NaiveDateTime,
+    pub title: String,
+    pub summary: String,
+    /// Relative to the feed, like every link the pages make.
+    pub link: String,
+}
+
+/// The snapshots to read from `sorted` (oldest first). Walking forward from the first, a
+/// snapshot is read if it was taken at least [`SNAPSHOT_INTERVAL_SECONDS`] after the last
+/// one read, so newer snapshots never change which older ones are read.
+#[must_use]
+pub fn feed_snapshot_times(sorted: &[NaiveDateTime]) -> Vec<NaiveDateTime> {
+    let mut read: Vec<NaiveDateTime> = Vec::new();
+    for taken_at in sorted {
+        if read
+            .last()
+            .is_none_or(|last| (*taken_at - *last).num_seconds() >= SNAPSHOT_INTERVAL_SECONDS)
+        {
+            read.push(*taken_at);
+        }
+    }
+    read
+}
+
+/// Every lead change, completed round, and eagle or better among the picks in `snapshots`
+/// (oldest first), newest first. The first snapshot is compared with no scores at all, so
+/// rounds and eagles it already holds are reported as of then.
+#[must_use]
+pub fn feed_entries(
+    event_id: i32,
+    year: i32,
+    snapshots: &[ScoresAndLastRefresh],
+) -> Vec<FeedEntry> {
+    let empty = ScoresAndLastRefresh {
+        score_struct: Vec::new(),
+        last_refresh: NaiveDateTime::default(),
+        last_refresh_source: RefreshSource::Db,
+    };
+    let scoreboard = format!("?event={event_id}&yr={year}");
+    let mut entries = Vec::new();
+    let mut previous = &empty;
+    for current in snapshots {
+        let updated = current.last_refresh;
+        let changes = diff_score_snapshots(previous, current);
+        entries.extend(hole_entries(
+            event_id,
+            year,
+            changes.iter().map(|change| &change.change),
+            updated,
+        ));
+        for change in &changes {
+            if matches!(change.change, ScoreChangeKind::LeadChange { .. }) {
+                entries.push(FeedEntry {
+                    key: format!("lead-{}", updated.format("%Y%m%dT%H%M%SZ")),
+                    updated,
+                    title: change.to_string(),
+                    summary: lead_summary(event_id, current),
+                    link: scoreboard.clone(),
+                });
+            }
+        }
+        for round in 0..=last_round(current) {
+            if round_complete(current, round) && !round_complete(previous, round) {
+                entries.push(FeedEntry {
+                    key: format!("round-{}", round + 1),
+                    updated,
+                    title: format!("Round {} complete", round + 1),
+                    summary: lead_summary(event_id, current),
+                    link: scoreboard.clone(),
+                });
+            }
+        }
+        previous = current;
+    }
+    newest_first(&mut entries);
+    entries
+}
+
+/// Newest first; entries from the same snapshot in key order.
+fn newest_first(entries: &mut [FeedEntry]) {
+    entries.sort_by(|a, b| b.updated.cmp(&a.updated).then_with(|| a.key.cmp(&b.key)));
+}
+
+/// Eagles, albatrosses and aces, one entry per golfer and hole however many bettors picked
+/// them.
+fn hole_entries<'a>(
+    event_id: i32,
+    year: i32,
+    changes: impl Iterator<Item = &'a ScoreChangeKind>,
+    updated: NaiveDateTime,
+) -> Vec<FeedEntry> {
+    let mut holes: Vec<(&'a ScoreChangeKind, Vec<&'a str>)> = Vec::new();
+    for change in changes {
+        let ScoreChangeKind::Hole {
+            bettor_name,
+            golfer_espn_id,
+            round,
+            hole,
+            result: HoleResult::Ace | HoleResult::Albatross | HoleResult::Eagle,
+            ..
+        } = change
+        else {
+            continue;
+        };
+        let seen = holes.iter_mut().find(|(seen, _)| {
+            matches!(seen, ScoreChangeKind::Hole { golfer_espn_id: id, round: r, hole: h, .. }
+                if id == golfer_espn_id && r == round && h == hole)
+        });
+        match seen {
+            Some((_, picked_by)) => picked_by.push(bettor_name),
+            None => holes.push((change, vec![bettor_name])),
+        }
+    }
+    holes
+        .into_iter()
+        .filter_map(|(change, picked_by)| {
+            let ScoreChangeKind::Hole {
+                golfer_name,
+                golfer_espn_id,
+                round,
+                hole,
+                par,
+                strokes,
+                result,
+                ..
+            } = change
+            else {
+                return None;
+            };
+            Some(FeedEntry {
+                key: format!("hole-{golfer_espn_id}-r{round}-h{hole}"),
+                updated,
+                title: format!(
+                    "{golfer_name} {} {hole} in round {round}",
+                    result.past_tense()
+                ),
+                summary: format!(
+                    "{strokes} on the par {par}. Picked by {}.",
+                    picked_by.join(" & ")
+                ),
+                link: format!("golfer?espn_id={golfer_espn_id}&event={event_id}&yr={year}"),
+            })
+        })
+        .collect()
+}
+
+/// The highest 0-based round any pick has a tee time or a hole in; -1 before any.
+fn last_round(scores: &ScoresAndLastRefresh) -> i32 {
+    scores
+        .score_struct
+        .iter()
+        .flat_map(|golfer| {
+            let stats = &golfer.detailed_statistics;
+            let teed = i32::try_from(stats.tee_times.len()).unwrap_or(0) - 1;
+            stats
+                .line_scores
+                .iter()
+                .filter(|ls| ls.score > 0)
+                .map(|ls| ls.round)
+                .chain(std::iter::once(teed))
+        })
+        .max()
+        .unwrap_or(-1)
+}
+
+/// A round (0-based) is complete once someone has played in it and every pick with a tee
+/// time or a hole in it has played all 18. Picks who missed the cut never tee off, so they
+/// don't hold it open.
+fn round_complete(scores: &ScoresAndLastRefresh, round: i32) -> bool {
+    let mut played = false;
+    for golfer in &scores.score_struct {
+        let stats = &golfer.detailed_statistics;
+        let holes = stats
+            .line_scores
+            .iter()
+            .filter(|ls| ls.round == round && ls.score > 0)
+            .count();
+        let teed = usize::try_from(round)
+            .ok()
+            .and_then(|index| stats.tee_times.get(index))
+            .is_some_and(|tee_time| !tee_time.val.is_empty());
+        if holes == 0 && !teed {
+            continue;
+        }
+        if holes < HOLES_PER_ROUND {
+            return false;
+        }
+        played = true;
+    }
+    played
+}
+
+/// The bettors ranked first and their total.
+fn leaders(event_id: i32, scores: &ScoresAndLastRefresh) -> (Vec<String>, i32) {
+    let standings = standings_from_scores(event_id, scores).standings;
+    let total = standings.first().map_or(0, |first| first.total_score);
+    let leaders = standings
+        .into_iter()
+        .filter(|standing| standing.rank == 1)
+        .map(|standing| standing.bettor_name)
+        .collect();
+    (leaders, total)
+}
+
+fn lead_summary(event_id: i32, scores: &ScoresAndLastRefresh) -> String {
+    match leaders(event_id, scores) {
+        (leaders, total) if leaders.len() > 1 => format!(
+            "{} share the lead at {}.",
+            leaders.join(" & "),
+            to_par(total)
+        ),
+        (leaders, total) => format!("{} leads at {}.", leaders.join(""), to_par(total)),
+    }
+}
+
+fn winner_summary(event_id: i32, scores: &ScoresAndLastRefresh) -> String {
+    match leaders(event_id, scores) {
+        (winners, total) if winners.len() > 1 => format!(
+            "{} tie for the win at {}.",
+            winners.join(" & "),
+            to_par(total)
+        ),
+        (winners, total) => format!("{} wins at {}.", winners.join(""), to_par(total)),
+    }
+}
+
+fn atom_time(time: NaiveDateTime) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// The feed document. Ids are `tag:` URIs keyed by event and entry, so they survive a move
+/// to another host.
+#[must_use]
+pub fn write_atom(
+    event_id: i32,
+    year: i32,
+    event_name: &str,
+    entries: &[FeedEntry],
+    updated: NaiveDateTime,
+) -> String {
+    let id = format!("tag:rusty-golf,{year}:event/{event_id}");
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    out.push_str(&format!(
+        "  <title>{}</title>\n",
+        escape_xml(&format!("{event_name} leaderboard"))
+    ));
+    out.push_str(&format!("  <id>{id}</id>\n"));
+    out.push_str(&format!(
+        "  <link rel=\"self\" href=\"feed.atom?event={event_id}&amp;yr={year}\"/>\n"
+    ));
+    out.push_str(&format!(
+        "  <link rel=\"alternate\" href=\"?event={event_id}&amp;yr={year}\"/>\n"
+    ));
+    out.push_str(&format!("  <updated>{}</updated>\n", atom_time(updated)));
+    out.push_str("  <author><name>rusty-golf</name></author>\n");
+    for entry in entries.iter().take(MAX_ENTRIES) {
+        out.push_str("  <entry>\n");
+        out.push_str(&format!("    <id>{id}/{}</id>\n", escape_xml(&entry.key)));
+        out.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        out.push_str(&format!(
+            "    <link rel=\"alternate\" href=\"{}\"/>\n",
+            escape_xml(&entry.link)
+        ));
+        out.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            atom_time(entry.updated)
+        ));
+        out.push_str(&format!(
+            "    <summary>{}</summary>\n",
+            escape_xml(&entry.summary)
+        ));
+        out.push_str("  </entry>\n");
+    }
+    out.push_str("</feed>\n");
+    out
+}
+
+/// Read the event's stored snapshots and build its feed. A completed event gets a final
+/// entry with the winners from its current (or archived) scores, dated by its end date.
+/// Nothing here calls ESPN, so the feed trails the scoreboard by up to a snapshot interval.
+///
+/// # Errors
+/// Returns `NotFound` if the event doesn't exist, and other errors if a snapshot can't be
+/// read.
+pub async fn load_feed(
+    storage: &dyn Storage,
+    event_id: i32,
+    year: i32,
+) -> Result<String, CoreError> {
+    let details = storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| CoreError::NotFound(format!("event {event_id} not found")))?;
+    let taken = storage.list_score_snapshots(event_id).await?;
+    let mut snapshots = Vec::new();
+    for taken_at in feed_snapshot_times(&taken) {
+        if let Some(snapshot) = storage.get_score_snapshot(event_id, taken_at).await? {
+            snapshots.push(snapshot);
+        }
+    }
+    let mut entries = feed_entries(event_id, year, &snapshots);
+
+    if details.completed
+        && let Ok(scores) = storage.get_scores(event_id, RefreshSource::Db).await
+    {
+        let completed_at = details
+            .end_date
+            .as_deref()
+            .and_then(|end| DateTime::parse_from_rfc3339(end).ok())
+            .map_or(scores.last_refresh, |end| end.naive_utc());
+        entries.push(FeedEntry {
+            key: "completed".to_string(),
+            updated: completed_at,
+            title: format!("{} is over", details.event_name),
+            summary: winner_summary(event_id, &scores),
+            link: format!("?event={event_id}&yr={year}"),
+        });
+        newest_first(&mut entries);
+    }
+    let updated = entries
+        .iter()
+        .map(|entry| entry.updated)
+        .chain(snapshots.last().map(|snapshot| snapshot.last_refresh))
+        .max()
+        .unwrap_or_default();
+    Ok(write_atom(
+        event_id,
+        year,
+        &details.event_name,
+        &entries,
+        updated,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{IntStat, LineScore, ScoreDisplay, Scores, Statistic, StringStat};
+
+    fn ts(value: &str) -> NaiveDateTime {
+        crate::score::parse_as_of(value).unwrap()
+    }
+
+    /// `holes` holes of round `round` (0-based), all pars except the eagle on 13 if asked.
+    fn pick(eup_id: i64, bettor: &str, round: i32, holes: i32, eagle: bool) -> Scores {
+        let line_scores: Vec<LineScore> = (0..=round)
+            .flat_map(|r| {
+                let played = if r == round { holes } else { 18 };
+                (1..=played).map(move |hole| {
+                    let score = if eagle && r == round && hole == 13 {
+                        3
+                    } else {
+                        5
+                    };
+                    LineScore {
+                        round: r,
+                        hole,
+                        score,
+                        par: 5,
+                        score_display: ScoreDisplay::from_i32(score - 5),
+                    }
+                })
+            })
+            .collect();
+        let total = line_scores.iter().map(|ls| ls.score - ls.par).sum();
+        Scores {
+            eup_id,
+            espn_id: eup_id * 100,
+            golfer_name: format!("Golfer {eup_id}"),
+            bettor_name: bettor.to_string(),
+            detailed_statistics: Statistic {
+                eup_id,
+                rounds: Vec::new(),
+                round_scores: vec![IntStat { val: total }],
+                tee_times: (0..=round)
+                    .map(|_| StringStat {
+                        val: "5/16 7:15a".to_string(),
+                    })
+                    .collect(),
+                holes_completed_by_round: Vec::new(),
+                line_scores,
+                total_score: total,
+            },
+            group: 1,
+            score_view_step_factor: None,
+        }
+    }
+
+    fn snapshot(at: &str, scores: Vec<Scores>) -> ScoresAndLastRefresh {
+        ScoresAndLastRefresh {
+            score_struct: scores,
+            last_refresh: ts(at),
+            last_refresh_source: RefreshSource::Db,
+        }
+    }
+
+    #[test]
+    fn snapshots_are_read_once_per_interval_from_the_first() {
+        let sorted = [
+            ts("2024-05-16T12:00:00Z"),
+            ts("2024-05-16T12:05:00Z"),
+            ts("2024-05-16T12:15:00Z"),
+            ts("2024-05-16T12:20:00Z"),
+        ];
+        let read = feed_snapshot_times(&sorted);
+        assert_eq!(read, [sorted[0], sorted[2]]);
+        assert_eq!(
+            feed_snapshot_times(&sorted[..3]),
+            read,
+            "stable as more arrive"
+        );
+    }
+
+    #[test]
+    fn eagles_leads_and_rounds_become_entries() {
+        let snapshots = [
+            snapshot(
+                "2024-05-16T14:00:00Z",
+                vec![pick(1, "Alice", 0, 10, false), pick(2, "Bob", 0, 10, false)],
+            ),
+            snapshot(
+                "2024-05-16T15:00:00Z",
+                vec![pick(1, "Alice", 0, 18, true), pick(2, "Bob", 0, 17, false)],
+            ),
+            snapshot(
+                "2024-05-16T16:00:00Z",
+                vec![pick(1, "Alice", 0, 18, true), pick(2, "Bob", 0, 18, false)],
+            ),
+        ];
+        let entries = feed_entries(1, 2024, &snapshots);
+        let titles: Vec<&str> = entries.iter().map(|entry| entry.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Round 1 complete",
+                "Golfer 1 eagled 13 in round 1",
+                "Alice takes the lead",
+            ]
+        );
+        assert_eq!(entries[0].summary, "Alice leads at -2.");
+        assert_eq!(entries[1].summary, "3 on the par 5. Picked by Alice.");
+        assert_eq!(entries[1].link, "golfer?espn_id=100&event=1&yr=2024");
+        assert_eq!(entries[2].key, "lead-20240516T150000Z");
+        assert_eq!(
+            feed_entries(1, 2024, &snapshots[..2]),
+            entries[1..],
+            "entries keep their date as snapshots arrive"
+        );
+    }
+
+    #[test]
+    fn atom_escapes_text() {
+        let entry = FeedEntry {
+            key: "completed".to_string(),
+            updated: ts("2024-05-19T23:00:00Z"),
+            title: "Smith & Jones <win>".to_string(),
+            summary: String::new(),
+            link: "?event=1&yr=2024".to_string(),
+        };
+        let atom = write_atom(1, 2024, "PGA", &[entry], ts("2024-05-19T23:00:00Z"));
+        assert!(atom.contains("<title>Smith &amp; Jones &lt;win&gt;</title>"));
+        assert!(atom.contains("<id>tag:rusty-golf,2024:event/1/completed</id>"));
+        assert!(atom.contains("href=\"?event=1&amp;yr=2024\""));
+        assert!(atom.contains("<updated>2024-05-19T23:00:00Z</updated>"));
+    }
+}
@@ -1,5 +1,9 @@
+pub mod api;
+pub mod calendar;
 pub mod error;
 pub mod espn;
+pub mod export;
+pub mod feed;
 pub mod model;
 pub mod score;
 pub mod storage;
@@ -1,7 +1,9 @@
+pub mod prefill;
 pub mod score;
 pub mod types;
 pub mod utils;
 
+pub use prefill::*;
 pub use score::*;
 pub use types::*;
 pub use utils::*;
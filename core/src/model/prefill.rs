@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+/// One event in a `--db-populate-json` payload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrefillEvent {
+    pub event: i64,
+    pub year: i64,
+    pub name: String,
+    pub score_view_step_factor: f64,
+    /// RFC 3339 timestamps, e.g. `2024-05-19T23:00:00Z`.
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+    pub data_to_fill_if_event_and_year_missing: Vec<PrefillData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrefillData {
+    pub bettors: Vec<String>,
+    pub golfers: Vec<PrefillGolfer>,
+    pub event_user_player: Vec<PrefillEventUserPlayer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrefillGolfer {
+    pub name: String,
+    pub espn_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrefillEventUserPlayer {
+    pub bettor: String,
+    pub golfer_espn_id: i64,
+    #[serde(default)]
+    pub score_view_step_factor: Option<f64>,
+}
+
+/// Parse a `--db-populate-json` payload.
+///
+/// # Errors
+/// Returns an error if the payload doesn't match the prefill shape.
+pub fn parse_prefill_events(json: &serde_json::Value) -> Result<Vec<PrefillEvent>, String> {
+    Vec::<PrefillEvent>::deserialize(json)
+        .map_err(|e| format!("Invalid db_populate_json payload: {e}"))
+}
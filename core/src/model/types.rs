@@ -63,6 +63,8 @@ pub enum RefreshSource {
     R2,
     Kv,
     Memory,
+    File,
+    Archive,
     Espn,
 }
 
@@ -73,12 +75,152 @@ impl fmt::Display for RefreshSource {
             RefreshSource::R2 => "R2",
             RefreshSource::Kv => "kv",
             RefreshSource::Memory => "memory",
+            RefreshSource::File => "file",
+            RefreshSource::Archive => "archive",
             RefreshSource::Espn => "ESPN",
         };
         write!(f, "{s}")
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HoleResult {
+    Ace,
+    Albatross,
+    Eagle,
+    Birdie,
+    Bogey,
+    DoubleBogeyOrWorse,
+}
+
+impl HoleResult {
+    /// Classify a completed hole; pars (and unplayed holes) produce no result.
+    #[must_use]
+    pub fn from_strokes(strokes: i32, par: i32) -> Option<Self> {
+        if strokes <= 0 || par <= 0 {
+            return None;
+        }
+        if strokes == 1 {
+            return Some(Self::Ace);
+        }
+        match strokes - par {
+            i32::MIN..=-3 => Some(Self::Albatross),
+            -2 => Some(Self::Eagle),
+            -1 => Some(Self::Birdie),
+            0 => None,
+            1 => Some(Self::Bogey),
+            _ => Some(Self::DoubleBogeyOrWorse),
+        }
+    }
+
+    pub(crate) fn past_tense(self) -> &'static str {
+        match self {
+            HoleResult::Ace => "made an ace on",
+            HoleResult::Albatross => "made an albatross on",
+            HoleResult::Eagle => "eagled",
+            HoleResult::Birdie => "birdied",
+            HoleResult::Bogey => "bogeyed",
+            HoleResult::DoubleBogeyOrWorse => "made double bogey or worse on",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScoreChangeKind {
+    Hole {
+        bettor_name: String,
+        golfer_name: String,
+        golfer_espn_id: i64,
+        round: i32,
+        hole: i32,
+        par: i32,
+        strokes: i32,
+        result: HoleResult,
+    },
+    RoundFinished {
+        bettor_name: String,
+        golfer_name: String,
+        golfer_espn_id: i64,
+        round: i32,
+        round_score: i32,
+    },
+    PositionChange {
+        bettor_name: String,
+        from_position: usize,
+        to_position: usize,
+    },
+    LeadChange {
+        leaders: Vec<String>,
+        previous_leaders: Vec<String>,
+    },
+}
+
+/// A single notable difference between two stored score snapshots.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScoreChange {
+    pub detected_at: NaiveDateTime,
+    #[serde(flatten)]
+    pub change: ScoreChangeKind,
+}
+
+impl fmt::Display for ScoreChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            ScoreChangeKind::Hole {
+                golfer_name,
+                bettor_name,
+                hole,
+                result,
+                ..
+            } => write!(
+                f,
+                "{golfer_name} just {} {hole} ({bettor_name})",
+                result.past_tense()
+            ),
+            ScoreChangeKind::RoundFinished {
+                golfer_name,
+                bettor_name,
+                round,
+                round_score,
+                ..
+            } => write!(
+                f,
+                "{golfer_name} finished round {round} at {round_score:+} ({bettor_name})"
+            ),
+            ScoreChangeKind::PositionChange {
+                bettor_name,
+                from_position,
+                to_position,
+            } => {
+                let verb = if to_position < from_position {
+                    "moves up"
+                } else {
+                    "drops"
+                };
+                write!(f, "{bettor_name} {verb} to {}", ordinal(*to_position))
+            }
+            ScoreChangeKind::LeadChange { leaders, .. } => match leaders.as_slice() {
+                [] => write!(f, "Nobody leads"),
+                [leader] => write!(f, "{leader} takes the lead"),
+                _ => write!(f, "{} are tied for the lead", leaders.join(", ")),
+            },
+        }
+    }
+}
+
+fn ordinal(position: usize) -> String {
+    let suffix = match (position % 10, position % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{position}{suffix}")
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BettorScoreByRound {
     pub bettor_name: String,
@@ -0,0 +1,122 @@
+use crate::error::CoreError;
+use crate::espn::EspnApiClient;
+use crate::model::Scores;
+use crate::score::final_round_done;
+use crate::storage::Storage;
+use chrono::{NaiveDateTime, Utc};
+
+const COMPLETION_PROMOTION_GRACE_DAYS: i64 = 5;
+
+/// Decide whether an event should be promoted to completed based on ESPN and age.
+#[must_use]
+pub fn should_promote_completed(
+    espn_completed: bool,
+    end_date: Option<&str>,
+    now: NaiveDateTime,
+) -> bool {
+    if !espn_completed {
+        return false;
+    }
+
+    let Some(end_date) = end_date else {
+        return false;
+    };
+    let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(end_date) else {
+        return false;
+    };
+    let end_date = parsed.with_timezone(&Utc).naive_utc();
+    now > end_date + chrono::Duration::days(COMPLETION_PROMOTION_GRACE_DAYS)
+}
+
+/// Mark an event completed once ESPN reports it finished and its end date is old enough.
+///
+/// Called after `store_scores` on a successful refresh with the scores just stored. The
+/// scoreboard header is only requested when the stored end date is past the grace period,
+/// or, with no end date stored, once `scores` show the final round is done.
+/// Returns whether the event was promoted.
+///
+/// # Errors
+/// Returns an error if event details, the scoreboard header, or the update fail.
+pub async fn promote_completed_if_ready(
+    storage: &dyn Storage,
+    api: &dyn EspnApiClient,
+    event_id: i32,
+    scores: &[Scores],
+    now: NaiveDateTime,
+) -> Result<bool, CoreError> {
+    let details = storage.get_event_details(event_id).await?;
+    if details.completed {
+        return Ok(false);
+    }
+
+    let stored_end_date = details
+        .end_date
+        .as_deref()
+        .and_then(|end_date| chrono::DateTime::parse_from_rfc3339(end_date).ok());
+    match stored_end_date {
+        Some(parsed)
+            if now
+                <= parsed.naive_utc() + chrono::Duration::days(COMPLETION_PROMOTION_GRACE_DAYS) =>
+        {
+            return Ok(false);
+        }
+        None if !final_round_done(scores) => return Ok(false),
+        _ => {}
+    }
+
+    let Some(espn) = api.event_completion(event_id).await? else {
+        return Ok(false);
+    };
+    let end_date = details.end_date.as_deref().or(espn.end_date.as_deref());
+    if !should_promote_completed(espn.completed, end_date, now) {
+        return Ok(false);
+    }
+
+    storage.mark_event_completed(event_id, end_date).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn promotes_completed_after_five_days() {
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(should_promote_completed(
+            true,
+            Some("2026-04-14T12:00:00Z"),
+            now,
+        ));
+    }
+
+    #[test]
+    fn does_not_promote_before_five_days() {
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(!should_promote_completed(
+            true,
+            Some("2026-04-16T12:00:00Z"),
+            now,
+        ));
+    }
+
+    #[test]
+    fn does_not_promote_without_espn_completion() {
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .unwrap()
+            .naive_utc();
+        assert!(!should_promote_completed(
+            false,
+            Some("2026-04-14T12:00:00Z"),
+            now,
+        ));
+    }
+}
@@ -1,16 +1,18 @@
 use crate::error::CoreError;
 use crate::espn::{EspnApiClient, FetchScoresRequest, fetch_scores_from_espn_with_timing};
 use crate::model::{ScoreData, ScoresAndLastRefresh};
-use crate::storage::Storage;
+use crate::score::refresh::refresh_interval;
+use crate::storage::{EventArchive, Storage};
 use crate::timed;
 use crate::timing::TimingSink;
+use chrono::Utc;
 use std::collections::HashMap;
 
 mod context_cache;
 mod context_data;
 
 pub use context_cache::{load_cached_scores, store_scores_and_reload};
-pub use context_data::{score_data_from_scores, score_data_from_scores_with_cache};
+pub use context_data::{rank_bettors, score_data_from_scores, score_data_from_scores_with_cache};
 
 #[derive(Debug)]
 pub struct ScoreContext {
@@ -18,6 +20,8 @@ pub struct ScoreContext {
     pub from_db_scores: ScoresAndLastRefresh,
     pub global_step_factor: f32,
     pub player_step_factors: HashMap<(i64, String), f32>,
+    /// Seconds between fragment refreshes on an open page; `None` once scores are final.
+    pub refresh_interval: Option<u32>,
 }
 
 /// Load scores and compute the score data view model.
@@ -57,6 +61,11 @@ pub async fn load_scores_data_with_timing(
     cache_max_age: i64,
     timing: Option<&dyn TimingSink>,
 ) -> Result<ScoreData, CoreError> {
+    if let Some(archive) = load_archive_if_completed(storage, event_id, timing).await?
+        && let Some(scores) = archive.archived_scores()
+    {
+        return Ok(score_data_from_scores_with_cache(&scores, true));
+    }
     let active_golfers = timed!(
         timing,
         "storage.get_golfers_for_event_ms",
@@ -122,6 +131,17 @@ pub async fn load_score_context_with_timing(
     cache_max_age: i64,
     timing: Option<&dyn TimingSink>,
 ) -> Result<ScoreContext, CoreError> {
+    if let Some(archive) = load_archive_if_completed(storage, event_id, timing).await?
+        && let Some(scores) = archive.archived_scores()
+    {
+        return Ok(ScoreContext {
+            data: score_data_from_scores_with_cache(&scores, true),
+            from_db_scores: scores,
+            global_step_factor: archive.event.score_view_step_factor,
+            player_step_factors: archive.player_step_factors(),
+            refresh_interval: None,
+        });
+    }
     let active_golfers = timed!(
         timing,
         "storage.get_golfers_for_event_ms",
@@ -157,10 +177,60 @@ pub async fn load_score_context_with_timing(
         "storage.get_player_step_factors_ms",
         storage.get_player_step_factors(event_id).await
     )?;
+    let refresh_interval =
+        refresh_interval(&event_details, &scores_and_refresh.score_struct, Utc::now());
     Ok(ScoreContext {
         data,
         from_db_scores: scores_and_refresh,
         global_step_factor: event_details.score_view_step_factor,
         player_step_factors,
+        refresh_interval,
+    })
+}
+
+/// The archive of a finished event, if it was archived. Archived scores are final, so the
+/// loaders serve them as they are and never reach the ESPN refresh path.
+async fn load_archive_if_completed(
+    storage: &dyn Storage,
+    event_id: i32,
+    timing: Option<&dyn TimingSink>,
+) -> Result<Option<EventArchive>, CoreError> {
+    // A missing event is reported by the regular path, as it always was.
+    let completed = timed!(
+        timing,
+        "storage.get_event_details_ms",
+        storage.get_event_details(event_id).await
+    )
+    .is_ok_and(|details| details.completed);
+    if !completed {
+        return Ok(None);
+    }
+    Ok(timed!(
+        timing,
+        "storage.get_event_archive_ms",
+        storage.get_event_archive(event_id).await
+    )?)
+}
+
+/// Build the score context from a stored snapshot instead of refreshing, for replaying an
+/// event as it stood at some earlier time. Step factors are the event's current ones.
+///
+/// # Errors
+/// Returns an error if the event details or step factors cannot be read.
+pub async fn score_context_from_snapshot(
+    storage: &dyn Storage,
+    event_id: i32,
+    snapshot: ScoresAndLastRefresh,
+) -> Result<ScoreContext, CoreError> {
+    let data = score_data_from_scores_with_cache(&snapshot, true);
+    let event_details = storage.get_event_details(event_id).await?;
+    let player_step_factors = storage.get_player_step_factors(event_id).await?;
+    Ok(ScoreContext {
+        data,
+        from_db_scores: snapshot,
+        global_step_factor: event_details.score_view_step_factor,
+        player_step_factors,
+        // A replay never changes, so the page has nothing to poll for.
+        refresh_interval: None,
     })
 }
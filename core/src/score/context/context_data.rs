@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 
 use crate::model::format_time_ago_for_score_view;
-use crate::model::{Bettors, RefreshSource, ScoreData, ScoresAndLastRefresh};
+use crate::model::{Bettors, RefreshSource, ScoreData, Scores, ScoresAndLastRefresh};
 
 #[must_use]
 pub fn score_data_from_scores(scores: &ScoresAndLastRefresh) -> ScoreData {
     let cache_hit = matches!(
         scores.last_refresh_source,
-        RefreshSource::Db | RefreshSource::R2 | RefreshSource::Kv | RefreshSource::Memory
+        RefreshSource::Db
+            | RefreshSource::R2
+            | RefreshSource::Kv
+            | RefreshSource::Memory
+            | RefreshSource::File
+            | RefreshSource::Archive
     );
     score_data_from_scores_with_cache(scores, cache_hit)
 }
@@ -17,8 +22,23 @@ pub fn score_data_from_scores_with_cache(
     scores: &ScoresAndLastRefresh,
     cache_hit: bool,
 ) -> ScoreData {
+    let bettors = rank_bettors(&scores.score_struct);
+
+    let elapsed = chrono::Utc::now().naive_utc() - scores.last_refresh;
+    ScoreData {
+        bettor_struct: bettors,
+        score_struct: scores.score_struct.clone(),
+        last_refresh: format_time_ago_for_score_view(elapsed),
+        last_refresh_source: scores.last_refresh_source.clone(),
+        cache_hit,
+    }
+}
+
+/// Total each bettor's golfers and order them best (lowest) first, ties by name.
+#[must_use]
+pub fn rank_bettors(scores: &[Scores]) -> Vec<Bettors> {
     let mut totals: HashMap<String, i32> = HashMap::new();
-    for golfer in &scores.score_struct {
+    for golfer in scores {
         *totals.entry(golfer.bettor_name.clone()).or_insert(0) +=
             golfer.detailed_statistics.total_score;
     }
@@ -51,12 +71,5 @@ pub fn score_data_from_scores_with_cache(
         };
     }
 
-    let elapsed = chrono::Utc::now().naive_utc() - scores.last_refresh;
-    ScoreData {
-        bettor_struct: bettors,
-        score_struct: scores.score_struct.clone(),
-        last_refresh: format_time_ago_for_score_view(elapsed),
-        last_refresh_source: scores.last_refresh_source.clone(),
-        cache_hit,
-    }
+    bettors
 }
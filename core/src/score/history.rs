@@ -0,0 +1,163 @@
+//! Timestamped score snapshots for backends that overwrite their current scores.
+//!
+//! SQL keeps every refresh in `eup_statistic_hx`. The key-value backends keep one
+//! `events/<id>/scores.json`, so they also write `events/<id>/history/<ts>.json` and
+//! prune it with a [`ScoreHistoryRetention`].
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::Serialize;
+
+use crate::error::CoreError;
+
+const SNAPSHOT_TS_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// How many snapshots an event keeps, and how often a new one is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScoreHistoryRetention {
+    /// The oldest snapshots beyond this many are deleted.
+    pub max_snapshots: usize,
+    /// Refreshes within this many seconds of the newest snapshot don't take another.
+    pub min_interval_seconds: i64,
+}
+
+impl Default for ScoreHistoryRetention {
+    /// One snapshot per five minutes covers a four-day tournament with room to spare.
+    fn default() -> Self {
+        Self {
+            max_snapshots: 1500,
+            min_interval_seconds: 300,
+        }
+    }
+}
+
+impl ScoreHistoryRetention {
+    /// Whether a refresh at `now` should be snapshotted, given the newest snapshot so far.
+    #[must_use]
+    pub fn should_snapshot(&self, newest: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+        if self.max_snapshots == 0 {
+            return false;
+        }
+        newest.is_none_or(|newest| (now - newest).num_seconds() >= self.min_interval_seconds)
+    }
+
+    /// The snapshots to delete from `sorted` (oldest first) to get back under the limit.
+    #[must_use]
+    pub fn to_prune<'a>(&self, sorted: &'a [NaiveDateTime]) -> &'a [NaiveDateTime] {
+        &sorted[..sorted.len().saturating_sub(self.max_snapshots)]
+    }
+}
+
+#[must_use]
+pub fn score_history_prefix(event_id: i32) -> String {
+    format!("events/{event_id}/history/")
+}
+
+#[must_use]
+pub fn score_history_key(event_id: i32, taken_at: NaiveDateTime) -> String {
+    format!(
+        "{}{}.json",
+        score_history_prefix(event_id),
+        taken_at.format(SNAPSHOT_TS_FORMAT)
+    )
+}
+
+/// The time a snapshot key was taken at; `None` for keys outside the event's history.
+#[must_use]
+pub fn parse_score_history_key(event_id: i32, key: &str) -> Option<NaiveDateTime> {
+    let ts = key
+        .strip_prefix(&score_history_prefix(event_id))?
+        .strip_suffix(".json")?;
+    NaiveDateTime::parse_from_str(ts, SNAPSHOT_TS_FORMAT).ok()
+}
+
+/// The newest snapshot in `sorted` (oldest first) taken no later than `as_of`.
+#[must_use]
+pub fn snapshot_at_or_before(
+    sorted: &[NaiveDateTime],
+    as_of: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    let after = sorted.partition_point(|taken_at| *taken_at <= as_of);
+    after.checked_sub(1).map(|index| sorted[index])
+}
+
+/// Parse an `as_of` query value: RFC 3339, or a UTC date-time without an offset.
+///
+/// # Errors
+/// Returns an error if the value is neither.
+pub fn parse_as_of(value: &str) -> Result<NaiveDateTime, CoreError> {
+    let value = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+        return Ok(parsed.naive_utc());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").map_err(|_| {
+        CoreError::Parse(format!(
+            "as_of must be RFC 3339, like 2025-07-18T14:00:00Z; got {value:?}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> NaiveDateTime {
+        parse_as_of(value).unwrap()
+    }
+
+    #[test]
+    fn keys_round_trip_and_sort_by_time() {
+        let key = score_history_key(7, ts("2025-07-18T14:05:09Z"));
+        assert_eq!(key, "events/7/history/20250718T140509Z.json");
+        assert_eq!(
+            parse_score_history_key(7, &key),
+            Some(ts("2025-07-18T14:05:09"))
+        );
+        assert_eq!(parse_score_history_key(8, &key), None);
+        assert_eq!(parse_score_history_key(7, "events/7/scores.json"), None);
+        assert!(key < score_history_key(7, ts("2025-07-19T00:00:00Z")));
+    }
+
+    #[test]
+    fn snapshots_are_spaced_and_pruned_oldest_first() {
+        let retention = ScoreHistoryRetention {
+            max_snapshots: 2,
+            min_interval_seconds: 300,
+        };
+        let newest = ts("2025-07-18T14:00:00Z");
+        assert!(retention.should_snapshot(None, newest));
+        assert!(!retention.should_snapshot(Some(newest), ts("2025-07-18T14:04:59Z")));
+        assert!(retention.should_snapshot(Some(newest), ts("2025-07-18T14:05:00Z")));
+
+        let sorted = [
+            ts("2025-07-18T13:00:00Z"),
+            ts("2025-07-18T13:30:00Z"),
+            newest,
+        ];
+        assert_eq!(retention.to_prune(&sorted), &sorted[..1]);
+        assert!(retention.to_prune(&sorted[..2]).is_empty());
+
+        let disabled = ScoreHistoryRetention {
+            max_snapshots: 0,
+            ..retention
+        };
+        assert!(!disabled.should_snapshot(None, newest));
+    }
+
+    #[test]
+    fn as_of_picks_the_newest_snapshot_not_after_it() {
+        let sorted = [ts("2025-07-18T13:00:00Z"), ts("2025-07-18T14:00:00Z")];
+        assert_eq!(
+            snapshot_at_or_before(&sorted, ts("2025-07-18T12:59:59Z")),
+            None
+        );
+        assert_eq!(
+            snapshot_at_or_before(&sorted, ts("2025-07-18T13:59:59Z")),
+            Some(sorted[0])
+        );
+        assert_eq!(
+            snapshot_at_or_before(&sorted, ts("2025-07-18T14:00:00+00:00")),
+            Some(sorted[1])
+        );
+        assert!(parse_as_of("yesterday").is_err());
+    }
+}
@@ -1,9 +1,17 @@
+pub mod completion;
 pub mod context;
+pub mod history;
+pub mod refresh;
 pub mod request;
 pub mod score_aggregators;
+pub mod score_changes;
 pub mod sort_utils;
 
+pub use completion::*;
 pub use context::*;
+pub use history::*;
+pub use refresh::*;
 pub use request::*;
 pub use score_aggregators::*;
+pub use score_changes::*;
 pub use sort_utils::*;
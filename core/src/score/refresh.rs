@@ -0,0 +1,225 @@
+use crate::model::Scores;
+use crate::storage::EventDetails;
+use chrono::{DateTime, Duration, Utc};
+
+/// Scores are refreshed from ESPN this long before an event's start date.
+const PRE_EVENT_REFRESH_LEAD: Duration = Duration::minutes(10);
+
+/// Fragment refresh interval while golfers are on the course.
+pub const LIVE_ROUND_REFRESH_SECS: u32 = 60;
+/// Fragment refresh interval while the event is under way but nobody is mid-round.
+pub const BETWEEN_ROUNDS_REFRESH_SECS: u32 = 600;
+/// Longest fragment refresh interval before an event starts.
+pub const UPCOMING_REFRESH_SECS: u32 = 900;
+
+/// Where an event stands, as far as refreshing its scores goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    /// More than ten minutes before the start date; stored scores are final until then.
+    Upcoming,
+    InProgress,
+    Completed,
+}
+
+impl EventPhase {
+    /// The phase of the event described by `details` at `now`. Events without a parseable
+    /// start date are in progress until they are marked completed.
+    #[must_use]
+    pub fn of(details: &EventDetails, now: DateTime<Utc>) -> Self {
+        if details.completed {
+            Self::Completed
+        } else if live_cutoff(details).is_some_and(|cutoff| now < cutoff) {
+            Self::Upcoming
+        } else {
+            Self::InProgress
+        }
+    }
+}
+
+/// When scores start refreshing from ESPN: ten minutes before the start date.
+fn live_cutoff(details: &EventDetails) -> Option<DateTime<Utc>> {
+    let start = details.start_date.as_deref()?;
+    let parsed = DateTime::parse_from_rfc3339(start).ok()?;
+    Some(parsed.with_timezone(&Utc) - PRE_EVENT_REFRESH_LEAD)
+}
+
+/// Cache max age in seconds for an event that is in progress.
+pub(crate) fn in_progress_cache_max_age(details: &EventDetails) -> i64 {
+    match details.refresh_from_espn {
+        1 => 300,
+        _ => 0,
+    }
+}
+
+/// True if any golfer has started their latest round without finishing it.
+#[must_use]
+pub fn round_in_play(scores: &[Scores]) -> bool {
+    scores.iter().any(|golfer| {
+        let line_scores = &golfer.detailed_statistics.line_scores;
+        let Some(latest) = line_scores.iter().map(|line| line.round).max() else {
+            return false;
+        };
+        let holes = line_scores
+            .iter()
+            .filter(|line| line.round == latest)
+            .count();
+        holes > 0 && holes < 18
+    })
+}
+
+/// Line-score round of an event's last round; rounds count from 0.
+pub const FINAL_ROUND: i32 = 3;
+
+/// True once some golfer has played all 18 holes of the final round and nobody is still on
+/// the course. Golfers who missed the cut never reach it, so one finisher is enough.
+#[must_use]
+pub fn final_round_done(scores: &[Scores]) -> bool {
+    !round_in_play(scores)
+        && scores.iter().any(|golfer| {
+            golfer
+                .detailed_statistics
+                .line_scores
+                .iter()
+                .filter(|line| line.round == FINAL_ROUND)
+                .count()
+                >= 18
+        })
+}
+
+/// How often, in seconds, an open scores page should re-poll its fragments, or `None` once
+/// the event is completed and its scores can no longer change.
+///
+/// Follows the same phases as `cache_max_age_for_event`: a live round polls every minute,
+/// but never faster than the cache refreshes; between rounds every ten minutes; an upcoming
+/// event as its scores start refreshing, at most fifteen minutes out.
+#[must_use]
+pub fn refresh_interval(
+    details: &EventDetails,
+    scores: &[Scores],
+    now: DateTime<Utc>,
+) -> Option<u32> {
+    match EventPhase::of(details, now) {
+        EventPhase::Completed => None,
+        EventPhase::Upcoming => {
+            let until_live = live_cutoff(details).map_or(0, |cutoff| (cutoff - now).num_seconds());
+            let secs = until_live.clamp(
+                i64::from(LIVE_ROUND_REFRESH_SECS),
+                i64::from(UPCOMING_REFRESH_SECS),
+            );
+            Some(u32::try_from(secs).unwrap_or(UPCOMING_REFRESH_SECS))
+        }
+        EventPhase::InProgress if round_in_play(scores) => {
+            let cache_secs = u32::try_from(in_progress_cache_max_age(details)).unwrap_or(0);
+            Some(LIVE_ROUND_REFRESH_SECS.max(cache_secs))
+        }
+        EventPhase::InProgress => Some(BETWEEN_ROUNDS_REFRESH_SECS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{LineScore, ScoreDisplay, Statistic};
+    use chrono::TimeZone;
+
+    fn details(start_date: Option<&str>, refresh_from_espn: i64, completed: bool) -> EventDetails {
+        EventDetails {
+            event_name: "Test Open".to_string(),
+            score_view_step_factor: 3.0,
+            refresh_from_espn,
+            start_date: start_date.map(str::to_string),
+            end_date: None,
+            completed,
+        }
+    }
+
+    /// A golfer with `holes` played in each listed round.
+    fn golfer(rounds: &[(i32, usize)]) -> Scores {
+        let line_scores = rounds
+            .iter()
+            .flat_map(|&(round, holes)| {
+                (1..=holes).map(move |hole| LineScore {
+                    round,
+                    hole: i32::try_from(hole).unwrap(),
+                    score: 4,
+                    par: 4,
+                    score_display: ScoreDisplay::Par,
+                })
+            })
+            .collect();
+        Scores {
+            eup_id: 1,
+            espn_id: 3470,
+            golfer_name: "Rory McIlroy".to_string(),
+            bettor_name: "Player1".to_string(),
+            detailed_statistics: Statistic {
+                eup_id: 1,
+                rounds: Vec::new(),
+                round_scores: Vec::new(),
+                tee_times: Vec::new(),
+                holes_completed_by_round: Vec::new(),
+                line_scores,
+                total_score: 0,
+            },
+            group: 1,
+            score_view_step_factor: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn completed_events_stop_polling() {
+        let scores = [golfer(&[(0, 18), (1, 9)])];
+        let details = details(Some("2026-04-09T11:00:00Z"), 1, true);
+        assert_eq!(EventPhase::of(&details, now()), EventPhase::Completed);
+        assert_eq!(refresh_interval(&details, &scores, now()), None);
+    }
+
+    #[test]
+    fn live_rounds_poll_no_faster_than_the_cache() {
+        let scores = [golfer(&[(0, 18)]), golfer(&[(0, 18), (1, 9)])];
+        let cached = details(Some("2026-04-09T11:00:00Z"), 1, false);
+        assert_eq!(refresh_interval(&cached, &scores, now()), Some(300));
+        let uncached = details(None, 0, false);
+        assert_eq!(
+            refresh_interval(&uncached, &scores, now()),
+            Some(LIVE_ROUND_REFRESH_SECS)
+        );
+    }
+
+    #[test]
+    fn between_rounds_polls_slowly() {
+        let scores = [golfer(&[(0, 18), (1, 18)]), golfer(&[(0, 18)])];
+        let details = details(Some("2026-04-09T11:00:00Z"), 0, false);
+        assert!(!round_in_play(&scores));
+        assert_eq!(
+            refresh_interval(&details, &scores, now()),
+            Some(BETWEEN_ROUNDS_REFRESH_SECS)
+        );
+    }
+
+    #[test]
+    fn final_round_is_done_once_nobody_is_left_on_it() {
+        let finished = golfer(&[(0, 18), (1, 18), (2, 18), (3, 18)]);
+        let cut = golfer(&[(0, 18), (1, 18)]);
+        assert!(final_round_done(&[finished.clone(), cut.clone()]));
+        assert!(!final_round_done(std::slice::from_ref(&cut)));
+        let on_course = golfer(&[(0, 18), (1, 18), (2, 18), (3, 12)]);
+        assert!(!final_round_done(&[finished, on_course, cut]));
+    }
+
+    #[test]
+    fn upcoming_events_poll_when_scores_start_refreshing() {
+        let soon = details(Some("2026-04-10T12:15:00Z"), 1, false);
+        assert_eq!(EventPhase::of(&soon, now()), EventPhase::Upcoming);
+        assert_eq!(refresh_interval(&soon, &[], now()), Some(300));
+        let later = details(Some("2026-04-11T12:00:00Z"), 1, false);
+        assert_eq!(
+            refresh_interval(&later, &[], now()),
+            Some(UPCOMING_REFRESH_SECS)
+        );
+    }
+}
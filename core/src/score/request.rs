@@ -1,11 +1,11 @@
 use crate::error::CoreError;
+use crate::score::history::parse_as_of;
+use crate::score::refresh::{EventPhase, in_progress_cache_max_age};
 use crate::storage::Storage;
 use chrono::{NaiveDateTime, Utc};
 use std::collections::HashMap;
 use std::hash::BuildHasher;
 
-const COMPLETION_PROMOTION_GRACE_DAYS: i64 = 5;
-
 #[derive(Debug, Clone, Copy)]
 pub struct ScoreRequest {
     pub event_id: i32,
@@ -13,6 +13,8 @@ pub struct ScoreRequest {
     pub use_cache: bool,
     pub want_json: bool,
     pub expanded: bool,
+    /// Replay the scores as stored at this time instead of refreshing.
+    pub as_of: Option<NaiveDateTime>,
 }
 
 /// Parse query parameters into a score request.
@@ -41,12 +43,18 @@ pub fn parse_score_request<S: BuildHasher>(
         Some("0") | None => false,
         Some(other) => other.parse().unwrap_or(false),
     };
+    let as_of = query
+        .get("as_of")
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| parse_as_of(value))
+        .transpose()?;
     Ok(ScoreRequest {
         event_id,
         year,
         use_cache,
         want_json,
         expanded,
+        as_of,
     })
 }
 
@@ -62,47 +70,12 @@ pub async fn cache_max_age_for_event(
         return Ok(0);
     };
 
-    if event_details.completed {
-        return Ok(-1);
-    }
-
-    if let Some(start_date) = event_details.start_date.as_deref()
-        && let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(start_date)
-    {
-        let start_utc = parsed.with_timezone(&Utc);
-        let cutoff = start_utc - chrono::Duration::minutes(10);
-        if Utc::now() < cutoff {
-            return Ok(-1);
-        }
-    }
-
-    Ok(match event_details.refresh_from_espn {
-        1 => 300,
-        _ => 0,
+    Ok(match EventPhase::of(&event_details, Utc::now()) {
+        EventPhase::Completed | EventPhase::Upcoming => -1,
+        EventPhase::InProgress => in_progress_cache_max_age(&event_details),
     })
 }
 
-/// Decide whether an event should be promoted to completed based on ESPN and age.
-#[must_use]
-pub fn should_promote_completed(
-    espn_completed: bool,
-    end_date: Option<&str>,
-    now: NaiveDateTime,
-) -> bool {
-    if !espn_completed {
-        return false;
-    }
-
-    let Some(end_date) = end_date else {
-        return false;
-    };
-    let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(end_date) else {
-        return false;
-    };
-    let end_date = parsed.with_timezone(&Utc).naive_utc();
-    now > end_date + chrono::Duration::days(COMPLETION_PROMOTION_GRACE_DAYS)
-}
-
 /// Parse a score request and build a derived value.
 ///
 /// # Errors
@@ -116,48 +89,3 @@ pub async fn decode_score_request<T, S: BuildHasher>(
     let cache_max_age = cache_max_age_for_event(storage, score_request.event_id).await?;
     Ok(builder(score_request, cache_max_age))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::{TimeZone, Utc};
-
-    #[test]
-    fn promotes_completed_after_five_days() {
-        let now = Utc
-            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
-            .unwrap()
-            .naive_utc();
-        assert!(should_promote_completed(
-            true,
-            Some("2026-04-14T12:00:00Z"),
-            now,
-        ));
-    }
-
-    #[test]
-    fn does_not_promote_before_five_days() {
-        let now = Utc
-            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
-            .unwrap()
-            .naive_utc();
-        assert!(!should_promote_completed(
-            true,
-            Some("2026-04-16T12:00:00Z"),
-            now,
-        ));
-    }
-
-    #[test]
-    fn does_not_promote_without_espn_completion() {
-        let now = Utc
-            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
-            .unwrap()
-            .naive_utc();
-        assert!(!should_promote_completed(
-            false,
-            Some("2026-04-14T12:00:00Z"),
-            now,
-        ));
-    }
-}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::context::rank_bettors;
+use crate::model::{HoleResult, ScoreChange, ScoreChangeKind, Scores, ScoresAndLastRefresh};
+
+/// How many recent changes a backend keeps per event.
+pub const MAX_RECENT_SCORE_CHANGES: usize = 200;
+
+const HOLES_PER_ROUND: usize = 18;
+
+/// Compare two score snapshots and emit the notable changes between them.
+///
+/// Hole and round events are reported per pick (bettor + golfer), so a golfer
+/// picked by two bettors produces two entries. Standings are compared with
+/// competition ranking, so ties share a position.
+#[must_use]
+pub fn diff_score_snapshots(
+    previous: &ScoresAndLastRefresh,
+    current: &ScoresAndLastRefresh,
+) -> Vec<ScoreChange> {
+    let detected_at = current.last_refresh;
+    let previous_by_eup: HashMap<i64, &Scores> = previous
+        .score_struct
+        .iter()
+        .map(|score| (score.eup_id, score))
+        .collect();
+
+    let mut changes = Vec::new();
+    for score in &current.score_struct {
+        let before = previous_by_eup.get(&score.eup_id).copied();
+        changes.extend(
+            golfer_changes(before, score)
+                .into_iter()
+                .map(|change| ScoreChange {
+                    detected_at,
+                    change,
+                }),
+        );
+    }
+
+    changes.extend(
+        standings_changes(&previous.score_struct, &current.score_struct)
+            .into_iter()
+            .map(|change| ScoreChange {
+                detected_at,
+                change,
+            }),
+    );
+    changes
+}
+
+/// Prepend new changes to the stored list, newest first, capped at
+/// [`MAX_RECENT_SCORE_CHANGES`].
+#[must_use]
+pub fn merge_recent_score_changes(
+    existing: Vec<ScoreChange>,
+    new_changes: &[ScoreChange],
+) -> Vec<ScoreChange> {
+    let mut merged: Vec<ScoreChange> = new_changes.iter().rev().cloned().collect();
+    merged.extend(existing);
+    merged.truncate(MAX_RECENT_SCORE_CHANGES);
+    merged
+}
+
+fn golfer_changes(before: Option<&Scores>, after: &Scores) -> Vec<ScoreChangeKind> {
+    let played_before: HashMap<(i32, i32), i32> = before
+        .map(|score| {
+            score
+                .detailed_statistics
+                .line_scores
+                .iter()
+                .filter(|ls| ls.score > 0)
+                .map(|ls| ((ls.round, ls.hole), ls.score))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut changes = Vec::new();
+    let mut holes_by_round: BTreeMap<i32, (usize, usize)> = BTreeMap::new();
+    let mut line_scores = after.detailed_statistics.line_scores.clone();
+    line_scores.sort_by_key(|ls| (ls.round, ls.hole));
+
+    for ls in line_scores.iter().filter(|ls| ls.score > 0) {
+        let counts = holes_by_round.entry(ls.round).or_default();
+        counts.1 += 1;
+        if played_before.contains_key(&(ls.round, ls.hole)) {
+            counts.0 += 1;
+            continue;
+        }
+        if let Some(result) = HoleResult::from_strokes(ls.score, ls.par) {
+            changes.push(ScoreChangeKind::Hole {
+                bettor_name: after.bettor_name.clone(),
+                golfer_name: after.golfer_name.clone(),
+                golfer_espn_id: after.espn_id,
+                round: ls.round + 1,
+                hole: ls.hole,
+                par: ls.par,
+                strokes: ls.score,
+                result,
+            });
+        }
+    }
+
+    for (round, (before_count, after_count)) in holes_by_round {
+        if before_count < HOLES_PER_ROUND && after_count >= HOLES_PER_ROUND {
+            let round_score = usize::try_from(round)
+                .ok()
+                .and_then(|idx| after.detailed_statistics.round_scores.get(idx))
+                .map_or(0, |stat| stat.val);
+            changes.push(ScoreChangeKind::RoundFinished {
+                bettor_name: after.bettor_name.clone(),
+                golfer_name: after.golfer_name.clone(),
+                golfer_espn_id: after.espn_id,
+                round: round + 1,
+                round_score,
+            });
+        }
+    }
+
+    changes
+}
+
+fn standings_changes(previous: &[Scores], current: &[Scores]) -> Vec<ScoreChangeKind> {
+    let before = competition_positions(previous);
+    let after = competition_positions(current);
+    if before.is_empty() || after.is_empty() {
+        return Vec::new();
+    }
+
+    let mut changes = Vec::new();
+    let mut names: Vec<&String> = after.keys().collect();
+    names.sort_by_key(|name| (after[*name], (*name).clone()));
+    for name in names {
+        if let Some(&from_position) = before.get(name) {
+            let to_position = after[name];
+            if from_position != to_position {
+                changes.push(ScoreChangeKind::PositionChange {
+                    bettor_name: name.clone(),
+                    from_position,
+                    to_position,
+                });
+            }
+        }
+    }
+
+    let previous_leaders = leaders(&before);
+    let current_leaders = leaders(&after);
+    if previous_leaders != current_leaders {
+        changes.push(ScoreChangeKind::LeadChange {
+            leaders: current_leaders,
+            previous_leaders,
+        });
+    }
+
+    changes
+}
+
+/// 1-based positions where tied totals share the better position.
+fn competition_positions(scores: &[Scores]) -> HashMap<String, usize> {
+    let ranked = rank_bettors(scores);
+    let mut positions = HashMap::new();
+    let mut position = 0;
+    let mut last_total = None;
+    for (idx, bettor) in ranked.iter().enumerate() {
+        if last_total != Some(bettor.total_score) {
+            position = idx + 1;
+            last_total = Some(bettor.total_score);
+        }
+        positions.insert(bettor.bettor_name.clone(), position);
+    }
+    positions
+}
+
+fn leaders(positions: &HashMap<String, usize>) -> Vec<String> {
+    let mut names: Vec<String> = positions
+        .iter()
+        .filter(|&(_, &position)| position == 1)
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{IntStat, LineScore, RefreshSource, ScoreDisplay, Statistic};
+    use chrono::NaiveDateTime;
+
+    fn line(round: i32, hole: i32, par: i32, score: i32) -> LineScore {
+        LineScore {
+            round,
+            hole,
+            score,
+            par,
+            score_display: ScoreDisplay::from_i32(score - par),
+        }
+    }
+
+    fn pick(eup_id: i64, bettor: &str, golfer: &str, lines: Vec<LineScore>) -> Scores {
+        let round_total: i32 = lines.iter().map(|ls| ls.score - ls.par).sum();
+        Scores {
+            eup_id,
+            espn_id: eup_id * 100,
+            golfer_name: golfer.to_string(),
+            bettor_name: bettor.to_string(),
+            detailed_statistics: Statistic {
+                eup_id,
+                rounds: vec![IntStat { val: 0 }],
+                round_scores: vec![IntStat { val: round_total }],
+                tee_times: Vec::new(),
+                holes_completed_by_round: Vec::new(),
+                line_scores: lines,
+                total_score: round_total,
+            },
+            group: 1,
+            score_view_step_factor: None,
+        }
+    }
+
+    fn snapshot(scores: Vec<Scores>) -> ScoresAndLastRefresh {
+        ScoresAndLastRefresh {
+            score_struct: scores,
+            last_refresh: NaiveDateTime::default(),
+            last_refresh_source: RefreshSource::Espn,
+        }
+    }
+
+    #[test]
+    fn reports_new_eagle_and_lead_change() {
+        let previous = snapshot(vec![
+            pick(1, "Alice", "Rory McIlroy", vec![line(0, 12, 4, 4)]),
+            pick(2, "Bob", "Jon Rahm", vec![line(0, 12, 4, 3)]),
+        ]);
+        let current = snapshot(vec![
+            pick(
+                1,
+                "Alice",
+                "Rory McIlroy",
+                vec![line(0, 12, 4, 4), line(0, 13, 5, 3)],
+            ),
+            pick(2, "Bob", "Jon Rahm", vec![line(0, 12, 4, 3)]),
+        ]);
+
+        let changes = diff_score_snapshots(&previous, &current);
+        let messages: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Rory McIlroy just eagled 13 (Alice)",
+                "Alice moves up to 1st",
+                "Bob drops to 2nd",
+                "Alice takes the lead",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_round_finished_once() {
+        let full_round: Vec<LineScore> = (1..=18).map(|hole| line(0, hole, 4, 4)).collect();
+        let previous = snapshot(vec![pick(
+            1,
+            "Alice",
+            "Rory McIlroy",
+            full_round[..17].to_vec(),
+        )]);
+        let current = snapshot(vec![pick(1, "Alice", "Rory McIlroy", full_round.clone())]);
+
+        let changes = diff_score_snapshots(&previous, &current);
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(
+            changes[0].change,
+            ScoreChangeKind::RoundFinished { round: 1, .. }
+        ));
+        assert!(diff_score_snapshots(&current, &current).is_empty());
+    }
+}
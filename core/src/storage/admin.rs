@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{CompactionPolicy, CompactionReport, EventArchive, Storage, StorageError};
+use crate::model::{Scores, Statistic};
+
+/// Everything an administrator sets on an event itself.
+///
+/// Only SQL keeps `year`; the key-value backends derive it from the dates when they need it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventConfig {
+    pub event_name: String,
+    pub year: i32,
+    pub score_view_step_factor: f32,
+    pub refresh_from_espn: i64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+/// A golfer as ESPN knows them. Golfers are shared by every event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GolferRecord {
+    pub espn_id: i64,
+    pub name: String,
+}
+
+/// One pick: `bettor_name` took the golfer with `golfer_espn_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Assignment {
+    pub bettor_name: String,
+    pub golfer_espn_id: i64,
+}
+
+/// Overrides the event's step factor for one pick.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStepFactor {
+    pub golfer_espn_id: i64,
+    pub bettor_name: String,
+    pub step_factor: f32,
+}
+
+// Every backend should pass `tests/tests/common/admin_conformance.rs`.
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+pub trait AdminStorage: Storage {
+    /// Fails if the event already exists.
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError>;
+    /// Replace the event's settings; picks and scores are left alone. Fails if the event
+    /// doesn't exist.
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError>;
+    /// Remove the event with its picks, step factors, scores and score changes. Golfers and
+    /// bettors stay. Returns whether the event existed.
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError>;
+    /// Add golfers, renaming any that already exist. Backends that copy names into picks
+    /// only pick up a rename the next time those picks are set.
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError>;
+    /// Replace the event's picks, in order: each bettor's picks are grouped 1.. as listed.
+    /// Step factors and stored scores are cleared. Fails if the event doesn't exist or a
+    /// pick names an unknown golfer.
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError>;
+    /// Replace every per-pick step factor. Fails if the event doesn't exist or a factor
+    /// names a pick the event doesn't have.
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError>;
+    /// Store `archive` as the event's frozen document, drop the live copies it replaces and
+    /// thin its score history to `policy`. Reads of anything dropped are answered from the
+    /// archive from then on, and `delete_event` removes it too. Use
+    /// [`archive_completed_event`](super::archive_completed_event), which checks the event
+    /// is finished and builds the archive.
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError>;
+}
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+pub trait AdminStorage: Storage {
+    /// Fails if the event already exists.
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError>;
+    /// Replace the event's settings; picks and scores are left alone. Fails if the event
+    /// doesn't exist.
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError>;
+    /// Remove the event with its picks, step factors, scores and score changes. Golfers and
+    /// bettors stay. Returns whether the event existed.
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError>;
+    /// Add golfers, renaming any that already exist. Backends that copy names into picks
+    /// only pick up a rename the next time those picks are set.
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError>;
+    /// Replace the event's picks, in order: each bettor's picks are grouped 1.. as listed.
+    /// Step factors and stored scores are cleared. Fails if the event doesn't exist or a
+    /// pick names an unknown golfer.
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError>;
+    /// Replace every per-pick step factor. Fails if the event doesn't exist or a factor
+    /// names a pick the event doesn't have.
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError>;
+    /// Store `archive` as the event's frozen document, drop the live copies it replaces and
+    /// thin its score history to `policy`. Reads of anything dropped are answered from the
+    /// archive from then on, and `delete_event` removes it too. Use
+    /// [`archive_completed_event`](super::archive_completed_event), which checks the event
+    /// is finished and builds the archive.
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError>;
+}
+
+/// Picks as `Scores` without statistics, the way the key-value backends store them: eup ids
+/// run 1.. in order and each bettor's picks are grouped 1.. as listed.
+///
+/// # Errors
+/// Returns an error if a pick names a golfer missing from `golfer_names`.
+pub fn picks_from_assignments(
+    event_id: i32,
+    assignments: &[Assignment],
+    golfer_names: &HashMap<i64, String>,
+) -> Result<Vec<Scores>, StorageError> {
+    let mut per_bettor: HashMap<&str, i64> = HashMap::new();
+    let mut picks = Vec::with_capacity(assignments.len());
+    for (eup_id, assignment) in (1_i64..).zip(assignments) {
+        let golfer_name = golfer_names
+            .get(&assignment.golfer_espn_id)
+            .ok_or_else(|| {
+                StorageError::new(format!(
+                    "event {event_id}: unknown golfer espn_id {}",
+                    assignment.golfer_espn_id
+                ))
+            })?;
+        let group = per_bettor
+            .entry(assignment.bettor_name.as_str())
+            .or_insert(0);
+        *group += 1;
+        picks.push(Scores {
+            eup_id,
+            espn_id: assignment.golfer_espn_id,
+            golfer_name: golfer_name.clone(),
+            bettor_name: assignment.bettor_name.clone(),
+            detailed_statistics: Statistic {
+                eup_id,
+                rounds: Vec::new(),
+                round_scores: Vec::new(),
+                tee_times: Vec::new(),
+                holes_completed_by_round: Vec::new(),
+                line_scores: Vec::new(),
+                total_score: 0,
+            },
+            group: *group,
+            score_view_step_factor: None,
+        });
+    }
+    Ok(picks)
+}
+
+/// Clear every pick's step factor, then set the ones in `factors`.
+///
+/// # Errors
+/// Returns an error if a factor names a pick that isn't in `picks`; `picks` is left as it was.
+pub fn apply_player_step_factors(
+    event_id: i32,
+    picks: &mut [Scores],
+    factors: &[PlayerStepFactor],
+) -> Result<(), StorageError> {
+    let mut updated: Vec<Option<f32>> = vec![None; picks.len()];
+    for factor in factors {
+        let index = picks
+            .iter()
+            .position(|pick| {
+                pick.espn_id == factor.golfer_espn_id && pick.bettor_name == factor.bettor_name
+            })
+            .ok_or_else(|| {
+                StorageError::new(format!(
+                    "event {event_id}: no pick of golfer {} by {}",
+                    factor.golfer_espn_id, factor.bettor_name
+                ))
+            })?;
+        updated[index] = Some(factor.step_factor);
+    }
+    for (pick, factor) in picks.iter_mut().zip(updated) {
+        pick.score_view_step_factor = factor;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> HashMap<i64, String> {
+        HashMap::from([
+            (3470, "Rory McIlroy".to_string()),
+            (9780, "Jon Rahm".to_string()),
+        ])
+    }
+
+    fn assignment(bettor_name: &str, golfer_espn_id: i64) -> Assignment {
+        Assignment {
+            bettor_name: bettor_name.to_string(),
+            golfer_espn_id,
+        }
+    }
+
+    #[test]
+    fn picks_are_numbered_and_grouped_per_bettor() {
+        let picks = picks_from_assignments(
+            1,
+            &[
+                assignment("Alice", 3470),
+                assignment("Bob", 3470),
+                assignment("Alice", 9780),
+            ],
+            &names(),
+        )
+        .unwrap();
+        let order: Vec<(i64, &str, i64)> = picks
+            .iter()
+            .map(|p| (p.eup_id, p.bettor_name.as_str(), p.group))
+            .collect();
+        assert_eq!(order, vec![(1, "Alice", 1), (2, "Bob", 1), (3, "Alice", 2)]);
+        assert_eq!(picks[2].golfer_name, "Jon Rahm");
+
+        let err = picks_from_assignments(1, &[assignment("Alice", 1)], &names()).unwrap_err();
+        assert_eq!(err.to_string(), "event 1: unknown golfer espn_id 1");
+    }
+
+    #[test]
+    fn step_factors_replace_and_reject_unknown_picks() {
+        let mut picks = picks_from_assignments(
+            1,
+            &[assignment("Alice", 3470), assignment("Bob", 9780)],
+            &names(),
+        )
+        .unwrap();
+        picks[1].score_view_step_factor = Some(2.0);
+
+        let factor = |golfer_espn_id, bettor_name: &str| PlayerStepFactor {
+            golfer_espn_id,
+            bettor_name: bettor_name.to_string(),
+            step_factor: 4.5,
+        };
+        apply_player_step_factors(1, &mut picks, &[factor(3470, "Alice")]).unwrap();
+        assert_eq!(picks[0].score_view_step_factor, Some(4.5));
+        assert_eq!(picks[1].score_view_step_factor, None);
+
+        assert!(apply_player_step_factors(1, &mut picks, &[factor(3470, "Bob")]).is_err());
+        assert_eq!(picks[0].score_view_step_factor, Some(4.5));
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AdminStorage, Assignment, EventConfig, EventDetails, GolferRecord, PlayerStepFactor, Storage,
+    StorageError, apply_player_step_factors, picks_from_assignments,
+};
+use crate::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh};
+
+/// The archive layout written by [`export_event`]. Bump it when a change would make older
+/// readers misread an archive.
+pub const EVENT_ARCHIVE_VERSION: u32 = 1;
+
+/// One event with everything needed to rebuild it on another backend.
+///
+/// Nothing in it is backend-specific: picks are keyed by bettor and golfer, not eup id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventArchive {
+    pub version: u32,
+    pub event_id: i32,
+    pub event: EventConfig,
+    /// Every golfer picked in the event.
+    pub golfers: Vec<GolferRecord>,
+    /// Bettors in the order they first appear in `assignments`.
+    pub bettors: Vec<String>,
+    /// Picks ordered so that importing them recreates each bettor's groups.
+    pub assignments: Vec<Assignment>,
+    pub step_factors: Vec<PlayerStepFactor>,
+    /// Scores as last stored, if any were.
+    #[serde(default)]
+    pub scores: Option<ScoresAndLastRefresh>,
+    /// Recent score changes, newest first.
+    #[serde(default)]
+    pub score_changes: Vec<ScoreChange>,
+}
+
+impl EventArchive {
+    /// Check the archive is one this build understands and hangs together.
+    ///
+    /// # Errors
+    /// Returns an error for an unknown version, a pick of a golfer the archive doesn't
+    /// list, a pick by a bettor it doesn't list, or a step factor without a pick.
+    pub fn validate(&self) -> Result<(), StorageError> {
+        let id = self.event_id;
+        if self.version != EVENT_ARCHIVE_VERSION {
+            return Err(StorageError::new(format!(
+                "event {id}: archive version {} is not supported (expected {EVENT_ARCHIVE_VERSION})",
+                self.version
+            )));
+        }
+        for assignment in &self.assignments {
+            if !self
+                .golfers
+                .iter()
+                .any(|golfer| golfer.espn_id == assignment.golfer_espn_id)
+            {
+                return Err(StorageError::new(format!(
+                    "event {id}: archive picks unlisted golfer espn_id {}",
+                    assignment.golfer_espn_id
+                )));
+            }
+            if !self.bettors.contains(&assignment.bettor_name) {
+                return Err(StorageError::new(format!(
+                    "event {id}: archive picks by unlisted bettor {}",
+                    assignment.bettor_name
+                )));
+            }
+        }
+        for factor in &self.step_factors {
+            if !self.assignments.iter().any(|assignment| {
+                assignment.golfer_espn_id == factor.golfer_espn_id
+                    && assignment.bettor_name == factor.bettor_name
+            }) {
+                return Err(StorageError::new(format!(
+                    "event {id}: archive has a step factor without a pick of golfer {} by {}",
+                    factor.golfer_espn_id, factor.bettor_name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The event's details as `Storage::get_event_details` reports them.
+    #[must_use]
+    pub fn details(&self) -> EventDetails {
+        EventDetails {
+            event_name: self.event.event_name.clone(),
+            score_view_step_factor: self.event.score_view_step_factor,
+            refresh_from_espn: self.event.refresh_from_espn,
+            start_date: self.event.start_date.clone(),
+            end_date: self.event.end_date.clone(),
+            completed: self.event.completed,
+        }
+    }
+
+    /// Picks the way the key-value backends number them, step factors applied.
+    ///
+    /// # Errors
+    /// Returns an error if the archive doesn't hang together; see [`Self::validate`].
+    pub fn picks(&self) -> Result<Vec<Scores>, StorageError> {
+        let names: HashMap<i64, String> = self
+            .golfers
+            .iter()
+            .map(|golfer| (golfer.espn_id, golfer.name.clone()))
+            .collect();
+        let mut picks = picks_from_assignments(self.event_id, &self.assignments, &names)?;
+        apply_player_step_factors(self.event_id, &mut picks, &self.step_factors)?;
+        Ok(picks)
+    }
+
+    #[must_use]
+    pub fn player_step_factors(&self) -> HashMap<(i64, String), f32> {
+        self.step_factors
+            .iter()
+            .map(|factor| {
+                (
+                    (factor.golfer_espn_id, factor.bettor_name.clone()),
+                    factor.step_factor,
+                )
+            })
+            .collect()
+    }
+
+    /// The frozen scores, reported as read from the archive.
+    #[must_use]
+    pub fn archived_scores(&self) -> Option<ScoresAndLastRefresh> {
+        let mut scores = self.scores.clone()?;
+        scores.last_refresh_source = RefreshSource::Archive;
+        Some(scores)
+    }
+}
+
+/// Read one event out of any backend.
+///
+/// An archived event comes back as the document it was frozen into.
+///
+/// The `Storage` contract has no year, so it is taken from the start date, then the end
+/// date, then the current year.
+///
+/// # Errors
+/// Returns an error if the event doesn't exist or a read fails. Missing scores are not an
+/// error: the archive just has none.
+pub async fn export_event(
+    storage: &dyn Storage,
+    event_id: i32,
+) -> Result<EventArchive, StorageError> {
+    if let Some(archive) = storage.get_event_archive(event_id).await? {
+        return Ok(archive);
+    }
+    let details = storage.get_event_details(event_id).await?;
+    let mut picks = storage.get_golfers_for_event(event_id).await?;
+    picks.sort_by_key(|pick| (pick.group, pick.eup_id));
+
+    let mut golfers: Vec<GolferRecord> = Vec::new();
+    let mut bettors: Vec<String> = Vec::new();
+    let mut assignments = Vec::with_capacity(picks.len());
+    for pick in &picks {
+        if !golfers.iter().any(|golfer| golfer.espn_id == pick.espn_id) {
+            golfers.push(GolferRecord {
+                espn_id: pick.espn_id,
+                name: pick.golfer_name.clone(),
+            });
+        }
+        if !bettors.contains(&pick.bettor_name) {
+            bettors.push(pick.bettor_name.clone());
+        }
+        assignments.push(Assignment {
+            bettor_name: pick.bettor_name.clone(),
+            golfer_espn_id: pick.espn_id,
+        });
+    }
+    golfers.sort_by_key(|golfer| golfer.espn_id);
+
+    let mut step_factors: Vec<PlayerStepFactor> = storage
+        .get_player_step_factors(event_id)
+        .await?
+        .into_iter()
+        .map(
+            |((golfer_espn_id, bettor_name), step_factor)| PlayerStepFactor {
+                golfer_espn_id,
+                bettor_name,
+                step_factor,
+            },
+        )
+        .collect();
+    step_factors.sort_by(|a, b| {
+        (a.golfer_espn_id, &a.bettor_name).cmp(&(b.golfer_espn_id, &b.bettor_name))
+    });
+
+    let scores = storage.get_scores(event_id, RefreshSource::Db).await.ok();
+    let score_changes = storage.get_recent_score_changes(event_id).await?;
+
+    Ok(EventArchive {
+        version: EVENT_ARCHIVE_VERSION,
+        event_id,
+        event: EventConfig {
+            year: year_from_dates(details.start_date.as_deref(), details.end_date.as_deref()),
+            event_name: details.event_name,
+            score_view_step_factor: details.score_view_step_factor,
+            refresh_from_espn: details.refresh_from_espn,
+            start_date: details.start_date,
+            end_date: details.end_date,
+            completed: details.completed,
+        },
+        golfers,
+        bettors,
+        assignments,
+        step_factors,
+        scores,
+        score_changes,
+    })
+}
+
+/// Write an archive into any backend under its own event id.
+///
+/// Scores are re-keyed to the eup ids the target gives the picks and stamped with the
+/// import time, like any other store.
+///
+/// # Errors
+/// Returns an error if the archive is invalid, the event already exists and `replace` is
+/// false, or a write fails. A failed import can leave the event partly written; importing
+/// again with `replace` starts it over.
+pub async fn import_event(
+    storage: &dyn AdminStorage,
+    archive: &EventArchive,
+    replace: bool,
+) -> Result<(), StorageError> {
+    archive.validate()?;
+    let id = archive.event_id;
+    if storage.get_event_details(id).await.is_ok() {
+        if !replace {
+            return Err(StorageError::new(format!("event {id} already exists")));
+        }
+        storage.delete_event(id).await?;
+    }
+
+    storage.create_event(id, &archive.event).await?;
+    storage.upsert_golfers(&archive.golfers).await?;
+    storage.set_assignments(id, &archive.assignments).await?;
+    storage
+        .set_player_step_factors(id, &archive.step_factors)
+        .await?;
+
+    if let Some(scores) = &archive.scores {
+        let picks = storage.get_golfers_for_event(id).await?;
+        let rekeyed = rekey_scores(id, &scores.score_struct, &picks)?;
+        storage.store_scores(id, &rekeyed).await?;
+    }
+
+    // Backends take changes oldest first and keep them newest first.
+    let oldest_first: Vec<ScoreChange> = archive.score_changes.iter().rev().cloned().collect();
+    storage.store_score_changes(id, &oldest_first).await
+}
+
+/// Give each score the eup id and group of the matching pick in `picks`.
+///
+/// # Errors
+/// Returns an error if a score has no pick by the same bettor of the same golfer.
+pub(super) fn rekey_scores(
+    event_id: i32,
+    scores: &[Scores],
+    picks: &[Scores],
+) -> Result<Vec<Scores>, StorageError> {
+    let eup_ids: HashMap<(i64, &str), (i64, i64)> = picks
+        .iter()
+        .map(|pick| {
+            (
+                (pick.espn_id, pick.bettor_name.as_str()),
+                (pick.eup_id, pick.group),
+            )
+        })
+        .collect();
+    scores
+        .iter()
+        .map(|score| {
+            let &(eup_id, group) = eup_ids
+                .get(&(score.espn_id, score.bettor_name.as_str()))
+                .ok_or_else(|| {
+                    StorageError::new(format!(
+                        "event {event_id}: archive scores golfer {} for {} without a pick",
+                        score.espn_id, score.bettor_name
+                    ))
+                })?;
+            let mut score = score.clone();
+            score.eup_id = eup_id;
+            score.detailed_statistics.eup_id = eup_id;
+            score.group = group;
+            Ok(score)
+        })
+        .collect()
+}
+
+pub(crate) fn year_from_dates(start_date: Option<&str>, end_date: Option<&str>) -> i32 {
+    [start_date, end_date]
+        .into_iter()
+        .flatten()
+        .find_map(|date| date.get(..4)?.parse().ok())
+        .unwrap_or_else(|| Utc::now().year())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    fn config() -> EventConfig {
+        EventConfig {
+            event_name: "The Open".to_string(),
+            year: 2025,
+            score_view_step_factor: 2.0,
+            refresh_from_espn: 1,
+            start_date: Some("2025-07-17T00:00:00Z".to_string()),
+            end_date: None,
+            completed: true,
+        }
+    }
+
+    fn assignment(bettor_name: &str, golfer_espn_id: i64) -> Assignment {
+        Assignment {
+            bettor_name: bettor_name.to_string(),
+            golfer_espn_id,
+        }
+    }
+
+    async fn seeded() -> InMemoryStorage {
+        let storage = InMemoryStorage::new();
+        storage.create_event(7, &config()).await.unwrap();
+        storage
+            .upsert_golfers(&[
+                GolferRecord {
+                    espn_id: 1,
+                    name: "One".to_string(),
+                },
+                GolferRecord {
+                    espn_id: 2,
+                    name: "Two".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+        storage
+            .set_assignments(
+                7,
+                &[
+                    assignment("Alice", 1),
+                    assignment("Bob", 1),
+                    assignment("Alice", 2),
+                ],
+            )
+            .await
+            .unwrap();
+        storage
+            .set_player_step_factors(
+                7,
+                &[PlayerStepFactor {
+                    golfer_espn_id: 2,
+                    bettor_name: "Alice".to_string(),
+                    step_factor: 3.0,
+                }],
+            )
+            .await
+            .unwrap();
+        let picks = storage.get_golfers_for_event(7).await.unwrap();
+        storage.store_scores(7, &picks).await.unwrap();
+        storage
+    }
+
+    #[tokio::test]
+    async fn an_archive_round_trips_with_fresh_eup_ids() {
+        let archive = export_event(&seeded().await, 7).await.unwrap();
+        assert_eq!(archive.bettors, vec!["Alice", "Bob"]);
+        assert_eq!(archive.event.year, 2025);
+        assert_eq!(archive.scores.as_ref().unwrap().score_struct.len(), 3);
+
+        // Burn some eup ids so the target numbers the picks differently.
+        let target = InMemoryStorage::new();
+        target.create_event(8, &config()).await.unwrap();
+        target
+            .upsert_golfers(&[GolferRecord {
+                espn_id: 1,
+                name: "One".to_string(),
+            }])
+            .await
+            .unwrap();
+        target
+            .set_assignments(8, &[assignment("Carol", 1), assignment("Dave", 1)])
+            .await
+            .unwrap();
+
+        import_event(&target, &archive, false).await.unwrap();
+        let again = export_event(&target, 7).await.unwrap();
+        assert_eq!(again.assignments, archive.assignments);
+        assert_eq!(again.step_factors, archive.step_factors);
+        assert!(again.event.completed);
+
+        let picks = target.get_golfers_for_event(7).await.unwrap();
+        let scores = target.get_scores(7, RefreshSource::Db).await.unwrap();
+        for score in &scores.score_struct {
+            let pick = picks.iter().find(|p| p.eup_id == score.eup_id).unwrap();
+            assert_eq!(
+                (pick.espn_id, &pick.bettor_name, pick.group),
+                (score.espn_id, &score.bettor_name, score.group)
+            );
+        }
+
+        let err = import_event(&target, &archive, false).await.unwrap_err();
+        assert_eq!(err.to_string(), "event 7 already exists");
+        import_event(&target, &archive, true).await.unwrap();
+    }
+
+    #[test]
+    fn invalid_archives_are_rejected() {
+        let mut archive = EventArchive {
+            version: EVENT_ARCHIVE_VERSION,
+            event_id: 7,
+            event: config(),
+            golfers: Vec::new(),
+            bettors: vec!["Alice".to_string()],
+            assignments: vec![assignment("Alice", 1)],
+            step_factors: Vec::new(),
+            scores: None,
+            score_changes: Vec::new(),
+        };
+        assert_eq!(
+            archive.validate().unwrap_err().to_string(),
+            "event 7: archive picks unlisted golfer espn_id 1"
+        );
+        archive.version = 99;
+        assert!(archive.validate().is_err());
+    }
+
+    #[test]
+    fn year_falls_back_through_the_dates() {
+        assert_eq!(
+            year_from_dates(Some("2024-04-11"), Some("2025-01-01")),
+            2024
+        );
+        assert_eq!(year_from_dates(None, Some("2023-06-15T00:00:00Z")), 2023);
+        assert_eq!(year_from_dates(Some("TBD"), None), Utc::now().year());
+    }
+}
@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+use super::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    EventDetails, GolferRecord, PlayerStepFactor, Storage, StorageError,
+};
+use crate::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh};
+
+/// How long [`CachedStorage`] keeps each kind of read, in seconds. Zero or less turns
+/// caching off for that read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheTtls {
+    pub event_details: i64,
+    pub golfers: i64,
+    pub player_step_factors: i64,
+    /// Used when no [`CachedStorage::with_scores_ttl`] policy is set.
+    pub scores: i64,
+    /// Only archives that exist are cached; a miss is asked again every time.
+    pub archives: i64,
+}
+
+impl Default for CacheTtls {
+    /// Settings and picks rarely change mid-event; scores are kept about as long as one
+    /// page load takes to ask for them again. Archives never change.
+    fn default() -> Self {
+        Self {
+            event_details: 60,
+            golfers: 60,
+            player_step_factors: 60,
+            scores: 30,
+            archives: 3600,
+        }
+    }
+}
+
+struct CacheEntry<T> {
+    value: T,
+    inserted_at: i64,
+    ttl_seconds: i64,
+}
+
+impl<T> CacheEntry<T> {
+    fn remaining(&self, now: i64) -> Option<i64> {
+        let remaining = self.ttl_seconds - (now - self.inserted_at);
+        (remaining >= 0).then_some(remaining)
+    }
+}
+
+/// Cached values by event id.
+type Entries<T> = HashMap<i32, CacheEntry<T>>;
+
+#[derive(Default)]
+struct CacheState {
+    event_details: Entries<EventDetails>,
+    golfers: Entries<Vec<Scores>>,
+    player_step_factors: Entries<HashMap<(i64, String), f32>>,
+    scores: Entries<ScoresAndLastRefresh>,
+    archives: Entries<EventArchive>,
+}
+
+fn get_fresh<T: Clone>(map: &mut Entries<T>, event_id: i32) -> Option<T> {
+    let now = Utc::now().timestamp();
+    match map.get(&event_id) {
+        Some(entry) if entry.remaining(now).is_some() => Some(entry.value.clone()),
+        Some(_) => {
+            map.remove(&event_id);
+            None
+        }
+        None => None,
+    }
+}
+
+fn put<T>(map: &mut Entries<T>, event_id: i32, value: T, ttl_seconds: i64) {
+    if ttl_seconds > 0 {
+        map.insert(
+            event_id,
+            CacheEntry {
+                value,
+                inserted_at: Utc::now().timestamp(),
+                ttl_seconds,
+            },
+        );
+    }
+}
+
+/// The entries behind a [`CachedStorage`]. Clones share entries, so one cache can outlive
+/// the storages built around it (the serverless flavor builds a storage per request).
+#[derive(Clone, Default)]
+pub struct StorageCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl StorageCache {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop everything cached for the event.
+    pub fn invalidate_event(&self, event_id: i32) {
+        let mut state = self.state();
+        state.event_details.remove(&event_id);
+        state.golfers.remove(&event_id);
+        state.player_step_factors.remove(&event_id);
+        state.scores.remove(&event_id);
+        state.archives.remove(&event_id);
+    }
+
+    /// Drop the event's cached scores.
+    pub fn invalidate_scores(&self, event_id: i32) {
+        self.state().scores.remove(&event_id);
+    }
+
+    /// Seconds left on the event's cached scores, or `None` if none are cached.
+    #[must_use]
+    pub fn scores_remaining_ttl(&self, event_id: i32) -> Option<i64> {
+        let now = Utc::now().timestamp();
+        self.state()
+            .scores
+            .get(&event_id)
+            .and_then(|entry| entry.remaining(now))
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Wraps any [`Storage`] so repeated reads of event details, picks, step factors, scores and
+/// archives are served from memory until their TTL runs out.
+///
+/// `store_scores`, `mark_event_completed` and the [`AdminStorage`] writes invalidate what
+/// they change. Writes made around the wrapper (directly on the inner storage, or by another
+/// process) are only seen once the entries expire, unless the shared [`StorageCache`] is
+/// invalidated too. Event lists, freshness checks and score changes are never cached.
+pub struct CachedStorage<S> {
+    inner: S,
+    cache: StorageCache,
+    ttls: CacheTtls,
+    scores_ttl: Option<fn(Option<&EventDetails>) -> i64>,
+}
+
+impl<S: Storage> CachedStorage<S> {
+    #[must_use]
+    pub fn new(inner: S) -> Self {
+        Self::with_cache(inner, StorageCache::new())
+    }
+
+    /// Wrap `inner` around an existing, possibly shared, cache.
+    #[must_use]
+    pub fn with_cache(inner: S, cache: StorageCache) -> Self {
+        Self {
+            inner,
+            cache,
+            ttls: CacheTtls::default(),
+            scores_ttl: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ttls(mut self, ttls: CacheTtls) -> Self {
+        self.ttls = ttls;
+        self
+    }
+
+    /// Decide the scores TTL per event from its details (`None` if they can't be read),
+    /// for example to keep a finished event's scores for good.
+    #[must_use]
+    pub fn with_scores_ttl(mut self, policy: fn(Option<&EventDetails>) -> i64) -> Self {
+        self.scores_ttl = Some(policy);
+        self
+    }
+
+    #[must_use]
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    #[must_use]
+    pub fn cache(&self) -> &StorageCache {
+        &self.cache
+    }
+
+    async fn scores_ttl_for_event(&self, event_id: i32) -> i64 {
+        match self.scores_ttl {
+            Some(policy) => policy(self.get_event_details(event_id).await.ok().as_ref()),
+            None => self.ttls.scores,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<S: Storage> Storage for CachedStorage<S> {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        self.inner.list_events().await
+    }
+
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
+        if let Some(details) = get_fresh(&mut self.cache.state().event_details, event_id) {
+            return Ok(details);
+        }
+        let details = self.inner.get_event_details(event_id).await?;
+        put(
+            &mut self.cache.state().event_details,
+            event_id,
+            details.clone(),
+            self.ttls.event_details,
+        );
+        Ok(details)
+    }
+
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError> {
+        if let Some(golfers) = get_fresh(&mut self.cache.state().golfers, event_id) {
+            return Ok(golfers);
+        }
+        let golfers = self.inner.get_golfers_for_event(event_id).await?;
+        put(
+            &mut self.cache.state().golfers,
+            event_id,
+            golfers.clone(),
+            self.ttls.golfers,
+        );
+        Ok(golfers)
+    }
+
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError> {
+        if let Some(factors) = get_fresh(&mut self.cache.state().player_step_factors, event_id) {
+            return Ok(factors);
+        }
+        let factors = self.inner.get_player_step_factors(event_id).await?;
+        put(
+            &mut self.cache.state().player_step_factors,
+            event_id,
+            factors.clone(),
+            self.ttls.player_step_factors,
+        );
+        Ok(factors)
+    }
+
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError> {
+        if let Some(mut scores) = get_fresh(&mut self.cache.state().scores, event_id) {
+            scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
+                RefreshSource::Espn
+            } else {
+                RefreshSource::Memory
+            };
+            return Ok(scores);
+        }
+        let scores = self.inner.get_scores(event_id, source).await?;
+        let ttl_seconds = self.scores_ttl_for_event(event_id).await;
+        put(
+            &mut self.cache.state().scores,
+            event_id,
+            scores.clone(),
+            ttl_seconds,
+        );
+        Ok(scores)
+    }
+
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
+        let result = self.inner.store_scores(event_id, scores).await;
+        self.cache.invalidate_scores(event_id);
+        result
+    }
+
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError> {
+        self.inner
+            .event_and_scores_already_in_db(event_id, max_age_seconds)
+            .await
+    }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let result = self.inner.mark_event_completed(event_id, end_date).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        self.inner.store_score_changes(event_id, changes).await
+    }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        self.inner.get_recent_score_changes(event_id).await
+    }
+
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        if let Some(archive) = get_fresh(&mut self.cache.state().archives, event_id) {
+            return Ok(Some(archive));
+        }
+        let archive = self.inner.get_event_archive(event_id).await?;
+        if let Some(archive) = &archive {
+            put(
+                &mut self.cache.state().archives,
+                event_id,
+                archive.clone(),
+                self.ttls.archives,
+            );
+        }
+        Ok(archive)
+    }
+
+    async fn list_score_snapshots(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        self.inner.list_score_snapshots(event_id).await
+    }
+
+    async fn get_score_snapshot(
+        &self,
+        event_id: i32,
+        taken_at: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        self.inner.get_score_snapshot(event_id, taken_at).await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<S: AdminStorage> AdminStorage for CachedStorage<S> {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        let result = self.inner.create_event(event_id, config).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        let result = self.inner.update_event(event_id, config).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        let result = self.inner.delete_event(event_id).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        // A rename can show up in any event's picks.
+        let result = self.inner.upsert_golfers(golfers).await;
+        self.cache.state().golfers.clear();
+        result
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        let result = self.inner.set_assignments(event_id, assignments).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        let result = self.inner.set_player_step_factors(event_id, factors).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        let result = self.inner.archive_event(event_id, archive, policy).await;
+        self.cache.invalidate_event(event_id);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{InMemoryStorage, StorageOperation};
+    use serde_json::json;
+
+    const EVENT_ID: i32 = 401_580_351;
+
+    fn storage() -> CachedStorage<InMemoryStorage> {
+        let prefill = json!([{
+            "event": EVENT_ID,
+            "year": 2024,
+            "name": "PGA Championship",
+            "score_view_step_factor": 3.0,
+            "data_to_fill_if_event_and_year_missing": [{
+                "bettors": ["Alice"],
+                "golfers": [{ "name": "Rory McIlroy", "espn_id": 3470 }],
+                "event_user_player": [{ "bettor": "Alice", "golfer_espn_id": 3470 }]
+            }]
+        }]);
+        CachedStorage::new(InMemoryStorage::from_prefill_json(&prefill).unwrap())
+    }
+
+    #[tokio::test]
+    async fn reads_are_served_from_cache_until_invalidated() {
+        let storage = storage();
+        storage.get_event_details(EVENT_ID).await.unwrap();
+        storage.get_golfers_for_event(EVENT_ID).await.unwrap();
+
+        storage
+            .inner()
+            .fail_on(StorageOperation::GetEventDetails, "backend down");
+        storage
+            .inner()
+            .fail_on(StorageOperation::GetGolfersForEvent, "backend down");
+        assert!(storage.get_event_details(EVENT_ID).await.is_ok());
+        assert_eq!(
+            storage.get_golfers_for_event(EVENT_ID).await.unwrap().len(),
+            1
+        );
+
+        storage.cache().invalidate_event(EVENT_ID);
+        assert!(storage.get_event_details(EVENT_ID).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn store_scores_invalidates_cached_scores() {
+        let storage = storage();
+        let golfers = storage.get_golfers_for_event(EVENT_ID).await.unwrap();
+        storage.store_scores(EVENT_ID, &golfers).await.unwrap();
+        assert_eq!(storage.cache().scores_remaining_ttl(EVENT_ID), None);
+
+        storage
+            .get_scores(EVENT_ID, RefreshSource::Db)
+            .await
+            .unwrap();
+        storage
+            .inner()
+            .fail_on(StorageOperation::GetScores, "backend down");
+        let cached = storage
+            .get_scores(EVENT_ID, RefreshSource::Espn)
+            .await
+            .unwrap();
+        assert!(matches!(cached.last_refresh_source, RefreshSource::Espn));
+        assert!(storage.cache().scores_remaining_ttl(EVENT_ID).is_some());
+
+        storage.store_scores(EVENT_ID, &golfers).await.unwrap();
+        assert_eq!(storage.cache().scores_remaining_ttl(EVENT_ID), None);
+        assert!(
+            storage
+                .get_scores(EVENT_ID, RefreshSource::Db)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn ttls_and_policies_control_what_is_kept() {
+        let storage = storage()
+            .with_ttls(CacheTtls {
+                event_details: 0,
+                ..CacheTtls::default()
+            })
+            .with_scores_ttl(|details| match details {
+                Some(details) if details.completed => 3600,
+                _ => 0,
+            });
+        storage.get_event_details(EVENT_ID).await.unwrap();
+        storage
+            .inner()
+            .fail_on(StorageOperation::GetEventDetails, "backend down");
+        assert!(storage.get_event_details(EVENT_ID).await.is_err());
+        storage
+            .inner()
+            .clear_failure(StorageOperation::GetEventDetails);
+
+        let golfers = storage.get_golfers_for_event(EVENT_ID).await.unwrap();
+        storage.store_scores(EVENT_ID, &golfers).await.unwrap();
+        storage
+            .get_scores(EVENT_ID, RefreshSource::Db)
+            .await
+            .unwrap();
+        assert_eq!(storage.cache().scores_remaining_ttl(EVENT_ID), None);
+
+        storage.mark_event_completed(EVENT_ID, None).await.unwrap();
+        storage
+            .get_scores(EVENT_ID, RefreshSource::Db)
+            .await
+            .unwrap();
+        assert!(storage.cache().scores_remaining_ttl(EVENT_ID) > Some(3000));
+    }
+}
@@ -0,0 +1,128 @@
+//! Archiving finished events.
+//!
+//! A live event is spread over many rows or keys that are rewritten on every refresh. Once
+//! it is over, [`archive_completed_event`] freezes it into one `EventArchive`, lets the
+//! backend drop the live copies that document replaces, and thins the score history. The
+//! backends then answer reads of the event from the archive.
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use super::archive::rekey_scores;
+use super::{AdminStorage, StorageError, export_event};
+
+/// How much score history an archived event keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CompactionPolicy {
+    /// Keep at most one snapshot per this many seconds, plus the first and the last.
+    /// Zero or less keeps every snapshot.
+    pub history_interval_seconds: i64,
+}
+
+impl Default for CompactionPolicy {
+    /// Hourly is enough to replay how a finished event unfolded.
+    fn default() -> Self {
+        Self {
+            history_interval_seconds: 3600,
+        }
+    }
+}
+
+impl CompactionPolicy {
+    /// The snapshots to delete from `sorted` (oldest first).
+    ///
+    /// Walking forward from the first, a snapshot is kept if it was taken at least
+    /// `history_interval_seconds` after the last one kept. The newest is always kept.
+    #[must_use]
+    pub fn history_to_prune(&self, sorted: &[NaiveDateTime]) -> Vec<NaiveDateTime> {
+        if self.history_interval_seconds <= 0 {
+            return Vec::new();
+        }
+        let Some((_newest, older)) = sorted.split_last() else {
+            return Vec::new();
+        };
+        let mut prune = Vec::new();
+        let mut last_kept: Option<NaiveDateTime> = None;
+        for taken_at in older {
+            let keep = last_kept.is_none_or(|kept| {
+                (*taken_at - kept).num_seconds() >= self.history_interval_seconds
+            });
+            if keep {
+                last_kept = Some(*taken_at);
+            } else {
+                prune.push(*taken_at);
+            }
+        }
+        prune
+    }
+}
+
+/// What archiving an event changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CompactionReport {
+    pub event_id: i32,
+    /// Live rows or keys the archive replaced.
+    pub records_removed: usize,
+    pub history_kept: usize,
+    pub history_pruned: usize,
+}
+
+/// Freeze a completed event into its archive and compact what it replaces.
+///
+/// The archive's scores are re-keyed to the eup ids its own picks get, so the document
+/// stands on its own whichever backend reads it back.
+///
+/// # Errors
+/// Returns an error if the event doesn't exist, isn't completed, is already archived or has
+/// no stored scores, or if a read or write fails.
+pub async fn archive_completed_event(
+    storage: &dyn AdminStorage,
+    event_id: i32,
+    policy: &CompactionPolicy,
+) -> Result<CompactionReport, StorageError> {
+    let details = storage.get_event_details(event_id).await?;
+    if !details.completed {
+        return Err(StorageError::new(format!(
+            "event {event_id} is not completed"
+        )));
+    }
+    if storage.get_event_archive(event_id).await?.is_some() {
+        return Err(StorageError::new(format!(
+            "event {event_id} is already archived"
+        )));
+    }
+
+    let mut archive = export_event(storage, event_id).await?;
+    let mut scores = archive.scores.take().ok_or_else(|| {
+        StorageError::new(format!("event {event_id} has no stored scores to archive"))
+    })?;
+    scores.score_struct = rekey_scores(event_id, &scores.score_struct, &archive.picks()?)?;
+    archive.scores = Some(scores);
+    archive.validate()?;
+
+    storage.archive_event(event_id, &archive, policy).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2025-07-18T12:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+            + chrono::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn history_thins_to_one_per_interval_keeping_both_ends() {
+        let policy = CompactionPolicy::default();
+        let sorted: Vec<NaiveDateTime> = [0, 5, 30, 60, 65, 130, 135].map(at).to_vec();
+        assert_eq!(policy.history_to_prune(&sorted), [5, 30, 65].map(at));
+
+        assert!(policy.history_to_prune(&[]).is_empty());
+        assert!(policy.history_to_prune(&[at(0)]).is_empty());
+        let keep_all = CompactionPolicy {
+            history_interval_seconds: 0,
+        };
+        assert!(keep_all.history_to_prune(&sorted).is_empty());
+    }
+}
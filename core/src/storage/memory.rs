@@ -0,0 +1,687 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+use super::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    EventDetails, GolferRecord, PlayerStepFactor, Storage, StorageError, apply_player_step_factors,
+    picks_from_assignments,
+};
+use crate::model::{
+    PrefillEvent, RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh, Statistic,
+    parse_prefill_events,
+};
+use crate::score::merge_recent_score_changes;
+
+/// Names each `Storage` and `AdminStorage` method so failures can be injected per operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageOperation {
+    ListEvents,
+    GetEventDetails,
+    GetGolfersForEvent,
+    GetPlayerStepFactors,
+    GetScores,
+    StoreScores,
+    EventAndScoresAlreadyInDb,
+    MarkEventCompleted,
+    StoreScoreChanges,
+    GetRecentScoreChanges,
+    GetEventArchive,
+    CreateEvent,
+    UpdateEvent,
+    DeleteEvent,
+    UpsertGolfers,
+    SetAssignments,
+    SetPlayerStepFactors,
+    ArchiveEvent,
+}
+
+/// A [`Storage`] kept entirely in process memory.
+///
+/// Seed it with the same JSON `db_prefill` reads, then drive core flows without a database.
+/// Failures can be injected per operation and a fixed latency added to every call.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    state: Mutex<MemoryState>,
+    failures: Mutex<HashMap<StorageOperation, String>>,
+    latency: Option<Duration>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    events: HashMap<i32, EventDetails>,
+    golfer_names: HashMap<i64, String>,
+    picks: HashMap<i32, Vec<Pick>>,
+    scores: HashMap<i32, ScoresAndLastRefresh>,
+    score_changes: HashMap<i32, Vec<ScoreChange>>,
+    archives: HashMap<i32, EventArchive>,
+    next_eup_id: i64,
+}
+
+struct Pick {
+    eup_id: i64,
+    golfer_espn_id: i64,
+    bettor_name: String,
+    score_view_step_factor: Option<f32>,
+}
+
+impl InMemoryStorage {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a storage seeded from a `--db-populate-json` payload.
+    ///
+    /// # Errors
+    /// Returns an error if the payload is malformed or a pick names an unknown golfer.
+    pub fn from_prefill_json(json: &serde_json::Value) -> Result<Self, StorageError> {
+        let storage = Self::new();
+        storage.seed_prefill_json(json)?;
+        Ok(storage)
+    }
+
+    /// Add events from a `--db-populate-json` payload, returning how many were added.
+    ///
+    /// Like `db_prefill`, events that already exist are skipped and eup ids are assigned in
+    /// payload order.
+    ///
+    /// # Errors
+    /// Returns an error if the payload is malformed or a pick names an unknown golfer.
+    pub fn seed_prefill_json(&self, json: &serde_json::Value) -> Result<usize, StorageError> {
+        let events = parse_prefill_events(json)?;
+        let mut state = self.state();
+        let mut added = 0;
+        for event in &events {
+            let event_id = i32::try_from(event.event)
+                .map_err(|_| StorageError::new(format!("event id {} out of range", event.event)))?;
+            if state.events.contains_key(&event_id) {
+                continue;
+            }
+            state.seed_event(event_id, event)?;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Delay every call by `latency` before it runs.
+    #[must_use]
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Make `operation` return a `StorageError` with `message` until cleared.
+    pub fn fail_on(&self, operation: StorageOperation, message: impl Into<String>) {
+        self.failures().insert(operation, message.into());
+    }
+
+    pub fn clear_failure(&self, operation: StorageOperation) {
+        self.failures().remove(&operation);
+    }
+
+    /// Backdate (or move forward) the stored scores' refresh time.
+    ///
+    /// # Errors
+    /// Returns an error if no scores are stored for the event.
+    pub fn set_last_refresh(
+        &self,
+        event_id: i32,
+        last_refresh: NaiveDateTime,
+    ) -> Result<(), StorageError> {
+        let mut state = self.state();
+        let scores = state
+            .scores
+            .get_mut(&event_id)
+            .ok_or_else(|| StorageError::new("scores not found"))?;
+        scores.last_refresh = last_refresh;
+        Ok(())
+    }
+
+    async fn enter(&self, operation: StorageOperation) -> Result<(), StorageError> {
+        if let Some(latency) = self.latency {
+            sleep(latency).await;
+        }
+        match self.failures().get(&operation) {
+            Some(message) => Err(StorageError::new(message.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn failures(&self) -> MutexGuard<'_, HashMap<StorageOperation, String>> {
+        self.failures.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl MemoryState {
+    fn seed_event(&mut self, event_id: i32, event: &PrefillEvent) -> Result<(), StorageError> {
+        let mut picks = Vec::new();
+        for data in &event.data_to_fill_if_event_and_year_missing {
+            for golfer in &data.golfers {
+                self.golfer_names
+                    .entry(golfer.espn_id)
+                    .or_insert_with(|| golfer.name.clone());
+            }
+            for eup in &data.event_user_player {
+                if !self.golfer_names.contains_key(&eup.golfer_espn_id) {
+                    return Err(StorageError::new(format!(
+                        "event {event_id}: unknown golfer espn_id {}",
+                        eup.golfer_espn_id
+                    )));
+                }
+                self.next_eup_id += 1;
+                #[allow(clippy::cast_possible_truncation)]
+                picks.push(Pick {
+                    eup_id: self.next_eup_id,
+                    golfer_espn_id: eup.golfer_espn_id,
+                    bettor_name: eup.bettor.clone(),
+                    score_view_step_factor: eup.score_view_step_factor.map(|v| v as f32),
+                });
+            }
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        self.events.insert(
+            event_id,
+            EventDetails {
+                event_name: event.name.clone(),
+                score_view_step_factor: event.score_view_step_factor as f32,
+                refresh_from_espn: 1,
+                start_date: event.start_date.clone(),
+                end_date: event.end_date.clone(),
+                completed: event.completed,
+            },
+        );
+        self.picks.insert(event_id, picks);
+        Ok(())
+    }
+
+    /// Picks as `Scores` with empty statistics, grouped the way the SQL backend groups them:
+    /// each bettor's picks are numbered 1.. in eup order, then sorted by (group, eup id).
+    fn golfers_for_event(&self, event_id: i32) -> Vec<Scores> {
+        let Some(picks) = self.picks.get(&event_id) else {
+            return Vec::new();
+        };
+        let mut per_bettor: HashMap<&str, i64> = HashMap::new();
+        let mut scores: Vec<Scores> = picks
+            .iter()
+            .map(|pick| {
+                let group = per_bettor.entry(pick.bettor_name.as_str()).or_insert(0);
+                *group += 1;
+                Scores {
+                    eup_id: pick.eup_id,
+                    espn_id: pick.golfer_espn_id,
+                    golfer_name: self
+                        .golfer_names
+                        .get(&pick.golfer_espn_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    bettor_name: pick.bettor_name.clone(),
+                    detailed_statistics: Statistic {
+                        eup_id: pick.eup_id,
+                        rounds: Vec::new(),
+                        round_scores: Vec::new(),
+                        tee_times: Vec::new(),
+                        holes_completed_by_round: Vec::new(),
+                        line_scores: Vec::new(),
+                        total_score: 0,
+                    },
+                    group: *group,
+                    score_view_step_factor: None,
+                }
+            })
+            .collect();
+        scores.sort_by_key(|score| (score.group, score.eup_id));
+        scores
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Storage for InMemoryStorage {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        self.enter(StorageOperation::ListEvents).await?;
+        let mut ids: Vec<i32> = self.state().events.keys().copied().collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
+        self.enter(StorageOperation::GetEventDetails).await?;
+        self.state()
+            .events
+            .get(&event_id)
+            .cloned()
+            .ok_or_else(|| StorageError::new("event details not found"))
+    }
+
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError> {
+        self.enter(StorageOperation::GetGolfersForEvent).await?;
+        let state = self.state();
+        match state.archives.get(&event_id) {
+            Some(archive) => archive.picks(),
+            None => Ok(state.golfers_for_event(event_id)),
+        }
+    }
+
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError> {
+        self.enter(StorageOperation::GetPlayerStepFactors).await?;
+        let state = self.state();
+        if let Some(archive) = state.archives.get(&event_id) {
+            return Ok(archive.player_step_factors());
+        }
+        Ok(state
+            .picks
+            .get(&event_id)
+            .map(|picks| {
+                picks
+                    .iter()
+                    .filter_map(|pick| {
+                        let step = pick.score_view_step_factor?;
+                        Some(((pick.golfer_espn_id, pick.bettor_name.clone()), step))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError> {
+        self.enter(StorageOperation::GetScores).await?;
+        let state = self.state();
+        if let Some(scores) = state
+            .archives
+            .get(&event_id)
+            .and_then(EventArchive::archived_scores)
+        {
+            return Ok(scores);
+        }
+        let mut scores = state
+            .scores
+            .get(&event_id)
+            .cloned()
+            .ok_or_else(|| StorageError::new("scores not found"))?;
+        scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
+            RefreshSource::Espn
+        } else {
+            RefreshSource::Memory
+        };
+        Ok(scores)
+    }
+
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
+        self.enter(StorageOperation::StoreScores).await?;
+        self.state().scores.insert(
+            event_id,
+            ScoresAndLastRefresh {
+                score_struct: scores.to_vec(),
+                last_refresh: Utc::now().naive_utc(),
+                last_refresh_source: RefreshSource::Espn,
+            },
+        );
+        Ok(())
+    }
+
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError> {
+        self.enter(StorageOperation::EventAndScoresAlreadyInDb)
+            .await?;
+        if max_age_seconds <= 0 {
+            return Ok(false);
+        }
+        let state = self.state();
+        if !state.events.contains_key(&event_id) {
+            return Ok(false);
+        }
+        let Some(scores) = state.scores.get(&event_id) else {
+            return Ok(false);
+        };
+        let age = Utc::now()
+            .naive_utc()
+            .signed_duration_since(scores.last_refresh);
+        Ok(age.num_seconds() <= max_age_seconds)
+    }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        self.enter(StorageOperation::MarkEventCompleted).await?;
+        let mut state = self.state();
+        let details = state
+            .events
+            .get_mut(&event_id)
+            .ok_or_else(|| StorageError::new("event details not found"))?;
+        details.completed = true;
+        if details.end_date.is_none() {
+            details.end_date = end_date.map(ToString::to_string);
+        }
+        Ok(())
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        self.enter(StorageOperation::StoreScoreChanges).await?;
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut state = self.state();
+        let existing = state.score_changes.remove(&event_id).unwrap_or_default();
+        state
+            .score_changes
+            .insert(event_id, merge_recent_score_changes(existing, changes));
+        Ok(())
+    }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        self.enter(StorageOperation::GetRecentScoreChanges).await?;
+        let state = self.state();
+        if let Some(archive) = state.archives.get(&event_id) {
+            return Ok(archive.score_changes.clone());
+        }
+        Ok(state
+            .score_changes
+            .get(&event_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        self.enter(StorageOperation::GetEventArchive).await?;
+        Ok(self.state().archives.get(&event_id).cloned())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl AdminStorage for InMemoryStorage {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        self.enter(StorageOperation::CreateEvent).await?;
+        let mut state = self.state();
+        if state.events.contains_key(&event_id) {
+            return Err(StorageError::new(format!(
+                "event {event_id} already exists"
+            )));
+        }
+        state.events.insert(event_id, event_details(config));
+        state.picks.insert(event_id, Vec::new());
+        Ok(())
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        self.enter(StorageOperation::UpdateEvent).await?;
+        let mut state = self.state();
+        let details = state
+            .events
+            .get_mut(&event_id)
+            .ok_or_else(|| StorageError::new("event details not found"))?;
+        *details = event_details(config);
+        Ok(())
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        self.enter(StorageOperation::DeleteEvent).await?;
+        let mut state = self.state();
+        state.picks.remove(&event_id);
+        state.scores.remove(&event_id);
+        state.score_changes.remove(&event_id);
+        state.archives.remove(&event_id);
+        Ok(state.events.remove(&event_id).is_some())
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        self.enter(StorageOperation::UpsertGolfers).await?;
+        let mut state = self.state();
+        for golfer in golfers {
+            state
+                .golfer_names
+                .insert(golfer.espn_id, golfer.name.clone());
+        }
+        Ok(())
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        self.enter(StorageOperation::SetAssignments).await?;
+        let mut state = self.state();
+        if !state.events.contains_key(&event_id) {
+            return Err(StorageError::new("event details not found"));
+        }
+        let picks = picks_from_assignments(event_id, assignments, &state.golfer_names)?
+            .into_iter()
+            .map(|pick| Pick {
+                eup_id: pick.eup_id,
+                golfer_espn_id: pick.espn_id,
+                bettor_name: pick.bettor_name,
+                score_view_step_factor: None,
+            })
+            .collect();
+        state.picks.insert(event_id, picks);
+        state.scores.remove(&event_id);
+        Ok(())
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        self.enter(StorageOperation::SetPlayerStepFactors).await?;
+        let mut state = self.state();
+        if !state.picks.contains_key(&event_id) {
+            return Err(StorageError::new("event details not found"));
+        }
+        let mut golfers = state.golfers_for_event(event_id);
+        apply_player_step_factors(event_id, &mut golfers, factors)?;
+        let updated: HashMap<i64, Option<f32>> = golfers
+            .iter()
+            .map(|golfer| (golfer.eup_id, golfer.score_view_step_factor))
+            .collect();
+        for pick in state.picks.get_mut(&event_id).into_iter().flatten() {
+            pick.score_view_step_factor = updated.get(&pick.eup_id).copied().flatten();
+        }
+        Ok(())
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        _policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        self.enter(StorageOperation::ArchiveEvent).await?;
+        let mut state = self.state();
+        if !state.events.contains_key(&event_id) {
+            return Err(StorageError::new("event details not found"));
+        }
+        // Nothing here keeps history, so there is none to thin.
+        let records_removed = [
+            state.picks.remove(&event_id).is_some(),
+            state.scores.remove(&event_id).is_some(),
+            state.score_changes.remove(&event_id).is_some(),
+        ]
+        .into_iter()
+        .filter(|removed| *removed)
+        .count();
+        state.archives.insert(event_id, archive.clone());
+        Ok(CompactionReport {
+            event_id,
+            records_removed,
+            ..CompactionReport::default()
+        })
+    }
+}
+
+fn event_details(config: &EventConfig) -> EventDetails {
+    EventDetails {
+        event_name: config.event_name.clone(),
+        score_view_step_factor: config.score_view_step_factor,
+        refresh_from_espn: config.refresh_from_espn,
+        start_date: config.start_date.clone(),
+        end_date: config.end_date.clone(),
+        completed: config.completed,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    let millis = duration.as_secs_f64() * 1000.0;
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &"setTimeout".into())
+            .ok()
+            .and_then(|f| wasm_bindgen::JsCast::dyn_into::<js_sys::Function>(f).ok());
+        match set_timeout {
+            Some(set_timeout) => {
+                let _ = set_timeout.call2(
+                    &wasm_bindgen::JsValue::NULL,
+                    &resolve,
+                    &wasm_bindgen::JsValue::from_f64(millis),
+                );
+            }
+            None => {
+                let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+            }
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn prefill() -> serde_json::Value {
+        json!([{
+            "event": 401_580_351,
+            "year": 2024,
+            "name": "PGA Championship",
+            "score_view_step_factor": 3.0,
+            "data_to_fill_if_event_and_year_missing": [{
+                "bettors": ["Alice", "Bob"],
+                "golfers": [
+                    { "name": "Rory McIlroy", "espn_id": 3470 },
+                    { "name": "Jon Rahm", "espn_id": 9780 },
+                    { "name": "Scottie Scheffler", "espn_id": 9478 }
+                ],
+                "event_user_player": [
+                    { "bettor": "Alice", "golfer_espn_id": 3470 },
+                    { "bettor": "Alice", "golfer_espn_id": 9780, "score_view_step_factor": 4.5 },
+                    { "bettor": "Bob", "golfer_espn_id": 9478 }
+                ]
+            }]
+        }])
+    }
+
+    #[tokio::test]
+    async fn seeds_from_prefill_json_like_sql() {
+        let storage = InMemoryStorage::from_prefill_json(&prefill()).unwrap();
+        assert_eq!(storage.seed_prefill_json(&prefill()).unwrap(), 0);
+
+        let details = storage.get_event_details(401_580_351).await.unwrap();
+        assert_eq!(details.event_name, "PGA Championship");
+        assert!(!details.completed);
+
+        let golfers = storage.get_golfers_for_event(401_580_351).await.unwrap();
+        let order: Vec<(i64, &str, &str)> = golfers
+            .iter()
+            .map(|s| (s.group, s.bettor_name.as_str(), s.golfer_name.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (1, "Alice", "Rory McIlroy"),
+                (1, "Bob", "Scottie Scheffler"),
+                (2, "Alice", "Jon Rahm"),
+            ]
+        );
+
+        let factors = storage.get_player_step_factors(401_580_351).await.unwrap();
+        assert_eq!(factors.get(&(9780, "Alice".to_string())), Some(&4.5));
+        assert_eq!(factors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn freshness_and_injected_failures() {
+        let storage = InMemoryStorage::from_prefill_json(&prefill()).unwrap();
+        assert!(
+            storage
+                .get_scores(401_580_351, RefreshSource::Db)
+                .await
+                .is_err()
+        );
+
+        let golfers = storage.get_golfers_for_event(401_580_351).await.unwrap();
+        storage.store_scores(401_580_351, &golfers).await.unwrap();
+        assert!(
+            storage
+                .event_and_scores_already_in_db(401_580_351, 300)
+                .await
+                .unwrap()
+        );
+        storage
+            .set_last_refresh(
+                401_580_351,
+                Utc::now().naive_utc() - chrono::Duration::minutes(10),
+            )
+            .unwrap();
+        assert!(
+            !storage
+                .event_and_scores_already_in_db(401_580_351, 300)
+                .await
+                .unwrap()
+        );
+
+        storage.fail_on(StorageOperation::GetScores, "boom");
+        let err = storage
+            .get_scores(401_580_351, RefreshSource::Db)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "boom");
+        storage.clear_failure(StorageOperation::GetScores);
+        let cached = storage
+            .get_scores(401_580_351, RefreshSource::Db)
+            .await
+            .unwrap();
+        assert!(matches!(cached.last_refresh_source, RefreshSource::Memory));
+    }
+
+    #[tokio::test]
+    async fn latency_delays_calls() {
+        let storage = InMemoryStorage::new().with_latency(Duration::from_millis(20));
+        let start = std::time::Instant::now();
+        assert!(storage.get_event_details(1).await.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+mod admin;
+mod archive;
+mod cached;
+mod compaction;
+mod memory;
+
+pub use admin::{
+    AdminStorage, Assignment, EventConfig, GolferRecord, PlayerStepFactor,
+    apply_player_step_factors, picks_from_assignments,
+};
+pub(crate) use archive::year_from_dates;
+pub use archive::{EVENT_ARCHIVE_VERSION, EventArchive, export_event, import_event};
+pub use cached::{CacheTtls, CachedStorage, StorageCache};
+pub use compaction::{CompactionPolicy, CompactionReport, archive_completed_event};
+pub use memory::{InMemoryStorage, StorageOperation};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDetails {
+    pub event_name: String,
+    pub score_view_step_factor: f32,
+    pub refresh_from_espn: i64,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageError {
+    message: String,
+}
+
+impl StorageError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for StorageError {}
+
+impl From<String> for StorageError {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for StorageError {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+// Every backend should pass `tests/tests/common/storage_conformance.rs`.
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Ids of every stored event, ascending.
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError>;
+    /// Fails if the event doesn't exist.
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError>;
+    /// Picks without statistics; empty if the event doesn't exist.
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError>;
+    /// Empty if the event doesn't exist.
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError>;
+    /// Scores as last stored. Fails if none were stored. `source` is reported back as-is
+    /// for `Espn`; otherwise the backend reports where it read from.
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError>;
+    /// Store scores and stamp the event's last refresh with the current time.
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError>;
+    /// True if scores were stored no more than `max_age_seconds` ago. Never true for
+    /// `max_age_seconds <= 0` or a missing event.
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError>;
+    /// Mark the event finished; `end_date` only fills in a missing end date. Fails if the
+    /// event doesn't exist.
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError>;
+    /// Persist changes detected between refreshes; backends keep only the most recent ones.
+    async fn store_score_changes(
+        &self,
+        _event_id: i32,
+        _changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+    /// Recently detected changes, newest first.
+    async fn get_recent_score_changes(
+        &self,
+        _event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        Ok(Vec::new())
+    }
+    /// The document the event was frozen into by [`AdminStorage::archive_event`], if any.
+    async fn get_event_archive(
+        &self,
+        _event_id: i32,
+    ) -> Result<Option<EventArchive>, StorageError> {
+        Ok(None)
+    }
+    /// When each of the event's score snapshots was taken, oldest first. Empty for backends
+    /// that keep no score history.
+    async fn list_score_snapshots(
+        &self,
+        _event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        Ok(Vec::new())
+    }
+    /// The scores as they stood in the snapshot taken at `taken_at`, one of
+    /// [`Storage::list_score_snapshots`]; `None` if there is no such snapshot.
+    async fn get_score_snapshot(
+        &self,
+        _event_id: i32,
+        _taken_at: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        Ok(None)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait(?Send)]
+pub trait Storage {
+    /// Ids of every stored event, ascending.
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError>;
+    /// Fails if the event doesn't exist.
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError>;
+    /// Picks without statistics; empty if the event doesn't exist.
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError>;
+    /// Empty if the event doesn't exist.
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError>;
+    /// Scores as last stored. Fails if none were stored. `source` is reported back as-is
+    /// for `Espn`; otherwise the backend reports where it read from.
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError>;
+    /// Store scores and stamp the event's last refresh with the current time.
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError>;
+    /// True if scores were stored no more than `max_age_seconds` ago. Never true for
+    /// `max_age_seconds <= 0` or a missing event.
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError>;
+    /// Mark the event finished; `end_date` only fills in a missing end date. Fails if the
+    /// event doesn't exist.
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError>;
+    /// Persist changes detected between refreshes; backends keep only the most recent ones.
+    async fn store_score_changes(
+        &self,
+        _event_id: i32,
+        _changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        Ok(())
+    }
+    /// Recently detected changes, newest first.
+    async fn get_recent_score_changes(
+        &self,
+        _event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        Ok(Vec::new())
+    }
+    /// The document the event was frozen into by [`AdminStorage::archive_event`], if any.
+    async fn get_event_archive(
+        &self,
+        _event_id: i32,
+    ) -> Result<Option<EventArchive>, StorageError> {
+        Ok(None)
+    }
+    /// When each of the event's score snapshots was taken, oldest first. Empty for backends
+    /// that keep no score history.
+    async fn list_score_snapshots(
+        &self,
+        _event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        Ok(Vec::new())
+    }
+    /// The scores as they stood in the snapshot taken at `taken_at`, one of
+    /// [`Storage::list_score_snapshots`]; `None` if there is no such snapshot.
+    async fn get_score_snapshot(
+        &self,
+        _event_id: i32,
+        _taken_at: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        Ok(None)
+    }
+}
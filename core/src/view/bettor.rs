@@ -0,0 +1,619 @@
+//! `/bettor`: one bettor's view of an event, or of a whole season.
+//!
+//! `?name=&event=&yr=` shows the bettor's golfers with their current round, thru, total and
+//! tee time, where the bettor stood after each round, and how far they are off the lead.
+//! `?name=&yr=` lists every event of that season the bettor played in.
+
+use chrono::{DateTime, Utc};
+use maud::{Markup, html};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::api::dto::{EventSummary, Standing};
+use crate::api::{round_summaries, standings_from_scores, summarize_event};
+use crate::error::CoreError;
+use crate::espn::EspnApiClient;
+use crate::model::{Scores, ScoresAndLastRefresh};
+use crate::score::{
+    EventPhase, ScoreContext, cache_max_age_for_event, group_by_bettor_golfer_round,
+    load_score_context,
+};
+use crate::storage::Storage;
+use crate::view::events::stored_scores;
+use crate::view::index::render_page;
+use crate::view::score::types::RefreshData;
+use crate::view::score::{render_line_score_tables, scores_and_last_refresh_to_line_score_tables};
+
+/// A parsed `/bettor` query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BettorRequest {
+    pub bettor_name: String,
+    pub year: i32,
+    /// `None` asks for the whole season.
+    pub event_id: Option<i32>,
+}
+
+/// Parse `name`, `yr` and the optional `event` of a `/bettor` query.
+///
+/// # Errors
+/// Returns an error if `name` or `yr` is missing, or `yr` or `event` isn't a number.
+pub fn parse_bettor_request<S: BuildHasher>(
+    query: &HashMap<String, String, S>,
+) -> Result<BettorRequest, CoreError> {
+    let bettor_name = query
+        .get("name")
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| CoreError::Other("name (bettor) parameter is required".into()))?;
+    let year = query
+        .get("yr")
+        .and_then(|s| s.trim().parse().ok())
+        .ok_or_else(|| CoreError::Other("yr (year) parameter is required".into()))?;
+    let event_id = match query.get("event").map(|event| event.trim()) {
+        None | Some("") => None,
+        Some(event) => Some(
+            event
+                .parse()
+                .map_err(|_| CoreError::Other("event parameter must be a number".into()))?,
+        ),
+    };
+    Ok(BettorRequest {
+        bettor_name,
+        year,
+        event_id,
+    })
+}
+
+/// One of the bettor's golfers as the dashboard shows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DashboardGolfer {
+    pub golfer_name: String,
+    pub golfer_espn_id: i64,
+    /// 1-based; `None` before the golfer's first round.
+    pub current_round: Option<usize>,
+    /// Holes played in the current round.
+    pub thru: usize,
+    /// The current round relative to par.
+    pub round_score: i32,
+    pub total_score: i32,
+    /// The current round's tee time, as ESPN's scoreboard shows it.
+    pub tee_time: Option<String>,
+    /// Relative to par, one entry per round started.
+    pub round_scores: Vec<i32>,
+}
+
+impl DashboardGolfer {
+    /// `F` once the round is over, the tee time before it starts, otherwise holes played.
+    #[must_use]
+    pub fn thru_label(&self) -> String {
+        match (self.current_round, self.thru) {
+            (None, _) => "-".to_string(),
+            (Some(_), 18) => "F".to_string(),
+            (Some(_), 0) => self.tee_time.clone().unwrap_or_else(|| "-".to_string()),
+            (Some(_), thru) => thru.to_string(),
+        }
+    }
+}
+
+/// Where the bettor stood once a round was in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundPosition {
+    /// 1-based.
+    pub round: i32,
+    /// Tied bettors share a rank.
+    pub rank: usize,
+    /// The bettor's total through this round.
+    pub cumulative: i32,
+    /// The best total through this round.
+    pub leader_cumulative: i32,
+}
+
+/// One bettor's view of one event.
+#[derive(Debug, Clone)]
+pub struct BettorDashboard {
+    pub event_id: i32,
+    pub year: i32,
+    pub event_name: String,
+    pub bettor_name: String,
+    pub standing: Standing,
+    /// Every bettor ranked first; may include this one.
+    pub leaders: Vec<Standing>,
+    /// The best bettor who isn't this one, to measure the lead by.
+    pub runner_up: Option<Standing>,
+    pub bettor_count: usize,
+    pub golfers: Vec<DashboardGolfer>,
+    pub positions: Vec<RoundPosition>,
+    pub refresh: RefreshData,
+    line_scores: ScoresAndLastRefresh,
+}
+
+impl BettorDashboard {
+    /// Strokes behind the leader, or ahead of the next bettor (negative) when leading.
+    #[must_use]
+    pub fn strokes_off_lead(&self) -> i32 {
+        match (self.standing.rank, &self.runner_up, self.leaders.first()) {
+            (1, Some(next), _) => self.standing.total_score - next.total_score,
+            (_, _, Some(leader)) => self.standing.total_score - leader.total_score,
+            _ => 0,
+        }
+    }
+}
+
+/// Build the dashboard for `bettor_name` from an event's scores, or `None` if they have no
+/// picks in it.
+#[must_use]
+pub fn bettor_dashboard(
+    event_id: i32,
+    year: i32,
+    event_name: &str,
+    bettor_name: &str,
+    context: &ScoreContext,
+) -> Option<BettorDashboard> {
+    let scores = &context.from_db_scores;
+    let standings = standings_from_scores(event_id, scores).standings;
+    let standing = standings
+        .iter()
+        .find(|standing| standing.bettor_name == bettor_name)?
+        .clone();
+    let leaders = standings
+        .iter()
+        .filter(|standing| standing.rank == 1)
+        .cloned()
+        .collect();
+    let runner_up = standings
+        .iter()
+        .find(|other| other.bettor_name != bettor_name)
+        .cloned();
+
+    let round_scores: HashMap<i64, Vec<i32>> = group_by_bettor_golfer_round(&scores.score_struct)
+        .detailed_scores
+        .into_iter()
+        .filter(|detail| detail.bettor_name == bettor_name)
+        .map(|detail| (detail.golfer_espn_id, detail.scores))
+        .collect();
+    let golfers = scores
+        .score_struct
+        .iter()
+        .filter(|golfer| golfer.bettor_name == bettor_name)
+        .map(|golfer| {
+            dashboard_golfer(
+                golfer,
+                round_scores
+                    .get(&golfer.espn_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    let mut line_scores = scores.clone();
+    line_scores
+        .score_struct
+        .retain(|golfer| golfer.bettor_name == bettor_name);
+
+    Some(BettorDashboard {
+        event_id,
+        year,
+        event_name: event_name.to_string(),
+        bettor_name: bettor_name.to_string(),
+        standing,
+        leaders,
+        runner_up,
+        bettor_count: standings.len(),
+        golfers,
+        positions: positions(event_id, bettor_name, scores),
+        refresh: RefreshData {
+            last_refresh: context.data.last_refresh.clone(),
+            last_refresh_source: context.data.last_refresh_source.clone(),
+        },
+        line_scores,
+    })
+}
+
+fn dashboard_golfer(golfer: &Scores, round_scores: Vec<i32>) -> DashboardGolfer {
+    let stats = &golfer.detailed_statistics;
+    let current = stats.rounds.last().map(|round| round.val);
+    let index = stats.rounds.len().checked_sub(1);
+    DashboardGolfer {
+        golfer_name: golfer.golfer_name.clone(),
+        golfer_espn_id: golfer.espn_id,
+        current_round: index.map(|index| index + 1),
+        thru: current.map_or(0, |round| {
+            stats
+                .line_scores
+                .iter()
+                .filter(|line| line.round == round)
+                .count()
+        }),
+        round_score: index
+            .and_then(|index| stats.round_scores.get(index))
+            .map_or(0, |score| score.val),
+        total_score: stats.total_score,
+        tee_time: index
+            .and_then(|index| stats.tee_times.get(index))
+            .map(|tee_time| tee_time.val.clone()),
+        round_scores,
+    }
+}
+
+/// The bettor's rank after each round, ranking every bettor by their running total.
+fn positions(
+    event_id: i32,
+    bettor_name: &str,
+    scores: &ScoresAndLastRefresh,
+) -> Vec<RoundPosition> {
+    round_summaries(event_id, scores)
+        .rounds
+        .into_iter()
+        .filter_map(|summary| {
+            let mine = summary
+                .bettors
+                .iter()
+                .find(|bettor| bettor.bettor_name == bettor_name)?
+                .cumulative;
+            let leader_cumulative = summary
+                .bettors
+                .iter()
+                .map(|bettor| bettor.cumulative)
+                .min()?;
+            let ahead = summary
+                .bettors
+                .iter()
+                .filter(|bettor| bettor.cumulative < mine)
+                .count();
+            Some(RoundPosition {
+                round: summary.round,
+                rank: ahead + 1,
+                cumulative: mine,
+                leader_cumulative,
+            })
+        })
+        .collect()
+}
+
+/// One event of a bettor's season.
+#[derive(Debug, Clone)]
+pub struct SeasonEvent {
+    pub event: EventSummary,
+    pub phase: EventPhase,
+    pub standing: Standing,
+    pub bettor_count: usize,
+    /// Strokes behind the best total; zero for a leader.
+    pub behind_leader: i32,
+}
+
+/// A bettor's events in one season, in the order they were played.
+#[derive(Debug, Clone)]
+pub struct BettorSeason {
+    pub bettor_name: String,
+    pub year: i32,
+    pub events: Vec<SeasonEvent>,
+}
+
+impl BettorSeason {
+    /// Completed events the bettor finished first in, ties included.
+    #[must_use]
+    pub fn wins(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| event.phase == EventPhase::Completed && event.standing.rank == 1)
+            .count()
+    }
+}
+
+/// Every event of `year` the bettor has picks in, from stored scores only.
+///
+/// # Errors
+/// Returns an error if the events can't be listed.
+pub async fn load_bettor_season(
+    storage: &dyn Storage,
+    bettor_name: &str,
+    year: i32,
+    now: DateTime<Utc>,
+) -> Result<BettorSeason, CoreError> {
+    let mut events = Vec::new();
+    for event_id in storage.list_events().await? {
+        let Ok(details) = storage.get_event_details(event_id).await else {
+            continue;
+        };
+        let phase = EventPhase::of(&details, now);
+        let event = summarize_event(event_id, details);
+        if event.year != year {
+            continue;
+        }
+        let Some(scores) = stored_scores(storage, event_id).await else {
+            continue;
+        };
+        let standings = standings_from_scores(event_id, &scores).standings;
+        let Some(standing) = standings
+            .iter()
+            .find(|standing| standing.bettor_name == bettor_name)
+        else {
+            continue;
+        };
+        let best = standings.first().map_or(0, |leader| leader.total_score);
+        events.push(SeasonEvent {
+            behind_leader: standing.total_score - best,
+            standing: standing.clone(),
+            bettor_count: standings.len(),
+            event,
+            phase,
+        });
+    }
+    events.sort_by(|a, b| {
+        a.event
+            .start_date
+            .cmp(&b.event.start_date)
+            .then_with(|| a.event.name.cmp(&b.event.name))
+    });
+    Ok(BettorSeason {
+        bettor_name: bettor_name.to_string(),
+        year,
+        events,
+    })
+}
+
+/// Load and render the page a `/bettor` query asks for. Event dashboards refresh scores by
+/// the same cache rules as `/scores`; seasons only read stored scores.
+///
+/// # Errors
+/// Returns `NotFound` if the event doesn't exist or the bettor has no picks in it, and other
+/// errors if scores can't be loaded.
+pub async fn load_bettor_page(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    request: &BettorRequest,
+    now: DateTime<Utc>,
+) -> Result<Markup, CoreError> {
+    let Some(event_id) = request.event_id else {
+        let season = load_bettor_season(storage, &request.bettor_name, request.year, now).await?;
+        return Ok(render_bettor_season_page(&season));
+    };
+    let details = storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| CoreError::NotFound(format!("event {event_id} not found")))?;
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context = load_score_context(
+        storage,
+        espn_api,
+        event_id,
+        request.year,
+        true,
+        cache_max_age,
+    )
+    .await?;
+    let dashboard = bettor_dashboard(
+        event_id,
+        request.year,
+        &details.event_name,
+        &request.bettor_name,
+        &context,
+    )
+    .ok_or_else(|| {
+        CoreError::NotFound(format!(
+            "bettor {} has no picks in event {event_id}",
+            request.bettor_name
+        ))
+    })?;
+    Ok(render_bettor_page(&dashboard))
+}
+
+/// Relative to par, as scoreboards show it: `-3`, `E`, `+2`.
+pub(crate) fn to_par(score: i32) -> String {
+    match score {
+        0 => "E".to_string(),
+        score if score > 0 => format!("+{score}"),
+        score => score.to_string(),
+    }
+}
+
+/// Percent-encode a query string value; bettor names may hold spaces and `&`.
+pub(crate) fn query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn ordinal(rank: usize) -> String {
+    let suffix = match (rank % 10, rank % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{rank}{suffix}")
+}
+
+fn lead_summary(dashboard: &BettorDashboard) -> String {
+    let off = dashboard.strokes_off_lead();
+    let strokes = |n: i32| if n.abs() == 1 { "stroke" } else { "strokes" };
+    if dashboard.standing.rank == 1 {
+        let tied_with: Vec<&str> = dashboard
+            .leaders
+            .iter()
+            .map(|leader| leader.bettor_name.as_str())
+            .filter(|name| *name != dashboard.bettor_name)
+            .collect();
+        if !tied_with.is_empty() {
+            format!("Tied for the lead with {}", tied_with.join(" & "))
+        } else if let Some(next) = &dashboard.runner_up {
+            format!("Leading {} by {} {}", next.bettor_name, -off, strokes(off))
+        } else {
+            "Leading".to_string()
+        }
+    } else {
+        let leaders: Vec<&str> = dashboard
+            .leaders
+            .iter()
+            .map(|leader| leader.bettor_name.as_str())
+            .collect();
+        format!("{} {} behind {}", off, strokes(off), leaders.join(" & "))
+    }
+}
+
+/// The event dashboard, laid out as stacked cards so it reads on a phone.
+#[must_use]
+pub fn render_bettor_dashboard(dashboard: &BettorDashboard) -> Markup {
+    let scores_link = format!("?event={}&yr={}", dashboard.event_id, dashboard.year);
+    let season_link = format!(
+        "bettor?name={}&yr={}",
+        query_value(&dashboard.bettor_name),
+        dashboard.year
+    );
+    let calendar_link = format!(
+        "tee-times.ics?event={}&yr={}&bettor={}",
+        dashboard.event_id,
+        dashboard.year,
+        query_value(&dashboard.bettor_name)
+    );
+    html! {
+        section class="panel bettor-dashboard" {
+            p class="bettor-links" {
+                a href=(scores_link) { (dashboard.event_name) }
+                " · "
+                a href=(season_link) { (dashboard.year) " season" }
+                " · "
+                a href=(calendar_link) { "Tee times (.ics)" }
+            }
+            div class="bettor-standing" {
+                span class="bettor-rank" { (ordinal(dashboard.standing.rank)) }
+                " of " (dashboard.bettor_count) " at "
+                span class="bettor-total" { (to_par(dashboard.standing.total_score)) }
+                p class="bettor-lead" { (lead_summary(dashboard)) }
+            }
+
+            h2 { "Golfers" }
+            div class="bettor-golfers" {
+                @for golfer in &dashboard.golfers {
+                    div class="bettor-golfer" data-espn-id=(golfer.golfer_espn_id) {
+                        div class="bettor-golfer-name" {
+                            a href=(format!("golfer?espn_id={}&event={}&yr={}", golfer.golfer_espn_id, dashboard.event_id, dashboard.year)) { (golfer.golfer_name) }
+                        }
+                        dl class="bettor-golfer-stats" {
+                            dt { "Round" }
+                            dd { @if let Some(round) = golfer.current_round { "R" (round) } @else { "-" } }
+                            dt { "Thru" }
+                            dd { (golfer.thru_label()) }
+                            dt { "Today" }
+                            dd { (to_par(golfer.round_score)) }
+                            dt { "Total" }
+                            dd { (to_par(golfer.total_score)) }
+                            @if let Some(tee_time) = &golfer.tee_time {
+                                dt { "Tee time" }
+                                dd { (tee_time) }
+                            }
+                        }
+                        @if !golfer.round_scores.is_empty() {
+                            p class="bettor-golfer-rounds" {
+                                @for (index, score) in golfer.round_scores.iter().enumerate() {
+                                    @if index > 0 { " · " }
+                                    "R" (index + 1) " " (to_par(*score))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            @if !dashboard.positions.is_empty() {
+                h2 { "Position by round" }
+                table class="bettor-positions" {
+                    thead {
+                        tr {
+                            th { "Round" }
+                            th { "Position" }
+                            th { "Total" }
+                            th { "Leader" }
+                            th { "Behind" }
+                        }
+                    }
+                    tbody {
+                        @for position in &dashboard.positions {
+                            tr {
+                                td { "R" (position.round) }
+                                td { (ordinal(position.rank)) }
+                                td { (to_par(position.cumulative)) }
+                                td { (to_par(position.leader_cumulative)) }
+                                td { (position.cumulative - position.leader_cumulative) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        section class="panel linescore-panel" {
+            (render_line_score_tables(
+                &scores_and_last_refresh_to_line_score_tables(&dashboard.line_scores),
+                &dashboard.refresh,
+            ))
+        }
+    }
+}
+
+/// The season summary: one row per event the bettor played in.
+#[must_use]
+pub fn render_bettor_season(season: &BettorSeason) -> Markup {
+    html! {
+        section class="panel bettor-season" {
+            @if season.events.is_empty() {
+                p { (season.bettor_name) " has no picks in " (season.year) "." }
+            } @else {
+                p class="bettor-standing" {
+                    (season.events.len()) @if season.events.len() == 1 { " event, " } @else { " events, " }
+                    (season.wins()) @if season.wins() == 1 { " win" } @else { " wins" }
+                }
+                table class="bettor-season-table" {
+                    thead {
+                        tr {
+                            th { "Event" }
+                            th { "Finish" }
+                            th { "Total" }
+                            th { "Behind" }
+                        }
+                    }
+                    tbody {
+                        @for entry in &season.events {
+                            @let link = format!(
+                                "bettor?name={}&event={}&yr={}",
+                                query_value(&season.bettor_name), entry.event.event_id, season.year
+                            );
+                            tr {
+                                td { a href=(link) { (entry.event.name) } }
+                                td {
+                                    (ordinal(entry.standing.rank)) " of " (entry.bettor_count)
+                                    @if entry.phase != EventPhase::Completed { " (so far)" }
+                                }
+                                td { (to_par(entry.standing.total_score)) }
+                                td { (entry.behind_leader) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn render_bettor_page(dashboard: &BettorDashboard) -> Markup {
+    render_page(
+        &format!("{} · {}", dashboard.bettor_name, dashboard.event_name),
+        render_bettor_dashboard(dashboard),
+    )
+}
+
+#[must_use]
+pub fn render_bettor_season_page(season: &BettorSeason) -> Markup {
+    render_page(
+        &format!("{} · {} season", season.bettor_name, season.year),
+        render_bettor_season(season),
+    )
+}
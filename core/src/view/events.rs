@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use maud::{Markup, html};
+
+use crate::api::dto::EventSummary;
+use crate::api::{standings_from_scores, summarize_event};
+use crate::error::CoreError;
+use crate::model::{RefreshSource, ScoresAndLastRefresh};
+use crate::score::EventPhase;
+use crate::storage::Storage;
+use crate::view::index::render_page;
+
+pub const EVENTS_INDEX_TITLE: &str = "Events";
+
+/// One row of the events index.
+#[derive(Debug, Clone)]
+pub struct EventIndexEntry {
+    pub event: EventSummary,
+    pub phase: EventPhase,
+    /// The bettors ranked first, once the event is completed; several if they tied.
+    pub winners: Vec<String>,
+}
+
+/// Every stored event with its phase at `now` and, for completed ones, the winners.
+///
+/// Only stored scores are read, so listing never refreshes from ESPN. Events that vanish
+/// while they are listed are left out.
+///
+/// # Errors
+/// Returns an error if the events can't be listed.
+pub async fn load_event_index(
+    storage: &dyn Storage,
+    now: DateTime<Utc>,
+) -> Result<Vec<EventIndexEntry>, CoreError> {
+    let mut entries = Vec::new();
+    for event_id in storage.list_events().await? {
+        let Ok(details) = storage.get_event_details(event_id).await else {
+            continue;
+        };
+        let phase = EventPhase::of(&details, now);
+        let winners = if phase == EventPhase::Completed {
+            stored_scores(storage, event_id)
+                .await
+                .map(|scores| winners(event_id, &scores))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        entries.push(EventIndexEntry {
+            event: summarize_event(event_id, details),
+            phase,
+            winners,
+        });
+    }
+    // Newest season first, and the latest start first within it.
+    entries.sort_by(|a, b| {
+        b.event
+            .year
+            .cmp(&a.event.year)
+            .then_with(|| b.event.start_date.cmp(&a.event.start_date))
+            .then_with(|| a.event.name.cmp(&b.event.name))
+    });
+    Ok(entries)
+}
+
+/// An event's scores without refreshing: its archive if it has one, otherwise the last
+/// stored refresh.
+pub(crate) async fn stored_scores(
+    storage: &dyn Storage,
+    event_id: i32,
+) -> Option<ScoresAndLastRefresh> {
+    if let Ok(Some(archive)) = storage.get_event_archive(event_id).await {
+        return archive.archived_scores();
+    }
+    storage.get_scores(event_id, RefreshSource::Db).await.ok()
+}
+
+fn winners(event_id: i32, scores: &ScoresAndLastRefresh) -> Vec<String> {
+    standings_from_scores(event_id, scores)
+        .standings
+        .into_iter()
+        .filter(|standing| standing.rank == 1)
+        .map(|standing| standing.bettor_name)
+        .collect()
+}
+
+pub(crate) fn phase_label(phase: EventPhase) -> (&'static str, &'static str) {
+    match phase {
+        EventPhase::Upcoming => ("upcoming", "Upcoming"),
+        EventPhase::InProgress => ("live", "Live"),
+        EventPhase::Completed => ("completed", "Completed"),
+    }
+}
+
+/// `2024-05-16T11:00:00Z` as `May 16`; anything unparseable is shown as stored.
+fn date_label(date: &str) -> String {
+    DateTime::parse_from_rfc3339(date).map_or_else(
+        |_| date.to_string(),
+        |parsed| parsed.format("%b %-d").to_string(),
+    )
+}
+
+fn dates_label(event: &EventSummary) -> String {
+    match (event.start_date.as_deref(), event.end_date.as_deref()) {
+        (Some(start), Some(end)) => format!("{} – {}", date_label(start), date_label(end)),
+        (Some(start), None) => date_label(start),
+        (None, Some(end)) => format!("until {}", date_label(end)),
+        (None, None) => String::new(),
+    }
+}
+
+/// The events grouped by season, as sorted by [`load_event_index`].
+#[must_use]
+pub fn render_events_index(entries: &[EventIndexEntry]) -> Markup {
+    let mut years: Vec<(i32, Vec<&EventIndexEntry>)> = Vec::new();
+    for entry in entries {
+        match years.last_mut() {
+            Some((year, group)) if *year == entry.event.year => group.push(entry),
+            _ => years.push((entry.event.year, vec![entry])),
+        }
+    }
+    html! {
+        section class="panel events-index" {
+            @if years.is_empty() {
+                p { "No events yet." }
+            }
+            @for (year, group) in &years {
+                h2 { (year) }
+                table class="events-table" {
+                    thead {
+                        tr {
+                            th { "Event" }
+                            th { "Dates" }
+                            th { "Status" }
+                            th { "Winner" }
+                            th { "Links" }
+                        }
+                    }
+                    tbody {
+                        @for entry in group {
+                            @let event = &entry.event;
+                            @let (status_class, status) = phase_label(entry.phase);
+                            tr data-event-id=(event.event_id) {
+                                td {
+                                    a href=(format!("?event={}&yr={}", event.event_id, event.year)) { (event.name) }
+                                }
+                                td { (dates_label(event)) }
+                                td { span class=(format!("event-status event-status-{status_class}")) { (status) } }
+                                td { (entry.winners.join(" & ")) }
+                                td class="event-links" {
+                                    a href=(format!("?event={}&yr={}&expanded=1", event.event_id, event.year)) { "Detailed" }
+                                    " · "
+                                    a href=(format!("api/v1/events/{}/standings", event.event_id)) { "JSON" }
+                                    " · "
+                                    a href=(format!("export/standings?event={}&yr={}", event.event_id, event.year)) { "CSV" }
+                                    " · "
+                                    a href=(format!("feed.atom?event={}&yr={}", event.event_id, event.year)) { "Feed" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The landing page when no event is asked for.
+#[must_use]
+pub fn render_events_page(entries: &[EventIndexEntry]) -> Markup {
+    render_page(EVENTS_INDEX_TITLE, render_events_index(entries))
+}
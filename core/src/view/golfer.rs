@@ -0,0 +1,296 @@
+//! `/golfer`: one golfer's hole-by-hole scorecard for an event, who picked them, and how
+//! they did in the other stored events.
+
+use chrono::{DateTime, Utc};
+use maud::{Markup, html};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use crate::api::dto::{EventSummary, GolferScorecard};
+use crate::api::{golfer_scorecard, summarize_event};
+use crate::error::CoreError;
+use crate::espn::EspnApiClient;
+use crate::model::{ScoreDisplay, Scores};
+use crate::score::{EventPhase, cache_max_age_for_event, load_score_context};
+use crate::storage::Storage;
+use crate::view::bettor::{query_value, to_par};
+use crate::view::events::{phase_label, stored_scores};
+use crate::view::index::render_page;
+use crate::view::score::score_with_shape;
+
+/// A parsed `/golfer` query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GolferRequest {
+    pub golfer_espn_id: i64,
+    pub event_id: i32,
+    pub year: i32,
+}
+
+/// Parse `espn_id`, `event` and `yr` of a `/golfer` query.
+///
+/// # Errors
+/// Returns an error if any of them is missing or isn't a number.
+pub fn parse_golfer_request<S: BuildHasher>(
+    query: &HashMap<String, String, S>,
+) -> Result<GolferRequest, CoreError> {
+    let number = |key: &str, what: &str| {
+        query
+            .get(key)
+            .and_then(|value| value.trim().parse().ok())
+            .ok_or_else(|| CoreError::Other(format!("{key} ({what}) parameter is required")))
+    };
+    Ok(GolferRequest {
+        golfer_espn_id: number("espn_id", "golfer")?,
+        event_id: i32::try_from(number("event", "event")?)
+            .map_err(|_| CoreError::Other("event parameter is out of range".into()))?,
+        year: i32::try_from(number("yr", "year")?)
+            .map_err(|_| CoreError::Other("yr parameter is out of range".into()))?,
+    })
+}
+
+/// How the golfer did in one stored event.
+#[derive(Debug, Clone)]
+pub struct GolferResult {
+    pub event: EventSummary,
+    pub phase: EventPhase,
+    pub total_score: i32,
+    /// Relative to par, one entry per round started.
+    pub round_scores: Vec<i32>,
+    pub picked_by: Vec<String>,
+}
+
+/// Every stored event other than `except_event` the golfer was picked in, newest first.
+///
+/// Only stored scores are read, so this never refreshes from ESPN.
+///
+/// # Errors
+/// Returns an error if the events can't be listed.
+pub async fn load_golfer_history(
+    storage: &dyn Storage,
+    golfer_espn_id: i64,
+    except_event: Option<i32>,
+    now: DateTime<Utc>,
+) -> Result<Vec<GolferResult>, CoreError> {
+    let mut results = Vec::new();
+    for event_id in storage.list_events().await? {
+        if Some(event_id) == except_event {
+            continue;
+        }
+        let Ok(details) = storage.get_event_details(event_id).await else {
+            continue;
+        };
+        let Some(scores) = stored_scores(storage, event_id).await else {
+            continue;
+        };
+        let picks: Vec<&Scores> = scores
+            .score_struct
+            .iter()
+            .filter(|golfer| golfer.espn_id == golfer_espn_id)
+            .collect();
+        // Every pick of a golfer carries the same statistics.
+        let Some(golfer) = picks.first() else {
+            continue;
+        };
+        let stats = &golfer.detailed_statistics;
+        results.push(GolferResult {
+            phase: EventPhase::of(&details, now),
+            event: summarize_event(event_id, details),
+            total_score: stats.total_score,
+            round_scores: stats.round_scores.iter().map(|score| score.val).collect(),
+            picked_by: picks.iter().map(|pick| pick.bettor_name.clone()).collect(),
+        });
+    }
+    results.sort_by(|a, b| {
+        b.event
+            .year
+            .cmp(&a.event.year)
+            .then_with(|| b.event.start_date.cmp(&a.event.start_date))
+            .then_with(|| a.event.name.cmp(&b.event.name))
+    });
+    Ok(results)
+}
+
+/// One golfer's page for one event.
+#[derive(Debug, Clone)]
+pub struct GolferPage {
+    pub year: i32,
+    pub event_name: String,
+    pub scorecard: GolferScorecard,
+    pub history: Vec<GolferResult>,
+}
+
+/// Load and render the page a `/golfer` query asks for. The event's scores refresh by the
+/// same cache rules as `/scores`; the other events only read stored scores.
+///
+/// # Errors
+/// Returns `NotFound` if the event doesn't exist or nobody picked the golfer in it, and other
+/// errors if scores can't be loaded.
+pub async fn load_golfer_page(
+    storage: &dyn Storage,
+    espn_api: &dyn EspnApiClient,
+    request: &GolferRequest,
+    now: DateTime<Utc>,
+) -> Result<Markup, CoreError> {
+    let event_id = request.event_id;
+    let details = storage
+        .get_event_details(event_id)
+        .await
+        .map_err(|_| CoreError::NotFound(format!("event {event_id} not found")))?;
+    let cache_max_age = cache_max_age_for_event(storage, event_id).await?;
+    let context = load_score_context(
+        storage,
+        espn_api,
+        event_id,
+        request.year,
+        true,
+        cache_max_age,
+    )
+    .await?;
+    let scorecard = golfer_scorecard(event_id, request.golfer_espn_id, &context.from_db_scores)
+        .map_err(|err| CoreError::NotFound(err.message))?;
+    let history = load_golfer_history(storage, request.golfer_espn_id, Some(event_id), now).await?;
+    Ok(render_golfer_page(&GolferPage {
+        year: request.year,
+        event_name: details.event_name,
+        scorecard,
+        history,
+    }))
+}
+
+fn golfer_link(golfer_espn_id: i64, event_id: i32, year: i32) -> String {
+    format!("golfer?espn_id={golfer_espn_id}&event={event_id}&yr={year}")
+}
+
+fn bettor_link(bettor_name: &str, event_id: i32, year: i32) -> String {
+    format!(
+        "bettor?name={}&event={event_id}&yr={year}",
+        query_value(bettor_name)
+    )
+}
+
+/// Every round on one card: a column per hole, a row per round, par on top.
+#[must_use]
+pub fn render_golfer_scorecard(scorecard: &GolferScorecard) -> Markup {
+    let mut pars: BTreeMap<i32, i32> = BTreeMap::new();
+    for hole in scorecard.rounds.iter().flat_map(|round| &round.holes) {
+        pars.entry(hole.hole).or_insert(hole.par);
+    }
+    html! {
+        @if scorecard.rounds.is_empty() {
+            p { "No rounds yet." }
+        } @else {
+            div class="golfer-scorecard-scroll" {
+                table class="golfer-scorecard" {
+                    thead {
+                        tr {
+                            th { "Hole" }
+                            @for hole in pars.keys() { th { (hole) } }
+                            th { "Tot" }
+                        }
+                    }
+                    tbody {
+                        tr class="golfer-scorecard-par" {
+                            th { "Par" }
+                            @for par in pars.values() { td { (par) } }
+                            td { (pars.values().sum::<i32>()) }
+                        }
+                        @for round in &scorecard.rounds {
+                            tr data-round=(round.round) {
+                                th {
+                                    "R" (round.round)
+                                    @if let Some(tee_time) = &round.tee_time {
+                                        br;
+                                        span class="golfer-tee-time" { (tee_time) }
+                                    }
+                                }
+                                @for hole in pars.keys() {
+                                    td {
+                                        @if let Some(played) = round.holes.iter().find(|played| played.hole == *hole) {
+                                            (score_with_shape(&played.strokes, &ScoreDisplay::from_i32(played.strokes - played.par)))
+                                        }
+                                    }
+                                }
+                                td { (to_par(round.score)) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The other events the golfer was picked in.
+#[must_use]
+pub fn render_golfer_history(golfer_espn_id: i64, history: &[GolferResult]) -> Markup {
+    html! {
+        @if history.is_empty() {
+            p { "No other events." }
+        } @else {
+            table class="golfer-history" {
+                thead {
+                    tr {
+                        th { "Event" }
+                        th { "Rounds" }
+                        th { "Total" }
+                        th { "Picked by" }
+                    }
+                }
+                tbody {
+                    @for result in history {
+                        @let event = &result.event;
+                        tr data-event-id=(event.event_id) {
+                            td {
+                                a href=(golfer_link(golfer_espn_id, event.event_id, event.year)) { (event.name) }
+                                " " (event.year)
+                                @if result.phase != EventPhase::Completed {
+                                    @let (status_class, status) = phase_label(result.phase);
+                                    " "
+                                    span class=(format!("event-status event-status-{status_class}")) { (status) }
+                                }
+                            }
+                            td {
+                                @for (index, score) in result.round_scores.iter().enumerate() {
+                                    @if index > 0 { " · " }
+                                    (to_par(*score))
+                                }
+                            }
+                            td { (to_par(result.total_score)) }
+                            td { (result.picked_by.join(" & ")) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn render_golfer_page(page: &GolferPage) -> Markup {
+    let scorecard = &page.scorecard;
+    let content = html! {
+        section class="panel golfer-page" {
+            p class="golfer-links" {
+                a href=(format!("?event={}&yr={}", scorecard.event_id, page.year)) { (page.event_name) }
+            }
+            p class="golfer-summary" {
+                "Total " span class="golfer-total" { (to_par(scorecard.total_score)) }
+            }
+            p class="golfer-picked-by" {
+                "Picked by "
+                @for (index, bettor) in scorecard.picked_by.iter().enumerate() {
+                    @if index > 0 { " & " }
+                    a href=(bettor_link(bettor, scorecard.event_id, page.year)) { (bettor) }
+                }
+            }
+            h2 { "Scorecard" }
+            (render_golfer_scorecard(scorecard))
+            h2 { "Other events" }
+            (render_golfer_history(scorecard.golfer_espn_id, &page.history))
+        }
+    };
+    render_page(
+        &format!("{} · {}", scorecard.golfer_name, page.event_name),
+        content,
+    )
+}
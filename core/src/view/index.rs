@@ -31,6 +31,22 @@ pub async fn resolve_index_title_or_default(storage: &dyn Storage, event_str: &s
 
 #[must_use]
 pub fn render_index_template_with_scores(title: &str, scores_markup: Option<Markup>) -> Markup {
+    render_page(
+        title,
+        html! {
+            div id="scores" {
+                @if let Some(markup) = scores_markup {
+                    (markup)
+                } @else {
+                    img alt="Result loading..." class="htmx-indicator" width="150" src="https://htmx.org//img/bars.svg" {}
+                }
+            }
+        },
+    )
+}
+
+/// The page chrome shared by the scoreboard and the events index.
+pub(crate) fn render_page(title: &str, content: Markup) -> Markup {
     html! {
         (maud::DOCTYPE)
         html lang="en" {
@@ -59,13 +75,7 @@ pub fn render_index_template_with_scores(title: &str, scores_markup: Option<Mark
                         h1 {
                             (title)
                         }
-                        div id="scores" {
-                            @if let Some(markup) = scores_markup {
-                                (markup)
-                            } @else {
-                                img alt="Result loading..." class="htmx-indicator" width="150" src="https://htmx.org//img/bars.svg" {}
-                            }
-                        }
+                        (content)
                     }
                 }
             }
@@ -1,2 +1,5 @@
+pub mod bettor;
+pub mod events;
+pub mod golfer;
 pub mod index;
 pub mod score;
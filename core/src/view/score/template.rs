@@ -19,6 +19,7 @@ pub fn render_scores_template_pure<S: BuildHasher>(
     player_step_factors: &HashMap<(i64, String), f32, S>,
     event_id: i32,
     year: i32,
+    refresh_interval: Option<u32>,
     _cache: bool,
 ) -> Markup {
     let summary_scores_x = group_by_bettor_name_and_round(&data.score_struct);
@@ -29,35 +30,104 @@ pub fn render_scores_template_pure<S: BuildHasher>(
         last_refresh_source: data.last_refresh_source.clone(),
     };
 
-    // Fragments should always read from the warmed DB snapshot on initial page load
-    // to avoid duplicate concurrent fetches; force cache=1 for hx requests.
-    let cache_str = "1";
-
     maud::html! {
         section class="panel linescore-panel" {
             div class="golf-ball-overlay" aria-hidden="true" {}
+            // `static/scores.js` follows the stream and reloads `#scores` on new standings.
+            div id="live-updates" class="live-updates" aria-live="polite"
+                data-stream-url=(format!("scores/stream?event={event_id}&yr={year}"))
+                data-scores-url=(format!("scores?event={}&yr={}&expanded={}", event_id, year, if expanded {"1"} else {"0"})) {}
+            (render_next_update(refresh_interval, false))
             (render_scoreboard(data))
             @if expanded {
-                div id="score-summary"
-                    data-hx-get=(format!("scores/summary?event={}&yr={}&cache={}&expanded={}", event_id, year, cache_str, "1"))
-                    data-hx-trigger="load" data-hx-swap="innerHTML" {
-                    (render_summary_scores(&summary_scores_x))
-                }
+                (render_polled_fragment(
+                    "score-summary",
+                    &fragment_url("summary", event_id, year, true),
+                    refresh_interval,
+                    true,
+                    render_summary_scores(&summary_scores_x),
+                ))
             }
 
-            div id="score-chart"
-                data-hx-get=(format!("scores/chart?event={}&yr={}&cache={}&expanded={}", event_id, year, cache_str, if expanded {"1"} else {"0"}))
-                data-hx-trigger="load" data-hx-swap="innerHTML" {
-                (render_drop_down_bar_pure(&summary_scores_x, &detailed_scores, global_step_factor, player_step_factors))
-            }
+            (render_polled_fragment(
+                "score-chart",
+                &fragment_url("chart", event_id, year, expanded),
+                refresh_interval,
+                true,
+                render_drop_down_bar_pure(&summary_scores_x, &detailed_scores, global_step_factor, player_step_factors),
+            ))
         }
 
         section class="panel linescore-panel" {
-            div id="linescore"
-                data-hx-get=(format!("scores/linescore?event={}&yr={}&cache={}&expanded={}", event_id, year, cache_str, if expanded {"1"} else {"0"}))
-                data-hx-trigger="load" data-hx-swap="innerHTML" {
-                (render_line_score_tables(bettor_struct_for_line_scores, &refresh_data))
+            (render_polled_fragment(
+                "linescore",
+                &fragment_url("linescore", event_id, year, expanded),
+                refresh_interval,
+                true,
+                render_line_score_tables(bettor_struct_for_line_scores, &refresh_data),
+            ))
+        }
+    }
+}
+
+/// The URL the `scores/{fragment}` div polls. Fragments always read from the warmed DB
+/// snapshot (`cache=1`) so an initial page load doesn't fan out into duplicate fetches.
+#[must_use]
+pub fn fragment_url(fragment: &str, event_id: i32, year: i32, expanded: bool) -> String {
+    let expanded = if expanded { "1" } else { "0" };
+    format!("scores/{fragment}?event={event_id}&yr={year}&cache=1&expanded={expanded}")
+}
+
+/// The div `#id` around a polled fragment, swapped as a whole so every response re-renders
+/// its trigger at the event's current refresh interval. On the page it also loads once
+/// straight away; in a fragment response (`on_page == false`) a completed event renders no
+/// trigger, which stops an open page polling.
+#[must_use]
+pub fn render_polled_fragment(
+    id: &str,
+    url: &str,
+    refresh_interval: Option<u32>,
+    on_page: bool,
+    content: Markup,
+) -> Markup {
+    let trigger = match (refresh_interval, on_page) {
+        (Some(secs), true) => Some(format!("load, every {secs}s")),
+        (Some(secs), false) => Some(format!("every {secs}s")),
+        (None, true) => Some("load".to_string()),
+        (None, false) => None,
+    };
+    maud::html! {
+        @if let Some(trigger) = trigger {
+            div id=(id) data-hx-get=(url) data-hx-trigger=(trigger) data-hx-swap="outerHTML" {
+                (content)
+            }
+        } @else {
+            div id=(id) { (content) }
+        }
+    }
+}
+
+/// `#next-update`, counted down by `static/scores.js` and restarted when `#linescore`
+/// reloads. `out_of_band` renders it for the `#linescore` response to swap in alongside,
+/// emptied once polling stops.
+#[must_use]
+pub fn render_next_update(refresh_interval: Option<u32>, out_of_band: bool) -> Markup {
+    maud::html! {
+        @match (refresh_interval, out_of_band) {
+            (Some(secs), _) => {
+                p id="next-update" class="next-update" data-refresh-seconds=(secs)
+                    data-hx-swap-oob=[out_of_band.then_some("true")] {
+                    "Next update in "
+                    span class="next-update-countdown" { (countdown_label(secs)) }
+                }
             }
+            (None, true) => { p id="next-update" data-hx-swap-oob="true" {} }
+            (None, false) => {}
         }
     }
 }
+
+/// `secs` as `M:SS`, the format `static/scores.js` keeps counting down in.
+fn countdown_label(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
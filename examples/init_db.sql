@@ -7,6 +7,9 @@ CREATE TABLE IF NOT EXISTS event (
     score_view_step_factor real not null default 3.0, --deprecated
     refresh_from_espn INTEGER not null DEFAULT 1,
     end_date TEXT,
+    start_date TEXT,
+    last_refresh_ts DATETIME,
+    completed INTEGER NOT NULL DEFAULT 0,
     UNIQUE (espn_id)
 );
 
@@ -104,3 +107,19 @@ BEGIN
         OLD.ins_ts
     );
 END;
+
+CREATE TABLE IF NOT EXISTS score_change (
+    score_change_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_espn_id INT NOT NULL,
+    change JSON NOT NULL,
+    detected_at DATETIME NOT NULL,
+    ins_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX IF NOT EXISTS score_change_event_idx ON score_change (event_espn_id, score_change_id);
+
+CREATE TABLE IF NOT EXISTS event_archive (
+    event_espn_id INT PRIMARY KEY,
+    archive JSON NOT NULL,
+    ins_ts DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
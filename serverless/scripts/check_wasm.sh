@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# The worker and core's wasm-only code only compile for wasm32, so the native
+# `cargo clippy --workspace` never sees them. Run this before pushing serverless changes.
+
+if ! command -v rustup >/dev/null 2>&1; then
+  echo "rustup is required for this script." >&2
+  exit 1
+fi
+
+if ! rustup target list --installed | grep -q "wasm32-unknown-unknown"; then
+  echo "Rust target wasm32-unknown-unknown is required. Run: rustup target add wasm32-unknown-unknown" >&2
+  exit 1
+fi
+
+script_dir="$(cd -- "$(dirname -- "${BASH_SOURCE[0]}")" && pwd)"
+workspace_root="${script_dir}/../.."
+
+cd "${workspace_root}"
+cargo check --target wasm32-unknown-unknown -p rusty-golf-serverless
+cargo clippy --target wasm32-unknown-unknown -p rusty-golf-serverless -p rusty-golf-core -- -D warnings
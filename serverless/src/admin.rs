@@ -1,15 +1,22 @@
 #![cfg(target_arch = "wasm32")]
 
+use rusty_golf_core::api::admin::{
+    AdminArchiveRequest, AdminCacheFlushRequest, AdminCleanupRequest, AdminCleanupScoresRequest,
+    AdminEspnFailRequest, AdminEventSelector, AdminExportRequest, AdminHistoryResponse,
+    AdminHistorySnapshot, AdminImportRequest, AdminSeedRequest, AdminTestLockRequest,
+    AdminTestLockResponse, AdminTestStorageRequest, AdminTestUnlockRequest,
+    AdminTestUnlockResponse, AdminUpdateDatesRequest,
+};
+use rusty_golf_core::model::RefreshSource;
+use rusty_golf_core::score::score_history_key;
+use rusty_golf_core::storage::{
+    CompactionPolicy, Storage, StorageError, archive_completed_event, export_event, import_event,
+};
 use worker::{Request, Response, Result, RouteContext};
 
 use crate::admin_auth::admin_auth_response;
-use crate::admin_types::{
-    AdminCacheFlushRequest, AdminCleanupRequest, AdminCleanupScoresRequest, AdminEspnFailRequest,
-    AdminEventSelector, AdminTestLockRequest, AdminTestLockResponse, AdminTestUnlockRequest,
-    AdminTestUnlockResponse, AdminUpdateDatesRequest,
-};
-use crate::storage::{AdminSeedRequest, TestLockMode};
-use crate::utils::storage_from_env;
+use crate::storage::{ServerlessStorage, TestLockMode, format_rfc3339, parse_rfc3339};
+use crate::utils::{parse_query_params, storage_from_env};
 
 mod cache_status;
 pub use cache_status::admin_cache_status_handler;
@@ -46,6 +53,84 @@ pub async fn admin_cleanup_handler(mut req: Request, ctx: RouteContext<()>) -> R
     Response::ok("cleaned")
 }
 
+pub async fn admin_export_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    if let Some(resp) = admin_auth_response(&req, &ctx.env)? {
+        return Ok(resp);
+    }
+    let payload: AdminExportRequest = req
+        .json()
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let storage = storage_from_env(&ctx.env)?;
+    let archive = export_event(&storage, payload.event_id)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Response::from_json(&archive)
+}
+
+pub async fn admin_import_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    if let Some(resp) = admin_auth_response(&req, &ctx.env)? {
+        return Ok(resp);
+    }
+    let payload: AdminImportRequest = req
+        .json()
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let storage = storage_from_env(&ctx.env)?;
+    import_event(&storage, &payload.archive, payload.replace)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Response::ok("imported")
+}
+
+pub async fn admin_archive_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    if let Some(resp) = admin_auth_response(&req, &ctx.env)? {
+        return Ok(resp);
+    }
+    let payload: AdminArchiveRequest = req
+        .json()
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let mut policy = CompactionPolicy::default();
+    if let Some(seconds) = payload.history_interval_seconds {
+        policy.history_interval_seconds = seconds;
+    }
+    let storage = storage_from_env(&ctx.env)?;
+    let report = archive_completed_event(&storage, payload.event_id, &policy)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Response::from_json(&report)
+}
+
+pub async fn admin_history_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    if let Some(resp) = admin_auth_response(&req, &ctx.env)? {
+        return Ok(resp);
+    }
+    let query = parse_query_params(&req)?;
+    let Some(event_id) = query
+        .get("event_id")
+        .and_then(|value| value.trim().parse::<i32>().ok())
+    else {
+        return Response::error("event_id query parameter is required", 400);
+    };
+    let storage = storage_from_env(&ctx.env)?;
+    let snapshots = storage
+        .list_score_history(event_id)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?
+        .into_iter()
+        .map(|taken_at| AdminHistorySnapshot {
+            taken_at: format_rfc3339(taken_at),
+            key: score_history_key(event_id, taken_at),
+        })
+        .collect();
+    Response::from_json(&AdminHistoryResponse {
+        event_id,
+        retention: storage.history_retention,
+        snapshots,
+    })
+}
+
 pub async fn admin_cleanup_scores_handler(
     mut req: Request,
     ctx: RouteContext<()>,
@@ -181,3 +266,90 @@ pub async fn admin_test_unlock_handler(
     };
     Response::from_json(&AdminTestUnlockResponse { is_last })
 }
+
+pub async fn admin_test_storage_handler(
+    mut req: Request,
+    ctx: RouteContext<()>,
+) -> Result<Response> {
+    if let Some(resp) = admin_auth_response(&req, &ctx.env)? {
+        return Ok(resp);
+    }
+    let payload: AdminTestStorageRequest = req
+        .json()
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    let storage = storage_from_env(&ctx.env)?;
+    match call_storage(&storage, payload).await {
+        Ok(Some(value)) => Response::from_json(&value),
+        Ok(None) => Response::error("unknown storage method", 400),
+        Err(e) => Response::error(e.to_string(), 500),
+    }
+}
+
+/// Run one `Storage` call for `/admin/test_storage`; `None` if `method` isn't one. Calls
+/// that return nothing answer `null`.
+async fn call_storage(
+    storage: &ServerlessStorage,
+    payload: AdminTestStorageRequest,
+) -> std::result::Result<Option<serde_json::Value>, StorageError> {
+    let event_id = payload.event_id;
+    let value = match payload.method.as_str() {
+        "list_events" => to_value(storage.list_events().await?)?,
+        "get_event_details" => to_value(storage.get_event_details(event_id).await?)?,
+        "get_golfers_for_event" => to_value(storage.get_golfers_for_event(event_id).await?)?,
+        "get_player_step_factors" => {
+            let factors: Vec<(i64, String, f32)> = storage
+                .get_player_step_factors(event_id)
+                .await?
+                .into_iter()
+                .map(|((espn_id, bettor), factor)| (espn_id, bettor, factor))
+                .collect();
+            to_value(factors)?
+        }
+        "get_scores" => {
+            let source = payload.source.unwrap_or(RefreshSource::Db);
+            to_value(storage.get_scores(event_id, source).await?)?
+        }
+        "store_scores" => {
+            storage.store_scores(event_id, &payload.scores).await?;
+            serde_json::Value::Null
+        }
+        "event_and_scores_already_in_db" => {
+            let max_age_seconds = payload.max_age_seconds.unwrap_or_default();
+            to_value(
+                storage
+                    .event_and_scores_already_in_db(event_id, max_age_seconds)
+                    .await?,
+            )?
+        }
+        "mark_event_completed" => {
+            storage
+                .mark_event_completed(event_id, payload.end_date.as_deref())
+                .await?;
+            serde_json::Value::Null
+        }
+        "store_score_changes" => {
+            storage
+                .store_score_changes(event_id, &payload.changes)
+                .await?;
+            serde_json::Value::Null
+        }
+        "get_recent_score_changes" => to_value(storage.get_recent_score_changes(event_id).await?)?,
+        "set_last_refresh" => {
+            let raw = payload
+                .last_refresh
+                .ok_or_else(|| StorageError::new("last_refresh is required"))?;
+            let last_refresh = parse_rfc3339(&raw).map_err(|e| StorageError::new(e.to_string()))?;
+            storage
+                .admin_set_last_refresh(event_id, last_refresh)
+                .await?;
+            serde_json::Value::Null
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> std::result::Result<serde_json::Value, StorageError> {
+    serde_json::to_value(value).map_err(|e| StorageError::new(e.to_string()))
+}
@@ -1,6 +1,5 @@
 #![cfg(target_arch = "wasm32")]
 
-use serde::Serialize;
 use std::collections::HashMap;
 use std::rc::Rc;
 use worker::{Env, Request, Response, Result, RouteContext};
@@ -8,27 +7,12 @@ use worker::{Env, Request, Response, Result, RouteContext};
 use crate::instrument::RequestInstrumentation;
 use crate::instrument::request_instrumentation;
 use crate::storage::ServerlessStorage;
-use crate::storage::storage_cache::{CacheStatus, in_memory_status, parse_kv_scores_entry};
+use crate::storage::storage_cache::{in_memory_status, parse_kv_scores_entry};
 use crate::utils::{parse_query_params, storage_from_env};
+use rusty_golf_core::api::admin::{CacheStatus, CacheStatusKeys, CacheStatusResponse};
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
 
-#[derive(Serialize)]
-struct CacheStatusKeys {
-    kv: String,
-    r2_scores: String,
-}
-
-#[derive(Serialize)]
-struct CacheStatusResponse {
-    event_id: i32,
-    year: i32,
-    in_memory: CacheStatus,
-    kv: CacheStatus,
-    r2: CacheStatus,
-    keys: CacheStatusKeys,
-}
-
 fn auth_details(event_id: i32, year: i32, status: i32) -> serde_json::Value {
     serde_json::json!({
         "event_id": event_id,
@@ -43,7 +27,6 @@ async fn require_authorized(
     req: &Request,
     env: &Env,
     storage: &ServerlessStorage,
-    timing: Option<&dyn TimingSink>,
     query: &HashMap<String, String>,
     event_id: i32,
     year: i32,
@@ -51,6 +34,7 @@ async fn require_authorized(
     if instrumentation.instrument_header_valid() {
         return Ok(None);
     }
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
     let auth_token = match query.get("auth_token") {
         Some(value) if !value.trim().is_empty() => value.trim(),
         _ => {
@@ -175,7 +159,6 @@ pub async fn admin_cache_status_handler(req: Request, ctx: RouteContext<()>) ->
         &req,
         &ctx.env,
         &storage,
-        timing,
         &query,
         event_id,
         year,
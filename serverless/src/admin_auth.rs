@@ -38,10 +38,10 @@ fn admin_token(env: &Env) -> Result<String> {
 }
 
 fn admin_request_token(req: &Request) -> Result<Option<String>> {
-    if let Ok(Some(token)) = req.headers().get("x-admin-token") {
-        if !token.trim().is_empty() {
-            return Ok(Some(token));
-        }
+    if let Ok(Some(token)) = req.headers().get("x-admin-token")
+        && !token.trim().is_empty()
+    {
+        return Ok(Some(token));
     }
     let query = parse_query_params(req)?;
     Ok(query.get("admin_token").cloned())
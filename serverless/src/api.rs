@@ -0,0 +1,59 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::rc::Rc;
+use worker::{Request, Response, Result, RouteContext};
+
+use rusty_golf_core::api::{handle_api_request, openapi_document, parse_api_request};
+use rusty_golf_core::timed;
+use rusty_golf_core::timing::TimingSink;
+
+use crate::espn_client::ServerlessEspnClient;
+use crate::instrument::request_instrumentation;
+use crate::storage::storage_cache::cached_storage;
+use crate::utils::{parse_query_params, storage_from_env};
+
+/// Everything under `/api/v1`, routed by core exactly as the actix runtime routes it.
+pub async fn api_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
+    let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
+    let storage =
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc);
+    let query = parse_query_params(&req)?;
+    let path = req.path();
+
+    let result = match parse_api_request(&path, &query) {
+        Ok(request) => {
+            let espn_client = ServerlessEspnClient::new(storage.clone());
+            let storage = cached_storage(storage);
+            timed!(
+                timing,
+                "api.handle_ms",
+                handle_api_request(&storage, &espn_client, &request).await
+            )
+        }
+        Err(err) => Err(err),
+    };
+    let (status, resp) = match result {
+        Ok(body) => (200, Response::from_json(&body)),
+        Err(err) => (
+            err.status,
+            Response::from_json(&err.body()).map(|resp| resp.with_status(err.status)),
+        ),
+    };
+    let details = serde_json::json!({
+        "path": path,
+        "status": status,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
+
+/// `/openapi.json`, built by core so it matches the actix document.
+pub async fn openapi_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let resp = Response::from_json(&openapi_document());
+    let details = serde_json::json!({
+        "status": 200,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
@@ -0,0 +1,48 @@
+#![cfg(target_arch = "wasm32")]
+
+use chrono::Utc;
+use std::rc::Rc;
+use worker::{Request, Response, Result, RouteContext};
+
+use rusty_golf_core::calendar::{load_tee_time_calendar, parse_tee_time_request};
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::timed;
+use rusty_golf_core::timing::TimingSink;
+
+use crate::espn_client::ServerlessEspnClient;
+use crate::instrument::request_instrumentation;
+use crate::storage::storage_cache::cached_storage;
+use crate::utils::{parse_query_params, respond_file, storage_from_env};
+
+/// `/tee-times.ics?event=&yr=&bettor=`: the picked golfers' tee times as a calendar feed.
+pub async fn tee_times_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
+    let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
+    let query = parse_query_params(&req)?;
+    let request = match parse_tee_time_request(&query) {
+        Ok(request) => request,
+        Err(err) => return Response::error(err.to_string(), 400),
+    };
+    let storage =
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc);
+    let espn_client = ServerlessEspnClient::new(storage.clone());
+    let storage = cached_storage(storage);
+    let calendar = timed!(
+        timing,
+        "calendar.load_ms",
+        load_tee_time_calendar(&storage, &espn_client, &request, Utc::now()).await
+    );
+    let (status, resp) = match calendar {
+        Ok(file) => (200, respond_file(file)),
+        Err(err @ CoreError::NotFound(_)) => (404, Response::error(err.to_string(), 404)),
+        Err(err) => (500, Response::error(err.to_string(), 500)),
+    };
+    let details = serde_json::json!({
+        "event_id": request.event_id,
+        "year": request.year,
+        "bettor": request.bettor_name,
+        "status": status,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
@@ -2,8 +2,10 @@
 
 use futures::{StreamExt, TryStreamExt, stream};
 use rusty_golf_core::error::CoreError;
-use rusty_golf_core::espn::EspnApiClient;
 use rusty_golf_core::espn::processing::{merge_statistics_with_scores, process_json_to_statistics};
+use rusty_golf_core::espn::{
+    ESPN_SCOREBOARD_HEADER_URL, EspnApiClient, EventCompletion, parse_event_completion,
+};
 use rusty_golf_core::model::{PlayerJsonResponse, Scores};
 use rusty_golf_core::storage::Storage;
 use serde::Deserialize;
@@ -73,11 +75,9 @@ impl EspnApiClient for ServerlessEspnClient {
             eup_ids: Vec::new(),
         };
 
-        for maybe_response in fetches {
-            if let Some((eup_id, json)) = maybe_response {
-                player_response.data.push(json);
-                player_response.eup_ids.push(eup_id);
-            }
+        for (eup_id, json) in fetches.into_iter().flatten() {
+            player_response.data.push(json);
+            player_response.eup_ids.push(eup_id);
         }
 
         Ok(player_response)
@@ -91,6 +91,20 @@ impl EspnApiClient for ServerlessEspnClient {
         };
         Ok(self.parse_cached_scores(event_id, cached).await)
     }
+
+    async fn event_completion(&self, event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        let url = Url::parse(ESPN_SCOREBOARD_HEADER_URL)
+            .map_err(|e| CoreError::Network(e.to_string()))?;
+        let mut response = Fetch::Url(url)
+            .send()
+            .await
+            .map_err(|e| CoreError::Network(e.to_string()))?;
+        let payload: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| CoreError::Network(e.to_string()))?;
+        parse_event_completion(&payload, event_id)
+    }
 }
 
 #[derive(Deserialize)]
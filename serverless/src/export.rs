@@ -0,0 +1,51 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::rc::Rc;
+use worker::{Request, Response, Result, RouteContext};
+
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::export::{load_export, parse_export_request};
+use rusty_golf_core::timed;
+use rusty_golf_core::timing::TimingSink;
+
+use crate::espn_client::ServerlessEspnClient;
+use crate::instrument::request_instrumentation;
+use crate::storage::storage_cache::cached_storage;
+use crate::utils::{parse_query_params, respond_file, storage_from_env};
+
+/// `/export/{table}?event=&yr=&format=csv|xlsx`, the same files the actix runtime serves.
+pub async fn export_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
+    let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
+    let query = parse_query_params(&req)?;
+    let path = req.path();
+    let table = path.strip_prefix("/export/").unwrap_or(&path);
+    let request = match parse_export_request(table, &query) {
+        Ok(request) => request,
+        Err(err @ CoreError::NotFound(_)) => return Response::error(err.to_string(), 404),
+        Err(err) => return Response::error(err.to_string(), 400),
+    };
+    let storage =
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc);
+    let espn_client = ServerlessEspnClient::new(storage.clone());
+    let storage = cached_storage(storage);
+    let file = timed!(
+        timing,
+        "export.load_ms",
+        load_export(&storage, &espn_client, &request).await
+    );
+    let (status, resp) = match file {
+        Ok(file) => (200, respond_file(file)),
+        Err(err @ CoreError::NotFound(_)) => (404, Response::error(err.to_string(), 404)),
+        Err(err) => (500, Response::error(err.to_string(), 500)),
+    };
+    let details = serde_json::json!({
+        "event_id": request.event_id,
+        "year": request.year,
+        "table": request.table.slug(),
+        "format": request.format.extension(),
+        "status": status,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
@@ -0,0 +1,45 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::rc::Rc;
+use worker::{Request, Response, Result, RouteContext};
+
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::feed::{FEED_CONTENT_TYPE, load_feed};
+use rusty_golf_core::score::parse_score_request;
+use rusty_golf_core::timed;
+use rusty_golf_core::timing::TimingSink;
+
+use crate::instrument::request_instrumentation;
+use crate::storage::storage_cache::cached_storage;
+use crate::utils::{parse_query_params, respond_text, storage_from_env};
+
+/// `/feed.atom?event=&yr=`: the event's lead changes, completed rounds and eagles as Atom.
+pub async fn feed_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
+    let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
+    let query = parse_query_params(&req)?;
+    let request = match parse_score_request(&query) {
+        Ok(request) => request,
+        Err(err) => return Response::error(err.to_string(), 400),
+    };
+    let storage = cached_storage(
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc),
+    );
+    let feed = timed!(
+        timing,
+        "feed.load_ms",
+        load_feed(&storage, request.event_id, request.year).await
+    );
+    let (status, resp) = match feed {
+        Ok(body) => (200, respond_text(body, FEED_CONTENT_TYPE)),
+        Err(err @ CoreError::NotFound(_)) => (404, Response::error(err.to_string(), 404)),
+        Err(err) => (500, Response::error(err.to_string(), 500)),
+    };
+    let details = serde_json::json!({
+        "event_id": request.event_id,
+        "year": request.year,
+        "status": status,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
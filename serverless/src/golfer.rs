@@ -0,0 +1,48 @@
+#![cfg(target_arch = "wasm32")]
+
+use chrono::Utc;
+use std::rc::Rc;
+use worker::{Request, Response, Result, RouteContext};
+
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::timed;
+use rusty_golf_core::timing::TimingSink;
+use rusty_golf_core::view::golfer::{load_golfer_page, parse_golfer_request};
+
+use crate::espn_client::ServerlessEspnClient;
+use crate::instrument::request_instrumentation;
+use crate::storage::storage_cache::cached_storage;
+use crate::utils::{parse_query_params, respond_html, storage_from_env};
+
+/// `/golfer?espn_id=&event=&yr=`: one golfer's scorecard and their other events.
+pub async fn golfer_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let instrumentation = request_instrumentation(&req, &ctx.env)?;
+    let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
+    let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
+    let query = parse_query_params(&req)?;
+    let request = match parse_golfer_request(&query) {
+        Ok(request) => request,
+        Err(err) => return Response::error(err.to_string(), 400),
+    };
+    let storage =
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc);
+    let espn_client = ServerlessEspnClient::new(storage.clone());
+    let storage = cached_storage(storage);
+    let page = timed!(
+        timing,
+        "view.load_golfer_page_ms",
+        load_golfer_page(&storage, &espn_client, &request, Utc::now()).await
+    );
+    let (status, resp) = match page {
+        Ok(markup) => (200, respond_html(markup.into_string())),
+        Err(err @ CoreError::NotFound(_)) => (404, Response::error(err.to_string(), 404)),
+        Err(err) => (500, Response::error(err.to_string(), 500)),
+    };
+    let details = serde_json::json!({
+        "golfer_espn_id": request.golfer_espn_id,
+        "event_id": request.event_id,
+        "year": request.year,
+        "status": status,
+    });
+    crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
+}
@@ -6,11 +6,14 @@ use maud::Markup;
 
 use worker::{Request, Response, Result, RouteContext};
 
+use chrono::Utc;
 use rusty_golf_core::score::{
     cache_max_age_for_event, load_score_context_with_timing, parse_score_request,
 };
+use rusty_golf_core::storage::CachedStorage;
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
+use rusty_golf_core::view::events::{load_event_index, render_events_page};
 use rusty_golf_core::view::index::{
     render_index_template_with_scores, resolve_index_title_or_default,
 };
@@ -20,13 +23,14 @@ use rusty_golf_core::view::score::{
 use std::rc::Rc;
 
 use crate::espn_client::ServerlessEspnClient;
-use crate::instrument::request_instrumentation;
+use crate::instrument::{RequestInstrumentation, request_instrumentation};
 use crate::storage::ServerlessStorage;
+use crate::storage::storage_cache::cached_storage;
 use crate::utils::{parse_query_params, respond_html, storage_from_env};
 
 async fn try_render_scores_markup(
     query: &HashMap<String, String>,
-    storage: &ServerlessStorage,
+    storage: &CachedStorage<ServerlessStorage>,
     timing: Option<&dyn TimingSink>,
 ) -> Option<Markup> {
     let score_req = timed!(
@@ -41,7 +45,7 @@ async fn try_render_scores_markup(
             .await
             .ok()
     )?;
-    let espn_client = ServerlessEspnClient::new(storage.clone());
+    let espn_client = ServerlessEspnClient::new(storage.inner().clone());
     let context = timed!(
         timing,
         "score_context.load_ms",
@@ -73,6 +77,7 @@ async fn try_render_scores_markup(
             &context.player_step_factors,
             score_req.event_id,
             score_req.year,
+            context.refresh_interval,
             score_req.use_cache,
         )
     );
@@ -85,8 +90,12 @@ pub async fn index_handler(req: Request, ctx: RouteContext<()>) -> Result<Respon
     let timing_rc: Option<Rc<dyn TimingSink>> = Some(instrumentation.timing_rc());
     let query = parse_query_params(&req)?;
     let event_str = query.get("event").map(String::as_str).unwrap_or("");
-    let storage =
-        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc);
+    let storage = cached_storage(
+        timed!(timing, "storage.from_env_ms", storage_from_env(&ctx.env))?.with_timing(timing_rc),
+    );
+    if !query.contains_key("event") {
+        return events_index_response(&req, &ctx, &instrumentation, &storage, timing).await;
+    }
     let title = timed!(
         timing,
         "view.resolve_index_title_ms",
@@ -117,3 +126,28 @@ pub async fn index_handler(req: Request, ctx: RouteContext<()>) -> Result<Respon
     });
     crate::finalize_resp!(instrumentation, &req, &ctx.env, details, resp)
 }
+
+/// The landing page without an `event`: every event, grouped by year.
+async fn events_index_response(
+    req: &Request,
+    ctx: &RouteContext<()>,
+    instrumentation: &RequestInstrumentation,
+    storage: &CachedStorage<ServerlessStorage>,
+    timing: Option<&dyn TimingSink>,
+) -> Result<Response> {
+    let entries = timed!(
+        timing,
+        "view.load_event_index_ms",
+        load_event_index(storage, Utc::now()).await
+    );
+    let resp = match entries {
+        Ok(entries) => timed!(
+            timing,
+            "response.html_ms",
+            respond_html(render_events_page(&entries).into_string())
+        ),
+        Err(err) => Response::error(err.to_string(), 500),
+    };
+    let details = serde_json::json!({ "events_index": true });
+    crate::finalize_resp!(instrumentation, req, &ctx.env, details, resp)
+}
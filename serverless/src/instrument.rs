@@ -156,7 +156,7 @@ fn should_emit_sampled_request(env: &Env) -> Result<bool> {
         _ => return Ok(false),
     };
     let count = REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed) + 1;
-    Ok(count % every_n == 0)
+    Ok(count.is_multiple_of(every_n))
 }
 
 fn has_valid_instrument_token(req: &Request, env: &Env) -> Result<bool> {
@@ -3,10 +3,20 @@ mod admin;
 #[cfg(target_arch = "wasm32")]
 mod admin_auth;
 #[cfg(target_arch = "wasm32")]
-mod admin_types;
+mod api;
+#[cfg(target_arch = "wasm32")]
+mod bettor;
+#[cfg(target_arch = "wasm32")]
+mod calendar;
 #[cfg(target_arch = "wasm32")]
 mod espn_client;
 #[cfg(target_arch = "wasm32")]
+mod export;
+#[cfg(target_arch = "wasm32")]
+mod feed;
+#[cfg(target_arch = "wasm32")]
+mod golfer;
+#[cfg(target_arch = "wasm32")]
 mod index;
 #[cfg(target_arch = "wasm32")]
 mod instrument;
@@ -27,11 +37,25 @@ pub use rusty_golf_core as core;
 
 #[cfg(target_arch = "wasm32")]
 use admin::{
-    admin_cache_flush_handler, admin_cache_status_handler, admin_cleanup_handler,
-    admin_cleanup_scores_handler, admin_espn_fail_handler, admin_seed_handler,
-    admin_test_lock_handler, admin_test_unlock_handler, admin_update_dates_handler,
+    admin_archive_handler, admin_cache_flush_handler, admin_cache_status_handler,
+    admin_cleanup_handler, admin_cleanup_scores_handler, admin_espn_fail_handler,
+    admin_export_handler, admin_history_handler, admin_import_handler, admin_seed_handler,
+    admin_test_lock_handler, admin_test_storage_handler, admin_test_unlock_handler,
+    admin_update_dates_handler,
 };
 #[cfg(target_arch = "wasm32")]
+use api::{api_handler, openapi_handler};
+#[cfg(target_arch = "wasm32")]
+use bettor::bettor_handler;
+#[cfg(target_arch = "wasm32")]
+use calendar::tee_times_handler;
+#[cfg(target_arch = "wasm32")]
+use export::export_handler;
+#[cfg(target_arch = "wasm32")]
+use feed::feed_handler;
+#[cfg(target_arch = "wasm32")]
+use golfer::golfer_handler;
+#[cfg(target_arch = "wasm32")]
 use index::index_handler;
 #[cfg(target_arch = "wasm32")]
 use listing::listing_handler;
@@ -69,6 +93,27 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
         .get_async("/scores", |req, ctx| async move {
             scores_handler(req, ctx).await
         })
+        .get_async("/api/v1/*path", |req, ctx| async move {
+            api_handler(req, ctx).await
+        })
+        .get_async("/openapi.json", |req, ctx| async move {
+            openapi_handler(req, ctx).await
+        })
+        .get_async("/bettor", |req, ctx| async move {
+            bettor_handler(req, ctx).await
+        })
+        .get_async("/golfer", |req, ctx| async move {
+            golfer_handler(req, ctx).await
+        })
+        .get_async("/export/*table", |req, ctx| async move {
+            export_handler(req, ctx).await
+        })
+        .get_async("/tee-times.ics", |req, ctx| async move {
+            tee_times_handler(req, ctx).await
+        })
+        .get_async("/feed.atom", |req, ctx| async move {
+            feed_handler(req, ctx).await
+        })
         .get_async("/listing", |req, ctx| async move {
             listing_handler(req, ctx).await
         })
@@ -87,6 +132,18 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
         .post_async("/admin/cleanup", |req, ctx| async move {
             admin_cleanup_handler(req, ctx).await
         })
+        .post_async("/admin/export", |req, ctx| async move {
+            admin_export_handler(req, ctx).await
+        })
+        .post_async("/admin/import", |req, ctx| async move {
+            admin_import_handler(req, ctx).await
+        })
+        .post_async("/admin/archive", |req, ctx| async move {
+            admin_archive_handler(req, ctx).await
+        })
+        .get_async("/admin/history", |req, ctx| async move {
+            admin_history_handler(req, ctx).await
+        })
         .post_async("/admin/cleanup_scores", |req, ctx| async move {
             admin_cleanup_scores_handler(req, ctx).await
         })
@@ -105,6 +162,9 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
         .post_async("/admin/test_unlock", |req, ctx| async move {
             admin_test_unlock_handler(req, ctx).await
         })
+        .post_async("/admin/test_storage", |req, ctx| async move {
+            admin_test_storage_handler(req, ctx).await
+        })
         .get_async("/admin/cache_status", |req, ctx| async move {
             admin_cache_status_handler(req, ctx).await
         })
@@ -1,7 +1,6 @@
 #![cfg(target_arch = "wasm32")]
 
 use maud::{Markup, html};
-use serde::Serialize;
 use std::rc::Rc;
 use worker::{Request, Response, Result, RouteContext};
 
@@ -13,16 +12,6 @@ use crate::utils::{parse_query_params, respond_html, storage_from_env};
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
 
-#[derive(Serialize)]
-pub(super) struct AdminListingResponse {
-    events: Vec<EventListing>,
-    kv_keys: Vec<String>,
-    r2_keys: Vec<String>,
-    event_id: Option<i32>,
-    scores_exists: Option<bool>,
-    espn_cache_exists: Option<bool>,
-}
-
 pub async fn listing_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let instrumentation = request_instrumentation(&req, &ctx.env)?;
     let timing: Option<&dyn TimingSink> = Some(instrumentation.timing());
@@ -6,7 +6,9 @@ use worker::{Request, Response, Result, RouteContext};
 use rusty_golf_core::score::{group_by_bettor_golfer_round, group_by_bettor_name_and_round};
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
-use rusty_golf_core::view::score::render_drop_down_bar_pure;
+use rusty_golf_core::view::score::{
+    fragment_url, render_drop_down_bar_pure, render_polled_fragment,
+};
 
 use crate::instrument::request_instrumentation;
 use crate::utils::{respond_html, storage_from_env};
@@ -47,14 +49,26 @@ pub async fn scores_chart_handler(req: Request, ctx: RouteContext<()>) -> Result
             group_by_bettor_golfer_round(&context.data.score_struct),
         )
     );
+    let url = fragment_url(
+        "chart",
+        score_req.event_id,
+        score_req.year,
+        score_req.expanded,
+    );
     let markup = timed!(
         timing,
         "view.render_chart_ms",
-        render_drop_down_bar_pure(
-            &summary_scores_x,
-            &detailed_scores,
-            context.global_step_factor,
-            &context.player_step_factors,
+        render_polled_fragment(
+            "score-chart",
+            &url,
+            context.refresh_interval,
+            false,
+            render_drop_down_bar_pure(
+                &summary_scores_x,
+                &detailed_scores,
+                context.global_step_factor,
+                &context.player_step_factors,
+            ),
         )
     );
     let resp = timed!(
@@ -6,7 +6,8 @@ use worker::{Request, Response, Result, RouteContext};
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
 use rusty_golf_core::view::score::{
-    RefreshData, render_line_score_tables, scores_and_last_refresh_to_line_score_tables,
+    RefreshData, fragment_url, render_line_score_tables, render_next_update,
+    render_polled_fragment, scores_and_last_refresh_to_line_score_tables,
 };
 
 use crate::instrument::request_instrumentation;
@@ -49,10 +50,25 @@ pub async fn scores_linescore_handler(req: Request, ctx: RouteContext<()>) -> Re
         last_refresh: context.data.last_refresh.clone(),
         last_refresh_source: context.data.last_refresh_source.clone(),
     };
+    let url = fragment_url(
+        "linescore",
+        score_req.event_id,
+        score_req.year,
+        score_req.expanded,
+    );
     let markup = timed!(
         timing,
         "view.render_linescore_ms",
-        render_line_score_tables(&bettor_struct, &refresh_data)
+        maud::html! {
+            (render_polled_fragment(
+                "linescore",
+                &url,
+                context.refresh_interval,
+                false,
+                render_line_score_tables(&bettor_struct, &refresh_data),
+            ))
+            (render_next_update(context.refresh_interval, true))
+        }
     );
     let resp = timed!(
         timing,
@@ -4,11 +4,13 @@ use worker::{Request, Result};
 
 use rusty_golf_core::score::{
     cache_max_age_for_event, load_score_context_with_timing, parse_score_request,
+    score_context_from_snapshot,
 };
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
 
 use crate::espn_client::ServerlessEspnClient;
+use crate::storage::storage_cache::cached_storage;
 use crate::utils::parse_query_params;
 
 mod chart_handler;
@@ -31,6 +33,11 @@ async fn load_context(
     storage: &crate::storage::ServerlessStorage,
     timing: Option<&dyn TimingSink>,
 ) -> Result<rusty_golf_core::score::ScoreContext> {
+    if let Some(as_of) = score_req.as_of {
+        return load_context_as_of(score_req.event_id, as_of, storage, timing).await;
+    }
+    let espn_client = ServerlessEspnClient::new(storage.clone());
+    let storage = &cached_storage(storage.clone());
     let cache_max_age = timed!(
         timing,
         "cache.max_age_ms",
@@ -38,7 +45,6 @@ async fn load_context(
             .await
             .map_err(|e| worker::Error::RustError(e.to_string()))
     )?;
-    let espn_client = ServerlessEspnClient::new(storage.clone());
     let context = timed!(
         timing,
         "score_context.load_ms",
@@ -56,3 +62,28 @@ async fn load_context(
     )?;
     Ok(context)
 }
+
+/// Replay the snapshot in effect at `as_of`; never refreshes from ESPN.
+async fn load_context_as_of(
+    event_id: i32,
+    as_of: chrono::NaiveDateTime,
+    storage: &crate::storage::ServerlessStorage,
+    timing: Option<&dyn TimingSink>,
+) -> Result<rusty_golf_core::score::ScoreContext> {
+    let snapshot = timed!(
+        timing,
+        "storage.get_scores_as_of_ms",
+        storage
+            .get_scores_as_of(event_id, as_of)
+            .await
+            .map_err(|e| worker::Error::RustError(e.to_string()))
+    )?
+    .ok_or_else(|| {
+        worker::Error::RustError(format!(
+            "no score snapshot for event {event_id} at or before {as_of}"
+        ))
+    })?;
+    score_context_from_snapshot(storage, event_id, snapshot)
+        .await
+        .map_err(|e| worker::Error::RustError(e.to_string()))
+}
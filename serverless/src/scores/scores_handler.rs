@@ -73,6 +73,7 @@ pub async fn scores_handler(req: Request, ctx: RouteContext<()>) -> Result<Respo
                 &context.player_step_factors,
                 score_req.event_id,
                 score_req.year,
+                context.refresh_interval,
                 score_req.use_cache,
             )
         );
@@ -6,7 +6,7 @@ use worker::{Request, Response, Result, RouteContext};
 use rusty_golf_core::score::group_by_bettor_name_and_round;
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::TimingSink;
-use rusty_golf_core::view::score::render_summary_scores;
+use rusty_golf_core::view::score::{fragment_url, render_polled_fragment, render_summary_scores};
 
 use crate::instrument::request_instrumentation;
 use crate::utils::{respond_html, storage_from_env};
@@ -65,7 +65,13 @@ pub async fn scores_summary_handler(req: Request, ctx: RouteContext<()>) -> Resu
     let markup = timed!(
         timing,
         "view.render_summary_ms",
-        render_summary_scores(&summary)
+        render_polled_fragment(
+            "score-summary",
+            &fragment_url("summary", score_req.event_id, score_req.year, true),
+            context.refresh_interval,
+            false,
+            render_summary_scores(&summary),
+        )
     );
     let resp = timed!(
         timing,
@@ -1,15 +1,18 @@
 #![cfg(target_arch = "wasm32")]
 
+use rusty_golf_core::score::ScoreHistoryRetention;
 use rusty_golf_core::storage::StorageError;
 use rusty_golf_core::timing::TimingSink;
 use std::rc::Rc;
 use worker::{Bucket, Env, KvStore};
 
+mod storage_admin_impl;
 mod storage_admin_lock;
 mod storage_admin_seed;
 mod storage_admin_seed_helpers;
 pub(crate) mod storage_cache;
 mod storage_helpers;
+mod storage_history;
 mod storage_impl;
 mod storage_kv;
 mod storage_kv_keys;
@@ -29,6 +32,7 @@ pub struct ServerlessStorage {
     pub(crate) kv: KvStore,
     pub(crate) bucket: Bucket,
     pub(crate) timing: Option<Rc<dyn TimingSink>>,
+    pub(crate) history_retention: ScoreHistoryRetention,
 }
 
 impl ServerlessStorage {
@@ -46,6 +50,7 @@ impl ServerlessStorage {
             kv,
             bucket,
             timing: None,
+            history_retention: ScoreHistoryRetention::default(),
         })
     }
 
@@ -55,6 +60,12 @@ impl ServerlessStorage {
         self
     }
 
+    #[must_use]
+    pub fn with_history_retention(mut self, retention: ScoreHistoryRetention) -> Self {
+        self.history_retention = retention;
+        self
+    }
+
     pub fn timing(&self) -> Option<&dyn TimingSink> {
         self.timing.as_deref()
     }
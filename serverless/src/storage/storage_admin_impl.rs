@@ -0,0 +1,230 @@
+#![cfg(target_arch = "wasm32")]
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rusty_golf_core::model::Scores;
+use rusty_golf_core::score::score_history_key;
+use rusty_golf_core::storage::{
+    AdminStorage, Assignment, CompactionPolicy, CompactionReport, EventArchive, EventConfig,
+    GolferRecord, PlayerStepFactor, Storage, StorageError, apply_player_step_factors,
+    picks_from_assignments,
+};
+use std::collections::HashMap;
+
+use super::storage_helpers::format_rfc3339;
+use super::storage_types::{EventDetailsDoc, GolferAssignment, PlayerFactorEntry, SeededAtDoc};
+use crate::storage::ServerlessStorage;
+use crate::storage::storage_cache::shared_storage_cache;
+
+impl ServerlessStorage {
+    async fn event_exists(&self, event_id: i32) -> Result<bool, StorageError> {
+        let key = Self::kv_event_details_key(event_id);
+        Ok(self.kv_get_optional_text(&key).await?.is_some())
+    }
+
+    async fn require_event(&self, event_id: i32) -> Result<(), StorageError> {
+        if self.event_exists(event_id).await? {
+            Ok(())
+        } else {
+            Err(StorageError::new(format!("event {event_id} not found")))
+        }
+    }
+
+    async fn golfer_directory(&self) -> Result<Vec<GolferRecord>, StorageError> {
+        match self
+            .kv_get_optional_text(Self::kv_golfer_directory_key())
+            .await?
+        {
+            Some(text) => serde_json::from_str(&text).map_err(|e| StorageError::new(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn store_event_config(
+        &self,
+        event_id: i32,
+        config: &EventConfig,
+    ) -> Result<(), StorageError> {
+        let details = EventDetailsDoc {
+            event_name: config.event_name.clone(),
+            score_view_step_factor: config.score_view_step_factor,
+            refresh_from_espn: config.refresh_from_espn,
+            start_date: config.start_date.clone(),
+            end_date: config.end_date.clone(),
+            completed: config.completed,
+        };
+        self.kv_put_json(&Self::kv_event_details_key(event_id), &details)
+            .await?;
+        shared_storage_cache().invalidate_event(event_id);
+        self.touch_seeded_at(event_id, "details").await
+    }
+
+    /// Write the event's picks and the factor list derived from them.
+    async fn store_picks(&self, event_id: i32, picks: Vec<Scores>) -> Result<(), StorageError> {
+        let factors: Vec<PlayerFactorEntry> = picks
+            .iter()
+            .filter_map(|pick| {
+                Some(PlayerFactorEntry {
+                    golfer_espn_id: pick.espn_id,
+                    bettor_name: pick.bettor_name.clone(),
+                    step_factor: pick.score_view_step_factor?,
+                })
+            })
+            .collect();
+        let golfers: Vec<GolferAssignment> = picks
+            .into_iter()
+            .map(|pick| GolferAssignment {
+                eup_id: pick.eup_id,
+                espn_id: pick.espn_id,
+                golfer_name: pick.golfer_name,
+                bettor_name: pick.bettor_name,
+                group: pick.group,
+                score_view_step_factor: pick.score_view_step_factor,
+            })
+            .collect();
+        self.kv_put_json(&Self::kv_golfers_key(event_id), &golfers)
+            .await?;
+        self.kv_put_json(&Self::kv_player_factors_key(event_id), &factors)
+            .await?;
+        shared_storage_cache().invalidate_event(event_id);
+        self.touch_seeded_at(event_id, "golfers").await?;
+        self.touch_seeded_at(event_id, "player_factors").await
+    }
+
+    async fn touch_seeded_at(&self, event_id: i32, suffix: &str) -> Result<(), StorageError> {
+        let seeded_at = SeededAtDoc {
+            seeded_at: format_rfc3339(Utc::now().naive_utc()),
+        };
+        self.kv_put_json(&Self::kv_seeded_at_key(event_id, suffix), &seeded_at)
+            .await
+    }
+}
+
+#[async_trait(?Send)]
+impl AdminStorage for ServerlessStorage {
+    async fn create_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        if self.event_exists(event_id).await? {
+            return Err(StorageError::new(format!(
+                "event {event_id} already exists"
+            )));
+        }
+        self.store_event_config(event_id, config).await
+    }
+
+    async fn update_event(&self, event_id: i32, config: &EventConfig) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        self.store_event_config(event_id, config).await
+    }
+
+    async fn delete_event(&self, event_id: i32) -> Result<bool, StorageError> {
+        let existed = self.event_exists(event_id).await?;
+        self.admin_cleanup_event(event_id, true).await?;
+        Ok(existed)
+    }
+
+    async fn upsert_golfers(&self, golfers: &[GolferRecord]) -> Result<(), StorageError> {
+        let mut directory: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        for golfer in golfers {
+            directory.insert(golfer.espn_id, golfer.name.clone());
+        }
+        let mut directory: Vec<GolferRecord> = directory
+            .into_iter()
+            .map(|(espn_id, name)| GolferRecord { espn_id, name })
+            .collect();
+        directory.sort_by_key(|golfer| golfer.espn_id);
+        self.kv_put_json(Self::kv_golfer_directory_key(), &directory)
+            .await
+    }
+
+    async fn set_assignments(
+        &self,
+        event_id: i32,
+        assignments: &[Assignment],
+    ) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        let names: HashMap<i64, String> = self
+            .golfer_directory()
+            .await?
+            .into_iter()
+            .map(|golfer| (golfer.espn_id, golfer.name))
+            .collect();
+        let picks = picks_from_assignments(event_id, assignments, &names)?;
+        self.store_picks(event_id, picks).await?;
+
+        // Stored scores belong to the old picks.
+        let _ = self.kv.delete(&Self::kv_last_refresh_key(event_id)).await;
+        let _ = self.kv.delete(&Self::kv_scores_cache_key(event_id)).await;
+        let _ = self.bucket.delete(Self::scores_key(event_id)).await;
+        shared_storage_cache().invalidate_event(event_id);
+        Ok(())
+    }
+
+    async fn set_player_step_factors(
+        &self,
+        event_id: i32,
+        factors: &[PlayerStepFactor],
+    ) -> Result<(), StorageError> {
+        self.require_event(event_id).await?;
+        let mut picks = self.get_golfers_for_event(event_id).await?;
+        apply_player_step_factors(event_id, &mut picks, factors)?;
+        self.store_picks(event_id, picks).await
+    }
+
+    async fn archive_event(
+        &self,
+        event_id: i32,
+        archive: &EventArchive,
+        policy: &CompactionPolicy,
+    ) -> Result<CompactionReport, StorageError> {
+        self.require_event(event_id).await?;
+        // The archive goes first so every read has somewhere to land; details stay so the
+        // event is still listed.
+        self.r2_put_json(&Self::archive_key(event_id), archive)
+            .await?;
+
+        let mut records_removed = 0;
+        for key in [
+            Self::kv_golfers_key(event_id),
+            Self::kv_player_factors_key(event_id),
+            Self::kv_last_refresh_key(event_id),
+            Self::kv_scores_cache_key(event_id),
+            Self::kv_score_changes_key(event_id),
+            Self::kv_seeded_at_key(event_id, "golfers"),
+            Self::kv_seeded_at_key(event_id, "player_factors"),
+            Self::kv_seeded_at_key(event_id, "last_refresh"),
+            Self::kv_force_espn_fail_key(event_id),
+        ] {
+            if self.kv_get_optional_text(&key).await?.is_some() {
+                let _ = self.kv.delete(&key).await;
+                records_removed += 1;
+            }
+        }
+        for key in [Self::scores_key(event_id), Self::espn_cache_key(event_id)] {
+            if self.r2_key_exists(&key).await? {
+                let _ = self.bucket.delete(key).await;
+                records_removed += 1;
+            }
+        }
+
+        let history = self.list_score_history(event_id).await?;
+        let pruned = policy.history_to_prune(&history);
+        for taken_at in &pruned {
+            let _ = self
+                .bucket
+                .delete(score_history_key(event_id, *taken_at))
+                .await;
+        }
+        shared_storage_cache().invalidate_event(event_id);
+        Ok(CompactionReport {
+            event_id,
+            records_removed,
+            history_kept: history.len() - pruned.len(),
+            history_pruned: pruned.len(),
+        })
+    }
+}
@@ -73,10 +73,10 @@ impl ServerlessStorage {
 
         let now = Utc::now().naive_utc();
         doc.shared_holders.remove(token);
-        if let Some((holder, _)) = doc.exclusive_holder.clone() {
-            if holder == token {
-                doc.exclusive_holder = None;
-            }
+        if let Some((holder, _)) = doc.exclusive_holder.clone()
+            && holder == token
+        {
+            doc.exclusive_holder = None;
         }
         doc.shared_holders.retain(|_, expires_at| {
             parse_rfc3339(expires_at)
@@ -10,7 +10,7 @@ use super::storage_admin_seed_helpers::{
 use super::storage_helpers::format_rfc3339;
 use super::storage_types::{AdminSeedRequest, AuthTokensDoc, LastRefreshDoc, SeededAtDoc};
 use crate::storage::ServerlessStorage;
-use crate::storage::storage_cache::clear_in_memory_scores;
+use crate::storage::storage_cache::shared_storage_cache;
 
 impl ServerlessStorage {
     pub async fn admin_seed_event(&self, request: AdminSeedRequest) -> Result<(), StorageError> {
@@ -34,10 +34,23 @@ impl ServerlessStorage {
         self.store_last_refresh_doc(request.event_id, last_refresh_ts)
             .await?;
         self.store_seeded_at_docs(request.event_id).await?;
+        shared_storage_cache().invalidate_event(request.event_id);
 
         Ok(())
     }
 
+    /// Move the event's last refresh to `last_refresh_ts` without touching its scores.
+    pub async fn admin_set_last_refresh(
+        &self,
+        event_id: i32,
+        last_refresh_ts: chrono::NaiveDateTime,
+    ) -> Result<(), StorageError> {
+        self.store_last_refresh_doc(event_id, last_refresh_ts)
+            .await?;
+        shared_storage_cache().invalidate_event(event_id);
+        Ok(())
+    }
+
     pub async fn admin_cleanup_event(
         &self,
         event_id: i32,
@@ -49,6 +62,7 @@ impl ServerlessStorage {
             Self::kv_player_factors_key(event_id),
             Self::kv_last_refresh_key(event_id),
             Self::kv_scores_cache_key(event_id),
+            Self::kv_score_changes_key(event_id),
             Self::kv_seeded_at_key(event_id, "details"),
             Self::kv_seeded_at_key(event_id, "golfers"),
             Self::kv_seeded_at_key(event_id, "player_factors"),
@@ -68,20 +82,23 @@ impl ServerlessStorage {
         let cache_key = Self::espn_cache_key(event_id);
         let _ = self.bucket.delete(scores_key).await;
         let _ = self.bucket.delete(cache_key).await;
-        clear_in_memory_scores(event_id);
+        let _ = self.bucket.delete(Self::archive_key(event_id)).await;
+        let _ = self.delete_score_history(event_id).await;
+        shared_storage_cache().invalidate_event(event_id);
         Ok(())
     }
 
     pub async fn admin_cleanup_scores(&self, event_id: i32) -> Result<(), StorageError> {
         let scores_key = Self::scores_key(event_id);
         let _ = self.bucket.delete(scores_key).await;
+        shared_storage_cache().invalidate_scores(event_id);
         Ok(())
     }
 
     pub async fn admin_flush_scores_cache(&self, event_id: i32) -> Result<(), StorageError> {
         let kv_key = Self::kv_scores_cache_key(event_id);
         let _ = self.kv.delete(&kv_key).await;
-        clear_in_memory_scores(event_id);
+        shared_storage_cache().invalidate_scores(event_id);
         Ok(())
     }
 
@@ -115,6 +132,7 @@ impl ServerlessStorage {
             details.completed = completed;
         }
         self.kv_put_json(&details_key, &details).await?;
+        shared_storage_cache().invalidate_event(event_id);
 
         let seeded_at = SeededAtDoc {
             seeded_at: format_rfc3339(Utc::now().naive_utc()),
@@ -37,8 +37,7 @@ pub fn build_golfers_out(
 
     let mut bettor_counts: HashMap<&str, usize> = HashMap::new();
     let mut golfers_out = Vec::new();
-    let mut eup_id = 1_i64;
-    for entry in &data_to_fill.event_user_player {
+    for (eup_id, entry) in (1_i64..).zip(&data_to_fill.event_user_player) {
         let count = bettor_counts.entry(entry.bettor.as_str()).or_insert(0);
         *count += 1;
 
@@ -60,7 +59,6 @@ pub fn build_golfers_out(
                 .as_ref()
                 .and_then(|value| value.as_f64().map(|num| num as f32)),
         });
-        eup_id += 1;
     }
 
     Ok(golfers_out)
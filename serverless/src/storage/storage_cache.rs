@@ -2,10 +2,12 @@
 
 use chrono::Utc;
 use once_cell::sync::Lazy;
+pub use rusty_golf_core::api::admin::CacheStatus;
 use rusty_golf_core::model::ScoresAndLastRefresh;
+use rusty_golf_core::storage::{CachedStorage, EventDetails, StorageCache};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Mutex;
+
+use crate::storage::ServerlessStorage;
 
 pub const KV_SCORES_TTL_SECONDS: u64 = 300;
 const IN_MEMORY_TTL_SECONDS: i64 = 30;
@@ -21,84 +23,36 @@ pub struct KvScoresCacheEntry {
     pub payload: ScoresAndLastRefresh,
 }
 
-#[derive(Serialize, Clone, Copy)]
-pub struct CacheStatus {
-    pub exists: bool,
-    pub remaining_ttl_seconds: Option<i64>,
-}
+static SHARED_CACHE: Lazy<StorageCache> = Lazy::new(StorageCache::new);
 
-struct CacheEntry {
-    value: ScoresAndLastRefresh,
-    inserted_at: i64,
-    ttl_seconds: i64,
+/// The in-memory cache shared by every request this isolate serves.
+pub fn shared_storage_cache() -> &'static StorageCache {
+    &SHARED_CACHE
 }
 
-static IN_MEMORY_SCORES: Lazy<Mutex<HashMap<i32, CacheEntry>>> =
-    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Wrap a request's storage in the isolate's shared cache, keeping scores as long as
+/// [`derive_cache_ttls`] allows for the event.
+pub fn cached_storage(storage: ServerlessStorage) -> CachedStorage<ServerlessStorage> {
+    CachedStorage::with_cache(storage, SHARED_CACHE.clone()).with_scores_ttl(in_memory_scores_ttl)
+}
 
-pub fn get_in_memory_scores(event_id: i32) -> Option<ScoresAndLastRefresh> {
-    let now = Utc::now().timestamp();
-    let mut cache = IN_MEMORY_SCORES.lock().ok()?;
-    if let Some(entry) = cache.get(&event_id) {
-        if now - entry.inserted_at <= entry.ttl_seconds {
-            return Some(entry.value.clone());
-        }
-    }
-    cache.remove(&event_id);
-    None
+fn in_memory_scores_ttl(details: Option<&EventDetails>) -> i64 {
+    derive_cache_ttls(
+        details.and_then(|details| details.start_date.as_deref()),
+        details.and_then(|details| details.end_date.as_deref()),
+        details.is_some_and(|details| details.completed),
+    )
+    .1
 }
 
 pub fn in_memory_status(event_id: i32) -> CacheStatus {
-    let now = Utc::now().timestamp();
-    let Ok(mut cache) = IN_MEMORY_SCORES.lock() else {
-        return CacheStatus {
-            exists: false,
-            remaining_ttl_seconds: None,
-        };
-    };
-    let Some(entry) = cache.get(&event_id) else {
-        return CacheStatus {
-            exists: false,
-            remaining_ttl_seconds: None,
-        };
-    };
-    let age = now - entry.inserted_at;
-    if age > entry.ttl_seconds {
-        cache.remove(&event_id);
-        return CacheStatus {
-            exists: false,
-            remaining_ttl_seconds: None,
-        };
-    }
-    let remaining = (entry.ttl_seconds - age).max(0);
+    let remaining_ttl_seconds = SHARED_CACHE.scores_remaining_ttl(event_id);
     CacheStatus {
-        exists: true,
-        remaining_ttl_seconds: Some(remaining),
+        exists: remaining_ttl_seconds.is_some(),
+        remaining_ttl_seconds,
     }
 }
 
-pub fn set_in_memory_scores(event_id: i32, scores: &ScoresAndLastRefresh, ttl_seconds: i64) {
-    let now = Utc::now().timestamp();
-    let Ok(mut cache) = IN_MEMORY_SCORES.lock() else {
-        return;
-    };
-    cache.insert(
-        event_id,
-        CacheEntry {
-            value: scores.clone(),
-            inserted_at: now,
-            ttl_seconds,
-        },
-    );
-}
-
-pub fn clear_in_memory_scores(event_id: i32) {
-    let Ok(mut cache) = IN_MEMORY_SCORES.lock() else {
-        return;
-    };
-    cache.remove(&event_id);
-}
-
 pub fn build_kv_scores_entry(
     scores: &ScoresAndLastRefresh,
     ttl_seconds: i64,
@@ -0,0 +1,76 @@
+#![cfg(target_arch = "wasm32")]
+
+use chrono::NaiveDateTime;
+use rusty_golf_core::model::ScoresAndLastRefresh;
+use rusty_golf_core::score::{
+    parse_score_history_key, score_history_key, score_history_prefix, snapshot_at_or_before,
+};
+use rusty_golf_core::storage::StorageError;
+
+use crate::storage::ServerlessStorage;
+
+impl ServerlessStorage {
+    /// When each of the event's score snapshots was taken, oldest first.
+    pub async fn list_score_history(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        let prefix = score_history_prefix(event_id);
+        let mut taken = Vec::new();
+        for key in self.r2_list_keys_with_prefix(Some(prefix.as_str())).await? {
+            if let Some(taken_at) = parse_score_history_key(event_id, &key) {
+                taken.push(taken_at);
+            }
+        }
+        taken.sort();
+        Ok(taken)
+    }
+
+    /// The newest snapshot taken no later than `as_of`, if there is one.
+    pub async fn get_scores_as_of(
+        &self,
+        event_id: i32,
+        as_of: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        let history = self.list_score_history(event_id).await?;
+        match snapshot_at_or_before(&history, as_of) {
+            Some(taken_at) => self
+                .r2_get_json(&score_history_key(event_id, taken_at))
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Snapshot freshly stored scores if the retention policy wants one now, then prune
+    /// the oldest snapshots beyond its limit.
+    pub(crate) async fn record_score_snapshot(
+        &self,
+        event_id: i32,
+        payload: &ScoresAndLastRefresh,
+    ) -> Result<(), StorageError> {
+        let retention = self.history_retention;
+        let mut history = self.list_score_history(event_id).await?;
+        if !retention.should_snapshot(history.last().copied(), payload.last_refresh) {
+            return Ok(());
+        }
+        self.r2_put_json(&score_history_key(event_id, payload.last_refresh), payload)
+            .await?;
+        history.push(payload.last_refresh);
+        for taken_at in retention.to_prune(&history) {
+            let _ = self
+                .bucket
+                .delete(score_history_key(event_id, *taken_at))
+                .await;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn delete_score_history(&self, event_id: i32) -> Result<(), StorageError> {
+        let prefix = score_history_prefix(event_id);
+        for key in self.r2_list_keys_with_prefix(Some(prefix.as_str())).await? {
+            let _ = self.bucket.delete(key).await;
+        }
+        Ok(())
+    }
+}
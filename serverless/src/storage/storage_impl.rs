@@ -1,32 +1,38 @@
 #![cfg(target_arch = "wasm32")]
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use rusty_golf_core::model::score::Statistic;
-use rusty_golf_core::model::{RefreshSource, Scores, ScoresAndLastRefresh};
-use rusty_golf_core::score::should_promote_completed;
-use rusty_golf_core::storage::{EventDetails, Storage, StorageError};
+use rusty_golf_core::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh};
+use rusty_golf_core::score::{merge_recent_score_changes, score_history_key};
+use rusty_golf_core::storage::{EventArchive, EventDetails, Storage, StorageError};
 use rusty_golf_core::timed;
 use rusty_golf_core::timing::{record_timing, start_timing};
-use serde::Deserialize;
 use std::collections::HashMap;
-use worker::{Fetch, Url};
+use worker::console_log;
 
-use super::storage_helpers::{format_rfc3339, parse_rfc3339};
+use super::storage_helpers::{format_rfc3339, parse_event_id, parse_rfc3339};
 use super::storage_types::{
     EventDetailsDoc, GolferAssignment, LastRefreshDoc, PlayerFactorEntry, SeededAtDoc,
 };
 use crate::storage::ServerlessStorage;
 use crate::storage::storage_cache::{
-    build_kv_scores_entry, derive_cache_ttls, get_in_memory_scores, parse_kv_scores_entry,
-    set_in_memory_scores,
+    build_kv_scores_entry, derive_cache_ttls, parse_kv_scores_entry,
 };
 
-const ESPN_SCOREBOARD_HEADER_URL: &str = "https://site.web.api.espn.com/apis/v2/scoreboard/header?sport=golf&league=pga&region=us&lang=en&contentorigin=espn";
-const COMPLETION_PROMOTION_GRACE_DAYS: i64 = 5;
-
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl Storage for ServerlessStorage {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        let mut ids: Vec<i32> = self
+            .kv_list_keys_with_prefix("event:")
+            .await?
+            .iter()
+            .filter_map(|key| parse_event_id(key, ":details"))
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
     async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
         let key = Self::kv_event_details_key(event_id);
         let doc: EventDetailsDoc = self.kv_get_json(&key).await?;
@@ -42,7 +48,17 @@ impl Storage for ServerlessStorage {
 
     async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError> {
         let key = Self::kv_golfers_key(event_id);
-        let assignments: Vec<GolferAssignment> = self.kv_get_json(&key).await?;
+        let assignments: Vec<GolferAssignment> = match self.kv_get_optional_text(&key).await? {
+            Some(text) => {
+                serde_json::from_str(&text).map_err(|e| StorageError::new(e.to_string()))?
+            }
+            None => {
+                return match self.get_event_archive(event_id).await? {
+                    Some(archive) => archive.picks(),
+                    None => Ok(Vec::new()),
+                };
+            }
+        };
         Ok(assignments
             .into_iter()
             .map(|assignment| Scores {
@@ -70,7 +86,18 @@ impl Storage for ServerlessStorage {
         event_id: i32,
     ) -> Result<HashMap<(i64, String), f32>, StorageError> {
         let key = Self::kv_player_factors_key(event_id);
-        let entries: Vec<PlayerFactorEntry> = self.kv_get_json(&key).await?;
+        let entries: Vec<PlayerFactorEntry> = match self.kv_get_optional_text(&key).await? {
+            Some(text) => {
+                serde_json::from_str(&text).map_err(|e| StorageError::new(e.to_string()))?
+            }
+            None => {
+                return Ok(self
+                    .get_event_archive(event_id)
+                    .await?
+                    .map(|archive| archive.player_step_factors())
+                    .unwrap_or_default());
+            }
+        };
         Ok(entries
             .into_iter()
             .map(|entry| ((entry.golfer_espn_id, entry.bettor_name), entry.step_factor))
@@ -82,22 +109,10 @@ impl Storage for ServerlessStorage {
         event_id: i32,
         source: RefreshSource,
     ) -> Result<ScoresAndLastRefresh, StorageError> {
+        // The in-memory layer is `CachedStorage`; see `storage_cache::cached_storage`.
         let key = Self::scores_key(event_id);
         let timing = self.timing();
-        let in_memory_start = start_timing();
-        let in_memory = get_in_memory_scores(event_id);
-        if let Some(mut scores) = in_memory {
-            record_timing(timing, "cache.in_memory_hit_ms", in_memory_start);
-            scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
-                RefreshSource::Espn
-            } else {
-                RefreshSource::Memory
-            };
-            return Ok(scores);
-        }
-        record_timing(timing, "cache.in_memory_miss_ms", in_memory_start);
-
-        let (kv_ttl_seconds, in_memory_ttl_seconds) = self.cache_ttls_for_event(event_id).await;
+        let (kv_ttl_seconds, _) = self.cache_ttls_for_event(event_id).await;
         let kv_key = Self::kv_scores_cache_key(event_id);
         let kv_start = start_timing();
         let kv_text = self.kv_get_optional_text(&kv_key).await?;
@@ -112,7 +127,6 @@ impl Storage for ServerlessStorage {
         };
         if let Some(mut scores) = timed!(timing, "cache.kv_scores_ms", kv_cached) {
             record_timing(timing, "cache.kv_hit_ms", kv_start);
-            set_in_memory_scores(event_id, &scores, in_memory_ttl_seconds);
             scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
                 RefreshSource::Espn
             } else {
@@ -122,7 +136,17 @@ impl Storage for ServerlessStorage {
         }
         record_timing(timing, "cache.kv_miss_ms", kv_start);
 
-        let mut scores: ScoresAndLastRefresh = self.r2_get_json(&key).await?;
+        let Some(mut scores) = self
+            .r2_get_optional_json::<ScoresAndLastRefresh>(&key)
+            .await?
+        else {
+            // Archived events keep their final scores only in the archive.
+            return self
+                .get_event_archive(event_id)
+                .await?
+                .and_then(|archive| archive.archived_scores())
+                .ok_or_else(|| StorageError::new(format!("R2 key missing: {key}")));
+        };
         let kv_entry = build_kv_scores_entry(&scores, kv_ttl_seconds as i64);
         let _ = timed!(
             timing,
@@ -130,7 +154,6 @@ impl Storage for ServerlessStorage {
             self.kv_put_json_with_ttl(&kv_key, &kv_entry, kv_ttl_seconds)
                 .await
         );
-        set_in_memory_scores(event_id, &scores, in_memory_ttl_seconds);
         scores.last_refresh_source = if matches!(source, RefreshSource::Espn) {
             RefreshSource::Espn
         } else {
@@ -140,7 +163,7 @@ impl Storage for ServerlessStorage {
     }
 
     async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
-        let (kv_ttl_seconds, in_memory_ttl_seconds) = self.cache_ttls_for_event(event_id).await;
+        let (kv_ttl_seconds, _) = self.cache_ttls_for_event(event_id).await;
         let now = Utc::now().naive_utc();
         let payload = ScoresAndLastRefresh {
             score_struct: scores.to_vec(),
@@ -149,11 +172,14 @@ impl Storage for ServerlessStorage {
         };
         let key = Self::scores_key(event_id);
         self.r2_put_json(&key, &payload).await?;
+        // History is for replays; losing a snapshot shouldn't fail the refresh.
+        if let Err(e) = self.record_score_snapshot(event_id, &payload).await {
+            console_log!("score history snapshot failed for event {event_id}: {e}");
+        }
         let kv_key = Self::kv_scores_cache_key(event_id);
         let kv_entry = build_kv_scores_entry(&payload, kv_ttl_seconds as i64);
         self.kv_put_json_with_ttl(&kv_key, &kv_entry, kv_ttl_seconds)
             .await?;
-        set_in_memory_scores(event_id, &payload, in_memory_ttl_seconds);
 
         let last_refresh = LastRefreshDoc {
             ts: format_rfc3339(now),
@@ -168,7 +194,6 @@ impl Storage for ServerlessStorage {
         let seeded_key = Self::kv_seeded_at_key(event_id, "last_refresh");
         self.kv_put_json(&seeded_key, &seeded_at).await?;
 
-        let _ = self.promote_completed_if_ready(event_id).await;
         Ok(())
     }
 
@@ -203,134 +228,86 @@ impl Storage for ServerlessStorage {
         let diff = now.signed_duration_since(last_refresh_ts);
         Ok(diff.num_seconds() <= max_age_seconds)
     }
-}
 
-impl ServerlessStorage {
-    async fn cache_ttls_for_event(&self, event_id: i32) -> (u64, i64) {
-        let details = self.get_event_details(event_id).await.ok();
-        derive_cache_ttls(
-            details.as_ref().and_then(|doc| doc.start_date.as_deref()),
-            details.as_ref().and_then(|doc| doc.end_date.as_deref()),
-            details.as_ref().map(|doc| doc.completed).unwrap_or(false),
-        )
-    }
-
-    async fn promote_completed_if_ready(&self, event_id: i32) -> Result<(), StorageError> {
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
         let details_key = Self::kv_event_details_key(event_id);
         let mut details: EventDetailsDoc = self.kv_get_json(&details_key).await?;
-        if details.completed {
-            return Ok(());
-        }
-
-        let now = Utc::now().naive_utc();
-        if let Some(end_date) = details.end_date.as_deref()
-            && let Ok(parsed) = parse_rfc3339(end_date)
-        {
-            if now <= parsed + chrono::Duration::days(COMPLETION_PROMOTION_GRACE_DAYS) {
-                return Ok(());
-            }
-        }
-
-        let Some((espn_completed, espn_end_date)) =
-            self.fetch_event_completion_state(event_id).await?
-        else {
-            return Ok(());
-        };
-        let end_date = details.end_date.as_deref().or(espn_end_date.as_deref());
-        if !should_promote_completed(espn_completed, end_date, now) {
-            return Ok(());
-        }
-
         details.completed = true;
+        if details.end_date.is_none() {
+            details.end_date = end_date.map(ToString::to_string);
+        }
         self.kv_put_json(&details_key, &details).await?;
 
         let seeded_at = SeededAtDoc {
-            seeded_at: format_rfc3339(now),
+            seeded_at: format_rfc3339(Utc::now().naive_utc()),
         };
         let seeded_key = Self::kv_seeded_at_key(event_id, "details");
         let _ = self.kv_put_json(&seeded_key, &seeded_at).await;
         Ok(())
     }
 
-    async fn fetch_event_completion_state(
+    async fn store_score_changes(
         &self,
         event_id: i32,
-    ) -> Result<Option<(bool, Option<String>)>, StorageError> {
-        let url =
-            Url::parse(ESPN_SCOREBOARD_HEADER_URL).map_err(|e| StorageError::new(e.to_string()))?;
-        let mut response = Fetch::Url(url)
-            .send()
-            .await
-            .map_err(|e| StorageError::new(e.to_string()))?;
-        let payload: ScoreboardHeader = response
-            .json()
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let existing = self.get_recent_score_changes(event_id).await?;
+        let merged = merge_recent_score_changes(existing, changes);
+        self.kv_put_json(&Self::kv_score_changes_key(event_id), &merged)
             .await
-            .map_err(|e| StorageError::new(e.to_string()))?;
+    }
 
-        for sport in payload.sports {
-            for league in sport.leagues {
-                for event in league.events {
-                    if event.id.parse::<i32>().ok() != Some(event_id) {
-                        continue;
-                    }
-                    let completed = event
-                        .full_status
-                        .as_ref()
-                        .map(ScoreboardFullStatus::completed)
-                        .unwrap_or(false);
-                    return Ok(Some((completed, event.end_date)));
-                }
-            }
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        let key = Self::kv_score_changes_key(event_id);
+        match self.kv_get_optional_text(&key).await? {
+            Some(text) => serde_json::from_str(&text).map_err(|e| StorageError::new(e.to_string())),
+            None => Ok(self
+                .get_event_archive(event_id)
+                .await?
+                .map(|archive| archive.score_changes)
+                .unwrap_or_default()),
         }
-
-        Ok(None)
     }
-}
-
-#[derive(Debug, Deserialize)]
-struct ScoreboardHeader {
-    sports: Vec<ScoreboardSport>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ScoreboardSport {
-    leagues: Vec<ScoreboardLeague>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ScoreboardLeague {
-    events: Vec<ScoreboardEvent>,
-}
 
-#[derive(Debug, Deserialize)]
-struct ScoreboardEvent {
-    id: String,
-    #[serde(rename = "endDate")]
-    end_date: Option<String>,
-    #[serde(rename = "fullStatus")]
-    full_status: Option<ScoreboardFullStatus>,
-}
+    async fn get_event_archive(&self, event_id: i32) -> Result<Option<EventArchive>, StorageError> {
+        self.r2_get_optional_json(&Self::archive_key(event_id))
+            .await
+    }
 
-#[derive(Debug, Deserialize)]
-struct ScoreboardFullStatus {
-    #[serde(default)]
-    completed: bool,
-    #[serde(rename = "type")]
-    status_type: Option<ScoreboardStatusType>,
-}
+    async fn list_score_snapshots(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<NaiveDateTime>, StorageError> {
+        self.list_score_history(event_id).await
+    }
 
-#[derive(Debug, Deserialize)]
-struct ScoreboardStatusType {
-    #[serde(default)]
-    completed: bool,
+    async fn get_score_snapshot(
+        &self,
+        event_id: i32,
+        taken_at: NaiveDateTime,
+    ) -> Result<Option<ScoresAndLastRefresh>, StorageError> {
+        self.r2_get_optional_json(&score_history_key(event_id, taken_at))
+            .await
+    }
 }
 
-impl ScoreboardFullStatus {
-    fn completed(&self) -> bool {
-        self.completed
-            || self
-                .status_type
-                .as_ref()
-                .is_some_and(|status| status.completed)
+impl ServerlessStorage {
+    async fn cache_ttls_for_event(&self, event_id: i32) -> (u64, i64) {
+        let details = self.get_event_details(event_id).await.ok();
+        derive_cache_ttls(
+            details.as_ref().and_then(|doc| doc.start_date.as_deref()),
+            details.as_ref().and_then(|doc| doc.end_date.as_deref()),
+            details.as_ref().map(|doc| doc.completed).unwrap_or(false),
+        )
     }
 }
@@ -7,6 +7,10 @@ impl ServerlessStorage {
         format!("events/{event_id}/scores.json")
     }
 
+    pub fn archive_key(event_id: i32) -> String {
+        format!("events/{event_id}/archive.json")
+    }
+
     pub fn espn_cache_key(event_id: i32) -> String {
         format!("cache/espn/{event_id}.json")
     }
@@ -35,10 +39,19 @@ impl ServerlessStorage {
         format!("event:{event_id}:{suffix}:seeded_at")
     }
 
+    pub fn kv_score_changes_key(event_id: i32) -> String {
+        format!("event:{event_id}:score_changes")
+    }
+
     pub fn kv_force_espn_fail_key(event_id: i32) -> String {
         format!("event:{event_id}:force_espn_fail")
     }
 
+    /// Golfers shared by every event, as a list of `GolferRecord`s.
+    pub fn kv_golfer_directory_key() -> &'static str {
+        "golfers:directory"
+    }
+
     pub fn kv_test_lock_key(event_id: i32) -> String {
         format!("event:{event_id}:test_lock")
     }
@@ -8,6 +8,15 @@ use rusty_golf_core::timed;
 
 impl ServerlessStorage {
     pub async fn r2_get_json<T>(&self, key: &str) -> Result<T, StorageError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        self.r2_get_optional_json(key)
+            .await?
+            .ok_or_else(|| StorageError::new(format!("R2 key missing: {key}")))
+    }
+
+    pub async fn r2_get_optional_json<T>(&self, key: &str) -> Result<Option<T>, StorageError>
     where
         T: for<'de> serde::Deserialize<'de>,
     {
@@ -21,7 +30,9 @@ impl ServerlessStorage {
                 .await
                 .map_err(|e| StorageError::new(e.to_string()))
         )?;
-        let obj = obj.ok_or_else(|| StorageError::new(format!("R2 key missing: {key}")))?;
+        let Some(obj) = obj else {
+            return Ok(None);
+        };
         let body = obj
             .body()
             .ok_or_else(|| StorageError::new(format!("R2 body missing for key: {key}")))?;
@@ -37,6 +48,7 @@ impl ServerlessStorage {
             "storage.r2_get_json_parse_ms",
             serde_json::from_str(&text).map_err(|e| StorageError::new(e.to_string()))
         )
+        .map(Some)
     }
 
     pub async fn r2_put_json<T>(&self, key: &str, value: &T) -> Result<(), StorageError>
@@ -70,10 +82,10 @@ impl ServerlessStorage {
         let mut cursor: Option<String> = None;
         loop {
             let mut builder = self.bucket.list();
-            if let Some(prefix_value) = prefix {
-                if !prefix_value.is_empty() {
-                    builder = builder.prefix(prefix_value.to_string());
-                }
+            if let Some(prefix_value) = prefix
+                && !prefix_value.is_empty()
+            {
+                builder = builder.prefix(prefix_value.to_string());
             }
             if let Some(cursor_value) = cursor {
                 builder = builder.cursor(cursor_value);
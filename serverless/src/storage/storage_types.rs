@@ -3,17 +3,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use rusty_golf_core::api::admin::{
+    AdminEupDataFill, AdminEupEvent, AdminEupEventUserPlayer, AdminEupGolfer, AdminSeedRequest,
+    EventListing,
+};
 use rusty_golf_core::model::RefreshSource;
-use rusty_golf_core::model::Scores;
-
-#[derive(Clone, Serialize)]
-pub struct EventListing {
-    pub event_id: i32,
-    pub event_name: String,
-    pub year: Option<i32>,
-    pub score_view_step_factor: f32,
-    pub refresh_from_espn: i64,
-}
 
 #[derive(Serialize, Deserialize)]
 pub struct EventDetailsDoc {
@@ -70,45 +64,3 @@ pub enum TestLockMode {
     Shared,
     Exclusive,
 }
-
-#[derive(Debug, Deserialize)]
-pub struct AdminSeedRequest {
-    pub event_id: i32,
-    pub refresh_from_espn: i64,
-    pub event: AdminEupEvent,
-    pub score_struct: Vec<Scores>,
-    pub espn_cache: serde_json::Value,
-    pub auth_tokens: Option<Vec<String>>,
-    pub last_refresh: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct AdminEupEvent {
-    pub event: i64,
-    pub name: String,
-    pub score_view_step_factor: serde_json::Value,
-    pub start_date: Option<String>,
-    pub end_date: Option<String>,
-    #[serde(default)]
-    pub completed: bool,
-    pub data_to_fill_if_event_and_year_missing: Vec<AdminEupDataFill>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct AdminEupDataFill {
-    pub golfers: Vec<AdminEupGolfer>,
-    pub event_user_player: Vec<AdminEupEventUserPlayer>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct AdminEupGolfer {
-    pub espn_id: i64,
-    pub name: String,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct AdminEupEventUserPlayer {
-    pub bettor: String,
-    pub golfer_espn_id: i64,
-    pub score_view_step_factor: Option<serde_json::Value>,
-}
@@ -4,6 +4,9 @@ use std::collections::HashMap;
 
 use worker::{Env, Request, Response, Result};
 
+use rusty_golf_core::export::ExportFile;
+use rusty_golf_core::score::ScoreHistoryRetention;
+
 use crate::storage::ServerlessStorage;
 
 pub fn parse_query_params(req: &Request) -> Result<HashMap<String, String>> {
@@ -24,6 +27,28 @@ pub fn respond_html(body: String) -> Result<Response> {
     Ok(resp)
 }
 
+pub fn respond_text(body: String, content_type: &str) -> Result<Response> {
+    let mut resp = Response::ok(body)?;
+    resp.headers_mut()
+        .set("Content-Type", content_type)
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Ok(resp)
+}
+
+/// Send an export as a download, named by its `Content-Disposition`.
+pub fn respond_file(file: ExportFile) -> Result<Response> {
+    let disposition = file.content_disposition();
+    let mut resp = Response::from_bytes(file.body)?;
+    let headers = resp.headers_mut();
+    headers
+        .set("Content-Type", file.content_type)
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    headers
+        .set("Content-Disposition", &disposition)
+        .map_err(|e| worker::Error::RustError(e.to_string()))?;
+    Ok(resp)
+}
+
 // pub fn escape_html(value: &str) -> String {
 //     value
 //         .replace('&', "&amp;")
@@ -52,9 +77,29 @@ pub fn read_env_binding(env: &Env, var_name: &str) -> Result<String> {
 pub fn storage_from_env(env: &Env) -> Result<ServerlessStorage> {
     let kv_binding = read_env_binding(env, "KV_BINDING")?;
     let r2_binding = read_env_binding(env, "R2_BINDING")?;
-    ServerlessStorage::from_env(env, &kv_binding, &r2_binding).map_err(|e| {
+    let storage = ServerlessStorage::from_env(env, &kv_binding, &r2_binding).map_err(|e| {
         worker::Error::RustError(format!(
             "Storage binding error (KV_BINDING={kv_binding}, R2_BINDING={r2_binding}): {e}"
         ))
-    })
+    })?;
+    Ok(storage.with_history_retention(history_retention_from_env(env)))
+}
+
+/// `SCORE_HISTORY_MAX_SNAPSHOTS` and `SCORE_HISTORY_MIN_INTERVAL_SECS` override the
+/// defaults; unset or unparsable values keep them. A max of 0 turns snapshots off.
+fn history_retention_from_env(env: &Env) -> ScoreHistoryRetention {
+    let defaults = ScoreHistoryRetention::default();
+    let read = |name: &str| {
+        env.var(name)
+            .ok()
+            .and_then(|value| value.to_string().trim().parse::<i64>().ok())
+            .filter(|value| *value >= 0)
+    };
+    ScoreHistoryRetention {
+        max_snapshots: read("SCORE_HISTORY_MAX_SNAPSHOTS")
+            .and_then(|value| usize::try_from(value).ok())
+            .unwrap_or(defaults.max_snapshots),
+        min_interval_seconds: read("SCORE_HISTORY_MIN_INTERVAL_SECS")
+            .unwrap_or(defaults.min_interval_seconds),
+    }
 }
@@ -0,0 +1,112 @@
+use anyhow::{Context, Result, anyhow, bail};
+use rusty_golf_core::storage::EventArchive;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+use crate::config::WorkerAccess;
+
+/// Fetch an event archive from a deployed worker's `/admin/export`.
+///
+/// # Errors
+/// Returns an error if the request fails, the worker refuses it, or the response is not a
+/// valid archive.
+pub fn export_event_from_worker(worker: &WorkerAccess, event_id: i32) -> Result<EventArchive> {
+    let response = admin_post(worker, "export", &json!({ "event_id": event_id }))?;
+    let archive: EventArchive = response.json().context("parse exported archive")?;
+    archive
+        .validate()
+        .map_err(|e| anyhow!("worker returned a bad archive: {e}"))?;
+    Ok(archive)
+}
+
+/// Send an event archive to a deployed worker's `/admin/import`.
+///
+/// # Errors
+/// Returns an error if the archive is invalid, the request fails, or the worker refuses it
+/// (for example because the event exists and `replace` is false).
+pub fn import_event_to_worker(
+    worker: &WorkerAccess,
+    archive: &EventArchive,
+    replace: bool,
+) -> Result<()> {
+    archive
+        .validate()
+        .map_err(|e| anyhow!("invalid archive: {e}"))?;
+    admin_post(
+        worker,
+        "import",
+        &json!({ "archive": archive, "replace": replace }),
+    )?;
+    Ok(())
+}
+
+/// `--mode export_event`: write the worker's archive to a file or stdout.
+///
+/// # Errors
+/// Returns an error if the export fails or the output can't be written.
+pub fn run_export_event(
+    worker: &WorkerAccess,
+    event_id: i32,
+    output_json: Option<&Path>,
+    output_json_stdout: bool,
+) -> Result<()> {
+    let archive = export_event_from_worker(worker, event_id)?;
+    let json = serde_json::to_string_pretty(&archive).context("serialize archive")?;
+    if output_json_stdout {
+        println!("{json}");
+        return Ok(());
+    }
+    let Some(output_json) = output_json else {
+        return Err(anyhow!("missing output path for exported archive"));
+    };
+    fs::write(output_json, json).with_context(|| format!("write {}", output_json.display()))?;
+    println!(
+        "exported event {event_id} ({} picks) to {}",
+        archive.assignments.len(),
+        output_json.display()
+    );
+    Ok(())
+}
+
+/// `--mode import_event`: load an archive file into the worker.
+///
+/// # Errors
+/// Returns an error if the file can't be read or parsed, or the import fails.
+pub fn run_import_event(worker: &WorkerAccess, archive_json: &Path, replace: bool) -> Result<()> {
+    let contents = fs::read_to_string(archive_json)
+        .with_context(|| format!("read {}", archive_json.display()))?;
+    let archive: EventArchive = serde_json::from_str(&contents)
+        .with_context(|| format!("parse {}", archive_json.display()))?;
+    import_event_to_worker(worker, &archive, replace)?;
+    println!(
+        "imported event {} ({} picks) from {}",
+        archive.event_id,
+        archive.assignments.len(),
+        archive_json.display()
+    );
+    Ok(())
+}
+
+fn admin_post(
+    worker: &WorkerAccess,
+    endpoint: &str,
+    body: &serde_json::Value,
+) -> Result<reqwest::blocking::Response> {
+    let url = format!(
+        "{}/admin/{endpoint}",
+        worker.worker_url.trim_end_matches('/')
+    );
+    let response = reqwest::blocking::Client::new()
+        .post(&url)
+        .header("x-admin-token", &worker.admin_token)
+        .json(body)
+        .send()
+        .with_context(|| format!("POST {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().unwrap_or_default();
+        bail!("POST {url} failed with {status}: {text}");
+    }
+    Ok(response)
+}
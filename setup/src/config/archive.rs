@@ -0,0 +1,72 @@
+use super::cli::{Cli, FileConfig};
+use super::{AppMode, WorkerAccess};
+use anyhow::{Result, anyhow};
+
+pub(crate) fn build_export_event_mode(cli: &Cli, file_config: &FileConfig) -> Result<AppMode> {
+    let worker = resolve_worker_access(cli, file_config)?;
+    let event_id = cli
+        .event_id
+        .clone()
+        .or_else(|| {
+            file_config
+                .event_id
+                .as_ref()
+                .map(super::cli::EventIdConfig::as_string)
+        })
+        .ok_or_else(|| anyhow!("missing --event-id for --mode=export_event"))?;
+    let event_id = event_id
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| anyhow!("--mode=export_event takes a single numeric --event-id"))?;
+
+    let output_json = cli
+        .output_json
+        .clone()
+        .or_else(|| file_config.output_json.clone());
+    let output_json_stdout =
+        cli.output_json_stdout || file_config.output_json_stdout.unwrap_or(false);
+    if output_json.is_none() && !output_json_stdout {
+        return Err(anyhow!(
+            "missing --output-json or --output-json-stdout for --mode=export_event"
+        ));
+    }
+
+    Ok(AppMode::ExportEvent {
+        worker,
+        event_id,
+        output_json,
+        output_json_stdout,
+    })
+}
+
+pub(crate) fn build_import_event_mode(cli: &Cli, file_config: &FileConfig) -> Result<AppMode> {
+    let worker = resolve_worker_access(cli, file_config)?;
+    let archive_json = cli
+        .archive_json
+        .clone()
+        .or_else(|| file_config.archive_json.clone())
+        .ok_or_else(|| anyhow!("missing --archive-json for --mode=import_event"))?;
+    Ok(AppMode::ImportEvent {
+        worker,
+        archive_json,
+        replace: cli.replace || file_config.replace.unwrap_or(false),
+    })
+}
+
+fn resolve_worker_access(cli: &Cli, file_config: &FileConfig) -> Result<WorkerAccess> {
+    let worker_url = cli
+        .worker_url
+        .clone()
+        .or_else(|| file_config.worker_url.clone())
+        .ok_or_else(|| anyhow!("missing --worker-url"))?;
+    let admin_token = cli
+        .admin_token
+        .clone()
+        .or_else(|| file_config.admin_token.clone())
+        .or_else(|| std::env::var("ADMIN_TOKEN").ok())
+        .ok_or_else(|| anyhow!("missing --admin-token (or ADMIN_TOKEN)"))?;
+    Ok(WorkerAccess {
+        worker_url,
+        admin_token,
+    })
+}
@@ -46,6 +46,17 @@ pub struct Cli {
         long_help = "Golfer assignments. Format: JSON array of {\"bettor\":\"Name\",\"golfer\":\"Golfer Name\"} entries. Example: --golfers-by-bettor='[{\"bettor\":\"Alice\",\"golfer\":\"Rory McIlroy\"},{\"bettor\":\"Bob\",\"golfer\":\"Jon Rahm\"}]'"
     )]
     pub golfers_by_bettor: Option<String>,
+    #[arg(
+        long,
+        help = "Base URL of the deployed worker, for export_event and import_event."
+    )]
+    pub worker_url: Option<String>,
+    #[arg(long, help = "Worker admin token; defaults to $ADMIN_TOKEN.")]
+    pub admin_token: Option<String>,
+    #[arg(long, help = "Event archive to import.")]
+    pub archive_json: Option<PathBuf>,
+    #[arg(long, help = "Let import_event delete and rebuild an existing event.")]
+    pub replace: bool,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -69,6 +80,10 @@ pub(crate) struct FileConfig {
     pub one_shot: Option<bool>,
     #[serde(rename = "golfers-by-bettor")]
     pub golfers_by_bettor: Option<GolfersByBettorConfig>,
+    pub worker_url: Option<String>,
+    pub admin_token: Option<String>,
+    pub archive_json: Option<PathBuf>,
+    pub replace: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -3,6 +3,7 @@ use anyhow::{Context, Result, anyhow};
 use clap::ValueEnum;
 use serde::Deserialize;
 
+mod archive;
 mod cli;
 mod get_event_details;
 mod new_event;
@@ -23,6 +24,10 @@ pub enum Mode {
     GetEventDetails,
     #[value(name = "update_event", alias = "edit_event")]
     UpdateEvent,
+    #[value(name = "export_event")]
+    ExportEvent,
+    #[value(name = "import_event")]
+    ImportEvent,
 }
 
 pub enum AppMode {
@@ -47,6 +52,17 @@ pub enum AppMode {
         output_json: Option<std::path::PathBuf>,
         kv_access: KvAccessConfig,
     },
+    ExportEvent {
+        worker: WorkerAccess,
+        event_id: i32,
+        output_json: Option<std::path::PathBuf>,
+        output_json_stdout: bool,
+    },
+    ImportEvent {
+        worker: WorkerAccess,
+        archive_json: std::path::PathBuf,
+        replace: bool,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -63,6 +79,12 @@ pub struct KvAccessConfig {
     pub wrangler_config_dir: Option<std::path::PathBuf>,
 }
 
+/// A deployed worker's admin endpoints.
+pub struct WorkerAccess {
+    pub worker_url: String,
+    pub admin_token: String,
+}
+
 /// Load config from CLI and optional TOML file.
 ///
 /// # Errors
@@ -82,6 +104,8 @@ pub fn load_config(cli: &Cli) -> Result<AppMode> {
         Mode::NewEvent => new_event::build_new_event_mode(cli, &file_config),
         Mode::GetEventDetails => get_event_details::build_get_event_details_mode(cli, &file_config),
         Mode::UpdateEvent => update_event::build_update_event_mode(cli, &file_config),
+        Mode::ExportEvent => archive::build_export_event_mode(cli, &file_config),
+        Mode::ImportEvent => archive::build_import_event_mode(cli, &file_config),
     }
 }
 
@@ -1,3 +1,4 @@
+pub mod archive;
 pub mod config;
 pub mod espn;
 pub mod event_details;
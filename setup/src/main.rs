@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::Parser;
+use rusty_golf_setup::archive::{run_export_event, run_import_event};
 use rusty_golf_setup::config::{AppMode, Cli, load_config};
 use rusty_golf_setup::repl::{
     run_get_event_details_one_shot, run_new_event_one_shot, run_new_event_repl,
@@ -51,5 +52,21 @@ fn main() -> Result<()> {
             output_json,
             kv_access,
         } => run_update_event_repl(eup_json, output_json, kv_access),
+        AppMode::ExportEvent {
+            worker,
+            event_id,
+            output_json,
+            output_json_stdout,
+        } => run_export_event(
+            &worker,
+            event_id,
+            output_json.as_deref(),
+            output_json_stdout,
+        ),
+        AppMode::ImportEvent {
+            worker,
+            archive_json,
+            replace,
+        } => run_import_event(&worker, &archive_json, replace),
     }
 }
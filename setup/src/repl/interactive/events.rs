@@ -211,6 +211,16 @@ pub(super) fn handle_get_event_details(
     Ok(())
 }
 
+fn print_events(events: &[(String, String)]) {
+    if events.is_empty() {
+        println!("No events found.");
+        return;
+    }
+    for (id, name) in events {
+        println!("{id} {name}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{ListEventsAction, parse_list_events_mode};
@@ -247,13 +257,3 @@ mod tests {
         assert_eq!(parsed, Some((ListEventsAction::RefreshKv, false)));
     }
 }
-
-fn print_events(events: &[(String, String)]) {
-    if events.is_empty() {
-        println!("No events found.");
-        return;
-    }
-    for (id, name) in events {
-        println!("{id} {name}");
-    }
-}
@@ -18,7 +18,6 @@ document.addEventListener("DOMContentLoaded", function () {
         if (cache) scoresUrl += `&cache=${cache}`;
 
         htmx.ajax('GET', scoresUrl, { target: '#scores', swap: 'innerHTML' });
-    } else {
-        console.error("event or yr parameters are missing in the URL.");
     }
+    // `/` without `event` is the events index, rendered by the server.
 });
\ No newline at end of file
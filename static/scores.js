@@ -174,3 +174,126 @@ function filterRound(round) {
 // } else {
 //     initThemeToggle();
 // }
+
+// Live scores: follow the `/scores/stream` Server-Sent Events named by `#live-updates`.
+// `standings` reloads `#scores` through htmx; `changes` lists the newest holes and rounds.
+// The worker has no stream, so a failed connection is simply dropped.
+const liveScores = {
+    source: null,
+    url: null,
+    recent: [],
+    maxRecent: 5,
+};
+
+function renderLiveChanges() {
+    const container = document.getElementById('live-updates');
+    if (!container) {
+        return;
+    }
+    container.replaceChildren();
+    if (liveScores.recent.length === 0) {
+        return;
+    }
+    const list = document.createElement('ul');
+    liveScores.recent.forEach(change => {
+        const item = document.createElement('li');
+        item.textContent = change.message;
+        list.appendChild(item);
+    });
+    container.appendChild(list);
+}
+
+function connectLiveScores() {
+    const container = document.getElementById('live-updates');
+    if (!container || typeof EventSource === 'undefined') {
+        return;
+    }
+    const url = container.getAttribute('data-stream-url');
+    const scoresUrl = container.getAttribute('data-scores-url');
+    renderLiveChanges();
+    if (!url || url === liveScores.url) {
+        return;
+    }
+    if (liveScores.source) {
+        liveScores.source.close();
+    }
+    liveScores.url = url;
+    liveScores.recent = [];
+    const source = new EventSource(url);
+    liveScores.source = source;
+    let initial = true;
+
+    source.addEventListener('standings', () => {
+        // The first frame is what the page already shows.
+        if (initial) {
+            initial = false;
+            return;
+        }
+        htmx.ajax('GET', scoresUrl, { target: '#scores', swap: 'innerHTML' });
+    });
+
+    source.addEventListener('changes', (event) => {
+        const changes = JSON.parse(event.data);
+        liveScores.recent = changes.reverse().concat(liveScores.recent).slice(0, liveScores.maxRecent);
+        renderLiveChanges();
+    });
+
+    source.addEventListener('error', () => {
+        // EventSource retries dropped connections itself, but gives up on error responses;
+        // keeping `url` stops later swaps from asking again.
+        if (source.readyState === EventSource.CLOSED) {
+            liveScores.source = null;
+        }
+    });
+}
+
+document.addEventListener('htmx:afterSwap', connectLiveScores);
+document.addEventListener('DOMContentLoaded', connectLiveScores);
+
+// Auto-refresh countdown: `#next-update` names the interval the fragments re-poll at.
+// The countdown restarts whenever `#linescore` (or the whole of `#scores`) reloads; the
+// `#linescore` response swaps in a new `#next-update` first, so a changed interval is picked up.
+const nextUpdate = {
+    deadline: 0,
+    timer: null,
+};
+
+function formatCountdown(seconds) {
+    const minutes = Math.floor(seconds / 60);
+    const rest = String(seconds % 60).padStart(2, '0');
+    return `${minutes}:${rest}`;
+}
+
+function tickNextUpdate() {
+    const label = document.querySelector('#next-update .next-update-countdown');
+    if (!label) {
+        clearInterval(nextUpdate.timer);
+        nextUpdate.timer = null;
+        return;
+    }
+    const remaining = Math.max(0, Math.ceil((nextUpdate.deadline - Date.now()) / 1000));
+    label.textContent = formatCountdown(remaining);
+}
+
+function restartNextUpdate(event) {
+    const indicator = document.getElementById('next-update');
+    if (!indicator) {
+        return;
+    }
+    const target = event && event.detail ? event.detail.target : null;
+    if (target && target.id !== 'linescore' && !target.contains(indicator)) {
+        return;
+    }
+    const seconds = parseInt(indicator.getAttribute('data-refresh-seconds'), 10);
+    if (!Number.isFinite(seconds) || seconds <= 0) {
+        return;
+    }
+    nextUpdate.deadline = Date.now() + seconds * 1000;
+    tickNextUpdate();
+    if (!nextUpdate.timer) {
+        nextUpdate.timer = setInterval(tickNextUpdate, 1000);
+    }
+}
+
+document.addEventListener('htmx:afterSwap', restartNextUpdate);
+document.addEventListener('DOMContentLoaded', restartNextUpdate);
@@ -0,0 +1,313 @@
+//! Behavior every `AdminStorage` backend must share.
+//!
+//! The checks build [`ADMIN_EVENT_ID`] from scratch, so they run against any storage that
+//! doesn't already hold that event or golfers with the ids in [`golfers`].
+
+use rusty_golf_core::model::RefreshSource;
+use rusty_golf_core::storage::{
+    AdminStorage, Assignment, CompactionPolicy, EventConfig, GolferRecord, PlayerStepFactor,
+    archive_completed_event,
+};
+use std::collections::HashMap;
+
+use super::storage_conformance::HarnessResult;
+
+pub const ADMIN_EVENT_ID: i32 = 401_999_001;
+
+fn config(event_name: &str, completed: bool) -> EventConfig {
+    EventConfig {
+        event_name: event_name.to_string(),
+        year: 2025,
+        score_view_step_factor: 2.5,
+        refresh_from_espn: 1,
+        start_date: Some("2025-07-17T00:00:00Z".to_string()),
+        end_date: Some("2025-07-20T23:00:00Z".to_string()),
+        completed,
+    }
+}
+
+fn golfers() -> Vec<GolferRecord> {
+    [
+        (900_001, "Admin Golfer One"),
+        (900_002, "Admin Golfer Two"),
+        (900_003, "Admin Golfer 3"),
+    ]
+    .into_iter()
+    .map(|(espn_id, name)| GolferRecord {
+        espn_id,
+        name: name.to_string(),
+    })
+    .collect()
+}
+
+fn assignment(bettor_name: &str, golfer_espn_id: i64) -> Assignment {
+    Assignment {
+        bettor_name: bettor_name.to_string(),
+        golfer_espn_id,
+    }
+}
+
+fn factor(golfer_espn_id: i64, bettor_name: &str, step_factor: f32) -> PlayerStepFactor {
+    PlayerStepFactor {
+        golfer_espn_id,
+        bettor_name: bettor_name.to_string(),
+        step_factor,
+    }
+}
+
+/// (bettor, golfer name, group), sorted: pick order is backend-specific.
+async fn picks(storage: &dyn AdminStorage) -> HarnessResult<Vec<(String, String, i64)>> {
+    let mut picks: Vec<_> = storage
+        .get_golfers_for_event(ADMIN_EVENT_ID)
+        .await?
+        .into_iter()
+        .map(|pick| (pick.bettor_name, pick.golfer_name, pick.group))
+        .collect();
+    picks.sort();
+    Ok(picks)
+}
+
+fn pick(bettor: &str, golfer: &str, group: i64) -> (String, String, i64) {
+    (bettor.to_string(), golfer.to_string(), group)
+}
+
+/// Run every admin check in order against `storage`; `name` labels failures.
+///
+/// # Errors
+/// Returns the first storage call that fails unexpectedly. Contract violations panic
+/// with the backend name in the message.
+pub async fn run_admin_conformance(name: &str, storage: &dyn AdminStorage) -> HarnessResult<()> {
+    let id = ADMIN_EVENT_ID;
+
+    // Nothing can be administered before the event exists.
+    assert!(
+        storage
+            .update_event(id, &config("Open", false))
+            .await
+            .is_err(),
+        "{name}: updating a missing event should fail"
+    );
+    assert!(
+        storage
+            .set_assignments(id, &[assignment("Alice", 900_001)])
+            .await
+            .is_err(),
+        "{name}: assigning picks to a missing event should fail"
+    );
+    assert!(
+        storage.set_player_step_factors(id, &[]).await.is_err(),
+        "{name}: setting factors on a missing event should fail"
+    );
+
+    storage.create_event(id, &config("Open", false)).await?;
+    assert!(
+        storage
+            .create_event(id, &config("Open", false))
+            .await
+            .is_err(),
+        "{name}: creating an existing event should fail"
+    );
+    assert!(
+        storage.list_events().await?.contains(&id),
+        "{name}: a created event is listed"
+    );
+    let details = storage.get_event_details(id).await?;
+    assert_eq!(details.event_name, "Open", "{name}: event name");
+    assert_eq!(details.score_view_step_factor, 2.5, "{name}: step factor");
+    assert!(
+        storage.get_golfers_for_event(id).await?.is_empty(),
+        "{name}: a new event has no picks"
+    );
+
+    // Golfers are upserted: the last name wins.
+    storage.upsert_golfers(&golfers()).await?;
+    storage
+        .upsert_golfers(&[GolferRecord {
+            espn_id: 900_003,
+            name: "Admin Golfer Three".to_string(),
+        }])
+        .await?;
+
+    assert!(
+        storage
+            .set_assignments(id, &[assignment("Alice", 999_999)])
+            .await
+            .is_err(),
+        "{name}: a pick of an unknown golfer should fail"
+    );
+    storage
+        .set_assignments(
+            id,
+            &[
+                assignment("Alice", 900_001),
+                assignment("Bob", 900_002),
+                assignment("Alice", 900_003),
+            ],
+        )
+        .await?;
+    assert_eq!(
+        picks(storage).await?,
+        vec![
+            pick("Alice", "Admin Golfer One", 1),
+            pick("Alice", "Admin Golfer Three", 2),
+            pick("Bob", "Admin Golfer Two", 1),
+        ],
+        "{name}: picks grouped per bettor in order"
+    );
+
+    // Step factors replace each other wholesale.
+    storage
+        .set_player_step_factors(id, &[factor(900_003, "Alice", 4.5)])
+        .await?;
+    assert_eq!(
+        storage.get_player_step_factors(id).await?,
+        HashMap::from([((900_003, "Alice".to_string()), 4.5)]),
+        "{name}: step factors"
+    );
+    storage
+        .set_player_step_factors(id, &[factor(900_002, "Bob", 2.0)])
+        .await?;
+    assert_eq!(
+        storage.get_player_step_factors(id).await?,
+        HashMap::from([((900_002, "Bob".to_string()), 2.0)]),
+        "{name}: step factors are replaced, not merged"
+    );
+    assert!(
+        storage
+            .set_player_step_factors(id, &[factor(900_001, "Bob", 1.0)])
+            .await
+            .is_err(),
+        "{name}: a factor for a pick the event doesn't have should fail"
+    );
+
+    // New picks drop the scores and factors that belonged to the old ones.
+    let golfers = storage.get_golfers_for_event(id).await?;
+    storage.store_scores(id, &golfers).await?;
+    assert!(storage.event_and_scores_already_in_db(id, 300).await?);
+    storage
+        .set_assignments(id, &[assignment("Carol", 900_002)])
+        .await?;
+    assert_eq!(
+        picks(storage).await?,
+        vec![pick("Carol", "Admin Golfer Two", 1)],
+        "{name}: picks are replaced"
+    );
+    assert!(
+        storage.get_player_step_factors(id).await?.is_empty(),
+        "{name}: new picks start without step factors"
+    );
+    assert!(
+        storage.get_scores(id, RefreshSource::Db).await.is_err(),
+        "{name}: new picks drop stored scores"
+    );
+    assert!(
+        !storage.event_and_scores_already_in_db(id, 300).await?,
+        "{name}: new picks are never fresh"
+    );
+
+    // Updating the event leaves its picks alone.
+    storage.update_event(id, &config("The Open", true)).await?;
+    let details = storage.get_event_details(id).await?;
+    assert_eq!(details.event_name, "The Open", "{name}: updated name");
+    assert!(details.completed, "{name}: updated completion");
+    assert_eq!(
+        picks(storage).await?.len(),
+        1,
+        "{name}: picks survive an update"
+    );
+
+    assert!(
+        storage.delete_event(id).await?,
+        "{name}: delete reports the event existed"
+    );
+    assert!(
+        storage.get_event_details(id).await.is_err(),
+        "{name}: a deleted event is gone"
+    );
+    assert!(
+        !storage.list_events().await?.contains(&id),
+        "{name}: a deleted event is no longer listed"
+    );
+    assert!(
+        storage.get_golfers_for_event(id).await?.is_empty(),
+        "{name}: a deleted event has no picks"
+    );
+    assert!(
+        !storage.delete_event(id).await?,
+        "{name}: deleting a missing event reports it didn't exist"
+    );
+
+    // Golfers outlive the events that pick them.
+    storage.create_event(id, &config("Open", false)).await?;
+    storage
+        .set_assignments(id, &[assignment("Alice", 900_001)])
+        .await?;
+    assert_eq!(
+        picks(storage).await?,
+        vec![pick("Alice", "Admin Golfer One", 1)],
+        "{name}: golfers survive deleting an event"
+    );
+
+    // Only a finished event with scores can be archived, and only once.
+    let policy = CompactionPolicy::default();
+    assert!(
+        archive_completed_event(storage, id, &policy).await.is_err(),
+        "{name}: an unfinished event can't be archived"
+    );
+    storage.mark_event_completed(id, None).await?;
+    assert!(
+        archive_completed_event(storage, id, &policy).await.is_err(),
+        "{name}: an event without scores can't be archived"
+    );
+    storage
+        .set_player_step_factors(id, &[factor(900_001, "Alice", 3.0)])
+        .await?;
+    let golfers = storage.get_golfers_for_event(id).await?;
+    storage.store_scores(id, &golfers).await?;
+    archive_completed_event(storage, id, &policy).await?;
+    assert!(
+        archive_completed_event(storage, id, &policy).await.is_err(),
+        "{name}: an archived event can't be archived again"
+    );
+
+    // Whatever the backend dropped, reads still answer from the archive.
+    let archive = storage
+        .get_event_archive(id)
+        .await?
+        .unwrap_or_else(|| panic!("{name}: the archive is stored"));
+    assert_eq!(
+        archive.assignments,
+        vec![assignment("Alice", 900_001)],
+        "{name}: archived picks"
+    );
+    assert!(
+        storage.get_event_details(id).await?.completed,
+        "{name}: an archived event keeps its details"
+    );
+    assert_eq!(
+        picks(storage).await?,
+        vec![pick("Alice", "Admin Golfer One", 1)],
+        "{name}: picks of an archived event"
+    );
+    assert_eq!(
+        storage.get_player_step_factors(id).await?,
+        HashMap::from([((900_001, "Alice".to_string()), 3.0)]),
+        "{name}: step factors of an archived event"
+    );
+    assert_eq!(
+        storage
+            .get_scores(id, RefreshSource::Db)
+            .await?
+            .score_struct
+            .len(),
+        1,
+        "{name}: scores of an archived event"
+    );
+
+    assert!(storage.delete_event(id).await?);
+    assert!(
+        storage.get_event_archive(id).await?.is_none(),
+        "{name}: deleting an event drops its archive"
+    );
+    Ok(())
+}
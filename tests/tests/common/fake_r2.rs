@@ -0,0 +1,110 @@
+//! A stand-in S3 endpoint for driving `R2Storage` without Cloudflare.
+//!
+//! Objects are held in memory keyed by `<bucket>/<key>`; requests are accepted whatever their
+//! signature. `ListObjectsV2` answers every matching key in one page. Start it from an actix
+//! runtime (`#[actix_web::test]`).
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+use rusty_golf_actix::storage::{R2Storage, R2StorageConfig};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+pub const BUCKET: &str = "golf";
+
+/// Objects kept by the fake endpoint, keyed by `<bucket>/<key>`.
+pub type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+async fn handle(req: HttpRequest, body: web::Bytes, objects: web::Data<Objects>) -> HttpResponse {
+    let key = req.path().trim_start_matches('/').to_string();
+    let mut objects = objects.lock().unwrap();
+    match req.method().as_str() {
+        "PUT" => {
+            objects.insert(key, body.to_vec());
+            HttpResponse::Ok().finish()
+        }
+        "GET" if req.query_string().contains("list-type=2") => {
+            let query = web::Query::<HashMap<String, String>>::from_query(req.query_string())
+                .map(web::Query::into_inner)
+                .unwrap_or_default();
+            let prefix = format!("{key}/{}", query.get("prefix").map_or("", String::as_str));
+            let mut keys: Vec<&str> = objects
+                .keys()
+                .filter_map(|stored| {
+                    stored
+                        .strip_prefix(&prefix)
+                        .map(|_| &stored[key.len() + 1..])
+                })
+                .collect();
+            keys.sort_unstable();
+            let contents: String = keys
+                .iter()
+                .map(|key| format!("<Contents><Key>{key}</Key></Contents>"))
+                .collect();
+            HttpResponse::Ok().content_type("application/xml").body(format!(
+                "<ListBucketResult><IsTruncated>false</IsTruncated>{contents}</ListBucketResult>"
+            ))
+        }
+        "GET" => match objects.get(&key) {
+            Some(bytes) => HttpResponse::Ok().body(bytes.clone()),
+            None => HttpResponse::NotFound().finish(),
+        },
+        "DELETE" => {
+            objects.remove(&key);
+            HttpResponse::NoContent().finish()
+        }
+        _ => HttpResponse::MethodNotAllowed().finish(),
+    }
+}
+
+pub struct FakeR2 {
+    pub objects: Objects,
+    pub storage: R2Storage,
+}
+
+impl FakeR2 {
+    /// Bind to an ephemeral port and return an `R2Storage` pointed at it.
+    ///
+    /// # Errors
+    /// Returns an error if the server can't bind.
+    pub fn start() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let objects = Objects::default();
+        let app_objects = objects.clone();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(app_objects.clone()))
+                .default_service(web::to(handle))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))?;
+        let addr = server.addrs()[0];
+        actix_web::rt::spawn(server.run());
+
+        let config = R2StorageConfig {
+            endpoint: format!("http://{addr}"),
+            bucket: BUCKET.to_string(),
+            region: "auto".to_string(),
+            access_key_id: "test".to_string(),
+            secret_access_key: "test".to_string(),
+            service: "s3".to_string(),
+        };
+        let storage = R2Storage::new(config.clone(), Arc::new(config.signer()));
+        Ok(Self { objects, storage })
+    }
+
+    /// Write `value` as JSON under `key`, bypassing `R2Storage`.
+    ///
+    /// # Errors
+    /// Returns an error if `value` can't be serialized.
+    pub fn put<T: serde::Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .insert(format!("{BUCKET}/{key}"), serde_json::to_vec(value)?);
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 #![allow(dead_code)]
+use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use rusty_golf_actix::args::CleanArgs;
+use rusty_golf_actix::args::{CleanArgs, StorageBackend};
 use sql_middleware::SqlMiddlewareDbError;
 use sql_middleware::middleware::{
     ConfigAndPool, DatabaseType, MiddlewarePoolConnection, ResultSet, RowValues, SqliteOptions,
@@ -12,7 +13,31 @@ pub struct TestContext {
     pub args: CleanArgs,
 }
 
+pub mod admin_conformance;
+pub mod fake_r2;
+pub mod pages;
 pub mod serverless;
+pub mod storage_conformance;
+
+/// A fresh directory under the system temp dir, removed on drop.
+pub struct TempDataDir(pub PathBuf);
+
+impl TempDataDir {
+    #[must_use]
+    pub fn new(prefix: &str) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time went backwards")
+            .as_nanos();
+        Self(std::env::temp_dir().join(format!("{prefix}_{nanos}")))
+    }
+}
+
+impl Drop for TempDataDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
 
 pub async fn setup_test_context(fixture_sql: &str) -> Result<TestContext, SqlMiddlewareDbError> {
     let db_name = format!(
@@ -35,6 +60,13 @@ pub async fn setup_test_context(fixture_sql: &str) -> Result<TestContext, SqlMid
         db_startup_script: None,
         db_populate_json: None,
         combined_sql_script: String::new(),
+        migrate: false,
+        storage: StorageBackend::Sql,
+        data_dir: None,
+        r2_endpoint: None,
+        r2_bucket: None,
+        r2_region: None,
+        command: None,
     };
 
     execute_batch(
@@ -49,6 +81,10 @@ pub async fn setup_test_context(fixture_sql: &str) -> Result<TestContext, SqlMid
         include_str!("../../../actix/src/sql/schema/sqlite/03_bettor.sql"),
         include_str!("../../../actix/src/sql/schema/sqlite/04_event_user_player.sql"),
         include_str!("../../../actix/src/sql/schema/sqlite/05_eup_statistic.sql"),
+        include_str!("../../../actix/src/sql/schema/sqlite/06_eup_statistic_hx.sql"),
+        include_str!("../../../actix/src/sql/schema/sqlite/07_eup_statistic_hx_trigger.sql"),
+        include_str!("../../../actix/src/sql/schema/sqlite/08_score_change.sql"),
+        include_str!("../../../actix/src/sql/schema/sqlite/09_event_archive.sql"),
     ]
     .join("\n");
     execute_batch(&config_and_pool, &schema).await?;
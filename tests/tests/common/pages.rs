@@ -0,0 +1,122 @@
+//! The prefill events in memory, and the actix page routes to request them through.
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use rusty_golf_actix::controller::bettor::bettor;
+use rusty_golf_actix::controller::calendar::tee_times;
+use rusty_golf_actix::controller::export::export;
+use rusty_golf_actix::controller::golfer::golfer;
+use rusty_golf_actix::controller::score::{scores, scores_chart, scores_linescore};
+use rusty_golf_core::model::Scores;
+use rusty_golf_core::storage::{AdminStorage, EventConfig, InMemoryStorage, Storage};
+use std::error::Error;
+use std::sync::Arc;
+
+/// The 2024 PGA Championship.
+pub const EVENT_ID: i32 = 401_580_351;
+pub const YEAR: i32 = 2024;
+pub const RORY: i64 = 3470;
+pub const PGA_START: &str = "2024-05-16T11:00:00Z";
+pub const PGA_END: &str = "2024-05-19T23:00:00Z";
+
+/// Every event, golfer and pick in `test05_dbprefill.json`, with no scores stored.
+pub fn prefilled() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let json = serde_json::from_str(include_str!("../test05_dbprefill.json"))?;
+    Ok(Arc::new(InMemoryStorage::from_prefill_json(&json)?))
+}
+
+/// Give `event_id` its year and dates, refreshed from ESPN and not yet completed.
+pub async fn schedule(
+    storage: &InMemoryStorage,
+    event_id: i32,
+    year: i32,
+    start: Option<&str>,
+    end: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let details = storage.get_event_details(event_id).await?;
+    let config = EventConfig {
+        event_name: details.event_name,
+        year,
+        score_view_step_factor: details.score_view_step_factor,
+        refresh_from_espn: 1,
+        start_date: start.map(str::to_string),
+        end_date: end.map(str::to_string),
+        completed: false,
+    };
+    storage.update_event(event_id, &config).await?;
+    Ok(())
+}
+
+/// The prefill with the PGA Championship scheduled for its real dates.
+pub async fn scheduled_pga() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = prefilled()?;
+    schedule(&storage, EVENT_ID, YEAR, Some(PGA_START), Some(PGA_END)).await?;
+    Ok(storage)
+}
+
+/// Store every pick of `event_id` as fresh scores, after `edit`.
+pub async fn store_golfers(
+    storage: &InMemoryStorage,
+    event_id: i32,
+    mut edit: impl FnMut(&mut Scores),
+) -> Result<(), Box<dyn Error>> {
+    let mut golfers = storage.get_golfers_for_event(event_id).await?;
+    golfers.iter_mut().for_each(&mut edit);
+    storage.store_scores(event_id, &golfers).await?;
+    Ok(())
+}
+
+pub struct Response {
+    pub status: StatusCode,
+    pub content_type: String,
+    pub disposition: String,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    #[must_use]
+    pub fn text(&self) -> &str {
+        std::str::from_utf8(&self.body).expect("a UTF-8 body")
+    }
+}
+
+/// GET `uri` from an app serving `storage` on the page routes of `main.rs`.
+pub async fn get(storage: &Arc<InMemoryStorage>, uri: &str) -> Response {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage.clone() as Arc<dyn Storage>))
+            .route("/scores", web::get().to(scores))
+            .route("/scores/chart", web::get().to(scores_chart))
+            .route("/scores/linescore", web::get().to(scores_linescore))
+            .route("/bettor", web::get().to(bettor))
+            .route("/golfer", web::get().to(golfer))
+            .route("/export/{table}", web::get().to(export))
+            .route("/tee-times.ics", web::get().to(tee_times)),
+    )
+    .await;
+    let resp = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    let header_value = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let (status, content_type, disposition) = (
+        resp.status(),
+        header_value(header::CONTENT_TYPE),
+        header_value(header::CONTENT_DISPOSITION),
+    );
+    Response {
+        status,
+        content_type,
+        disposition,
+        body: test::read_body(resp).await.to_vec(),
+    }
+}
+
+/// The status and body of an HTML page.
+pub async fn get_page(storage: &Arc<InMemoryStorage>, uri: &str) -> (StatusCode, String) {
+    let resp = get(storage, uri).await;
+    (resp.status, resp.text().to_string())
+}
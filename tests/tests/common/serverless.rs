@@ -1,7 +1,12 @@
-use chrono::Utc;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
 use reqwest::Client;
-use rusty_golf_core::model::Scores;
+use rusty_golf_core::api::admin::AdminTestStorageRequest;
+use rusty_golf_core::model::{RefreshSource, ScoreChange, Scores, ScoresAndLastRefresh};
+use rusty_golf_core::storage::{EventArchive, EventDetails, Storage, StorageError};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::error::Error;
 use std::fs;
@@ -118,6 +123,12 @@ struct AdminCleanupRequest {
     include_auth_tokens: bool,
 }
 
+#[derive(Debug, Serialize)]
+struct AdminImportRequest<'a> {
+    archive: &'a EventArchive,
+    replace: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct AdminCleanupScoresRequest {
     event_id: i32,
@@ -249,6 +260,27 @@ pub async fn admin_cleanup_events(
     Ok(())
 }
 
+pub async fn admin_import_event(
+    miniflare_url: &str,
+    admin_token: &str,
+    archive: &EventArchive,
+    replace: bool,
+) -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+    let resp = client
+        .post(format!("{miniflare_url}/admin/import"))
+        .header("x-admin-token", admin_token)
+        .json(&AdminImportRequest { archive, replace })
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("admin import failed: {status}\n{body}").into());
+    }
+    Ok(())
+}
+
 pub async fn admin_cleanup_scores(
     miniflare_url: &str,
     admin_token: &str,
@@ -469,3 +501,179 @@ pub async fn admin_update_dates(
 pub fn event_id_i32(event_id: i64) -> Result<i32, Box<dyn Error>> {
     i32::try_from(event_id).map_err(|_| format!("event_id out of range: {event_id}").into())
 }
+
+/// The worker's `ServerlessStorage`, driven call by call through `/admin/test_storage` so
+/// native tests can hold it to the `Storage` contract.
+pub struct ServerlessStorageClient {
+    client: Client,
+    miniflare_url: String,
+    admin_token: String,
+}
+
+impl ServerlessStorageClient {
+    pub fn new(miniflare_url: &str, admin_token: &str) -> Self {
+        Self {
+            client: Client::new(),
+            miniflare_url: miniflare_url.to_string(),
+            admin_token: admin_token.to_string(),
+        }
+    }
+
+    /// Move the event's last refresh to `last_refresh` without touching its scores.
+    ///
+    /// # Errors
+    /// Returns the worker's error text if the write fails.
+    pub async fn set_last_refresh(
+        &self,
+        event_id: i32,
+        last_refresh: NaiveDateTime,
+    ) -> Result<(), StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "set_last_refresh".to_string(),
+            event_id,
+            last_refresh: Some(last_refresh.and_utc().to_rfc3339()),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        request: AdminTestStorageRequest,
+    ) -> Result<T, StorageError> {
+        let method = request.method.clone();
+        let resp = self
+            .client
+            .post(format!("{}/admin/test_storage", self.miniflare_url))
+            .header("x-admin-token", &self.admin_token)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| StorageError::new(format!("{method}: {e}")))?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(StorageError::new(format!(
+                "{method} failed: {status}\n{body}"
+            )));
+        }
+        resp.json()
+            .await
+            .map_err(|e| StorageError::new(format!("{method}: {e}")))
+    }
+
+    async fn call_for_event<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        event_id: i32,
+    ) -> Result<T, StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: method.to_string(),
+            event_id,
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl Storage for ServerlessStorageClient {
+    async fn list_events(&self) -> Result<Vec<i32>, StorageError> {
+        self.call_for_event("list_events", 0).await
+    }
+
+    async fn get_event_details(&self, event_id: i32) -> Result<EventDetails, StorageError> {
+        self.call_for_event("get_event_details", event_id).await
+    }
+
+    async fn get_golfers_for_event(&self, event_id: i32) -> Result<Vec<Scores>, StorageError> {
+        self.call_for_event("get_golfers_for_event", event_id).await
+    }
+
+    async fn get_player_step_factors(
+        &self,
+        event_id: i32,
+    ) -> Result<HashMap<(i64, String), f32>, StorageError> {
+        let factors: Vec<(i64, String, f32)> = self
+            .call_for_event("get_player_step_factors", event_id)
+            .await?;
+        Ok(factors
+            .into_iter()
+            .map(|(espn_id, bettor, factor)| ((espn_id, bettor), factor))
+            .collect())
+    }
+
+    async fn get_scores(
+        &self,
+        event_id: i32,
+        source: RefreshSource,
+    ) -> Result<ScoresAndLastRefresh, StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "get_scores".to_string(),
+            event_id,
+            source: Some(source),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn store_scores(&self, event_id: i32, scores: &[Scores]) -> Result<(), StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "store_scores".to_string(),
+            event_id,
+            scores: scores.to_vec(),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn event_and_scores_already_in_db(
+        &self,
+        event_id: i32,
+        max_age_seconds: i64,
+    ) -> Result<bool, StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "event_and_scores_already_in_db".to_string(),
+            event_id,
+            max_age_seconds: Some(max_age_seconds),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn mark_event_completed(
+        &self,
+        event_id: i32,
+        end_date: Option<&str>,
+    ) -> Result<(), StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "mark_event_completed".to_string(),
+            event_id,
+            end_date: end_date.map(str::to_string),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn store_score_changes(
+        &self,
+        event_id: i32,
+        changes: &[ScoreChange],
+    ) -> Result<(), StorageError> {
+        self.call(AdminTestStorageRequest {
+            method: "store_score_changes".to_string(),
+            event_id,
+            changes: changes.to_vec(),
+            ..AdminTestStorageRequest::default()
+        })
+        .await
+    }
+
+    async fn get_recent_score_changes(
+        &self,
+        event_id: i32,
+    ) -> Result<Vec<ScoreChange>, StorageError> {
+        self.call_for_event("get_recent_score_changes", event_id)
+            .await
+    }
+}
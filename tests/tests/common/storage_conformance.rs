@@ -0,0 +1,307 @@
+//! Behavior every `Storage` backend must share.
+//!
+//! A backend opts in by implementing [`ConformanceHarness`] for a storage seeded with
+//! [`EVENT_ID`] (the picks in `test05_dbprefill.json` / `test01.sql`) and calling
+//! [`run_storage_conformance`]. [`MISSING_EVENT_ID`] must not exist.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rusty_golf_core::model::{IntStat, LineScore, RefreshSource, ScoreDisplay, Scores, StringStat};
+use rusty_golf_core::storage::Storage;
+use std::error::Error;
+
+pub const EVENT_ID: i32 = 401_580_351;
+pub const MISSING_EVENT_ID: i32 = 999_999_999;
+
+pub type HarnessResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+#[async_trait]
+pub trait ConformanceHarness: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn storage(&self) -> &dyn Storage;
+
+    /// Move the last refresh of `event_id` `seconds` into the past, however the backend
+    /// records it.
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()>;
+}
+
+/// Run every conformance check in order. Checks share state: scores stored by one are
+/// read by the next.
+///
+/// # Errors
+/// Returns the first storage call that fails unexpectedly. Contract violations panic
+/// with the backend name in the message.
+pub async fn run_storage_conformance(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    check_missing_event(harness).await?;
+    check_seeded_event(harness).await?;
+    check_scores_round_trip(harness).await?;
+    check_refresh_source(harness).await?;
+    check_freshness(harness).await?;
+    check_mark_completed(harness).await?;
+    Ok(())
+}
+
+/// Lookups that name a single record fail; lookups that return collections come back empty.
+async fn check_missing_event(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    assert!(
+        storage.get_event_details(MISSING_EVENT_ID).await.is_err(),
+        "{name}: get_event_details should fail for a missing event"
+    );
+    assert!(
+        storage
+            .get_scores(MISSING_EVENT_ID, RefreshSource::Db)
+            .await
+            .is_err(),
+        "{name}: get_scores should fail for a missing event"
+    );
+    assert!(
+        storage
+            .get_golfers_for_event(MISSING_EVENT_ID)
+            .await?
+            .is_empty(),
+        "{name}: get_golfers_for_event should be empty for a missing event"
+    );
+    assert!(
+        storage
+            .get_player_step_factors(MISSING_EVENT_ID)
+            .await?
+            .is_empty(),
+        "{name}: get_player_step_factors should be empty for a missing event"
+    );
+    assert!(
+        storage
+            .get_recent_score_changes(MISSING_EVENT_ID)
+            .await?
+            .is_empty(),
+        "{name}: get_recent_score_changes should be empty for a missing event"
+    );
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(MISSING_EVENT_ID, 3600)
+            .await?,
+        "{name}: a missing event is never fresh"
+    );
+    Ok(())
+}
+
+/// A configured event with no stored scores yet.
+async fn check_seeded_event(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    let events = storage.list_events().await?;
+    assert!(events.contains(&EVENT_ID), "{name}: seeded event is listed");
+    assert!(
+        !events.contains(&MISSING_EVENT_ID),
+        "{name}: missing event is not listed"
+    );
+    assert!(
+        events.windows(2).all(|pair| pair[0] < pair[1]),
+        "{name}: events are listed in ascending order"
+    );
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert_eq!(details.event_name, "PGA Championship", "{name}: event name");
+    assert!(!details.completed, "{name}: new events are not completed");
+
+    let golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    assert_eq!(golfers.len(), 15, "{name}: golfer count");
+    for golfer in &golfers {
+        assert!(
+            (1..=5).contains(&golfer.group),
+            "{name}: group {} out of range",
+            golfer.group
+        );
+        assert_eq!(
+            golfer.detailed_statistics.eup_id, golfer.eup_id,
+            "{name}: statistic eup id"
+        );
+        assert!(
+            golfer.detailed_statistics.rounds.is_empty(),
+            "{name}: golfers come back without statistics"
+        );
+    }
+
+    assert!(
+        storage
+            .get_scores(EVENT_ID, RefreshSource::Db)
+            .await
+            .is_err(),
+        "{name}: get_scores should fail before anything is stored"
+    );
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 3600)
+            .await?,
+        "{name}: an event without scores is never fresh"
+    );
+    Ok(())
+}
+
+/// Everything handed to `store_scores` comes back from `get_scores`.
+async fn check_scores_round_trip(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    let scores = scored_golfers(storage.get_golfers_for_event(EVENT_ID).await?);
+    let before = Utc::now().naive_utc();
+    storage.store_scores(EVENT_ID, &scores).await?;
+    let after = Utc::now().naive_utc();
+
+    let stored = storage.get_scores(EVENT_ID, RefreshSource::Db).await?;
+    assert_eq!(
+        comparable(&stored.score_struct),
+        comparable(&scores),
+        "{name}: stored scores should round-trip"
+    );
+
+    // Backends may store whole seconds.
+    let slack = chrono::Duration::seconds(1);
+    assert!(
+        stored.last_refresh >= before - slack && stored.last_refresh <= after + slack,
+        "{name}: last_refresh {} should fall between {before} and {after}",
+        stored.last_refresh
+    );
+    Ok(())
+}
+
+/// Asking for ESPN reports ESPN; anything else reports where the backend read from.
+async fn check_refresh_source(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    let from_espn = storage.get_scores(EVENT_ID, RefreshSource::Espn).await?;
+    assert!(
+        matches!(from_espn.last_refresh_source, RefreshSource::Espn),
+        "{name}: get_scores(Espn) should report Espn"
+    );
+    let cached = storage.get_scores(EVENT_ID, RefreshSource::Db).await?;
+    assert!(
+        !matches!(cached.last_refresh_source, RefreshSource::Espn),
+        "{name}: cached reads should not report Espn"
+    );
+    Ok(())
+}
+
+/// Fresh means "refreshed no more than `max_age_seconds` ago", to the second.
+async fn check_freshness(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    assert!(
+        storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?,
+        "{name}: just-stored scores should be fresh"
+    );
+    assert!(
+        !storage.event_and_scores_already_in_db(EVENT_ID, 0).await?,
+        "{name}: max_age 0 is never fresh"
+    );
+    assert!(
+        !storage.event_and_scores_already_in_db(EVENT_ID, -1).await?,
+        "{name}: negative max_age is never fresh"
+    );
+
+    harness.backdate_refresh(EVENT_ID, 600).await?;
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?,
+        "{name}: scores refreshed 10 minutes ago are stale at 5 minutes"
+    );
+    assert!(
+        storage
+            .event_and_scores_already_in_db(EVENT_ID, 900)
+            .await?,
+        "{name}: scores refreshed 10 minutes ago are fresh at 15 minutes"
+    );
+    Ok(())
+}
+
+/// Completion sticks, and an end date already on the event wins over the one passed in.
+async fn check_mark_completed(harness: &dyn ConformanceHarness) -> HarnessResult<()> {
+    let name = harness.name();
+    let storage = harness.storage();
+
+    storage
+        .mark_event_completed(EVENT_ID, Some("2024-05-19T23:00:00Z"))
+        .await?;
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert!(details.completed, "{name}: event should be completed");
+    assert_eq!(
+        details.end_date.as_deref(),
+        Some("2024-05-19T23:00:00Z"),
+        "{name}: end date should be filled in"
+    );
+
+    storage
+        .mark_event_completed(EVENT_ID, Some("2030-01-01T00:00:00Z"))
+        .await?;
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert_eq!(
+        details.end_date.as_deref(),
+        Some("2024-05-19T23:00:00Z"),
+        "{name}: an existing end date is kept"
+    );
+    assert!(
+        storage
+            .mark_event_completed(MISSING_EVENT_ID, None)
+            .await
+            .is_err(),
+        "{name}: completing a missing event should fail"
+    );
+    Ok(())
+}
+
+/// Give each golfer two rounds of statistics so the round trip has something to lose.
+fn scored_golfers(golfers: Vec<Scores>) -> Vec<Scores> {
+    golfers
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut golfer)| {
+            let offset = i32::try_from(i).unwrap_or_default();
+            let stats = &mut golfer.detailed_statistics;
+            stats.rounds = vec![IntStat { val: 0 }, IntStat { val: 1 }];
+            stats.round_scores = vec![IntStat { val: offset - 2 }, IntStat { val: 1 }];
+            stats.tee_times = vec![
+                StringStat {
+                    val: "Thursday 8:10 AM".to_string(),
+                },
+                StringStat {
+                    val: "Friday 1:25 PM".to_string(),
+                },
+            ];
+            stats.holes_completed_by_round = vec![IntStat { val: 18 }, IntStat { val: 9 }];
+            stats.line_scores = vec![LineScore {
+                round: 1,
+                hole: 1,
+                score: 3,
+                par: 4,
+                score_display: ScoreDisplay::Birdie,
+            }];
+            stats.total_score = offset - 1;
+            golfer
+        })
+        .collect()
+}
+
+/// Scores in eup order, minus the per-pick step factor: that's pick configuration (see
+/// `get_player_step_factors`), and SQL re-reads it from the pick rather than the stored score.
+fn comparable(scores: &[Scores]) -> Vec<serde_json::Value> {
+    let mut scores = scores.to_vec();
+    scores.sort_by_key(|score| score.eup_id);
+    scores
+        .into_iter()
+        .map(|score| {
+            serde_json::to_value(Scores {
+                score_view_step_factor: None,
+                ..score
+            })
+            .unwrap_or_default()
+        })
+        .collect()
+}
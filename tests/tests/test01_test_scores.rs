@@ -1,9 +1,13 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use actix_web::{App, test, web::Data};
 use serde_json::Value;
 
 use rusty_golf_actix::controller::score::scores;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::model::RefreshSource;
+use rusty_golf_core::storage::Storage;
 
 mod common;
 
@@ -13,10 +17,10 @@ async fn test1_scores_endpoint() -> Result<(), Box<dyn std::error::Error>> {
         .await
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
-    let storage = rusty_golf_actix::storage::SqlStorage::new(test_ctx.config_and_pool.clone());
+    let storage = SqlStorage::new(test_ctx.config_and_pool.clone());
     let app = test::init_service(
         App::new()
-            .app_data(Data::new(storage))
+            .app_data(Data::from(Arc::new(storage) as Arc<dyn Storage>))
             .route("/scores", actix_web::web::get().to(scores)),
     )
     .await;
@@ -120,3 +124,48 @@ async fn test1_scores_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[actix_web::test]
+async fn test1_first_page_load_with_no_stored_scores() -> Result<(), Box<dyn std::error::Error>> {
+    let test_ctx = common::setup_test_context(include_str!("test01.sql"))
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+    let storage = SqlStorage::new(test_ctx.config_and_pool.clone());
+    assert!(
+        storage
+            .get_scores(401_580_351, RefreshSource::Db)
+            .await
+            .is_err(),
+        "test01.sql should not store any scores"
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(Data::from(
+                Arc::new(SqlStorage::new(test_ctx.config_and_pool.clone())) as Arc<dyn Storage>,
+            ))
+            .route("/scores", actix_web::web::get().to(scores)),
+    )
+    .await;
+
+    // The HTML page reads stored scores back for its tables; the first load must store them
+    // before it does.
+    let req = test::TestRequest::get()
+        .uri("/scores?event=401580351&yr=2024&cache=1")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Unexpected status from the first /scores load: {}",
+        resp.status()
+    );
+    let body = String::from_utf8(test::read_body(resp).await.to_vec())?;
+    assert!(
+        body.contains("Score by Player"),
+        "page is missing its chart"
+    );
+
+    let stored = storage.get_scores(401_580_351, RefreshSource::Db).await?;
+    assert_eq!(stored.score_struct.len(), 15);
+    Ok(())
+}
@@ -215,6 +215,7 @@ async fn run_miniflare_checks(
             &player_step_factors,
             401_580_351,
             2024,
+            None,
             true,
         );
 
@@ -13,6 +13,8 @@ use sql_middleware::middleware::{
     ConfigAndPool as ConfigAndPool2, QueryAndParams, RowValues, SqliteOptions,
 };
 
+const THIRTY_DAYS: i64 = 30 * 24 * 60 * 60;
+
 #[tokio::test]
 async fn test4_get_scores_from_cache() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging (optional, but useful for debugging)
@@ -78,11 +80,8 @@ async fn test4_get_scores_from_cache() -> Result<(), Box<dyn std::error::Error>>
     let storage = SqlStorage::new(config_and_pool.clone());
 
     let score_data = if database_exists {
-        // cache max age of 0 means always use db, since model.event_and_scores_already_in_db does this:
-        // let now = chrono::Utc::now().naive_utc();
-        // let diff = now - z?;
-        // Ok(diff.num_days() > cache_max_age)
-        get_data_for_scores_page(401_580_351, 2024, true, &storage, 0)
+        // cache max age is in seconds; thirty days keeps the backdated rows fresh
+        get_data_for_scores_page(401_580_351, 2024, true, &storage, THIRTY_DAYS)
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
     } else {
@@ -103,7 +102,7 @@ async fn test4_get_scores_from_cache() -> Result<(), Box<dyn std::error::Error>>
         let mut conn = config_and_pool.get_connection().await?;
 
         conn.execute_dml(query, &params).await?;
-        get_data_for_scores_page(401_580_351, 2024, true, &storage, 0)
+        get_data_for_scores_page(401_580_351, 2024, true, &storage, THIRTY_DAYS)
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
     }?;
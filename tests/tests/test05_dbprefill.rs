@@ -19,7 +19,13 @@ async fn test_dbprefill() -> Result<(), Box<dyn std::error::Error>> {
     assert_empty_tables(&mut conn).await?;
 
     let json = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
-    db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await?;
+    let counts = db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await?;
+    assert_eq!(counts.events_skipped, 0);
+    assert!(counts.event_user_players_inserted > 0);
+
+    let rerun = db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await?;
+    assert_eq!(rerun.events_inserted, 0);
+    assert_eq!(rerun.events_skipped, counts.events_inserted);
 
     assert_events(&mut conn).await?;
     assert_golfers(&mut conn).await?;
@@ -30,6 +36,93 @@ async fn test_dbprefill() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_dbprefill_resumes_after_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let config_and_pool = setup_sqlite().await?;
+    let mut conn = config_and_pool.get_connection().await?;
+    // Fail The Open (the second event) after eight of its fifteen picks.
+    conn.execute_batch(
+        "CREATE TRIGGER fail_prefill BEFORE INSERT ON event_user_player \
+         WHEN NEW.event_id = (SELECT event_id FROM event WHERE espn_id = 401580360) \
+         AND (SELECT count(*) FROM event_user_player WHERE event_id = NEW.event_id) >= 8 \
+         BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+    )
+    .await?;
+
+    let json = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+    let err = db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await;
+    assert!(err.is_err(), "the injected failure should surface");
+    let events = conn
+        .execute_select("select espn_id from event order by espn_id;", &[])
+        .await?;
+    assert_eq!(
+        events.results.len(),
+        1,
+        "only the event before the failure is kept"
+    );
+    let picks = conn
+        .execute_select("select * from event_user_player;", &[])
+        .await?;
+    assert_eq!(picks.results.len(), 15);
+
+    conn.execute_batch("DROP TRIGGER fail_prefill;").await?;
+    let rerun = db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await?;
+    assert_eq!(rerun.events_skipped, 1);
+    assert_eq!(rerun.events_inserted, 4);
+
+    assert_events(&mut conn).await?;
+    assert_golfers(&mut conn).await?;
+    assert_bettors(&mut conn).await?;
+    assert_event_user_players(&mut conn, 401_580_360, "Player3", 4_364_873).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dbprefill_event_dates() -> Result<(), Box<dyn std::error::Error>> {
+    let config_and_pool = setup_sqlite().await?;
+    let json = serde_json::json!([
+        {
+            "event": 401_580_360,
+            "year": 2024,
+            "name": "The Open",
+            "score_view_step_factor": 4.5,
+            "start_date": "2024-07-18T04:00:00Z",
+            "end_date": "2024-07-21T04:00:00Z",
+            "completed": true,
+            "data_to_fill_if_event_and_year_missing": []
+        },
+        {
+            "event": 401_580_351,
+            "year": 2024,
+            "name": "PGA Championship",
+            "score_view_step_factor": 3.0,
+            "data_to_fill_if_event_and_year_missing": []
+        }
+    ]);
+    db_prefill(&json, &config_and_pool, DatabaseType::Sqlite).await?;
+
+    let mut conn = config_and_pool.get_connection().await?;
+    let query = "select start_date, end_date, completed from event where espn_id = ?1;";
+    let res = conn
+        .execute_select(query, &[RowValues::Int(401_580_360)])
+        .await?;
+    let row = &res.results[0];
+    assert_eq!(
+        row.get("end_date").and_then(|v| v.as_text()),
+        Some("2024-07-21T04:00:00Z")
+    );
+    assert_eq!(row.get("completed").and_then(|v| v.as_int()), Some(&1));
+
+    // Dates and completion are optional.
+    let res = conn
+        .execute_select(query, &[RowValues::Int(401_580_351)])
+        .await?;
+    let row = &res.results[0];
+    assert!(row.get("start_date").is_some_and(|v| v.is_null()));
+    assert_eq!(row.get("completed").and_then(|v| v.as_int()), Some(&0));
+    Ok(())
+}
+
 async fn setup_sqlite() -> Result<ConfigAndPool2, Box<dyn std::error::Error>> {
     let unique = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1,10 +1,13 @@
 use actix_web::{App, test, web};
 use scraper::{Html, Selector};
+use std::sync::Arc;
 
 use rusty_golf_actix::controller::score::http_handlers::{
     scores_chart, scores_linescore, scores_summary,
 };
 use rusty_golf_actix::controller::score::scores;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::storage::Storage;
 
 mod common;
 
@@ -14,15 +17,17 @@ async fn test_scores_hx_routes_are_relative() -> Result<(), Box<dyn std::error::
         .await
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
-    let storage = rusty_golf_actix::storage::SqlStorage::new(test_ctx.config_and_pool.clone());
+    let storage = SqlStorage::new(test_ctx.config_and_pool.clone());
     let app = test::init_service(
-        App::new().app_data(web::Data::new(storage)).service(
-            web::scope("/golf")
-                .route("/scores", web::get().to(scores))
-                .route("/scores/summary", web::get().to(scores_summary))
-                .route("/scores/chart", web::get().to(scores_chart))
-                .route("/scores/linescore", web::get().to(scores_linescore)),
-        ),
+        App::new()
+            .app_data(web::Data::from(Arc::new(storage) as Arc<dyn Storage>))
+            .service(
+                web::scope("/golf")
+                    .route("/scores", web::get().to(scores))
+                    .route("/scores/summary", web::get().to(scores_summary))
+                    .route("/scores/chart", web::get().to(scores_chart))
+                    .route("/scores/linescore", web::get().to(scores_linescore)),
+            ),
     )
     .await;
 
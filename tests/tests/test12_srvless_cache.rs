@@ -55,7 +55,8 @@ async fn test12_serverless_cache_behavior() -> Result<(), Box<dyn Error>> {
         // Force last_refresh into the past so cache age checks are meaningful.
         let last_refresh = (Utc::now() - Duration::days(2)).to_rfc3339();
         // Seed event, golfers, scores, and ESPN cache into KV/R2.
-        let payload = build_admin_seed_request(&workspace_root, event_id, Some(last_refresh))?;
+        let payload =
+            build_admin_seed_request(&workspace_root, event_id, Some(last_refresh.clone()))?;
         // Initialize serverless storage for the event under test.
         if lock.is_first {
             admin_seed_event(&miniflare_url, &admin_token, &payload).await?;
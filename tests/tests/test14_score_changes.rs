@@ -0,0 +1,50 @@
+mod common;
+
+use chrono::NaiveDateTime;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::model::{HoleResult, ScoreChange, ScoreChangeKind};
+use rusty_golf_core::score::MAX_RECENT_SCORE_CHANGES;
+use rusty_golf_core::storage::Storage;
+use std::error::Error;
+
+fn eagle(hole: i32) -> ScoreChange {
+    ScoreChange {
+        detected_at: NaiveDateTime::default(),
+        change: ScoreChangeKind::Hole {
+            bettor_name: "Alice".to_string(),
+            golfer_name: "Rory McIlroy".to_string(),
+            golfer_espn_id: 3470,
+            round: 1,
+            hole,
+            par: 5,
+            strokes: 3,
+            result: HoleResult::Eagle,
+        },
+    }
+}
+
+#[tokio::test]
+async fn test14_sql_score_changes_round_trip() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context("").await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    let event_id = 401_580_351;
+
+    assert!(storage.get_recent_score_changes(event_id).await?.is_empty());
+
+    storage
+        .store_score_changes(event_id, &[eagle(2), eagle(13)])
+        .await?;
+    let recent = storage.get_recent_score_changes(event_id).await?;
+    assert_eq!(recent.len(), 2);
+    assert_eq!(recent[0].to_string(), "Rory McIlroy just eagled 13 (Alice)");
+
+    let many: Vec<ScoreChange> = (1..=MAX_RECENT_SCORE_CHANGES + 5)
+        .map(|idx| eagle(i32::try_from(idx).unwrap_or(i32::MAX)))
+        .collect();
+    storage.store_score_changes(event_id, &many).await?;
+    let recent = storage.get_recent_score_changes(event_id).await?;
+    assert_eq!(recent.len(), MAX_RECENT_SCORE_CHANGES);
+
+    assert!(storage.get_recent_score_changes(1).await?.is_empty());
+    Ok(())
+}
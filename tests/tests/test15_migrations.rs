@@ -0,0 +1,68 @@
+mod common;
+
+use common::ConnExt;
+use rusty_golf_actix::model::migrations::{
+    MIGRATIONS, SchemaStatus, check_schema_version, latest_schema_version, run_migrations,
+};
+use sql_middleware::middleware::{ConfigAndPool, SqliteOptions};
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+async fn empty_sqlite() -> Result<ConfigAndPool, Box<dyn Error>> {
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time went backwards")
+        .as_nanos();
+    let db_name = format!("file:test_db_{unique}?mode=memory&cache=shared");
+    Ok(ConfigAndPool::new_sqlite(SqliteOptions::new(db_name)).await?)
+}
+
+#[tokio::test]
+async fn test15_migrations_apply_once_on_fresh_db() -> Result<(), Box<dyn Error>> {
+    let config_and_pool = empty_sqlite().await?;
+    assert_eq!(
+        check_schema_version(&config_and_pool).await?,
+        SchemaStatus::Unversioned
+    );
+
+    let applied = run_migrations(&config_and_pool).await?;
+    let expected: Vec<i64> = (1..=latest_schema_version()).collect();
+    assert_eq!(applied, expected);
+    assert_eq!(
+        check_schema_version(&config_and_pool).await?,
+        SchemaStatus::Current
+    );
+    assert!(run_migrations(&config_and_pool).await?.is_empty());
+
+    let mut conn = config_and_pool.get_connection().await?;
+    let res = conn
+        .execute_select("SELECT start_date FROM event;", &[])
+        .await?;
+    assert!(res.results.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test15_migrations_adopt_legacy_schema() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context("").await?;
+    let applied = run_migrations(&context.config_and_pool).await?;
+    assert_eq!(applied.len(), MIGRATIONS.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test15_refuses_newer_schema() -> Result<(), Box<dyn Error>> {
+    let config_and_pool = empty_sqlite().await?;
+    run_migrations(&config_and_pool).await?;
+    let mut conn = config_and_pool.get_connection().await?;
+    let newer = latest_schema_version() + 1;
+    conn.execute_dml(
+        &format!("INSERT INTO schema_version (version, name) VALUES ({newer}, 'future');"),
+        &[],
+    )
+    .await?;
+
+    assert!(check_schema_version(&config_and_pool).await.is_err());
+    assert!(run_migrations(&config_and_pool).await.is_err());
+    Ok(())
+}
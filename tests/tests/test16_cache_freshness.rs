@@ -0,0 +1,89 @@
+mod common;
+
+use common::ConnExt;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::storage::Storage;
+use sql_middleware::middleware::RowValues;
+use std::error::Error;
+
+const EVENT_ID: i32 = 401_580_351;
+
+fn minutes_ago(minutes: i64) -> RowValues {
+    let ts = chrono::Utc::now().naive_utc() - chrono::Duration::minutes(minutes);
+    RowValues::Text(ts.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+#[tokio::test]
+async fn test16_sql_freshness_is_second_accurate() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+
+    // Configured event, but nothing stored yet.
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+
+    let golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    storage.store_scores(EVENT_ID, &golfers).await?;
+    assert!(
+        storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    assert!(!storage.event_and_scores_already_in_db(EVENT_ID, 0).await?);
+
+    let mut conn = context.config_and_pool.get_connection().await?;
+    conn.execute_dml(
+        "UPDATE event SET last_refresh_ts = ?1 WHERE espn_id = ?2;",
+        &[minutes_ago(10), RowValues::Int(i64::from(EVENT_ID))],
+    )
+    .await?;
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    assert!(
+        storage
+            .event_and_scores_already_in_db(EVENT_ID, 3600)
+            .await?
+    );
+
+    // Rows written before `last_refresh_ts` existed fall back to the newest score row.
+    conn.execute_dml("UPDATE event SET last_refresh_ts = NULL;", &[])
+        .await?;
+    conn.execute_dml("UPDATE eup_statistic SET ins_ts = ?1;", &[minutes_ago(2)])
+        .await?;
+    assert!(
+        storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    assert!(!storage.event_and_scores_already_in_db(EVENT_ID, 60).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test16_sql_event_details_include_start_date() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    assert_eq!(storage.get_event_details(EVENT_ID).await?.start_date, None);
+
+    let mut conn = context.config_and_pool.get_connection().await?;
+    conn.execute_dml(
+        "UPDATE event SET start_date = '2024-04-11T12:00:00Z' WHERE espn_id = ?1;",
+        &[RowValues::Int(i64::from(EVENT_ID))],
+    )
+    .await?;
+    assert_eq!(
+        storage
+            .get_event_details(EVENT_ID)
+            .await?
+            .start_date
+            .as_deref(),
+        Some("2024-04-11T12:00:00Z")
+    );
+    Ok(())
+}
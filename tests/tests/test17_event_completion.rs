@@ -0,0 +1,151 @@
+mod common;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use common::ConnExt;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::{EspnApiClient, EventCompletion};
+use rusty_golf_core::model::{LineScore, PlayerJsonResponse, ScoreDisplay, Scores};
+use rusty_golf_core::score::{FINAL_ROUND, cache_max_age_for_event, promote_completed_if_ready};
+use rusty_golf_core::storage::Storage;
+use sql_middleware::middleware::RowValues;
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const EVENT_ID: i32 = 401_580_351;
+
+struct HeaderStub {
+    completion: EventCompletion,
+    calls: AtomicUsize,
+}
+
+impl HeaderStub {
+    fn new(completed: bool, end_date: NaiveDateTime) -> Self {
+        Self {
+            completion: EventCompletion {
+                completed,
+                end_date: Some(rfc3339(end_date)),
+            },
+            calls: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EspnApiClient for HeaderStub {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        Err(CoreError::Network("not used".to_string()))
+    }
+
+    async fn event_completion(&self, _event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Some(self.completion.clone()))
+    }
+}
+
+fn rfc3339(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// The event's picks, everyone through `final_round_holes` of the final round.
+async fn scores_through(
+    storage: &dyn Storage,
+    final_round_holes: i32,
+) -> Result<Vec<Scores>, Box<dyn Error>> {
+    let mut golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    for golfer in &mut golfers {
+        golfer.detailed_statistics.line_scores = (0..=FINAL_ROUND)
+            .flat_map(|round| {
+                let holes = if round == FINAL_ROUND {
+                    final_round_holes
+                } else {
+                    18
+                };
+                (1..=holes).map(move |hole| LineScore {
+                    round,
+                    hole,
+                    score: 4,
+                    par: 4,
+                    score_display: ScoreDisplay::Par,
+                })
+            })
+            .collect();
+    }
+    Ok(golfers)
+}
+
+#[tokio::test]
+async fn test17_sql_event_promoted_after_grace_period() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    let now = Utc::now().naive_utc();
+    let ended = now - Duration::days(10);
+
+    let scores = scores_through(&storage, 18).await?;
+
+    let in_progress = HeaderStub::new(false, ended);
+    assert!(!promote_completed_if_ready(&storage, &in_progress, EVENT_ID, &scores, now).await?);
+    assert!(!storage.get_event_details(EVENT_ID).await?.completed);
+
+    let finished = HeaderStub::new(true, ended);
+    assert!(promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert!(details.completed);
+    assert_eq!(details.end_date, Some(rfc3339(ended)));
+    assert_eq!(cache_max_age_for_event(&storage, EVENT_ID).await?, -1);
+
+    // Already completed: ESPN isn't asked again.
+    assert!(!promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    assert_eq!(finished.calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test17_sql_recent_end_date_skips_espn() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    let now = Utc::now().naive_utc();
+    let ended = now - Duration::days(2);
+
+    let mut conn = context.config_and_pool.get_connection().await?;
+    conn.execute_dml(
+        "UPDATE event SET end_date = ?1 WHERE espn_id = ?2;",
+        &[
+            RowValues::Text(rfc3339(ended)),
+            RowValues::Int(i64::from(EVENT_ID)),
+        ],
+    )
+    .await?;
+
+    let scores = scores_through(&storage, 18).await?;
+    let finished = HeaderStub::new(true, ended);
+    assert!(!promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    assert_eq!(finished.calls.load(Ordering::SeqCst), 0);
+    assert!(!storage.get_event_details(EVENT_ID).await?.completed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test17_sql_no_end_date_waits_for_final_round() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    let now = Utc::now().naive_utc();
+    let finished = HeaderStub::new(true, now - Duration::days(10));
+
+    // No end date stored and the final round still in play: ESPN isn't asked.
+    let scores = scores_through(&storage, 12).await?;
+    assert!(!promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    let scores = scores_through(&storage, 0).await?;
+    assert!(!promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    assert_eq!(finished.calls.load(Ordering::SeqCst), 0);
+
+    let scores = scores_through(&storage, 18).await?;
+    assert!(promote_completed_if_ready(&storage, &finished, EVENT_ID, &scores, now).await?);
+    assert_eq!(finished.calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::EspnApiClient;
+use rusty_golf_core::model::{PlayerJsonResponse, RefreshSource, Scores};
+use rusty_golf_core::score::load_score_context_with_timing;
+use rusty_golf_core::storage::{InMemoryStorage, Storage, StorageOperation};
+use rusty_golf_core::timing::TimingSink;
+use std::error::Error;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const EVENT_ID: i32 = 401_580_351;
+
+/// Returns no player data, so refreshed scores are the seeded picks with empty stats.
+#[derive(Default)]
+struct EmptyEspn {
+    calls: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait::async_trait]
+impl EspnApiClient for EmptyEspn {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(CoreError::Network("espn down".to_string()));
+        }
+        Ok(PlayerJsonResponse {
+            data: Vec::new(),
+            eup_ids: Vec::new(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct Timings(Mutex<Vec<(&'static str, f64)>>);
+
+impl TimingSink for Timings {
+    fn record(&self, name: &'static str, ms: f64) {
+        self.0.lock().unwrap().push((name, ms));
+    }
+}
+
+fn seeded() -> Result<InMemoryStorage, Box<dyn Error>> {
+    let json = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+    Ok(InMemoryStorage::from_prefill_json(&json)?)
+}
+
+#[tokio::test]
+async fn test18_score_context_refreshes_then_serves_cache() -> Result<(), Box<dyn Error>> {
+    let storage = seeded()?;
+    let espn = EmptyEspn::default();
+
+    let first =
+        load_score_context_with_timing(&storage, &espn, EVENT_ID, 2024, true, 300, None).await?;
+    assert!(!first.data.cache_hit);
+    assert_eq!(first.data.score_struct.len(), 15);
+    assert_eq!(first.global_step_factor, 3.0);
+
+    let second =
+        load_score_context_with_timing(&storage, &espn, EVENT_ID, 2024, true, 300, None).await?;
+    assert!(second.data.cache_hit);
+    assert!(matches!(
+        second.data.last_refresh_source,
+        RefreshSource::Memory
+    ));
+    assert_eq!(espn.calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test18_storage_failures_surface() -> Result<(), Box<dyn Error>> {
+    let storage = seeded()?;
+    let espn = EmptyEspn {
+        fail: true,
+        ..EmptyEspn::default()
+    };
+
+    // ESPN down and nothing cached: the refresh has nothing to fall back to.
+    assert!(
+        load_score_context_with_timing(&storage, &espn, EVENT_ID, 2024, false, 0, None)
+            .await
+            .is_err()
+    );
+
+    storage.fail_on(StorageOperation::GetGolfersForEvent, "golfers unavailable");
+    let err = load_score_context_with_timing(&storage, &espn, EVENT_ID, 2024, true, 300, None)
+        .await
+        .err()
+        .ok_or("expected an error")?;
+    assert!(err.to_string().contains("golfers unavailable"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test18_latency_shows_up_in_timings() -> Result<(), Box<dyn Error>> {
+    let storage = seeded()?.with_latency(Duration::from_millis(15));
+    let golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    storage.store_scores(EVENT_ID, &golfers).await?;
+
+    let timings = Timings::default();
+    let espn = EmptyEspn::default();
+    load_score_context_with_timing(&storage, &espn, EVENT_ID, 2024, true, -1, Some(&timings))
+        .await?;
+
+    let recorded = timings.0.lock().unwrap();
+    let details_ms = recorded
+        .iter()
+        .find(|(name, _)| *name == "storage.get_event_details_ms")
+        .map(|(_, ms)| *ms)
+        .ok_or("missing storage.get_event_details_ms timing")?;
+    assert!(details_ms >= 15.0);
+    assert_eq!(espn.calls.load(Ordering::SeqCst), 0);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::TempDataDir;
+use rusty_golf_actix::storage::FileStorage;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::EspnApiClient;
+use rusty_golf_core::model::{PlayerJsonResponse, RefreshSource, Scores};
+use rusty_golf_core::score::load_score_context_with_timing;
+use rusty_golf_core::storage::Storage;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+const EVENT_ID: i32 = 401_580_351;
+
+struct EmptyEspn;
+
+#[async_trait::async_trait]
+impl EspnApiClient for EmptyEspn {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        Ok(PlayerJsonResponse {
+            data: Vec::new(),
+            eup_ids: Vec::new(),
+        })
+    }
+}
+
+fn leftover_tmp_files(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            found.extend(leftover_tmp_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "tmp") {
+            found.push(path);
+        }
+    }
+    found
+}
+
+#[tokio::test]
+async fn test19_seeds_prefill_with_serverless_key_layout() -> Result<(), Box<dyn Error>> {
+    let dir = TempDataDir::new("rusty_golf_file_storage");
+    let storage = FileStorage::new(&dir.0);
+    let json = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+
+    assert_eq!(storage.seed_prefill_json(&json).await?, 5);
+    // Already seeded events are left alone.
+    assert_eq!(storage.seed_prefill_json(&json).await?, 0);
+
+    let kv = dir.0.join("kv");
+    assert!(kv.join(format!("event:{EVENT_ID}:details")).is_file());
+    assert!(kv.join(format!("event:{EVENT_ID}:golfers")).is_file());
+    assert!(
+        kv.join(format!("event:{EVENT_ID}:player_factors"))
+            .is_file()
+    );
+
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert_eq!(details.score_view_step_factor, 3.0);
+    assert!(!details.completed);
+
+    let golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    assert_eq!(golfers.len(), 15);
+    assert_eq!(golfers[0].eup_id, 1);
+    assert_eq!(golfers[0].group, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test19_scores_round_trip_through_files() -> Result<(), Box<dyn Error>> {
+    let dir = TempDataDir::new("rusty_golf_file_storage");
+    let storage = FileStorage::new(&dir.0);
+    let json = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+    storage.seed_prefill_json(&json).await?;
+
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    let first =
+        load_score_context_with_timing(&storage, &EmptyEspn, EVENT_ID, 2024, true, 300, None)
+            .await?;
+    assert!(!first.data.cache_hit);
+    assert!(
+        dir.0
+            .join(format!("events/{EVENT_ID}/scores.json"))
+            .is_file()
+    );
+    assert!(
+        dir.0
+            .join(format!("kv/event:{EVENT_ID}:last_refresh"))
+            .is_file()
+    );
+
+    // A second instance over the same directory sees what the first wrote.
+    let reopened = FileStorage::new(&dir.0);
+    assert!(
+        reopened
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    assert!(!reopened.event_and_scores_already_in_db(EVENT_ID, 0).await?);
+    let second =
+        load_score_context_with_timing(&reopened, &EmptyEspn, EVENT_ID, 2024, true, 300, None)
+            .await?;
+    assert!(second.data.cache_hit);
+    assert!(matches!(
+        second.data.last_refresh_source,
+        RefreshSource::File
+    ));
+    assert_eq!(second.data.score_struct.len(), 15);
+
+    reopened
+        .mark_event_completed(EVENT_ID, Some("2024-04-14T23:00:00Z"))
+        .await?;
+    let details = storage.get_event_details(EVENT_ID).await?;
+    assert!(details.completed);
+    assert_eq!(details.end_date.as_deref(), Some("2024-04-14T23:00:00Z"));
+
+    assert!(leftover_tmp_files(&dir.0).is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test19_missing_event_reports_not_found() -> Result<(), Box<dyn Error>> {
+    let dir = TempDataDir::new("rusty_golf_file_storage");
+    let storage = FileStorage::new(&dir.0);
+
+    let err = storage
+        .get_event_details(EVENT_ID)
+        .await
+        .err()
+        .ok_or("expected an error")?;
+    assert!(err.to_string().contains("event details not found"));
+    assert!(
+        storage
+            .get_scores(EVENT_ID, RefreshSource::File)
+            .await
+            .is_err()
+    );
+    assert!(storage.get_recent_score_changes(EVENT_ID).await?.is_empty());
+    assert!(
+        !storage
+            .event_and_scores_already_in_db(EVENT_ID, 300)
+            .await?
+    );
+    Ok(())
+}
@@ -0,0 +1,397 @@
+mod common;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use common::ConnExt;
+use common::fake_r2::FakeR2;
+use common::serverless::{
+    ServerlessStorageClient, WranglerPaths, admin_cleanup_events, admin_import_event,
+    admin_test_lock_retry, admin_test_unlock, is_local_miniflare, shared_wrangler_dirs,
+    test_lock_token,
+};
+use common::storage_conformance::{
+    ConformanceHarness, EVENT_ID, HarnessResult, run_storage_conformance,
+};
+use common::{TempDataDir, TestContext};
+use rusty_golf_actix::storage::{FileStorage, SqlStorage};
+use rusty_golf_core::model::RefreshSource;
+use rusty_golf_core::storage::{CachedStorage, InMemoryStorage, Storage, export_event};
+use sql_middleware::middleware::RowValues;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration as StdDuration;
+
+fn prefill_json() -> HarnessResult<serde_json::Value> {
+    Ok(serde_json::from_str(include_str!("test05_dbprefill.json"))?)
+}
+
+fn seconds_ago(seconds: i64) -> chrono::NaiveDateTime {
+    Utc::now().naive_utc() - Duration::seconds(seconds)
+}
+
+struct SqlHarness {
+    context: TestContext,
+    storage: SqlStorage,
+}
+
+#[async_trait]
+impl ConformanceHarness for SqlHarness {
+    fn name(&self) -> &'static str {
+        "sql"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.storage
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        let mut conn = self.context.config_and_pool.get_connection().await?;
+        conn.execute_dml(
+            "UPDATE event SET last_refresh_ts = ?1 WHERE espn_id = ?2;",
+            &[
+                RowValues::Text(seconds_ago(seconds).format("%Y-%m-%d %H:%M:%S").to_string()),
+                RowValues::Int(i64::from(event_id)),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+struct MemoryHarness(InMemoryStorage);
+
+#[async_trait]
+impl ConformanceHarness for MemoryHarness {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.0
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        self.0.set_last_refresh(event_id, seconds_ago(seconds))?;
+        Ok(())
+    }
+}
+
+struct CachedHarness(CachedStorage<InMemoryStorage>);
+
+#[async_trait]
+impl ConformanceHarness for CachedHarness {
+    fn name(&self) -> &'static str {
+        "cached memory"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.0
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        // Written around the cache, as another process would.
+        self.0
+            .inner()
+            .set_last_refresh(event_id, seconds_ago(seconds))?;
+        self.0.cache().invalidate_scores(event_id);
+        Ok(())
+    }
+}
+
+struct FileHarness {
+    dir: TempDataDir,
+    storage: FileStorage,
+}
+
+#[async_trait]
+impl ConformanceHarness for FileHarness {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.storage
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        let doc = serde_json::json!({
+            "ts": DateTime::<Utc>::from_naive_utc_and_offset(seconds_ago(seconds), Utc).to_rfc3339(),
+            "source": RefreshSource::Espn,
+        });
+        let path = self.dir.0.join(format!("kv/event:{event_id}:last_refresh"));
+        std::fs::write(path, serde_json::to_vec(&doc)?)?;
+        Ok(())
+    }
+}
+
+struct R2Harness(FakeR2);
+
+impl R2Harness {
+    /// Seed the event the way an R2 bucket would hold it, from the prefill picks.
+    async fn seed(&self) -> HarnessResult<()> {
+        let prefill: serde_json::Value =
+            serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+        let seeded = InMemoryStorage::from_prefill_json(&prefill)?;
+        let details = seeded.get_event_details(EVENT_ID).await?;
+        let golfers = seeded.get_golfers_for_event(EVENT_ID).await?;
+        self.0.put(
+            &format!("events/{EVENT_ID}/event.json"),
+            &serde_json::json!({
+                "event_name": details.event_name,
+                "score_view_step_factor": details.score_view_step_factor,
+                "refresh_from_espn": details.refresh_from_espn,
+                "start_date": details.start_date,
+                "end_date": details.end_date,
+            }),
+        )?;
+        self.0
+            .put(&format!("events/{EVENT_ID}/golfers.json"), &golfers)
+    }
+}
+
+#[async_trait]
+impl ConformanceHarness for R2Harness {
+    fn name(&self) -> &'static str {
+        "r2"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.0.storage
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        let mut scores = self
+            .0
+            .storage
+            .get_scores(event_id, RefreshSource::R2)
+            .await?;
+        scores.last_refresh = seconds_ago(seconds);
+        self.0
+            .put(&format!("events/{event_id}/scores.json"), &scores)
+    }
+}
+
+struct ServerlessHarness(ServerlessStorageClient);
+
+#[async_trait]
+impl ConformanceHarness for ServerlessHarness {
+    fn name(&self) -> &'static str {
+        "serverless"
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        &self.0
+    }
+
+    async fn backdate_refresh(&self, event_id: i32, seconds: i64) -> HarnessResult<()> {
+        self.0
+            .set_last_refresh(event_id, seconds_ago(seconds))
+            .await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test20_sql_storage_conforms() -> HarnessResult<()> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    run_storage_conformance(&SqlHarness { context, storage }).await
+}
+
+#[tokio::test]
+async fn test20_in_memory_storage_conforms() -> HarnessResult<()> {
+    let storage = InMemoryStorage::from_prefill_json(&prefill_json()?)?;
+    run_storage_conformance(&MemoryHarness(storage)).await
+}
+
+#[tokio::test]
+async fn test20_cached_storage_conforms() -> HarnessResult<()> {
+    let storage = InMemoryStorage::from_prefill_json(&prefill_json()?)?;
+    run_storage_conformance(&CachedHarness(CachedStorage::new(storage))).await
+}
+
+#[tokio::test]
+async fn test20_file_storage_conforms() -> HarnessResult<()> {
+    let dir = TempDataDir::new("rusty_golf_conformance");
+    let storage = FileStorage::new(&dir.0);
+    storage.seed_prefill_json(&prefill_json()?).await?;
+    run_storage_conformance(&FileHarness { dir, storage }).await
+}
+
+#[actix_web::test]
+async fn test20_r2_storage_conforms() -> HarnessResult<()> {
+    let harness = R2Harness(FakeR2::start()?);
+    harness.seed().await?;
+    run_storage_conformance(&harness).await
+}
+
+/// `ServerlessStorage` only builds for wasm, so it runs inside miniflare and the suite
+/// drives it over `/admin/test_storage`.
+#[tokio::test(flavor = "multi_thread")]
+async fn test20_serverless_storage_conforms() -> Result<(), Box<dyn Error>> {
+    if !run_serverless_enabled() {
+        eprintln!("Skipping serverless test: RUN_SERVERLESS=1 not set in .env");
+        return Ok(());
+    }
+
+    ensure_command("worker-build")?;
+    ensure_command("wrangler")?;
+
+    let workspace_root = workspace_root();
+    let miniflare_url = miniflare_base_url()?;
+    let admin_token = miniflare_admin_token()?;
+    let wrangler_paths = wrangler_paths(&workspace_root);
+    let event_id = i64::from(EVENT_ID);
+    let lock_token = test_lock_token("test20");
+
+    if is_local_miniflare(&miniflare_url) {
+        build_local(&workspace_root, &wrangler_paths)?;
+    } else {
+        println!("Skipping build_local; MINIFLARE_URL is non-localhost.");
+    }
+    wait_for_health(&format!("{miniflare_url}/health")).await?;
+
+    // The suite stores and backdates scores, so no other test may share the event.
+    admin_test_lock_retry(
+        &miniflare_url,
+        &admin_token,
+        event_id,
+        &lock_token,
+        "exclusive",
+    )
+    .await?;
+    admin_cleanup_events(&miniflare_url, &admin_token, &[event_id], false).await?;
+
+    let test_result = async {
+        // Picks only, no scores: the state every other harness starts from.
+        let prefill: serde_json::Value =
+            serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+        let seeded = InMemoryStorage::from_prefill_json(&prefill)?;
+        let archive = export_event(&seeded, EVENT_ID).await?;
+        admin_import_event(&miniflare_url, &admin_token, &archive, true).await?;
+
+        let harness = ServerlessHarness(ServerlessStorageClient::new(&miniflare_url, &admin_token));
+        run_storage_conformance(&harness)
+            .await
+            .map_err(|e| -> Box<dyn Error> { e })
+    }
+    .await;
+
+    admin_test_unlock(&miniflare_url, &admin_token, event_id, &lock_token).await?;
+    if let Err(err) = admin_cleanup_events(&miniflare_url, &admin_token, &[event_id], false).await {
+        eprintln!("admin cleanup failed after test20: {err}");
+    }
+
+    test_result
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
+
+fn run_serverless_enabled() -> bool {
+    init_env();
+    std::env::var("RUN_SERVERLESS")
+        .map(|value| value.trim() == "1")
+        .unwrap_or(false)
+}
+
+fn ensure_command(cmd: &str) -> Result<(), Box<dyn Error>> {
+    let status = Command::new("which").arg(cmd).status()?;
+    if !status.success() {
+        return Err(format!("Required command not found: {cmd}").into());
+    }
+    Ok(())
+}
+
+fn run_script(script_path: &Path, envs: &[(&str, &str)], cwd: &Path) -> Result<(), Box<dyn Error>> {
+    let output = Command::new("bash")
+        .arg(script_path)
+        .envs(envs.iter().copied())
+        .current_dir(cwd)
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(format!(
+        "Script failed: {}\nstdout:\n{}\nstderr:\n{}",
+        script_path.display(),
+        stdout,
+        stderr
+    )
+    .into())
+}
+
+fn wrangler_paths(workspace_root: &Path) -> WranglerPaths {
+    let (log_dir, config_dir) = shared_wrangler_dirs().unwrap_or_else(|| {
+        (
+            workspace_root.join(".wrangler-logs-test20"),
+            workspace_root.join(".wrangler-config-test20"),
+        )
+    });
+    WranglerPaths {
+        config: workspace_root.join("serverless/wrangler.toml"),
+        log_dir,
+        config_dir,
+    }
+}
+
+fn build_local(
+    workspace_root: &Path,
+    wrangler_paths: &WranglerPaths,
+) -> Result<(), Box<dyn Error>> {
+    let wrangler_log_dir_str = wrangler_paths.log_dir.to_str().unwrap_or_default();
+    let wrangler_config_dir_str = wrangler_paths.config_dir.to_str().unwrap_or_default();
+    run_script(
+        &workspace_root.join("serverless/scripts/build_local.sh"),
+        &[
+            (
+                "CONFIG_PATH",
+                wrangler_paths.config.to_str().unwrap_or_default(),
+            ),
+            ("WRANGLER_LOG_DIR", wrangler_log_dir_str),
+            ("XDG_CONFIG_HOME", wrangler_config_dir_str),
+        ],
+        workspace_root,
+    )
+}
+
+async fn wait_for_health(url: &str) -> Result<(), Box<dyn Error>> {
+    let client = reqwest::Client::builder()
+        .timeout(StdDuration::from_secs(2))
+        .build()?;
+    for _ in 0..240 {
+        match client.get(url).send().await {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            _ => tokio::time::sleep(StdDuration::from_millis(250)).await,
+        }
+    }
+    Err(format!("Timed out waiting for {url}").into())
+}
+
+fn init_env() {
+    let _ = dotenvy::dotenv();
+    if std::env::var("MINIFLARE_URL").is_err() || std::env::var("MINIFLARE_ADMIN_TOKEN").is_err() {
+        let _ = dotenvy::from_filename("../.env");
+    }
+}
+
+fn miniflare_base_url() -> Result<String, Box<dyn Error>> {
+    init_env();
+    let url = std::env::var("MINIFLARE_URL")
+        .map_err(|_| "MINIFLARE_URL not set in ../.env or environment")?;
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+fn miniflare_admin_token() -> Result<String, Box<dyn Error>> {
+    init_env();
+    let token = std::env::var("MINIFLARE_ADMIN_TOKEN")
+        .map_err(|_| "MINIFLARE_ADMIN_TOKEN not set in ../.env or environment")?;
+    Ok(token)
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::admin_conformance::run_admin_conformance;
+use common::fake_r2::FakeR2;
+use common::storage_conformance::HarnessResult;
+use common::{ConnExt, TempDataDir};
+use rusty_golf_actix::storage::{FileStorage, SqlStorage};
+use rusty_golf_core::storage::{AdminStorage, Assignment, CachedStorage, InMemoryStorage};
+
+#[tokio::test]
+async fn test21_sql_admin_conforms() -> HarnessResult<()> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    run_admin_conformance("sql", &storage).await
+}
+
+#[tokio::test]
+async fn test21_sql_admin_writes_are_atomic() -> HarnessResult<()> {
+    const EVENT_ID: i32 = 401_580_351;
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    let mut conn = context.config_and_pool.get_connection().await?;
+    let count_picks = "SELECT count(*) AS n FROM event_user_player;";
+    let picks_before = conn.execute_select(count_picks, &[]).await?.results[0]
+        .get("n")
+        .and_then(|v| v.as_int().copied());
+    conn.execute_batch(
+        "CREATE TRIGGER fail_pick BEFORE INSERT ON event_user_player \
+         BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+    )
+    .await?;
+
+    // The old picks are deleted before the new ones fail to insert.
+    let assignments = [Assignment {
+        bettor_name: "Player1".to_string(),
+        golfer_espn_id: 3470,
+    }];
+    assert!(
+        storage
+            .set_assignments(EVENT_ID, &assignments)
+            .await
+            .is_err()
+    );
+    let picks_after = conn.execute_select(count_picks, &[]).await?.results[0]
+        .get("n")
+        .and_then(|v| v.as_int().copied());
+    assert_eq!(
+        picks_after, picks_before,
+        "a failed replace keeps the old picks"
+    );
+
+    // The event row goes last, after its picks and scores are gone.
+    conn.execute_batch(
+        "DROP TRIGGER fail_pick; \
+         CREATE TRIGGER fail_event_delete BEFORE DELETE ON event \
+         BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+    )
+    .await?;
+    assert!(storage.delete_event(EVENT_ID).await.is_err());
+    let picks_after = conn.execute_select(count_picks, &[]).await?.results[0]
+        .get("n")
+        .and_then(|v| v.as_int().copied());
+    assert_eq!(picks_after, picks_before, "a failed delete keeps the picks");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test21_in_memory_admin_conforms() -> HarnessResult<()> {
+    run_admin_conformance("memory", &InMemoryStorage::new()).await
+}
+
+#[tokio::test]
+async fn test21_cached_admin_conforms() -> HarnessResult<()> {
+    run_admin_conformance("cached memory", &CachedStorage::new(InMemoryStorage::new())).await
+}
+
+#[tokio::test]
+async fn test21_file_admin_conforms() -> HarnessResult<()> {
+    let dir = TempDataDir::new("rusty_golf_admin");
+    let storage = FileStorage::new(&dir.0);
+    run_admin_conformance("file", &storage).await?;
+    assert!(dir.0.join("kv/golfers:directory").is_file());
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test21_r2_admin_conforms() -> HarnessResult<()> {
+    let r2 = FakeR2::start()?;
+    run_admin_conformance("r2", &r2.storage).await
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+use chrono::NaiveDateTime;
+use common::TempDataDir;
+use rusty_golf_actix::controller::archive::{export_event_to_file, import_event_from_file};
+use rusty_golf_actix::storage::{FileStorage, SqlStorage};
+use rusty_golf_core::model::{RefreshSource, ScoreChange, ScoreChangeKind};
+use rusty_golf_core::storage::{
+    AdminStorage, EventArchive, InMemoryStorage, PlayerStepFactor, Storage, export_event,
+    import_event,
+};
+use rusty_golf_setup::archive::{export_event_from_worker, import_event_to_worker};
+use rusty_golf_setup::config::WorkerAccess;
+use serde::Deserialize;
+use std::error::Error;
+use std::sync::mpsc;
+
+const EVENT_ID: i32 = 401_580_351;
+const ADMIN_TOKEN: &str = "archive-token";
+
+fn round_finished(bettor_name: &str, round: i32) -> ScoreChange {
+    ScoreChange {
+        detected_at: NaiveDateTime::default(),
+        change: ScoreChangeKind::RoundFinished {
+            bettor_name: bettor_name.to_string(),
+            golfer_name: "Rory McIlroy".to_string(),
+            golfer_espn_id: 3470,
+            round,
+            round_score: -3,
+        },
+    }
+}
+
+/// Give the event a step factor, stored scores and score changes, then archive it.
+async fn archive_sql_event(storage: &SqlStorage) -> Result<EventArchive, Box<dyn Error>> {
+    storage
+        .set_player_step_factors(
+            EVENT_ID,
+            &[PlayerStepFactor {
+                golfer_espn_id: 3470,
+                bettor_name: "Player1".to_string(),
+                step_factor: 4.0,
+            }],
+        )
+        .await?;
+    let picks = storage.get_golfers_for_event(EVENT_ID).await?;
+    storage.store_scores(EVENT_ID, &picks).await?;
+    storage
+        .store_score_changes(
+            EVENT_ID,
+            &[round_finished("Player1", 1), round_finished("Player1", 2)],
+        )
+        .await?;
+    Ok(export_event(storage, EVENT_ID).await?)
+}
+
+fn assert_same_event(name: &str, actual: &EventArchive, expected: &EventArchive) {
+    assert_eq!(actual.event, expected.event, "{name}: event");
+    assert_eq!(actual.golfers, expected.golfers, "{name}: golfers");
+    assert_eq!(actual.bettors, expected.bettors, "{name}: bettors");
+    assert_eq!(actual.assignments, expected.assignments, "{name}: picks");
+    assert_eq!(
+        actual.step_factors, expected.step_factors,
+        "{name}: step factors"
+    );
+    assert_eq!(
+        actual.scores.as_ref().map(|s| s.score_struct.len()),
+        expected.scores.as_ref().map(|s| s.score_struct.len()),
+        "{name}: scores"
+    );
+    assert_eq!(
+        actual.score_changes, expected.score_changes,
+        "{name}: score changes"
+    );
+}
+
+#[tokio::test]
+async fn test22_sql_event_moves_to_file_storage() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    let expected = archive_sql_event(&sql).await?;
+    assert_eq!(expected.assignments.len(), 15);
+    assert_eq!(expected.bettors.len(), 5);
+    assert_eq!(expected.score_changes.len(), 2);
+
+    let dir = TempDataDir::new("rusty_golf_archive");
+    std::fs::create_dir_all(&dir.0)?;
+    let path = dir.0.join("archive.json");
+    let summary = export_event_to_file(&sql, EVENT_ID, &path).await?;
+    assert_eq!(summary.picks, 15);
+    assert!(summary.has_scores);
+
+    let file = FileStorage::new(dir.0.join("data"));
+    import_event_from_file(&file, &path, false).await?;
+    assert_same_event("file", &export_event(&file, EVENT_ID).await?, &expected);
+
+    // Scores follow their picks to the new eup ids.
+    let picks = file.get_golfers_for_event(EVENT_ID).await?;
+    let stored = file.get_scores(EVENT_ID, RefreshSource::Db).await?;
+    for score in &stored.score_struct {
+        let pick = picks.iter().find(|p| p.eup_id == score.eup_id).unwrap();
+        assert_eq!(
+            (pick.espn_id, &pick.bettor_name),
+            (score.espn_id, &score.bettor_name)
+        );
+    }
+
+    assert!(import_event_from_file(&file, &path, false).await.is_err());
+    import_event_from_file(&file, &path, true).await?;
+    assert_eq!(file.get_golfers_for_event(EVENT_ID).await?.len(), 15);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test22_file_event_moves_back_to_sql() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    let expected = archive_sql_event(&sql).await?;
+
+    let dir = TempDataDir::new("rusty_golf_archive");
+    let file = FileStorage::new(&dir.0);
+    import_event(&file, &expected, false).await?;
+
+    // Rebuild the event in SQL from the file copy.
+    import_event(&sql, &export_event(&file, EVENT_ID).await?, true).await?;
+    assert_same_event("sql", &export_event(&sql, EVENT_ID).await?, &expected);
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ExportRequest {
+    event_id: i32,
+}
+
+#[derive(Deserialize)]
+struct ImportRequest {
+    archive: EventArchive,
+    #[serde(default)]
+    replace: bool,
+}
+
+fn authorized(req: &HttpRequest) -> bool {
+    req.headers()
+        .get("x-admin-token")
+        .is_some_and(|token| token == ADMIN_TOKEN)
+}
+
+/// The worker's `/admin/export` and `/admin/import`, over an in-memory storage.
+async fn fake_export(
+    req: HttpRequest,
+    body: web::Json<ExportRequest>,
+    storage: web::Data<InMemoryStorage>,
+) -> HttpResponse {
+    if !authorized(&req) {
+        return HttpResponse::Unauthorized().body("unauthorized");
+    }
+    match export_event(storage.get_ref(), body.event_id).await {
+        Ok(archive) => HttpResponse::Ok().json(archive),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+async fn fake_import(
+    req: HttpRequest,
+    body: web::Json<ImportRequest>,
+    storage: web::Data<InMemoryStorage>,
+) -> HttpResponse {
+    if !authorized(&req) {
+        return HttpResponse::Unauthorized().body("unauthorized");
+    }
+    match import_event(storage.get_ref(), &body.archive, body.replace).await {
+        Ok(()) => HttpResponse::Ok().body("imported"),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+/// Serve a fake worker on its own thread; the setup client blocks, so it can't share a
+/// runtime with the server.
+fn start_fake_worker() -> String {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        actix_web::rt::System::new().block_on(async move {
+            let storage = web::Data::new(InMemoryStorage::new());
+            let server = HttpServer::new(move || {
+                App::new()
+                    .app_data(storage.clone())
+                    .route("/admin/export", web::post().to(fake_export))
+                    .route("/admin/import", web::post().to(fake_import))
+            })
+            .workers(1)
+            .bind(("127.0.0.1", 0))
+            .expect("bind fake worker");
+            tx.send(server.addrs()[0])
+                .expect("report fake worker address");
+            server.run().await.expect("run fake worker");
+        });
+    });
+    let addr = rx.recv().expect("fake worker address");
+    format!("http://{addr}/")
+}
+
+#[test]
+fn test22_setup_moves_event_through_worker() -> Result<(), Box<dyn Error>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let expected = runtime.block_on(async {
+        let context = common::setup_test_context(include_str!("test01.sql")).await?;
+        let sql = SqlStorage::new(context.config_and_pool.clone());
+        archive_sql_event(&sql).await
+    })?;
+
+    let worker = WorkerAccess {
+        worker_url: start_fake_worker(),
+        admin_token: ADMIN_TOKEN.to_string(),
+    };
+    import_event_to_worker(&worker, &expected, false)?;
+    let err = import_event_to_worker(&worker, &expected, false).unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+    assert_same_event(
+        "worker",
+        &export_event_from_worker(&worker, EVENT_ID)?,
+        &expected,
+    );
+
+    let wrong_token = WorkerAccess {
+        worker_url: worker.worker_url.clone(),
+        admin_token: "nope".to_string(),
+    };
+    assert!(export_event_from_worker(&wrong_token, EVENT_ID).is_err());
+    Ok(())
+}
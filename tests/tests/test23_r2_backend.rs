@@ -0,0 +1,73 @@
+mod common;
+
+use actix_web::{App, test, web};
+use clap::Parser;
+use common::fake_r2::FakeR2;
+use rusty_golf_actix::args::{Args, StorageBackend};
+use rusty_golf_actix::controller::score::scores;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::storage::{Storage, export_event, import_event};
+use serde_json::Value;
+use std::error::Error;
+use std::sync::Arc;
+
+const EVENT_ID: i32 = 401_580_351;
+
+#[actix_web::test]
+async fn test23_r2_args_skip_the_database_flags() {
+    let mut args = Args::try_parse_from([
+        "rusty-golf",
+        "--storage=r2",
+        "--r2-endpoint=http://127.0.0.1:9000",
+        "--r2-bucket=golf",
+    ])
+    .unwrap();
+    assert_eq!(args.storage, StorageBackend::R2);
+    assert_eq!(args.r2_bucket.as_deref(), Some("golf"));
+    // No --db-name needed.
+    args.validate().unwrap();
+
+    let mut migrate = Args::try_parse_from(["rusty-golf", "--storage=r2", "--migrate"]).unwrap();
+    assert!(migrate.validate().is_err());
+}
+
+#[actix_web::test]
+async fn test23_scores_are_served_from_r2() -> Result<(), Box<dyn Error>> {
+    // Copy the test01 event, with freshly stored scores, into the fake bucket.
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    let picks = sql.get_golfers_for_event(EVENT_ID).await?;
+    sql.store_scores(EVENT_ID, &picks).await?;
+    let archive = export_event(&sql, EVENT_ID).await?;
+
+    let fake = FakeR2::start().map_err(|e| e.to_string())?;
+    import_event(&fake.storage, &archive, false).await?;
+
+    let storage: Arc<dyn Storage> = Arc::new(fake.storage.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage))
+            .route("/scores", web::get().to(scores)),
+    )
+    .await;
+
+    // The stored scores are fresh, so this is answered from the bucket without ESPN.
+    let req = test::TestRequest::get()
+        .uri(&format!("/scores?event={EVENT_ID}&yr=2024&cache=1&json=1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status {}", resp.status());
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["cache_hit"], Value::Bool(true));
+    assert_eq!(body["score_struct"].as_array().map(Vec::len), Some(15));
+    assert_eq!(body["bettor_struct"].as_array().map(Vec::len), Some(5));
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/scores?event={EVENT_ID}&yr=2024&cache=1"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "status {}", resp.status());
+    let html = String::from_utf8(test::read_body(resp).await.to_vec())?;
+    assert!(html.contains("Player1"), "{html}");
+    Ok(())
+}
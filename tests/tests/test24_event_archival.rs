@@ -0,0 +1,182 @@
+mod common;
+
+use common::ConnExt;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::{EspnApiClient, EventCompletion};
+use rusty_golf_core::model::{PlayerJsonResponse, RefreshSource, Scores};
+use rusty_golf_core::score::load_score_context;
+use rusty_golf_core::storage::{
+    AdminStorage, CompactionPolicy, InMemoryStorage, PlayerStepFactor, Storage,
+    archive_completed_event, export_event, import_event,
+};
+use sql_middleware::middleware::RowValues;
+use std::error::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const EVENT_ID: i32 = 401_580_351;
+
+/// Counts every ESPN call and fails it: an archived event must never need one.
+#[derive(Default)]
+struct EspnDown {
+    calls: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl EspnApiClient for EspnDown {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Err(CoreError::Network("espn is down".to_string()))
+    }
+
+    async fn event_completion(&self, _event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Err(CoreError::Network("espn is down".to_string()))
+    }
+}
+
+/// Complete the event with a step factor and stored scores, ready to archive.
+async fn finish_event(storage: &dyn AdminStorage) -> Result<(), Box<dyn Error>> {
+    storage
+        .set_player_step_factors(
+            EVENT_ID,
+            &[PlayerStepFactor {
+                golfer_espn_id: 3470,
+                bettor_name: "Player1".to_string(),
+                step_factor: 4.0,
+            }],
+        )
+        .await?;
+    let picks = storage.get_golfers_for_event(EVENT_ID).await?;
+    storage.store_scores(EVENT_ID, &picks).await?;
+    storage.mark_event_completed(EVENT_ID, None).await?;
+    Ok(())
+}
+
+async fn assert_served_from_archive(
+    name: &str,
+    storage: &dyn Storage,
+) -> Result<(), Box<dyn Error>> {
+    let espn = EspnDown::default();
+    let context = load_score_context(storage, &espn, EVENT_ID, 2024, false, 0).await?;
+    assert_eq!(
+        espn.calls.load(Ordering::SeqCst),
+        0,
+        "{name}: ESPN untouched"
+    );
+    assert!(
+        matches!(
+            context.from_db_scores.last_refresh_source,
+            RefreshSource::Archive
+        ),
+        "{name}: scores come from the archive"
+    );
+    assert!(
+        !context.from_db_scores.score_struct.is_empty(),
+        "{name}: archived scores"
+    );
+    assert_eq!(
+        context
+            .player_step_factors
+            .get(&(3470, "Player1".to_string())),
+        Some(&4.0),
+        "{name}: archived step factors"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test24_sql_archive_thins_history() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let storage = SqlStorage::new(context.config_and_pool.clone());
+    finish_event(&storage).await?;
+
+    // Four refreshes: two inside the first hour, then two more an hour later.
+    let mut conn = context.config_and_pool.get_connection().await?;
+    for hx_ts in [
+        "2024-04-14 12:00:00",
+        "2024-04-14 12:10:00",
+        "2024-04-14 13:05:00",
+        "2024-04-14 13:15:00",
+    ] {
+        conn.execute_dml(
+            "INSERT INTO eup_statistic_hx (event_espn_id, golfer_espn_id, eup_id, grp, rounds, \
+             round_scores, tee_times, holes_completed_by_round, line_scores, total_score, \
+             ins_ts, hx_ts) \
+             SELECT event_espn_id, golfer_espn_id, eup_id, grp, rounds, round_scores, tee_times, \
+             holes_completed_by_round, line_scores, total_score, ins_ts, ?1 \
+             FROM eup_statistic WHERE event_espn_id = ?2;",
+            &[
+                RowValues::Text(hx_ts.to_string()),
+                RowValues::Int(i64::from(EVENT_ID)),
+            ],
+        )
+        .await?;
+    }
+
+    let report = archive_completed_event(&storage, EVENT_ID, &CompactionPolicy::default()).await?;
+    assert_eq!(report.event_id, EVENT_ID);
+    assert_eq!(report.history_kept, 3);
+    assert_eq!(report.history_pruned, 1);
+
+    let res = conn
+        .execute_select(
+            "SELECT DISTINCT hx_ts FROM eup_statistic_hx WHERE event_espn_id = ?1 ORDER BY hx_ts;",
+            &[RowValues::Int(i64::from(EVENT_ID))],
+        )
+        .await?;
+    let remaining: Vec<&str> = res
+        .results
+        .iter()
+        .filter_map(|row| row.get("hx_ts")?.as_text())
+        .collect();
+    assert_eq!(
+        remaining,
+        [
+            "2024-04-14 12:00:00",
+            "2024-04-14 13:05:00",
+            "2024-04-14 13:15:00"
+        ]
+    );
+
+    assert_served_from_archive("sql", &storage).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test24_archived_event_survives_without_live_data() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    finish_event(&sql).await?;
+
+    let memory = InMemoryStorage::new();
+    import_event(&memory, &export_event(&sql, EVENT_ID).await?, false).await?;
+    let report = archive_completed_event(&memory, EVENT_ID, &CompactionPolicy::default()).await?;
+    assert!(report.records_removed > 0, "live records are dropped");
+
+    let archive = memory
+        .get_event_archive(EVENT_ID)
+        .await?
+        .expect("the archive is stored");
+    assert_eq!(
+        export_event(&memory, EVENT_ID).await?.assignments,
+        archive.assignments,
+        "export hands back the archive"
+    );
+    assert_served_from_archive("memory", &memory).await?;
+
+    // The archive moves like any other export, and stays archived only where it was archived.
+    let copy = InMemoryStorage::new();
+    import_event(&copy, &archive, false).await?;
+    assert!(copy.get_event_archive(EVENT_ID).await?.is_none());
+    assert_eq!(
+        copy.get_golfers_for_event(EVENT_ID).await?.len(),
+        memory.get_golfers_for_event(EVENT_ID).await?.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use actix_web::{App, test, web};
+use rusty_golf_actix::controller::api::api_v1;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::api::dto::{
+    BettorRoster, ErrorBody, EventDetail, EventList, GolferScorecard, RoundSummaries, Standings,
+};
+use rusty_golf_core::model::{IntStat, LineScore, ScoreDisplay, StringStat};
+use rusty_golf_core::storage::Storage;
+use std::error::Error;
+use std::sync::Arc;
+
+const EVENT_ID: i32 = 401_580_351;
+const RORY: i64 = 3470;
+
+/// Store two rounds for every pick (-1 then +2); Rory also gets a scored first hole.
+async fn store_two_rounds(storage: &SqlStorage) -> Result<(), Box<dyn Error>> {
+    let mut scores = storage.get_golfers_for_event(EVENT_ID).await?;
+    for golfer in &mut scores {
+        let stats = &mut golfer.detailed_statistics;
+        stats.rounds = vec![IntStat { val: 0 }, IntStat { val: 1 }];
+        stats.round_scores = vec![IntStat { val: -1 }, IntStat { val: 2 }];
+        stats.tee_times = vec![StringStat {
+            val: "Thu 8:15AM".to_string(),
+        }];
+        stats.total_score = 1;
+        if golfer.espn_id == RORY {
+            stats.line_scores = vec![LineScore {
+                round: 0,
+                hole: 1,
+                score: 3,
+                par: 4,
+                score_display: ScoreDisplay::Birdie,
+            }];
+        }
+    }
+    storage.store_scores(EVENT_ID, &scores).await?;
+    Ok(())
+}
+
+/// GET `$uri`, check the status and parse the JSON body.
+macro_rules! get_json {
+    ($app:expr, $uri:expr, $status:expr) => {{
+        let uri: &str = &$uri;
+        let resp = test::call_service(&$app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(resp.status().as_u16(), $status, "{uri}");
+        test::read_body_json(resp).await
+    }};
+}
+
+#[actix_web::test]
+async fn test25_api_serves_stored_scores_as_dtos() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    store_two_rounds(&sql).await?;
+
+    let storage: Arc<dyn Storage> = Arc::new(sql);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage))
+            .route("/api/v1/{tail:.*}", web::get().to(api_v1)),
+    )
+    .await;
+
+    let events: EventList = get_json!(app, "/api/v1/events", 200);
+    assert_eq!(events.events.len(), 1);
+    assert_eq!(events.events[0].event_id, EVENT_ID);
+    assert_eq!(events.events[0].name, "PGA Championship");
+
+    let event: EventDetail = get_json!(app, format!("/api/v1/events/{EVENT_ID}"), 200);
+    assert_eq!(event.event.name, "PGA Championship");
+    assert_eq!(event.bettors.len(), 5);
+    assert!(
+        event.bettors[0]
+            .picks
+            .iter()
+            .any(|pick| pick.golfer_espn_id == RORY)
+    );
+
+    // The stored scores are fresh, so none of these reach ESPN.
+    let standings: Standings = get_json!(app, format!("/api/v1/events/{EVENT_ID}/standings"), 200);
+    assert_eq!(standings.freshness.source, "database");
+    assert_eq!(standings.standings.len(), 5);
+    assert!(
+        standings
+            .standings
+            .iter()
+            .all(|standing| standing.rank == 1 && standing.total_score == 3),
+        "every bettor has three golfers at +1, so all tie for first"
+    );
+
+    let roster: BettorRoster = get_json!(
+        app,
+        format!("/api/v1/events/{EVENT_ID}/bettors/Player1"),
+        200
+    );
+    assert_eq!(roster.golfers.len(), 3);
+    assert_eq!(roster.total_score, 3);
+    assert_eq!(roster.golfers[0].round_scores, vec![-1, 2]);
+
+    let card: GolferScorecard = get_json!(
+        app,
+        format!("/api/v1/events/{EVENT_ID}/golfers/{RORY}"),
+        200
+    );
+    assert_eq!(card.golfer_name, "Rory McIlroy");
+    assert_eq!(card.picked_by, vec!["Player1".to_string()]);
+    assert_eq!(card.rounds.len(), 2);
+    assert_eq!(card.rounds[0].tee_time.as_deref(), Some("Thu 8:15AM"));
+    assert_eq!(card.rounds[0].holes.len(), 1);
+    assert_eq!(card.rounds[0].holes[0].strokes, 3);
+    assert!(card.rounds[1].holes.is_empty());
+
+    let rounds: RoundSummaries = get_json!(app, format!("/api/v1/events/{EVENT_ID}/rounds"), 200);
+    assert_eq!(rounds.rounds.len(), 2);
+    assert_eq!(rounds.rounds[0].bettors[0].score, -3);
+    assert_eq!(rounds.rounds[1].bettors[0].score, 6);
+    assert_eq!(rounds.rounds[1].bettors[0].cumulative, 3);
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test25_api_errors_share_one_body() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    store_two_rounds(&sql).await?;
+
+    let storage: Arc<dyn Storage> = Arc::new(sql);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage))
+            .route("/api/v1/{tail:.*}", web::get().to(api_v1)),
+    )
+    .await;
+
+    for (uri, status, code) in [
+        ("/api/v1/events/999".to_string(), 404, "not_found"),
+        ("/api/v1/events/abc".to_string(), 400, "bad_request"),
+        ("/api/v1/scores".to_string(), 404, "not_found"),
+        (
+            format!("/api/v1/events/{EVENT_ID}/standings?yr=soon"),
+            400,
+            "bad_request",
+        ),
+        (
+            format!("/api/v1/events/{EVENT_ID}/bettors/Nobody%20Here"),
+            404,
+            "not_found",
+        ),
+        (
+            format!("/api/v1/events/{EVENT_ID}/golfers/1"),
+            404,
+            "not_found",
+        ),
+    ] {
+        let body: ErrorBody = get_json!(app, uri, status);
+        assert_eq!(body.error.status, status, "{uri}");
+        assert_eq!(body.error.code, code, "{uri}");
+        assert!(!body.error.message.is_empty(), "{uri}");
+    }
+    Ok(())
+}
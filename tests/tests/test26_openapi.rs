@@ -0,0 +1,95 @@
+mod common;
+
+use actix_web::{App, test, web};
+use rusty_golf_actix::controller::api::{api_v1, openapi_json};
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::storage::Storage;
+use serde_json::Value;
+use std::error::Error;
+use std::sync::Arc;
+
+const EVENT_ID: i32 = 401_580_351;
+
+/// Every property `schema` requires, following `$ref`s and the `allOf` of flattened fields.
+fn required_fields(document: &Value, schema: &Value) -> Vec<String> {
+    if let Some(target) = schema["$ref"].as_str() {
+        let name = target.trim_start_matches("#/components/schemas/");
+        return required_fields(document, &document["components"]["schemas"][name]);
+    }
+    let mut fields: Vec<String> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|field| field.as_str().map(str::to_string))
+        .collect();
+    for part in schema["allOf"].as_array().into_iter().flatten() {
+        fields.extend(required_fields(document, part));
+    }
+    fields
+}
+
+#[actix_web::test]
+async fn test26_openapi_describes_live_responses() -> Result<(), Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    let picks = sql.get_golfers_for_event(EVENT_ID).await?;
+    sql.store_scores(EVENT_ID, &picks).await?;
+
+    let storage: Arc<dyn Storage> = Arc::new(sql);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage))
+            .route("/api/v1/{tail:.*}", web::get().to(api_v1))
+            .route("/openapi.json", web::get().to(openapi_json)),
+    )
+    .await;
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::get().uri("/openapi.json").to_request(),
+    )
+    .await;
+    assert!(resp.status().is_success());
+    let document: Value = test::read_body_json(resp).await;
+    assert_eq!(document["openapi"], "3.0.3");
+    assert!(document["paths"]["/admin/import"]["post"]["requestBody"].is_object());
+
+    for (template, uri) in [
+        ("/api/v1/events", "/api/v1/events".to_string()),
+        (
+            "/api/v1/events/{event_id}",
+            format!("/api/v1/events/{EVENT_ID}"),
+        ),
+        (
+            "/api/v1/events/{event_id}/standings",
+            format!("/api/v1/events/{EVENT_ID}/standings"),
+        ),
+        (
+            "/api/v1/events/{event_id}/bettors/{bettor_name}",
+            format!("/api/v1/events/{EVENT_ID}/bettors/Player1"),
+        ),
+        (
+            "/api/v1/events/{event_id}/golfers/{golfer_espn_id}",
+            format!("/api/v1/events/{EVENT_ID}/golfers/3470"),
+        ),
+        (
+            "/api/v1/events/{event_id}/rounds",
+            format!("/api/v1/events/{EVENT_ID}/rounds"),
+        ),
+        (
+            "/api/v1/events/{event_id}",
+            "/api/v1/events/999".to_string(),
+        ),
+    ] {
+        let resp = test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+        let status = resp.status().as_u16().to_string();
+        let body: Value = test::read_body_json(resp).await;
+        let documented = &document["paths"][template]["get"]["responses"][status.as_str()];
+        let schema = &documented["content"]["application/json"]["schema"];
+        assert!(schema.is_object(), "{uri}: {status} is documented");
+        for field in required_fields(&document, schema) {
+            assert!(body.get(&field).is_some(), "{uri}: {field} is present");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,180 @@
+mod common;
+
+use actix_web::body::MessageBody;
+use actix_web::{App, test, web};
+use rusty_golf_actix::controller::score::stream::next_frames;
+use rusty_golf_actix::controller::score::{ScoreStreams, scores_stream};
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::api::dto::Standings;
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::{EspnApiClient, EventCompletion};
+use rusty_golf_core::model::{
+    HoleResult, PlayerJsonResponse, RefreshSource, ScoreChange, ScoreChangeKind, Scores,
+};
+use rusty_golf_core::storage::{InMemoryStorage, Storage, export_event, import_event};
+use serde_json::Value;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+
+const EVENT_ID: i32 = 401_580_351;
+const YEAR: i32 = 2024;
+
+/// Refreshes never reach ESPN here, so new scores only appear when the test stores them.
+struct EspnDown;
+
+#[async_trait::async_trait]
+impl EspnApiClient for EspnDown {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        Err(CoreError::Network("espn is down".to_string()))
+    }
+
+    async fn event_completion(&self, _event_id: i32) -> Result<Option<EventCompletion>, CoreError> {
+        Err(CoreError::Network("espn is down".to_string()))
+    }
+}
+
+/// An in-memory copy of the test01 event with stored scores.
+async fn seeded_storage() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let sql = SqlStorage::new(context.config_and_pool.clone());
+    let picks = sql.get_golfers_for_event(EVENT_ID).await?;
+    sql.store_scores(EVENT_ID, &picks).await?;
+    let memory = Arc::new(InMemoryStorage::new());
+    import_event(memory.as_ref(), &export_event(&sql, EVENT_ID).await?, false).await?;
+    Ok(memory)
+}
+
+/// Store the scores again, as a refresh would, with Rory's birdie as the change it found.
+async fn refresh_with_birdie(storage: &InMemoryStorage) -> Result<(), Box<dyn Error>> {
+    let scores = storage.get_scores(EVENT_ID, RefreshSource::Db).await?;
+    storage.store_scores(EVENT_ID, &scores.score_struct).await?;
+    let stored = storage.get_scores(EVENT_ID, RefreshSource::Db).await?;
+    let change = ScoreChange {
+        detected_at: stored.last_refresh,
+        change: ScoreChangeKind::Hole {
+            bettor_name: "Player1".to_string(),
+            golfer_name: "Rory McIlroy".to_string(),
+            golfer_espn_id: 3470,
+            round: 1,
+            hole: 3,
+            par: 4,
+            strokes: 3,
+            result: HoleResult::Birdie,
+        },
+    };
+    storage.store_score_changes(EVENT_ID, &[change]).await?;
+    Ok(())
+}
+
+/// The named event's JSON from an SSE frame.
+fn frame_data(frame: &str, event: &str) -> Option<Value> {
+    let rest = frame.strip_prefix(&format!("event: {event}\ndata: "))?;
+    serde_json::from_str(rest.trim_end()).ok()
+}
+
+#[tokio::test]
+async fn test27_frames_follow_stored_refreshes() -> Result<(), Box<dyn Error>> {
+    let storage = seeded_storage().await?;
+
+    let (seen, frames) = next_frames(storage.as_ref(), &EspnDown, EVENT_ID, YEAR, None).await?;
+    assert_eq!(frames.len(), 1, "a new stream starts with the standings");
+    let standings: Standings =
+        serde_json::from_value(frame_data(&frames[0], "standings").expect("standings frame"))?;
+    assert_eq!(standings.event_id, EVENT_ID);
+    assert_eq!(standings.standings.len(), 5);
+
+    let (unchanged, frames) =
+        next_frames(storage.as_ref(), &EspnDown, EVENT_ID, YEAR, Some(seen)).await?;
+    assert_eq!(unchanged, seen);
+    assert!(frames.is_empty(), "nothing new was stored");
+
+    refresh_with_birdie(&storage).await?;
+    let (refreshed, frames) =
+        next_frames(storage.as_ref(), &EspnDown, EVENT_ID, YEAR, Some(seen)).await?;
+    assert!(refreshed > seen);
+    assert_eq!(frames.len(), 2);
+    assert!(frame_data(&frames[0], "standings").is_some());
+    let changes = frame_data(&frames[1], "changes").expect("changes frame");
+    assert_eq!(changes[0]["kind"], "hole");
+    assert_eq!(changes[0]["hole"], 3);
+    assert_eq!(
+        changes[0]["message"],
+        "Rory McIlroy just birdied 3 (Player1)"
+    );
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test27_stream_pushes_new_scores() -> Result<(), Box<dyn Error>> {
+    let storage = seeded_storage().await?;
+    let streams = ScoreStreams::new(Arc::new(EspnDown), Duration::from_millis(20));
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage.clone() as Arc<dyn Storage>))
+            .app_data(web::Data::new(streams))
+            .route("/scores/stream", web::get().to(scores_stream)),
+    )
+    .await;
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::get()
+            .uri("/scores/stream?event=401580351")
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status().as_u16(), 400, "yr is required");
+
+    let resp = test::call_service(
+        &app,
+        test::TestRequest::get()
+            .uri(&format!("/scores/stream?event={EVENT_ID}&yr={YEAR}"))
+            .to_request(),
+    )
+    .await;
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+    let mut body = Box::pin(resp.into_body());
+    let mut next_frame = async || -> Option<String> {
+        let chunk = tokio::time::timeout(
+            Duration::from_secs(5),
+            std::future::poll_fn(|cx| body.as_mut().poll_next(cx)),
+        )
+        .await
+        .ok()??
+        .ok()?;
+        Some(String::from_utf8_lossy(&chunk).into_owned())
+    };
+
+    let first = next_frame().await.expect("initial frame");
+    assert!(frame_data(&first, "standings").is_some(), "{first}");
+
+    refresh_with_birdie(&storage).await?;
+    let mut pushed = Vec::new();
+    while let Some(frame) = next_frame().await {
+        if frame.starts_with(':') {
+            continue;
+        }
+        let is_changes = frame.starts_with("event: changes");
+        pushed.push(frame);
+        if is_changes {
+            break;
+        }
+    }
+    assert_eq!(pushed.len(), 2, "{pushed:?}");
+    assert!(frame_data(&pushed[0], "standings").is_some());
+    assert_eq!(
+        frame_data(&pushed[1], "changes").expect("changes frame")[0]["result"],
+        "birdie"
+    );
+    Ok(())
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::pages::{EVENT_ID, YEAR, get_page, prefilled, schedule, store_golfers};
+use rusty_golf_core::error::CoreError;
+use rusty_golf_core::espn::EspnApiClient;
+use rusty_golf_core::model::{LineScore, PlayerJsonResponse, ScoreDisplay, Scores};
+use rusty_golf_core::score::{
+    BETWEEN_ROUNDS_REFRESH_SECS, cache_max_age_for_event, load_score_context,
+};
+use rusty_golf_core::storage::{InMemoryStorage, Storage};
+use std::error::Error;
+use std::sync::Arc;
+
+/// Never reached while the stored scores are fresh; fails if it is.
+struct EspnDown;
+
+#[async_trait::async_trait]
+impl EspnApiClient for EspnDown {
+    async fn get_json_from_espn(
+        &self,
+        _scores: &[Scores],
+        _year: i32,
+        _event_id: i32,
+    ) -> Result<PlayerJsonResponse, CoreError> {
+        Err(CoreError::Network("espn is down".to_string()))
+    }
+}
+
+/// The prefilled event, cached for 300 seconds once under way, with no start date.
+async fn seeded() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = prefilled()?;
+    schedule(&storage, EVENT_ID, YEAR, None, None).await?;
+    Ok(storage)
+}
+
+/// Store the picks with the first golfer `holes` into round 1 and everyone else through it.
+async fn store_round(storage: &InMemoryStorage, holes: i32) -> Result<(), Box<dyn Error>> {
+    let mut first = true;
+    store_golfers(storage, EVENT_ID, |golfer| {
+        let played = if std::mem::take(&mut first) {
+            holes
+        } else {
+            18
+        };
+        golfer.detailed_statistics.line_scores = (1..=played)
+            .map(|hole| LineScore {
+                round: 0,
+                hole,
+                score: 4,
+                par: 4,
+                score_display: ScoreDisplay::Par,
+            })
+            .collect();
+    })
+    .await
+}
+
+async fn scores_page(storage: &Arc<InMemoryStorage>) -> String {
+    let (status, page) = get_page(storage, &format!("/scores?event={EVENT_ID}&yr={YEAR}")).await;
+    assert!(status.is_success());
+    page
+}
+
+/// A fragment response, as htmx swaps it over the div that polled it.
+async fn fragment(storage: &Arc<InMemoryStorage>, name: &str) -> String {
+    let uri = format!("/scores/{name}?event={EVENT_ID}&yr={YEAR}&cache=1&expanded=0");
+    let (status, body) = get_page(storage, &uri).await;
+    assert!(status.is_success());
+    body
+}
+
+async fn context_interval(storage: &InMemoryStorage) -> Result<Option<u32>, Box<dyn Error>> {
+    let cache_max_age = cache_max_age_for_event(storage, EVENT_ID).await?;
+    let context =
+        load_score_context(storage, &EspnDown, EVENT_ID, YEAR, true, cache_max_age).await?;
+    Ok(context.refresh_interval)
+}
+
+#[actix_web::test]
+async fn test28_fragments_poll_by_event_phase() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    // Mid-round: poll as often as the 300 second cache lets new scores through.
+    store_round(&storage, 9).await?;
+    let page = scores_page(&storage).await;
+    assert_eq!(
+        page.matches(r#"data-hx-trigger="load, every 300s""#)
+            .count(),
+        2
+    );
+    assert!(page.contains(r#"data-refresh-seconds="300""#), "{page}");
+    assert!(page.contains("Next update in"));
+    assert!(page.contains("5:00"));
+    assert_eq!(context_interval(&storage).await?, Some(300));
+
+    // Nobody on the course.
+    store_round(&storage, 18).await?;
+    let page = scores_page(&storage).await;
+    let trigger = format!(r#"data-hx-trigger="load, every {BETWEEN_ROUNDS_REFRESH_SECS}s""#);
+    assert_eq!(page.matches(&trigger).count(), 2);
+    assert!(page.contains("10:00"));
+    assert_eq!(
+        context_interval(&storage).await?,
+        Some(BETWEEN_ROUNDS_REFRESH_SECS)
+    );
+
+    // Completed: load once and stop.
+    storage.mark_event_completed(EVENT_ID, None).await?;
+    let page = scores_page(&storage).await;
+    assert_eq!(page.matches(r#"data-hx-trigger="load""#).count(), 2);
+    assert!(!page.contains("every "));
+    assert!(!page.contains("next-update"));
+    assert_eq!(context_interval(&storage).await?, None);
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test28_open_page_follows_the_event_phase() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    // Each response replaces the div that polled it, trigger and all, without loading again.
+    store_round(&storage, 9).await?;
+    let chart = fragment(&storage, "chart").await;
+    assert!(chart.starts_with(r#"<div id="score-chart""#), "{chart}");
+    assert!(chart.contains(r#"data-hx-trigger="every 300s""#), "{chart}");
+    assert!(chart.contains(r#"data-hx-swap="outerHTML""#));
+    let linescore = fragment(&storage, "linescore").await;
+    assert!(linescore.contains(r#"data-hx-trigger="every 300s""#));
+    assert!(linescore.contains(r#"data-hx-swap-oob="true""#));
+    assert!(linescore.contains(r#"data-refresh-seconds="300""#));
+
+    // Between rounds an open page slows down.
+    store_round(&storage, 18).await?;
+    let trigger = format!(r#"data-hx-trigger="every {BETWEEN_ROUNDS_REFRESH_SECS}s""#);
+    assert!(fragment(&storage, "chart").await.contains(&trigger));
+    let linescore = fragment(&storage, "linescore").await;
+    assert!(linescore.contains(&trigger));
+    assert!(linescore.contains(&format!(
+        r#"data-refresh-seconds="{BETWEEN_ROUNDS_REFRESH_SECS}""#
+    )));
+
+    // Completed: the swapped-in divs carry no trigger, so polling stops, and the countdown
+    // is emptied.
+    storage.mark_event_completed(EVENT_ID, None).await?;
+    for name in ["chart", "linescore"] {
+        let body = fragment(&storage, name).await;
+        assert!(!body.contains("data-hx-trigger"), "{name}: {body}");
+        assert!(!body.contains("data-hx-get"), "{name}: {body}");
+    }
+    let linescore = fragment(&storage, "linescore").await;
+    assert!(linescore.contains(r#"<p id="next-update" data-hx-swap-oob="true"></p>"#));
+    assert!(!linescore.contains("Next update in"));
+    Ok(())
+}
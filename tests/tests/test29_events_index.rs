@@ -0,0 +1,145 @@
+use chrono::{DateTime, TimeZone, Utc};
+use rusty_golf_core::score::EventPhase;
+use rusty_golf_core::storage::{
+    AdminStorage, CompactionPolicy, EventConfig, InMemoryStorage, Storage, archive_completed_event,
+};
+use rusty_golf_core::view::events::{load_event_index, render_events_page};
+use serde_json::Value;
+use std::error::Error;
+
+const PGA_2024: i32 = 401_580_351;
+const MASTERS_2025: i32 = 401_703_504;
+const US_OPEN_2025: i32 = 401_703_511;
+
+fn event(name: &str, year: i32, start: &str, end: &str) -> EventConfig {
+    EventConfig {
+        event_name: name.to_string(),
+        year,
+        score_view_step_factor: 3.0,
+        refresh_from_espn: 1,
+        start_date: Some(start.to_string()),
+        end_date: Some(end.to_string()),
+        completed: false,
+    }
+}
+
+/// During the 2025 Masters.
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 4, 11, 15, 0, 0).unwrap()
+}
+
+/// The first prefilled event, the 2024 PGA Championship, completed with Player3 a stroke per golfer ahead,
+/// plus a live and an upcoming 2025 event.
+async fn seeded() -> Result<InMemoryStorage, Box<dyn Error>> {
+    let json: Value = serde_json::from_str(include_str!("test05_dbprefill.json"))?;
+    let storage = InMemoryStorage::from_prefill_json(&Value::Array(vec![json[0].clone()]))?;
+    storage
+        .update_event(
+            PGA_2024,
+            &event(
+                "PGA Championship",
+                2024,
+                "2024-05-16T11:00:00Z",
+                "2024-05-19T23:00:00Z",
+            ),
+        )
+        .await?;
+    let mut golfers = storage.get_golfers_for_event(PGA_2024).await?;
+    for golfer in &mut golfers {
+        golfer.detailed_statistics.total_score = if golfer.bettor_name == "Player3" {
+            -1
+        } else {
+            2
+        };
+    }
+    storage.store_scores(PGA_2024, &golfers).await?;
+    storage.mark_event_completed(PGA_2024, None).await?;
+
+    storage
+        .create_event(
+            MASTERS_2025,
+            &event(
+                "The Masters",
+                2025,
+                "2025-04-10T11:00:00Z",
+                "2025-04-13T23:00:00Z",
+            ),
+        )
+        .await?;
+    storage
+        .create_event(
+            US_OPEN_2025,
+            &event(
+                "U.S. Open",
+                2025,
+                "2025-06-12T11:00:00Z",
+                "2025-06-15T23:00:00Z",
+            ),
+        )
+        .await?;
+    Ok(storage)
+}
+
+#[tokio::test]
+async fn test29_index_groups_events_by_year() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let entries = load_event_index(&storage, now()).await?;
+    let listed: Vec<(i32, i32, EventPhase)> = entries
+        .iter()
+        .map(|entry| (entry.event.event_id, entry.event.year, entry.phase))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (US_OPEN_2025, 2025, EventPhase::Upcoming),
+            (MASTERS_2025, 2025, EventPhase::InProgress),
+            (PGA_2024, 2024, EventPhase::Completed),
+        ]
+    );
+    assert!(entries[0].winners.is_empty());
+    assert!(entries[1].winners.is_empty(), "live events have no winner");
+    assert_eq!(entries[2].winners, vec!["Player3".to_string()]);
+
+    let page = render_events_page(&entries).into_string();
+    let (y2025, y2024) = (
+        page.find("<h2>2025</h2>").expect("2025 heading"),
+        page.find("<h2>2024</h2>").expect("2024 heading"),
+    );
+    assert!(y2025 < y2024, "newest season first");
+    assert!(page.contains(r#"href="?event=401580351&amp;yr=2024""#));
+    assert!(page.contains(r#"href="api/v1/events/401703504/standings""#));
+    assert!(page.contains(r#"href="export/standings?event=401580351&amp;yr=2024""#));
+    assert!(page.contains(r#"href="feed.atom?event=401580351&amp;yr=2024""#));
+    assert!(page.contains("Upcoming") && page.contains("Live") && page.contains("Completed"));
+    assert!(page.contains("May 16 – May 19"));
+    assert!(page.contains("<td>Player3</td>"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test29_archived_events_keep_their_winner() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    archive_completed_event(&storage, PGA_2024, &CompactionPolicy::default()).await?;
+
+    let entries = load_event_index(&storage, now()).await?;
+    let pga = entries
+        .iter()
+        .find(|entry| entry.event.event_id == PGA_2024)
+        .expect("archived event is listed");
+    assert_eq!(pga.winners, vec!["Player3".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test29_empty_index() -> Result<(), Box<dyn Error>> {
+    let storage = InMemoryStorage::new();
+    let entries = load_event_index(&storage, now()).await?;
+    assert!(entries.is_empty());
+    assert!(
+        render_events_page(&entries)
+            .into_string()
+            .contains("No events yet.")
+    );
+    Ok(())
+}
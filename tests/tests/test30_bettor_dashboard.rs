@@ -0,0 +1,153 @@
+mod common;
+
+use actix_web::http::StatusCode;
+use common::pages::{EVENT_ID, RORY, YEAR, get_page, scheduled_pga, store_golfers};
+use rusty_golf_core::model::{IntStat, LineScore, ScoreDisplay, StringStat};
+use rusty_golf_core::storage::{InMemoryStorage, Storage};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Two rounds in: Player1's golfers went -2 then -1, Player3's -3 then +1 and everyone else
+/// level, so Player1 trails Player3 after round 1 and leads by three after round 2. Rory
+/// McIlroy is nine holes into round 2. The event isn't marked completed and its scores are
+/// fresh, so ESPN is never called.
+async fn seeded() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = scheduled_pga().await?;
+    store_golfers(&storage, EVENT_ID, |golfer| {
+        let round_scores = match golfer.bettor_name.as_str() {
+            "Player1" => [-2, -1],
+            "Player3" => [-3, 1],
+            _ => [0, 0],
+        };
+        let round_two_holes = if golfer.espn_id == RORY { 9 } else { 18 };
+        let stats = &mut golfer.detailed_statistics;
+        stats.rounds = vec![IntStat { val: 0 }, IntStat { val: 1 }];
+        stats.round_scores = round_scores.map(|val| IntStat { val }).to_vec();
+        stats.tee_times = ["8:10 AM", "1:45 PM"]
+            .map(|val| StringStat {
+                val: val.to_string(),
+            })
+            .to_vec();
+        stats.line_scores = [(0, 18), (1, round_two_holes)]
+            .into_iter()
+            .flat_map(|(round, holes)| {
+                (1..=holes).map(move |hole| LineScore {
+                    round,
+                    hole,
+                    score: 4,
+                    par: 4,
+                    score_display: ScoreDisplay::Par,
+                })
+            })
+            .collect();
+        stats.total_score = round_scores.iter().sum();
+    })
+    .await?;
+    Ok(storage)
+}
+
+#[actix_web::test]
+async fn test30_event_dashboard() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (status, page) = get_page(
+        &storage,
+        &format!("/bettor?name=Player1&event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{page}");
+    assert!(page.contains("Player1 · PGA Championship"));
+    assert!(page.contains(r#"<span class="bettor-rank">1st</span> of 5"#));
+    assert!(page.contains("Leading Player3 by 3 strokes"), "{page}");
+
+    // Only Player1's three golfers, with Rory mid-round and the rest finished.
+    assert_eq!(page.matches(r#"class="bettor-golfer" "#).count(), 3);
+    assert!(page.contains(&format!(r#"data-espn-id="{RORY}""#)));
+    assert!(!page.contains("Viktor Hovland"));
+    assert!(page.contains("<dt>Thru</dt><dd>9</dd>"));
+    assert_eq!(page.matches("<dt>Thru</dt><dd>F</dd>").count(), 2);
+    assert!(page.contains("<dt>Round</dt><dd>R2</dd>"));
+    assert!(page.contains("<dt>Today</dt><dd>-1</dd>"));
+    assert!(page.contains("<dt>Total</dt><dd>-3</dd>"));
+    assert!(page.contains("<dt>Tee time</dt><dd>1:45 PM</dd>"));
+    assert!(page.contains("R1 -2 · R2 -1"));
+
+    // Second to Player3 after round 1, first after round 2.
+    assert!(page.contains("<td>R1</td><td>2nd</td><td>-6</td><td>-9</td><td>3</td>"));
+    assert!(page.contains("<td>R2</td><td>1st</td><td>-9</td><td>-9</td><td>0</td>"));
+    assert!(page.contains("linescore-panel"));
+    assert!(page.contains(&format!(r#"href="bettor?name=Player1&amp;yr={YEAR}""#)));
+
+    let (status, page) = get_page(
+        &storage,
+        &format!("/bettor?name=Player3&event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains(r#"<span class="bettor-rank">2nd</span> of 5"#));
+    assert!(page.contains("3 strokes behind Player1"), "{page}");
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test30_season_view() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (status, page) = get_page(&storage, &format!("/bettor?name=Player1&yr={YEAR}")).await;
+    assert_eq!(status, StatusCode::OK, "{page}");
+    assert!(page.contains(&format!("Player1 · {YEAR} season")));
+    assert!(page.contains("1 event, 0 wins"), "{page}");
+    assert!(page.contains("1st of 5 (so far)"));
+    assert!(page.contains(&format!(
+        r#"href="bettor?name=Player1&amp;event={EVENT_ID}&amp;yr={YEAR}""#
+    )));
+
+    storage.mark_event_completed(EVENT_ID, None).await?;
+    let (_, page) = get_page(&storage, &format!("/bettor?name=Player1&yr={YEAR}")).await;
+    assert!(page.contains("1 event, 1 win"));
+    assert!(!page.contains("(so far)"));
+
+    let (status, page) = get_page(&storage, "/bettor?name=Player1&yr=2023").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("Player1 has no picks in 2023."));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test30_bad_requests() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (status, _) = get_page(
+        &storage,
+        &format!("/bettor?name=Nobody&event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_page(&storage, &format!("/bettor?name=Player1&event=1&yr={YEAR}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = get_page(&storage, &format!("/bettor?event={EVENT_ID}&yr={YEAR}")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("name"));
+    let (status, _) = get_page(&storage, "/bettor?name=Player1&event=abc&yr=2024").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test30_parse_bettor_request() {
+    use rusty_golf_core::view::bettor::{BettorRequest, parse_bettor_request};
+
+    let query: HashMap<String, String> = [("name", " Player 1 "), ("yr", "2024"), ("event", "")]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    assert_eq!(
+        parse_bettor_request(&query).unwrap(),
+        BettorRequest {
+            bettor_name: "Player 1".to_string(),
+            year: 2024,
+            event_id: None,
+        }
+    );
+}
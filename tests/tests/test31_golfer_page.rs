@@ -0,0 +1,199 @@
+mod common;
+
+use actix_web::http::StatusCode;
+use common::pages::{
+    EVENT_ID as PGA_2024, PGA_END, PGA_START, RORY, get_page, prefilled, schedule, store_golfers,
+};
+use rusty_golf_core::model::{IntStat, LineScore, ScoreDisplay, Scores, StringStat};
+use rusty_golf_core::storage::{
+    CompactionPolicy, InMemoryStorage, Storage, archive_completed_event,
+};
+use std::error::Error;
+use std::sync::Arc;
+
+const OPEN_2024: i32 = 401_580_360;
+const MASTERS_2025: i32 = 401_703_504;
+
+fn hole(round: i32, hole: i32, score: i32) -> LineScore {
+    LineScore {
+        round,
+        hole,
+        score,
+        par: 4,
+        score_display: ScoreDisplay::from_i32(score - 4),
+    }
+}
+
+/// Rory McIlroy's PGA Championship: round 1 with birdies on 3 and 7 and a bogey on 5, then an
+/// eagle on 2 and nine holes into round 2.
+fn rory_at_the_pga(golfer: &mut Scores) {
+    let stats = &mut golfer.detailed_statistics;
+    stats.rounds = vec![IntStat { val: 0 }, IntStat { val: 1 }];
+    stats.round_scores = vec![IntStat { val: -1 }, IntStat { val: -2 }];
+    stats.tee_times = ["8:10 AM", "1:45 PM"]
+        .map(|val| StringStat {
+            val: val.to_string(),
+        })
+        .to_vec();
+    stats.line_scores = (1..=18)
+        .map(|number| match number {
+            3 | 7 => hole(0, number, 3),
+            5 => hole(0, number, 5),
+            _ => hole(0, number, 4),
+        })
+        .chain((1..=9).map(|number| hole(1, number, if number == 2 { 2 } else { 4 })))
+        .collect();
+    stats.total_score = -3;
+}
+
+/// Store every pick of `event_id` with Rory's total set to `rory_total`, after `edit`.
+async fn store(
+    storage: &InMemoryStorage,
+    event_id: i32,
+    rory_total: i32,
+    edit: fn(&mut Scores),
+) -> Result<(), Box<dyn Error>> {
+    store_golfers(storage, event_id, |golfer| {
+        if golfer.espn_id == RORY {
+            golfer.detailed_statistics.round_scores = vec![IntStat { val: rory_total }];
+            golfer.detailed_statistics.total_score = rory_total;
+            edit(golfer);
+        }
+    })
+    .await
+}
+
+/// The prefilled events with fresh scores for the 2024 PGA Championship, an archived 2024
+/// Open and a 2025 Masters still under way. The 2024 U.S. Open has no stored scores.
+async fn seeded() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = prefilled()?;
+
+    schedule(&storage, PGA_2024, 2024, Some(PGA_START), Some(PGA_END)).await?;
+    store(&storage, PGA_2024, -3, rory_at_the_pga).await?;
+
+    schedule(
+        &storage,
+        OPEN_2024,
+        2024,
+        Some("2024-07-18T06:00:00Z"),
+        Some("2024-07-21T18:00:00Z"),
+    )
+    .await?;
+    store(&storage, OPEN_2024, 1, |_| {}).await?;
+    storage.mark_event_completed(OPEN_2024, None).await?;
+    archive_completed_event(storage.as_ref(), OPEN_2024, &CompactionPolicy::default()).await?;
+
+    schedule(
+        &storage,
+        MASTERS_2025,
+        2025,
+        Some("2025-04-10T11:00:00Z"),
+        Some("2025-04-13T23:00:00Z"),
+    )
+    .await?;
+    store(&storage, MASTERS_2025, -4, |_| {}).await?;
+    Ok(storage)
+}
+
+#[actix_web::test]
+async fn test31_full_scorecard() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (status, page) = get_page(
+        &storage,
+        &format!("/golfer?espn_id={RORY}&event={PGA_2024}&yr=2024"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{page}");
+    assert!(page.contains("Rory McIlroy · PGA Championship"));
+    assert!(page.contains(r#"<span class="golfer-total">-3</span>"#));
+    assert!(page.contains(&format!(
+        r#"<a href="bettor?name=Player1&amp;event={PGA_2024}&amp;yr=2024">Player1</a>"#
+    )));
+
+    // Every hole across the top, par on the first row, both rounds below it.
+    assert!(page.contains("<th>Hole</th><th>1</th><th>2</th>"));
+    assert!(page.contains("<th>18</th><th>Tot</th>"));
+    assert!(page.contains(r#"<tr class="golfer-scorecard-par"><th>Par</th><td>4</td>"#));
+    assert!(page.contains("<td>72</td>"));
+    assert!(page.contains(
+        r#"<tr data-round="1"><th>R1<br><span class="golfer-tee-time">8:10 AM</span></th>"#
+    ));
+    assert!(page.contains(
+        r#"<tr data-round="2"><th>R2<br><span class="golfer-tee-time">1:45 PM</span></th>"#
+    ));
+    assert_eq!(
+        page.matches(r#"class="score-shape-birdie birdie""#).count(),
+        2
+    );
+    assert_eq!(
+        page.matches(r#"class="score-shape-bogey bogey""#).count(),
+        1
+    );
+    assert_eq!(
+        page.matches(r#"class="score-shape-eagle eagle""#).count(),
+        1
+    );
+    // Round 2's back nine is still to play.
+    assert_eq!(page.matches("<td></td>").count(), 9);
+    assert!(page.contains("<td>-1</td></tr>") && page.contains("<td>-2</td></tr>"));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test31_other_events() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (_, page) = get_page(
+        &storage,
+        &format!("/golfer?espn_id={RORY}&event={PGA_2024}&yr=2024"),
+    )
+    .await;
+    let (masters, open) = (
+        page.find(&format!(r#"data-event-id="{MASTERS_2025}""#))
+            .expect("the Masters is listed"),
+        page.find(&format!(r#"data-event-id="{OPEN_2024}""#))
+            .expect("the archived Open is listed"),
+    );
+    assert!(masters < open, "newest first");
+    assert!(
+        !page.contains(r#"data-event-id="401580355""#),
+        "no stored scores"
+    );
+    assert!(!page.contains(&format!(r#"data-event-id="{PGA_2024}""#)));
+    assert!(page.contains(&format!(
+        r#"href="golfer?espn_id={RORY}&amp;event={MASTERS_2025}&amp;yr=2025""#
+    )));
+    assert!(page.contains("event-status-live"));
+    assert!(page.contains("<td>-4</td><td>-4</td><td>Player3</td>"));
+    assert!(page.contains("<td>+1</td><td>+1</td><td>Player1</td>"));
+
+    // The bettor dashboard links each golfer here.
+    let (_, dashboard) = get_page(
+        &storage,
+        &format!("/bettor?name=Player1&event={PGA_2024}&yr=2024"),
+    )
+    .await;
+    assert!(dashboard.contains(&format!(
+        r#"href="golfer?espn_id={RORY}&amp;event={PGA_2024}&amp;yr=2024""#
+    )));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test31_bad_requests() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let (status, _) = get_page(
+        &storage,
+        &format!("/golfer?espn_id=1&event={PGA_2024}&yr=2024"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_page(&storage, &format!("/golfer?espn_id={RORY}&event=1&yr=2024")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = get_page(&storage, &format!("/golfer?event={PGA_2024}&yr=2024")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("espn_id"));
+    Ok(())
+}
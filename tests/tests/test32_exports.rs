@@ -0,0 +1,160 @@
+mod common;
+
+use actix_web::http::StatusCode;
+use common::pages::{EVENT_ID, RORY, YEAR, get, scheduled_pga, store_golfers};
+use rusty_golf_core::model::IntStat;
+use rusty_golf_core::storage::InMemoryStorage;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Two rounds: Player1's golfers went -2 then -1, Player3's -3 then +1 and everyone else
+/// level, except that Rory McIlroy (Player1) hasn't posted round 2 yet.
+async fn seeded() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = scheduled_pga().await?;
+    store_golfers(&storage, EVENT_ID, |golfer| {
+        let mut round_scores = match golfer.bettor_name.as_str() {
+            "Player1" => vec![-2, -1],
+            "Player3" => vec![-3, 1],
+            _ => vec![0, 0],
+        };
+        if golfer.espn_id == RORY {
+            round_scores.truncate(1);
+        }
+        let stats = &mut golfer.detailed_statistics;
+        stats.total_score = round_scores.iter().sum();
+        stats.round_scores = round_scores
+            .into_iter()
+            .map(|val| IntStat { val })
+            .collect();
+    })
+    .await?;
+    Ok(storage)
+}
+
+fn csv_lines(body: &[u8]) -> Vec<String> {
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    let text = text.strip_prefix('\u{feff}').expect("UTF-8 BOM for Excel");
+    text.split_terminator("\r\n").map(str::to_string).collect()
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[actix_web::test]
+async fn test32_csv_exports() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let standings = get(
+        &storage,
+        &format!("/export/standings?event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(standings.status, StatusCode::OK);
+    assert_eq!(standings.content_type, "text/csv; charset=utf-8");
+    assert_eq!(
+        standings.disposition,
+        r#"attachment; filename="401580351-2024-standings.csv""#
+    );
+    let lines = csv_lines(&standings.body);
+    assert_eq!(
+        lines[..3],
+        ["Rank,Bettor,Total", "1,Player1,-8", "2,Player3,-6"]
+    );
+    assert_eq!(lines.len(), 6);
+    assert!(lines[3..].iter().all(|line| line.starts_with("3,")));
+
+    let rounds = get(
+        &storage,
+        &format!("/export/rounds?event={EVENT_ID}&yr={YEAR}&format=csv"),
+    )
+    .await;
+    assert_eq!(
+        rounds.disposition,
+        r#"attachment; filename="401580351-2024-rounds.csv""#
+    );
+    let lines = csv_lines(&rounds.body);
+    assert_eq!(
+        lines[..3],
+        ["Bettor,R1,R2,Total", "Player1,-6,-2,-8", "Player3,-9,3,-6"]
+    );
+
+    let golfers = get(
+        &storage,
+        &format!("/export/golfers?event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    let lines = csv_lines(&golfers.body);
+    assert_eq!(lines[0], "Bettor,Golfer,ESPN ID,R1,R2,Total");
+    assert_eq!(lines.len(), 16, "one row per pick");
+    // Leaders first; a round not yet posted is blank, not level par.
+    assert!(lines[1..4].iter().all(|line| line.starts_with("Player1,")));
+    assert!(lines.contains(&"Player1,Rory McIlroy,3470,-2,,-2".to_string()));
+    assert!(lines[4..7].iter().all(|line| line.starts_with("Player3,")));
+    assert!(lines.contains(&"Player3,Viktor Hovland,4364873,-3,1,-2".to_string()));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test32_xlsx_export() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let rounds = get(
+        &storage,
+        &format!("/export/rounds?event={EVENT_ID}&yr={YEAR}&format=xlsx"),
+    )
+    .await;
+    assert_eq!(rounds.status, StatusCode::OK);
+    assert_eq!(
+        rounds.content_type,
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+    assert_eq!(
+        rounds.disposition,
+        r#"attachment; filename="401580351-2024-rounds.xlsx""#
+    );
+    assert!(rounds.body.starts_with(b"PK\x03\x04"), "a zip archive");
+    // Entries are stored uncompressed, so the sheet XML can be read straight from the bytes.
+    assert!(contains(
+        &rounds.body,
+        r#"<sheet name="Rounds" sheetId="1""#
+    ));
+    assert!(contains(
+        &rounds.body,
+        r#"<c r="A1" t="inlineStr" s="1"><is><t>Bettor</t></is></c>"#
+    ));
+    assert!(contains(
+        &rounds.body,
+        concat!(
+            r#"<row r="2"><c r="A2" t="inlineStr"><is><t>Player1</t></is></c>"#,
+            r#"<c r="B2"><v>-6</v></c><c r="C2"><v>-2</v></c><c r="D2"><v>-8</v></c></row>"#
+        )
+    ));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test32_bad_requests() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+
+    let unknown_table = get(
+        &storage,
+        &format!("/export/leaderboard?event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(unknown_table.status, StatusCode::NOT_FOUND);
+    let unknown_event = get(&storage, &format!("/export/standings?event=1&yr={YEAR}")).await;
+    assert_eq!(unknown_event.status, StatusCode::NOT_FOUND);
+    let missing_year = get(&storage, &format!("/export/standings?event={EVENT_ID}")).await;
+    assert_eq!(missing_year.status, StatusCode::BAD_REQUEST);
+    let bad_format = get(
+        &storage,
+        &format!("/export/standings?event={EVENT_ID}&yr={YEAR}&format=pdf"),
+    )
+    .await;
+    assert_eq!(bad_format.status, StatusCode::BAD_REQUEST);
+    assert!(contains(&bad_format.body, "format must be csv or xlsx"));
+    Ok(())
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use actix_web::http::StatusCode;
+use common::pages::{EVENT_ID, RORY, YEAR, get, scheduled_pga, store_golfers};
+use rusty_golf_core::model::StringStat;
+use rusty_golf_core::storage::InMemoryStorage;
+use std::error::Error;
+use std::sync::Arc;
+
+const HOVLAND: i64 = 4_364_873;
+
+/// Everyone tees off round 1 at 7:15am Central, except Rory McIlroy (Player1), who has
+/// round 2 posted too, and Viktor Hovland (Player3), whose tee time ESPN hasn't set.
+async fn seeded() -> Result<Arc<InMemoryStorage>, Box<dyn Error>> {
+    let storage = scheduled_pga().await?;
+    store_golfers(&storage, EVENT_ID, |golfer| {
+        let tee_times: &[&str] = match golfer.espn_id {
+            RORY => &["5/16 8:10a", "5/17 1:45p"],
+            HOVLAND => &["TBD"],
+            _ => &["5/16 7:15a"],
+        };
+        golfer.detailed_statistics.tee_times = tee_times
+            .iter()
+            .map(|val| StringStat {
+                val: (*val).to_string(),
+            })
+            .collect();
+    })
+    .await?;
+    Ok(storage)
+}
+
+/// Each `VEVENT` of a calendar, without its `BEGIN` and `END` lines.
+fn vevents(calendar: &str) -> Vec<&str> {
+    calendar
+        .split("BEGIN:VEVENT\r\n")
+        .skip(1)
+        .map(|event| event.split("END:VEVENT").next().unwrap_or_default())
+        .collect()
+}
+
+#[actix_web::test]
+async fn test33_everyones_tee_times() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    let feed = get(
+        &storage,
+        &format!("/tee-times.ics?event={EVENT_ID}&yr={YEAR}"),
+    )
+    .await;
+    assert_eq!(feed.status, StatusCode::OK);
+    assert_eq!(feed.content_type, "text/calendar; charset=utf-8");
+    assert_eq!(
+        feed.disposition,
+        r#"attachment; filename="401580351-2024-tee-times.ics""#
+    );
+    let calendar = feed.text();
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    assert!(calendar.contains("REFRESH-INTERVAL;VALUE=DURATION:PT1H\r\n"));
+
+    let events = vevents(calendar);
+    // 15 picks, Rory twice, Hovland's unset tee time left out.
+    assert_eq!(events.len(), 15);
+    assert!(
+        events[..13]
+            .iter()
+            .all(|event| event.contains("DTSTART:20240516T121500Z\r\n")),
+        "7:15am Central (UTC-5) is 12:15 UTC, earliest first"
+    );
+    let rory_r1 = events[13];
+    assert!(rory_r1.contains("UID:401580351-3470-r1@rusty-golf\r\n"));
+    assert!(rory_r1.contains("DTSTART:20240516T131000Z\r\n"));
+    assert!(rory_r1.contains("DURATION:PT4H30M\r\n"));
+    assert!(rory_r1.contains("SUMMARY:Rory McIlroy R1\r\n"));
+    assert!(rory_r1.contains("Picked by Player1."));
+    let rory_r2 = events[14];
+    assert!(rory_r2.contains("UID:401580351-3470-r2@rusty-golf\r\n"));
+    assert!(rory_r2.contains("DTSTART:20240517T184500Z\r\n"));
+    assert!(!calendar.contains("Viktor Hovland"));
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test33_one_bettors_tee_times() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    let feed = get(
+        &storage,
+        &format!("/tee-times.ics?event={EVENT_ID}&yr={YEAR}&bettor=Player1"),
+    )
+    .await;
+    assert_eq!(feed.status, StatusCode::OK);
+    let calendar = feed.text();
+    assert!(calendar.contains("X-WR-CALNAME:"));
+    assert!(calendar.contains("tee times (Player1)"));
+    let events = vevents(calendar);
+    assert_eq!(events.len(), 4, "three golfers, Rory twice");
+    assert!(
+        events
+            .iter()
+            .all(|event| event.contains("Picked by Player1."))
+    );
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test33_bad_requests() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    let unknown_bettor = get(
+        &storage,
+        &format!("/tee-times.ics?event={EVENT_ID}&yr={YEAR}&bettor=Nobody"),
+    )
+    .await;
+    assert_eq!(unknown_bettor.status, StatusCode::NOT_FOUND);
+    let unknown_event = get(&storage, &format!("/tee-times.ics?event=1&yr={YEAR}")).await;
+    assert_eq!(unknown_event.status, StatusCode::NOT_FOUND);
+    let missing_year = get(&storage, &format!("/tee-times.ics?event={EVENT_ID}")).await;
+    assert_eq!(missing_year.status, StatusCode::BAD_REQUEST);
+    Ok(())
+}
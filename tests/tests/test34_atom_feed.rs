@@ -0,0 +1,273 @@
+mod common;
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{App, test, web};
+use common::ConnExt;
+use rusty_golf_actix::controller::feed::feed;
+use rusty_golf_actix::storage::SqlStorage;
+use rusty_golf_core::model::{IntStat, LineScore, ScoreDisplay};
+use rusty_golf_core::storage::Storage;
+use sql_middleware::middleware::{ConfigAndPool, RowValues};
+use std::error::Error;
+use std::sync::Arc;
+
+const EVENT_ID: i32 = 401_580_351;
+const YEAR: i32 = 2024;
+const RORY: i64 = 3470;
+const HOVLAND: i64 = 4_364_873;
+const DAY: i64 = 1680;
+
+/// Holes 1 to `holes` of round 1, all pars except where `under` says how far under.
+fn line_scores(holes: i32, under: &[(i32, i32)]) -> Vec<LineScore> {
+    (1..=holes)
+        .map(|hole| {
+            let par = 4;
+            let score = par
+                - under
+                    .iter()
+                    .find(|(h, _)| *h == hole)
+                    .map_or(0, |(_, under)| *under);
+            LineScore {
+                round: 0,
+                hole,
+                score,
+                par,
+                score_display: ScoreDisplay::from_i32(score - par),
+            }
+        })
+        .collect()
+}
+
+/// Store scores, then copy them into `eup_statistic_hx` as the snapshot taken at `taken_at`.
+async fn snapshot(
+    storage: &SqlStorage,
+    config_and_pool: &ConfigAndPool,
+    taken_at: &str,
+    holes: i32,
+    under: impl Fn(i64) -> Vec<(i32, i32)>,
+) -> Result<(), Box<dyn Error>> {
+    let mut golfers = storage.get_golfers_for_event(EVENT_ID).await?;
+    for golfer in &mut golfers {
+        let stats = &mut golfer.detailed_statistics;
+        stats.line_scores = line_scores(holes, &under(golfer.espn_id));
+        stats.total_score = stats.line_scores.iter().map(|ls| ls.score - ls.par).sum();
+        stats.round_scores = vec![IntStat {
+            val: stats.total_score,
+        }];
+    }
+    storage.store_scores(EVENT_ID, &golfers).await?;
+    let mut conn = config_and_pool.get_connection().await?;
+    conn.execute_dml(
+        "INSERT INTO eup_statistic_hx (event_espn_id, golfer_espn_id, eup_id, grp, rounds, \
+         round_scores, tee_times, holes_completed_by_round, line_scores, total_score, \
+         ins_ts, hx_ts) \
+         SELECT event_espn_id, golfer_espn_id, eup_id, grp, rounds, round_scores, tee_times, \
+         holes_completed_by_round, line_scores, total_score, ?1, ?1 \
+         FROM eup_statistic WHERE event_espn_id = ?2;",
+        &[
+            RowValues::Text(taken_at.to_string()),
+            RowValues::Int(i64::from(EVENT_ID)),
+        ],
+    )
+    .await?;
+    Ok(())
+}
+
+/// Round 1 of the PGA Championship in four snapshots:
+///
+/// - 14:00, ten holes in, everyone level.
+/// - 14:05, Hovland (Player2) eagles 11; too soon after 14:00 to be read.
+/// - 15:00, 17 holes in, Rory (Player1) has eagled 13 too: Player1 and Player2 tie.
+/// - 16:00, everyone is in and Day's birdie on 18 puts Player1 ahead alone.
+async fn seeded() -> Result<SqlStorage, Box<dyn Error>> {
+    let context = common::setup_test_context(include_str!("test01.sql")).await?;
+    let config_and_pool = context.config_and_pool.clone();
+    let storage = SqlStorage::new(config_and_pool.clone());
+    snapshot(
+        &storage,
+        &config_and_pool,
+        "2024-05-16 14:00:00",
+        10,
+        |_| Vec::new(),
+    )
+    .await?;
+    snapshot(
+        &storage,
+        &config_and_pool,
+        "2024-05-16 14:05:00",
+        11,
+        |id| {
+            if id == HOVLAND {
+                vec![(11, 2)]
+            } else {
+                Vec::new()
+            }
+        },
+    )
+    .await?;
+    snapshot(
+        &storage,
+        &config_and_pool,
+        "2024-05-16 15:00:00",
+        17,
+        |id| match id {
+            HOVLAND => vec![(11, 2)],
+            RORY => vec![(13, 2)],
+            _ => Vec::new(),
+        },
+    )
+    .await?;
+    snapshot(
+        &storage,
+        &config_and_pool,
+        "2024-05-16 16:00:00",
+        18,
+        |id| match id {
+            HOVLAND => vec![(11, 2)],
+            RORY => vec![(13, 2)],
+            DAY => vec![(18, 1)],
+            _ => Vec::new(),
+        },
+    )
+    .await?;
+    // Drop the rows the history trigger added as each store replaced the last.
+    let mut conn = config_and_pool.get_connection().await?;
+    conn.execute_dml(
+        "DELETE FROM eup_statistic_hx WHERE hx_ts NOT LIKE '2024-05-16 %';",
+        &[],
+    )
+    .await?;
+    Ok(storage)
+}
+
+struct Feed {
+    status: StatusCode,
+    content_type: String,
+    body: String,
+}
+
+impl Feed {
+    fn titles(&self) -> Vec<&str> {
+        self.body
+            .split("<entry>")
+            .skip(1)
+            .filter_map(|entry| {
+                let start = entry.find("<title>")? + "<title>".len();
+                let end = entry.find("</title>")?;
+                Some(&entry[start..end])
+            })
+            .collect()
+    }
+}
+
+async fn fetch(storage: &SqlStorage, uri: &str) -> Feed {
+    let storage: Arc<dyn Storage> = Arc::new(storage.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(storage))
+            .route("/feed.atom", web::get().to(feed)),
+    )
+    .await;
+    let resp = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = test::read_body(resp).await;
+    Feed {
+        status,
+        content_type,
+        body: String::from_utf8(body.to_vec()).unwrap(),
+    }
+}
+
+#[actix_web::test]
+async fn test34_feed_from_snapshots() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    let feed = fetch(&storage, &format!("/feed.atom?event={EVENT_ID}&yr={YEAR}")).await;
+    assert_eq!(feed.status, StatusCode::OK);
+    assert_eq!(feed.content_type, "application/atom+xml; charset=utf-8");
+    assert!(feed.body.starts_with(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">"
+    ));
+    assert!(
+        feed.body
+            .contains("<title>PGA Championship leaderboard</title>")
+    );
+    assert!(
+        feed.body
+            .contains("<updated>2024-05-16T16:00:00Z</updated>\n  <author>")
+    );
+
+    // Newest first; Hovland's eagle is reported with the 15:00 snapshot, not 14:05.
+    assert_eq!(
+        feed.titles(),
+        [
+            "Player1 takes the lead",
+            "Round 1 complete",
+            "Rory McIlroy eagled 13 in round 1",
+            "Viktor Hovland eagled 11 in round 1",
+            "Player1, Player2 are tied for the lead",
+        ]
+    );
+    assert!(feed.body.contains(concat!(
+        "    <id>tag:rusty-golf,2024:event/401580351/round-1</id>\n",
+        "    <title>Round 1 complete</title>\n",
+        "    <link rel=\"alternate\" href=\"?event=401580351&amp;yr=2024\"/>\n",
+        "    <updated>2024-05-16T16:00:00Z</updated>\n",
+        "    <summary>Player1 leads at -3.</summary>\n",
+    )));
+    assert!(feed.body.contains(concat!(
+        "    <id>tag:rusty-golf,2024:event/401580351/hole-4364873-r1-h11</id>\n",
+        "    <title>Viktor Hovland eagled 11 in round 1</title>\n",
+        "    <link rel=\"alternate\" href=\"golfer?espn_id=4364873&amp;event=401580351&amp;yr=2024\"/>\n",
+        "    <updated>2024-05-16T15:00:00Z</updated>\n",
+        "    <summary>2 on the par 4. Picked by Player2.</summary>\n",
+    )));
+
+    let again = fetch(&storage, &format!("/feed.atom?event={EVENT_ID}&yr={YEAR}")).await;
+    assert_eq!(
+        again.body, feed.body,
+        "the same snapshots give the same feed"
+    );
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test34_completed_event() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    storage
+        .mark_event_completed(EVENT_ID, Some("2024-05-19T23:00:00Z"))
+        .await?;
+    let feed = fetch(&storage, &format!("/feed.atom?event={EVENT_ID}&yr={YEAR}")).await;
+    assert_eq!(feed.status, StatusCode::OK);
+    assert_eq!(
+        feed.titles()[..2],
+        ["PGA Championship is over", "Player1 takes the lead"]
+    );
+    assert!(feed.body.contains(concat!(
+        "    <id>tag:rusty-golf,2024:event/401580351/completed</id>\n",
+        "    <title>PGA Championship is over</title>\n",
+        "    <link rel=\"alternate\" href=\"?event=401580351&amp;yr=2024\"/>\n",
+        "    <updated>2024-05-19T23:00:00Z</updated>\n",
+        "    <summary>Player1 wins at -3.</summary>\n",
+    )));
+    assert!(
+        feed.body
+            .contains("  <updated>2024-05-19T23:00:00Z</updated>\n  <author>")
+    );
+    Ok(())
+}
+
+#[actix_web::test]
+async fn test34_bad_requests() -> Result<(), Box<dyn Error>> {
+    let storage = seeded().await?;
+    let unknown_event = fetch(&storage, &format!("/feed.atom?event=1&yr={YEAR}")).await;
+    assert_eq!(unknown_event.status, StatusCode::NOT_FOUND);
+    let missing_year = fetch(&storage, &format!("/feed.atom?event={EVENT_ID}")).await;
+    assert_eq!(missing_year.status, StatusCode::BAD_REQUEST);
+    Ok(())
+}